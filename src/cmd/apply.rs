@@ -2,13 +2,17 @@ static USAGE: &str = r#"
 Apply a series of transformation functions to a given CSV column. This can be used to
 perform typical data-wrangling tasks and/or to harmonize some values, etc.
 
-It has six subcommands:
- * operations - 36 string, format, currency, regex & NLP operators.
+It has nine subcommands:
+ * operations - 51 string, format, currency, phone, email, geohash, regex, hashing, encoding,
+     NLP & DNS operators.
  * emptyreplace - replace empty cells with <--replacement> string.
+ * lookup - replace values using a key-value mapping file specified with <--map>.
  * datefmt - Formats a recognized date column to a specified format using <--formatstr>.
  * dynfmt - Dynamically constructs a new column from other columns using the <--formatstr> template.
  * geocode - geocodes a WGS84 location against a static copy of the Geonames cities database.
+ * geodist - computes the haversine distance between two WGS84 locations.
  * calcconv - parse and evaluate math expressions, with support for units and conversions.
+ * expr - evaluate arithmetic/string expressions that reference other columns by name.
 
 OPERATIONS
 Multiple operations can be applied, with the comma-delimited operation series
@@ -19,12 +23,20 @@ applied in order:
   lower,simdln => Lowercase the cell, then compute the normalized 
       Damerau-Levenshtein similarity to --comparand
 
+Operations can also be delimited with a pipe (e.g. trim|lower|squeeze).
+
 Operations support multi-column transformations. Just make sure the
 number of transformed columns with the --rename option is the same. e.g.:
 
 $ qsv apply operations trim,upper col1,col2,col3 -r newcol1,newcol2,newcol3 file.csv
 
-It has 36 supported operations:
+To apply different operations to different columns in a single pass over the data,
+use --ops-map instead of the <operations> and <column> arguments. It takes a
+semicolon-delimited list of <columns>:<operations> mappings, e.g.:
+
+$ qsv apply operations --ops-map 'name,city:trim|titlecase;email:trim|lower;notes:squeeze' file.csv
+
+It has 51 supported operations:
 
   * len: Return string length
   * lower: Transform to lowercase
@@ -40,8 +52,15 @@ It has 36 supported operations:
   * strip_prefix: Removes specified prefix in --comparand
   * strip_suffix: Remove specified suffix in --comparand
   * escape - escape (Rust escape_default)
-  * encode64: base64 encode
-  * decode64: base64 decode
+  * encode64: base64 encode (also available as encode)
+  * decode64: base64 decode (also available as decode)
+  * urlencode: URL encode (percent-encode) the string
+  * urldecode: URL decode (percent-decode) the string
+  * md5: MD5 hash, as lowercase hex
+  * sha1: SHA-1 hash, as lowercase hex
+  * sha256: SHA-256 hash, as lowercase hex
+  * blake3: BLAKE3 hash, as lowercase hex
+  * hmac: HMAC-SHA256 of the string, using --comparand as the key, as lowercase hex
   * replace: Replace all matches of a pattern (using --comparand)
       with a string (using --replacement) (Rust replace)
   * regex_replace: Replace all regex matches in --comparand w/ --replacement.
@@ -63,10 +82,23 @@ It has 36 supported operations:
   * currencytonum: Gets the numeric value of a currency. Supports currency symbols
       (e.g. $,¥,£,€,֏,₱,₽,₪,₩,ƒ,฿,₫) and strings (e.g. USD, EUR, RMB, JPY, etc.). 
       Recognizes point, comma and space separators.
+  * currencycode: Gets the ISO 4217 currency code of a currency value, from its currency
+      code (e.g. USD, EUR) or symbol (e.g. $ => USD, € => EUR, £ => GBP, ¥ => JPY).
+      Recognizes point, comma and space separators. Returns an empty string if the value
+      is not a currency or has no code or symbol.
   * numtocurrency: Convert a numeric value to a currency. Specify the currency symbol
       with --comparand. Automatically rounds values to two decimal places. Specify
       "euro" formatting (e.g. 1.000,00 instead of 1,000.00 ) by setting --formatstr to "euro".
       Specify conversion rate by setting --replacement to a number.
+  * e164: Parse and normalize a phone number to E.164 format (e.g. +12024561111).
+      Numbers without a country calling code are parsed using the ISO 3166-1 alpha-2
+      default region in --comparand (e.g. US, GB). Invalid numbers are left untouched.
+  * email: Normalize a valid email address (trimmed & lowercased).
+      Invalid email addresses are left untouched.
+  * email_check: check if the value is a valid email address (boolean).
+  * geohash: Encode a WGS84 location (lat, long) to a geohash. The geohash length
+      can be set with --formatstr (1 to 12, default: 9).
+  * geohash_decode: Decode a geohash to the WGS84 location (lat, long) of its center.
   * copy: Mark a column for copying
   * simdl: Damerau-Levenshtein similarity to --comparand
   * simdln: Normalized Damerau-Levenshtein similarity to --comparand (between 0.0 & 1.0)
@@ -86,6 +118,10 @@ It has 36 supported operations:
        If you want to always displays the confidence score, end the --comparand value with a
        question mark (e.g. 0.9?)
        https://github.com/greyblake/whatlang-rs/blob/master/SUPPORTED_LANGUAGES.md
+  * dns_lookup: Resolve a hostname to its first IP address, using the system resolver.
+  * reverse_dns: Resolve an IP address to its hostname, using the system resolver.
+      For both DNS operations, values that cannot be resolved are set to empty.
+      Lookups are cached, and at most --jobs lookups are run concurrently.
 
 Examples:
 Trim, then transform to uppercase the surname field.
@@ -114,10 +150,22 @@ Replace ' and ' with ' & ' in the description field.
 
   $ qsv apply replace description --comparand ' and ' --replacement ' & ' file.csv
 
+Resolve the client_ip column of a web server log export to hostnames.
+
+  $ qsv apply operations reverse_dns client_ip -c client_host --jobs 8 access_log.csv
+
 Extract the numeric value of the Salary column in a new column named Salary_num.
 
   $ qsv apply operations currencytonum Salary -c Salary_num file.csv
 
+Normalize phone numbers to E.164, using US as the default region.
+
+  $ qsv apply operations e164 Phone -C US file.csv
+
+Get the currency code of a price, then convert it to a number.
+
+  $ qsv apply operations currencycode Price -c Currency file.csv | qsv apply operations currencytonum Price
+
 Convert the USD_Price to PHP_Price using the currency symbol "PHP" with a conversion rate of 60.
 
   $ qsv apply operations numtocurrency USD_Price -C PHP -R 60 -c PHP_Price file.csv
@@ -127,6 +175,10 @@ and then decode it.
 
   $ qsv apply operations encode plaintext_col -c encoded_col file.csv | qsv apply operations decode encode_col
 
+Pseudonymize the email column with a keyed HMAC, so the same email always gets the same token.
+
+  $ qsv apply operations lower,trim,hmac email --comparand 'my secret key' -c email_token file.csv
+
 Compute the Normalized Damerau-Levenshtein similarity of the neighborhood column to the string 'Roxbury'
 and save it to a new column named dln_roxbury_score.
 
@@ -136,6 +188,10 @@ You can also use this subcommand command to make a copy of a column:
 
   $ qsv apply operations copy col_to_copy -c col_copy file.csv
 
+Clean up several columns with different operations, reading the file only once.
+
+  $ qsv apply operations --ops-map 'name:trim|titlecase;email:trim|lower;zip:trim' file.csv
+
 EMPTYREPLACE
 Replace empty cells with <--replacement> string.
 Non-empty cells are not modified. See the `fill` command for more complex empty field operations.
@@ -149,6 +205,25 @@ Replace empty cells in file.csv Measurement column with 'Unknown Measurement'.
 
 $ qsv apply emptyreplace --replacement 'Unknown Measurement' file.csv
 
+LOOKUP
+Replace values using the mapping file specified with <--map>. The mapping file is a CSV
+with a header row, whose first column is the key and second column is the value to replace
+it with. Lookups are exact and case-sensitive. If a key appears more than once, the first
+value is used.
+
+Values not found in the mapping file are replaced with <--replacement> if it is set,
+and left unchanged otherwise. Lookup also supports multi-column replacements.
+
+Examples:
+Recode the state codes in the State column to state names, using states.csv
+(with code,name columns):
+
+$ qsv apply lookup State --map states.csv file.csv
+
+Put the department names in a new column, using 'Unknown' for unmapped department codes.
+
+$ qsv apply lookup dept_code --map departments.csv --replacement Unknown -c dept_name file.csv
+
 DATEFMT
 Formats a recognized date column to a specified format using <--formatstr>.
 See https://github.com/jqnatividad/belt/tree/main/dateparser#accepted-date-formats for
@@ -184,6 +259,27 @@ Get the day of the week for several date columns and store it in the correspondi
 
   $ qsv apply dateformat OpenDate,CloseDate --formatstr '%u' --rename Open_weekday,Close_weekday file.csv
 
+Extract the year and month of OpenDate into new columns:
+
+  $ qsv apply datefmt OpenDate --formatstr '%Y' -c open_year file.csv | \
+      qsv apply datefmt OpenDate --formatstr '%m' -c open_month
+
+Convert OpenDate to New York time:
+
+  $ qsv apply datefmt OpenDate --output-tz America/New_York file.csv
+
+Truncate OpenDate to the start of its week (Monday) in Manila time:
+
+  $ qsv apply datefmt OpenDate --trunc week --output-tz Asia/Manila --formatstr '%Y-%m-%d' file.csv
+
+Compute the age in years from a BirthDate column:
+
+  $ qsv apply datefmt BirthDate --elapsed-to now --elapsed-unit years -c age file.csv
+
+Compute the number of days it took to close a ticket:
+
+  $ qsv apply datefmt OpenDate --elapsed-to CloseDate -c days_to_close file.csv
+
 DYNFMT
 Dynamically constructs a new column from other columns using the <--formatstr> template.
 The template can contain arbitrary characters. To insert a column value, enclose the
@@ -222,6 +318,23 @@ set the geocoded value a new column named City.
 
 $ qsv apply geocode Location --formatstr city-state --new-column City file.csv
 
+GEODIST
+Computes the haversine (great-circle) distance between two WGS84 locations into a
+new column. The <column> selection is either two location columns (each containing
+a latitude, longitude coordinate) or four columns in lat1,long1,lat2,long2 order.
+Rows with missing or invalid coordinates have an empty distance.
+
+The distance unit is set with --formatstr - km (default), m, mi or nmi.
+
+Examples:
+Compute the distance in kilometers between the Pickup and Dropoff location columns:
+
+$ qsv apply geodist Pickup,Dropoff --new-column distance_km file.csv
+
+Compute the distance in miles from separate latitude and longitude columns:
+
+$ qsv apply geodist from_lat,from_lon,to_lat,to_lon --formatstr mi -c distance_mi file.csv
+
 CALCCONV
 Parse and evaluate math expressions into a new column, with support for units and conversions.
 The math expression is built dynamically using the <--formatstr> template, similar to the DYNFMT
@@ -259,15 +372,41 @@ $ qsv apply calcconv --formatstr '10% of abs(sin(pi)) horsepower to watts' -c wa
 And use very large numbers:
 $ qsv apply calcconv --formatstr '{col1} Billion Trillion * {col2} quadrillion vigintillion' -c num_atoms file.csv 
 
+EXPR
+Evaluate an expression for each row into a new column. Unlike CALCCONV, columns are referenced
+directly by name - no {} template is required. Column names are converted to "safe" names
+(lowercased, non-alphanumeric characters replaced with underscore, and prefixed with an
+underscore if the name starts with a digit). Cells that look like integers or floats are
+evaluated as numbers, empty cells as none, and all other cells as strings.
+
+The expression uses MiniJinja expression syntax (https://docs.rs/minijinja/latest/minijinja/syntax),
+so arithmetic (+ - * / // % **), comparisons, boolean logic, string concatenation (~),
+inline if/else and filters are all supported. If an expression fails to evaluate for a row,
+"ERROR: <reason>" is written instead.
+
+Examples:
+Compute a total:
+$ qsv apply expr --formatstr 'price * qty * 1.2' --new-column total file.csv
+
+Concatenate columns, with filters:
+$ qsv apply expr --formatstr 'last_name | upper ~ ", " ~ first_name' -c full_name file.csv
+
+Use a conditional, treating empty discounts as zero:
+$ qsv apply expr --formatstr 'price - (discount or 0) if qty > 10 else price' -c net file.csv
+
 For more extensive examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_apply.rs.
 
 Usage:
 qsv apply operations <operations> [options] <column> [<input>]
+qsv apply operations --ops-map=<spec> [options] [<input>]
 qsv apply emptyreplace --replacement=<string> [options] <column> [<input>]
+qsv apply lookup --map=<file> [options] <column> [<input>]
 qsv apply datefmt [--formatstr=<string>] [options] <column> [<input>]
 qsv apply dynfmt --formatstr=<string> [options] --new-column=<name> [<input>]
 qsv apply geocode [--formatstr=<string>] [options] <column> [<input>]
+qsv apply geodist [--formatstr=<string>] [options] --new-column=<name> <column> [<input>]
 qsv apply calcconv --formatstr=<string> [options] --new-column=<name> [<input>]
+qsv apply expr --formatstr=<string> [options] --new-column=<name> [<input>]
 qsv apply --help
 
 The <column> argument can be a list of columns for the operations and datefmt subcommands.
@@ -277,13 +416,36 @@ apply options:
     -c, --new-column <name>     Put the transformed values in a new column instead.
     -r, --rename <name>         New name for the transformed column.
     -C, --comparand=<string>    The string to compare against for replace & similarity operations.
-                                Also used with numtocurrency operation to specify currency symbol.
+                                Also used with numtocurrency operation to specify currency symbol,
+                                and with the e164 operation to specify the default region.
     -R, --replacement=<string>  The string to use for the replace & emptyreplace operations.
-                                Also used with numtocurrency operation to conversion rate.
+                                Also used with numtocurrency operation to conversion rate,
+                                and as the default for values not found by lookup.
+    --map=<file>                The key-value mapping CSV file to use with the LOOKUP
+                                subcommand.
+    --ops-map=<spec>            Apply different operation series to different columns
+                                in one pass. A semicolon-delimited list of
+                                <columns>:<operations> mappings. The columns are transformed
+                                in place, so --new-column and --rename are not allowed.
+                                Only used with the OPERATIONS subcommand.
     --prefer-dmy                Prefer to parse dates in dmy format. Otherwise, use mdy format.
                                 Only used with the DATEFMT subcommand.
     --keep-zero-time            If a formatted date ends with "T00:00:00+00:00", keep the time
                                 instead of removing it. Only used with the DATEFMT subcommand.
+    --output-tz=<tz>            Convert dates to this IANA timezone (e.g. America/New_York)
+                                before formatting. Dates without timezone info are parsed as UTC.
+                                Only used with the DATEFMT subcommand.
+    --trunc=<unit>              Truncate dates to the start of the minute, hour, day, week
+                                (Monday), month or year before formatting. Truncation is
+                                done after --output-tz conversion.
+                                Only used with the DATEFMT subcommand.
+    --elapsed-to=<arg>          Instead of formatting, compute the elapsed time from the date
+                                to "now", or to the date in the given column. The result is
+                                positive if the date is earlier, and empty if the target date
+                                cannot be parsed. Only used with the DATEFMT subcommand.
+    --elapsed-unit=<unit>       The unit to use for --elapsed-to. One of seconds, minutes,
+                                hours, days, weeks, months or years. Only whole units are
+                                returned (e.g. age in years). [default: days]
     -f, --formatstr=<string>    This option is used by several subcommands:
 
                                 OPERATIONS: 
@@ -300,6 +462,9 @@ apply options:
                                   round
                                     The number of decimal places to round to (default: 3)
 
+                                  geohash
+                                    The geohash length, from 1 to 12 (default: 9)
+
                                 DATEFMT: The date format to use. For formats, see
                                   https://docs.rs/chrono/latest/chrono/format/strftime/
                                   Default to ISO 8601 / RFC 3339 date & time format.
@@ -307,6 +472,10 @@ apply options:
 
                                 DYNFMT: the template to use to construct a new column.
 
+                                CALCCONV: the math expression template to evaluate.
+
+                                EXPR: the expression to evaluate.
+
                                 GEOCODE: the place format to use with the geocode subcommand.
                                   The available formats are:
                                   - 'city-state' (default) - e.g. Brooklyn, New York
@@ -318,6 +487,8 @@ apply options:
                                   - 'county-country' | 'admin2-country' - Kings County, US
                                   - 'county-state-country' | 'admin2-admin1-country' - Kings County, New York US
                                   - 'country' - US
+
+                                GEODIST: the distance unit - km (default), m, mi or nmi.
     -j, --jobs <arg>            The number of jobs to run in parallel.
                                 When not set, the number of jobs is set to the number of CPUs detected.
     -b, --batch <size>          The number of rows per batch to load into memory, before running in parallel.
@@ -333,23 +504,37 @@ Common options:
     -p, --progressbar           Show progress bars. Not valid for stdin.
 "#;
 
-use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, ToSocketAddrs},
+    str::FromStr,
+};
 
 use cached::proc_macro::cached;
 use censor::{Censor, Sex, Zealous};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone,
+    Timelike, Utc,
+};
+use chrono_tz::Tz;
 use cpc::{eval, units::Unit};
-use data_encoding::BASE64;
+use data_encoding::{BASE64, HEXLOWER};
 use dynfmt::Format;
 use eudex::Hash;
+use hmac::{Hmac, Mac};
 use indicatif::{ProgressBar, ProgressDrawTarget};
 use log::debug;
+use md5::Md5;
+use minijinja::{value::Value, Environment, Expression};
 use once_cell::sync::OnceCell;
 use qsv_currency::Currency;
 use qsv_dateparser::parse_with_preference;
 use rayon::prelude::*;
 use regex::Regex;
-use reverse_geocoder::{Locations, ReverseGeocoder};
+use reverse_geocoder::{Locations, Record, ReverseGeocoder};
 use serde::Deserialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 use strsim::{
     damerau_levenshtein, hamming, jaro_winkler, normalized_damerau_levenshtein, osa_distance,
     sorensen_dice,
@@ -364,7 +549,7 @@ use crate::{
     clitypes::CliError,
     config::{Config, Delimiter},
     regex_once_cell,
-    select::SelectColumns,
+    select::{SelectColumns, Selection},
     util, CliResult,
 };
 
@@ -372,27 +557,42 @@ use crate::{
 #[strum(ascii_case_insensitive)]
 #[allow(non_camel_case_types)]
 enum Operations {
+    Blake3,
     Censor,
     Censor_Check,
     Censor_Count,
     Copy,
+    Currencycode,
     Currencytonum,
+    #[strum(serialize = "decode", serialize = "decode64")]
     Decode,
+    Dns_Lookup,
+    E164,
+    Email,
+    Email_Check,
+    #[strum(serialize = "encode", serialize = "encode64")]
     Encode,
     Escape,
+    Geohash,
+    Geohash_Decode,
     Eudex,
+    Hmac,
     Len,
     Lower,
     Ltrim,
+    Md5,
     Mltrim,
     Mrtrim,
     Mtrim,
     Numtocurrency,
     Regex_Replace,
     Replace,
+    Reverse_Dns,
     Round,
     Rtrim,
     Sentiment,
+    Sha1,
+    Sha256,
     Simdl,
     Simdln,
     Simhm,
@@ -407,6 +607,8 @@ enum Operations {
     Titlecase,
     Trim,
     Upper,
+    Urldecode,
+    Urlencode,
     Whatlang,
 }
 
@@ -418,14 +620,23 @@ struct Args {
     cmd_datefmt:         bool,
     cmd_dynfmt:          bool,
     cmd_emptyreplace:    bool,
+    cmd_lookup:          bool,
     cmd_geocode:         bool,
+    cmd_geodist:         bool,
     cmd_calcconv:        bool,
+    cmd_expr:            bool,
     arg_input:           Option<String>,
     flag_rename:         Option<String>,
     flag_comparand:      String,
     flag_replacement:    String,
+    flag_ops_map:        Option<String>,
+    flag_map:            Option<String>,
     flag_prefer_dmy:     bool,
     flag_keep_zero_time: bool,
+    flag_output_tz:      Option<String>,
+    flag_trunc:          Option<String>,
+    flag_elapsed_to:     Option<String>,
+    flag_elapsed_unit:   String,
     flag_formatstr:      String,
     flag_batch:          u32,
     flag_jobs:           Option<usize>,
@@ -437,13 +648,15 @@ struct Args {
 }
 
 static CENSOR: OnceCell<Censor> = OnceCell::new();
-static LOCS: OnceCell<Locations> = OnceCell::new();
-static GEOCODER: OnceCell<ReverseGeocoder> = OnceCell::new();
+pub(crate) static LOCS: OnceCell<Locations> = OnceCell::new();
+pub(crate) static GEOCODER: OnceCell<ReverseGeocoder> = OnceCell::new();
 static EUDEX_COMPARAND_HASH: OnceCell<eudex::Hash> = OnceCell::new();
 static REGEX_REPLACE: OnceCell<Regex> = OnceCell::new();
 static SENTIMENT_ANALYZER: OnceCell<SentimentIntensityAnalyzer> = OnceCell::new();
 static THOUSANDS_POLICY: OnceCell<SeparatorPolicy> = OnceCell::new();
 static ROUND_PLACES: OnceCell<u32> = OnceCell::new();
+static GEOHASH_PRECISION: OnceCell<usize> = OnceCell::new();
+static PHONE_REGION: OnceCell<Option<phonenumber::country::Id>> = OnceCell::new();
 static WHATLANG_CONFIDENCE_THRESHOLD: OnceCell<f64> = OnceCell::new();
 
 // default confidence threshold for whatlang language detection - 90% confidence
@@ -452,6 +665,15 @@ const DEFAULT_THRESHOLD: f64 = 0.9;
 // default number of decimal places to round to
 const DEFAULT_ROUND_PLACES: u32 = 3;
 
+// default geohash length - about 4.8m x 4.8m cells
+const DEFAULT_GEOHASH_PRECISION: usize = 9;
+
+// geohash base32 alphabet
+const GEOHASH_BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+// mean earth radius in kilometers, used for haversine distances
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
 // for thousands operator
 static INDIANCOMMA_POLICY: SeparatorPolicy = SeparatorPolicy {
     separator: ",",
@@ -459,18 +681,129 @@ static INDIANCOMMA_POLICY: SeparatorPolicy = SeparatorPolicy {
     digits:    thousands::digits::ASCII_DECIMAL,
 };
 
+// datefmt --trunc units
+#[derive(Clone, Copy, EnumString)]
+#[strum(ascii_case_insensitive)]
+enum TruncUnit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+// datefmt --elapsed-unit units
+#[derive(Clone, Copy, EnumString)]
+#[strum(ascii_case_insensitive)]
+enum ElapsedUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+// datefmt --elapsed-to target
+enum ElapsedTo {
+    Now(DateTime<Utc>),
+    Column(usize),
+}
+
+impl ElapsedTo {
+    // the elapsed time from date to the target, in whole units
+    fn elapsed(
+        &self,
+        date: DateTime<Utc>,
+        record: &csv::StringRecord,
+        prefer_dmy: bool,
+        unit: ElapsedUnit,
+    ) -> Option<i64> {
+        let target = match self {
+            ElapsedTo::Now(now) => *now,
+            ElapsedTo::Column(idx) => parse_with_preference(record.get(*idx)?, prefer_dmy).ok()?,
+        };
+        let elapsed = target - date;
+        Some(match unit {
+            ElapsedUnit::Seconds => elapsed.num_seconds(),
+            ElapsedUnit::Minutes => elapsed.num_minutes(),
+            ElapsedUnit::Hours => elapsed.num_hours(),
+            ElapsedUnit::Days => elapsed.num_days(),
+            ElapsedUnit::Weeks => elapsed.num_weeks(),
+            ElapsedUnit::Months => whole_months(date.naive_utc(), target.naive_utc()),
+            ElapsedUnit::Years => whole_months(date.naive_utc(), target.naive_utc()) / 12,
+        })
+    }
+}
+
+// the number of whole calendar months from one datetime to another
+fn whole_months(from: NaiveDateTime, to: NaiveDateTime) -> i64 {
+    if to < from {
+        return -whole_months(to, from);
+    }
+    let mut months =
+        i64::from(to.year() - from.year()) * 12 + i64::from(to.month()) - i64::from(from.month());
+    if (to.day(), to.time()) < (from.day(), from.time()) {
+        months -= 1;
+    }
+    months
+}
+
+// convert a parsed date to the --output-tz timezone (UTC if not set),
+// and truncate it to the --trunc unit
+fn localize_date(
+    date: DateTime<Utc>,
+    output_tz: Option<Tz>,
+    trunc_unit: Option<TruncUnit>,
+) -> DateTime<FixedOffset> {
+    let date = match output_tz {
+        Some(tz) => {
+            let local = date.with_timezone(&tz);
+            local.with_timezone(&local.offset().fix())
+        }
+        None => date.with_timezone(&Utc.fix()),
+    };
+    let Some(trunc_unit) = trunc_unit else {
+        return date;
+    };
+
+    let naive = date.naive_local();
+    let day = naive.date();
+    let truncated = match trunc_unit {
+        TruncUnit::Minute => day.and_hms_opt(naive.hour(), naive.minute(), 0),
+        TruncUnit::Hour => day.and_hms_opt(naive.hour(), 0, 0),
+        TruncUnit::Day => day.and_hms_opt(0, 0, 0),
+        TruncUnit::Week => (day - Duration::days(i64::from(day.weekday().num_days_from_monday())))
+            .and_hms_opt(0, 0, 0),
+        TruncUnit::Month => {
+            NaiveDate::from_ymd_opt(day.year(), day.month(), 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+        }
+        TruncUnit::Year => {
+            NaiveDate::from_ymd_opt(day.year(), 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+        }
+    };
+    truncated
+        .and_then(|naive| date.offset().from_local_datetime(&naive).single())
+        .unwrap_or(date)
+}
+
 // valid subcommands
-enum ApplySubCmd {
+enum ApplySubCmd<'env, 'source> {
     Operations,
     DateFmt,
     DynFmt,
     Geocode,
+    GeoDist,
     EmptyReplace,
+    Lookup,
     CalcConv,
+    Expr(Expression<'env, 'source>),
 }
 
 #[inline]
-fn replace_column_value(
+pub(crate) fn replace_column_value(
     record: &csv::StringRecord,
     column_index: usize,
     new_value: &str,
@@ -484,6 +817,10 @@ fn replace_column_value(
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
+    if args.flag_ops_map.is_some() && (args.flag_new_column.is_some() || args.flag_rename.is_some())
+    {
+        return fail!("--new-column and --rename cannot be used with --ops-map.");
+    }
     let rconfig = Config::new(&args.arg_input)
         .delimiter(args.flag_delimiter)
         .no_headers(args.flag_no_headers)
@@ -494,6 +831,37 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let headers = rdr.byte_headers()?.clone();
     let sel = rconfig.selection(&headers)?;
+
+    // datefmt timezone conversion, truncation & elapsed time options
+    let output_tz = match args.flag_output_tz {
+        Some(ref tz) => match tz.parse::<Tz>() {
+            Ok(tz) => Some(tz),
+            Err(e) => return fail_clierror!("Invalid --output-tz: {e}"),
+        },
+        None => None,
+    };
+    let trunc_unit = match args.flag_trunc {
+        Some(ref unit) => match TruncUnit::from_str(unit) {
+            Ok(unit) => Some(unit),
+            Err(_) => return fail_clierror!("Invalid --trunc unit '{unit}'."),
+        },
+        None => None,
+    };
+    let Ok(elapsed_unit) = ElapsedUnit::from_str(&args.flag_elapsed_unit) else {
+        return fail_clierror!("Invalid --elapsed-unit '{}'.", args.flag_elapsed_unit);
+    };
+    let elapsed_to = match args.flag_elapsed_to {
+        Some(ref target) if target.eq_ignore_ascii_case("now") => Some(ElapsedTo::Now(Utc::now())),
+        Some(ref target) => {
+            let target_sel =
+                SelectColumns::parse(target)?.selection(&headers, !rconfig.no_headers)?;
+            if target_sel.len() != 1 {
+                return fail!("--elapsed-to must be \"now\" or a single column.");
+            }
+            Some(ElapsedTo::Column(target_sel[0]))
+        }
+        None => None,
+    };
     let column_index = *sel.iter().next().unwrap();
 
     let mut headers = rdr.headers()?.clone();
@@ -545,30 +913,85 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         debug!("dynfmt_fields: {dynfmt_fields:?}  dynfmt_template: {dynfmt_template}");
     }
 
-    let mut ops_vec: Vec<Operations> = Vec::new();
+    // for lookup, load the key-value mapping file
+    let lookup_map = match args.flag_map {
+        Some(ref map_path) if args.cmd_lookup => load_lookup_map(map_path)?,
+        _ => HashMap::new(),
+    };
+
+    // for expr, the expression is compiled once and evaluated against a context
+    // keyed by the "safe" column names
+    let expr_env = Environment::new();
+    let mut expr_names = Vec::new();
+
+    // each entry is a column selection & the operation series to apply to it
+    let mut ops_map: Vec<(Selection, Vec<Operations>)> = Vec::new();
 
     let apply_cmd = if args.cmd_operations {
-        match validate_operations(
-            &args.arg_operations.split(',').collect(),
-            &args.flag_comparand,
-            &args.flag_replacement,
-            &args.flag_new_column,
-            &args.flag_formatstr,
-        ) {
-            Ok(operations_vec) => ops_vec = operations_vec,
-            Err(e) => return Err(e),
+        if let Some(ref ops_map_spec) = args.flag_ops_map {
+            let byte_headers = rdr.byte_headers()?;
+            for mapping in ops_map_spec.split(';').map(str::trim) {
+                if mapping.is_empty() {
+                    continue;
+                }
+                let Some((columns, operations)) = mapping.rsplit_once(':') else {
+                    return fail_clierror!(
+                        "Invalid --ops-map entry '{mapping}'. Expected <columns>:<operations>."
+                    );
+                };
+                let map_sel = SelectColumns::parse(columns.trim())?
+                    .selection(byte_headers, !rconfig.no_headers)?;
+                let operations_vec = validate_operations(
+                    &split_operations(operations),
+                    &args.flag_comparand,
+                    &args.flag_replacement,
+                    &args.flag_new_column,
+                    &args.flag_formatstr,
+                )?;
+                ops_map.push((map_sel, operations_vec));
+            }
+            if ops_map.is_empty() {
+                return fail!("--ops-map requires at least one <columns>:<operations> mapping.");
+            }
+        } else {
+            let operations_vec = validate_operations(
+                &split_operations(&args.arg_operations),
+                &args.flag_comparand,
+                &args.flag_replacement,
+                &args.flag_new_column,
+                &args.flag_formatstr,
+            )?;
+            ops_map.push((sel.clone(), operations_vec));
         }
         ApplySubCmd::Operations
     } else if args.cmd_geocode {
         ApplySubCmd::Geocode
+    } else if args.cmd_geodist {
+        if sel.len() != 2 && sel.len() != 4 {
+            return fail!(
+                "geodist requires two location columns, or four lat1,long1,lat2,long2 columns."
+            );
+        }
+        ApplySubCmd::GeoDist
     } else if args.cmd_datefmt {
         ApplySubCmd::DateFmt
     } else if args.cmd_dynfmt {
         ApplySubCmd::DynFmt
     } else if args.cmd_emptyreplace {
         ApplySubCmd::EmptyReplace
+    } else if args.cmd_lookup {
+        ApplySubCmd::Lookup
     } else if args.cmd_calcconv {
         ApplySubCmd::CalcConv
+    } else if args.cmd_expr {
+        if args.flag_no_headers {
+            return fail!("expr subcommand requires headers.");
+        }
+        expr_names = util::safe_header_names(&headers, true, false, None, "_").0;
+        match expr_env.compile_expression(&args.flag_formatstr) {
+            Ok(expression) => ApplySubCmd::Expr(expression),
+            Err(e) => return fail_clierror!("Invalid expression: {e}"),
+        }
     } else {
         return fail!("Unknown apply subcommand.");
     };
@@ -586,6 +1009,19 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let prefer_dmy = args.flag_prefer_dmy || rconfig.get_dmy_preference();
 
+    // geodist distance unit conversion factor from kilometers
+    let geodist_factor = if args.cmd_geodist {
+        match args.flag_formatstr.as_str() {
+            "%+" | "km" => 1.0,
+            "m" => 1000.0,
+            "mi" => 1.0 / 1.609_344,
+            "nmi" => 1.0 / 1.852,
+            unit => return fail_clierror!("Invalid geodist unit '{unit}'. Use km, m, mi or nmi."),
+        }
+    } else {
+        1.0
+    };
+
     // amortize memory allocation by reusing record
     #[allow(unused_assignments)]
     let mut batch_record = csv::StringRecord::new();
@@ -643,21 +1079,43 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             record = replace_column_value(&record, column_index, &cell);
                         }
                     }
+                    ApplySubCmd::GeoDist => {
+                        let (from, to) = if sel.len() == 2 {
+                            (
+                                parse_location(&record[sel[0]]),
+                                parse_location(&record[sel[1]]),
+                            )
+                        } else {
+                            (
+                                parse_lat_long(&record[sel[0]], &record[sel[1]]),
+                                parse_lat_long(&record[sel[2]], &record[sel[3]]),
+                            )
+                        };
+                        let distance = match (from, to) {
+                            (Some(from), Some(to)) => {
+                                util::round_num(haversine_km(from, to) * geodist_factor, 3)
+                            }
+                            _ => String::new(),
+                        };
+                        record.push_field(&distance);
+                    }
                     ApplySubCmd::Operations => {
                         let mut cell = String::new();
-                        for col_index in sel.iter() {
-                            record[*col_index].clone_into(&mut cell);
-                            apply_operations(
-                                &ops_vec,
-                                &mut cell,
-                                &args.flag_comparand,
-                                &args.flag_replacement,
-                                &args.flag_formatstr,
-                            );
-                            if args.flag_new_column.is_some() {
-                                record.push_field(&cell);
-                            } else {
-                                record = replace_column_value(&record, *col_index, &cell);
+                        for (ops_sel, ops_vec) in &ops_map {
+                            for col_index in ops_sel.iter() {
+                                record[*col_index].clone_into(&mut cell);
+                                apply_operations(
+                                    ops_vec,
+                                    &mut cell,
+                                    &args.flag_comparand,
+                                    &args.flag_replacement,
+                                    &args.flag_formatstr,
+                                );
+                                if args.flag_new_column.is_some() {
+                                    record.push_field(&cell);
+                                } else {
+                                    record = replace_column_value(&record, *col_index, &cell);
+                                }
                             }
                         }
                     }
@@ -672,6 +1130,23 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             record = replace_column_value(&record, column_index, &cell);
                         }
                     }
+                    ApplySubCmd::Lookup => {
+                        let mut cell = String::new();
+                        for col_index in sel.iter() {
+                            if let Some(value) = lookup_map.get(&record[*col_index]) {
+                                value.clone_into(&mut cell);
+                            } else if args.flag_replacement.is_empty() {
+                                record[*col_index].clone_into(&mut cell);
+                            } else {
+                                args.flag_replacement.clone_into(&mut cell);
+                            }
+                            if args.flag_new_column.is_some() {
+                                record.push_field(&cell);
+                            } else {
+                                record = replace_column_value(&record, *col_index, &cell);
+                            }
+                        }
+                    }
                     ApplySubCmd::DateFmt => {
                         let mut cell = String::new();
                         for col_index in sel.iter() {
@@ -679,14 +1154,24 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             if !cell.is_empty() {
                                 let parsed_date = parse_with_preference(&cell, prefer_dmy);
                                 if let Ok(format_date) = parsed_date {
-                                    let formatted_date =
-                                        format_date.format(&args.flag_formatstr).to_string();
-                                    if !args.flag_keep_zero_time
-                                        && formatted_date.ends_with("T00:00:00+00:00")
-                                    {
-                                        cell = formatted_date[..10].to_string();
+                                    if let Some(ref elapsed_to) = elapsed_to {
+                                        cell = elapsed_to
+                                            .elapsed(format_date, &record, prefer_dmy, elapsed_unit)
+                                            .map_or_else(String::new, |elapsed| {
+                                                elapsed.to_string()
+                                            });
                                     } else {
-                                        cell = formatted_date;
+                                        let format_date =
+                                            localize_date(format_date, output_tz, trunc_unit);
+                                        let formatted_date =
+                                            format_date.format(&args.flag_formatstr).to_string();
+                                        if !args.flag_keep_zero_time
+                                            && formatted_date.ends_with("T00:00:00+00:00")
+                                        {
+                                            cell = formatted_date[..10].to_string();
+                                        } else {
+                                            cell = formatted_date;
+                                        }
                                     }
                                 }
                             }
@@ -758,6 +1243,19 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             record = replace_column_value(&record, column_index, &result);
                         }
                     }
+                    ApplySubCmd::Expr(ref expression) => {
+                        let context: HashMap<&str, Value> = expr_names
+                            .iter()
+                            .zip(record.iter())
+                            .map(|(name, field)| (name.as_str(), expr_value(field)))
+                            .collect();
+                        let result = match expression.eval(&context) {
+                            Ok(value) if value.is_undefined() || value.is_none() => String::new(),
+                            Ok(value) => value.to_string(),
+                            Err(e) => format!("ERROR: {e}"),
+                        };
+                        record.push_field(&result);
+                    }
                 }
 
                 record
@@ -785,6 +1283,47 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(wtr.flush()?)
 }
 
+// convert a cell to a typed expr value, so arithmetic works on numeric cells
+#[inline]
+fn expr_value(field: &str) -> Value {
+    if field.is_empty() {
+        Value::from(())
+    } else if let Ok(int) = field.parse::<i64>() {
+        Value::from(int)
+    } else if let Ok(float) = field.parse::<f64>() {
+        Value::from(float)
+    } else {
+        Value::from(field)
+    }
+}
+
+// load a lookup mapping file - the first column is the key & the second the value.
+// If a key is repeated, the first value wins.
+fn load_lookup_map(map_path: &str) -> CliResult<HashMap<String, String>> {
+    let map_conf = Config::new(&Some(map_path.to_string()));
+    let mut map_rdr = map_conf.reader()?;
+    if map_rdr.headers()?.len() < 2 {
+        return fail_clierror!(
+            "Lookup map file {map_path} needs at least two columns (key & value)."
+        );
+    }
+
+    let mut lookup_map = HashMap::new();
+    let mut map_record = csv::StringRecord::new();
+    while map_rdr.read_record(&mut map_record)? {
+        lookup_map
+            .entry(map_record[0].to_string())
+            .or_insert_with(|| map_record.get(1).unwrap_or_default().to_string());
+    }
+    Ok(lookup_map)
+}
+
+// operations can be delimited with either commas or pipes
+#[inline]
+fn split_operations(operations: &str) -> Vec<&str> {
+    operations.split([',', '|']).map(str::trim).collect()
+}
+
 // validate apply operations for required options
 // and prepare operations enum vec
 fn validate_operations(
@@ -816,6 +1355,7 @@ fn validate_operations(
                     return fail!("--new_column (-c) is required for censor operations.");
                 }
                 if censor_invokes == 0
+                    && CENSOR.get().is_none()
                     && CENSOR
                         .set({
                             let mut censored_words = Censor::Standard + Zealous + Sex;
@@ -841,6 +1381,7 @@ fn validate_operations(
                     return fail!("--comparand (-C) and --new_column (-c) is required for eudex.");
                 }
                 if eudex_invokes == 0
+                    && EUDEX_COMPARAND_HASH.get().is_none()
                     && EUDEX_COMPARAND_HASH
                         .set(eudex::Hash::new(flag_comparand))
                         .is_err()
@@ -849,6 +1390,31 @@ fn validate_operations(
                 }
                 eudex_invokes = eudex_invokes.saturating_add(1);
             }
+            Operations::E164 => {
+                if PHONE_REGION.get().is_none() {
+                    let region = if flag_comparand.is_empty() {
+                        None
+                    } else if let Ok(region) = flag_comparand
+                        .trim()
+                        .to_ascii_uppercase()
+                        .parse::<phonenumber::country::Id>()
+                    {
+                        Some(region)
+                    } else {
+                        return fail_clierror!(
+                            "Invalid e164 default region '{flag_comparand}'. Use an ISO 3166-1 \
+                             alpha-2 country code (e.g. US)."
+                        );
+                    };
+                    #[allow(clippy::let_underscore_untyped)]
+                    let _ = PHONE_REGION.set(region);
+                }
+            }
+            Operations::Hmac => {
+                if flag_comparand.is_empty() {
+                    return fail!("--comparand (-C) is required for hmac operation (the key).");
+                }
+            }
             Operations::Mtrim | Operations::Mltrim | Operations::Mrtrim => {
                 if flag_comparand.is_empty() {
                     return fail!("--comparand (-C) is required for match trim operations.");
@@ -861,7 +1427,7 @@ fn validate_operations(
                          operation."
                     );
                 }
-                if regex_replace_invokes == 0 {
+                if regex_replace_invokes == 0 && REGEX_REPLACE.get().is_none() {
                     let re = match regex::Regex::new(flag_comparand) {
                         Ok(re) => re,
                         Err(err) => {
@@ -917,18 +1483,33 @@ fn validate_operations(
                     "indiancomma" => INDIANCOMMA_POLICY,
                     _ => policies::COMMA_SEPARATOR,
                 };
-                if THOUSANDS_POLICY.set(separator_policy).is_err() {
+                if THOUSANDS_POLICY.get().is_none()
+                    && THOUSANDS_POLICY.set(separator_policy).is_err()
+                {
                     return fail!("Cannot initialize Thousands policy.");
                 };
             }
+            Operations::Geohash => {
+                if GEOHASH_PRECISION.get().is_none() {
+                    let precision = flag_formatstr
+                        .parse::<usize>()
+                        .unwrap_or(DEFAULT_GEOHASH_PRECISION);
+                    if !(1..=12).contains(&precision) {
+                        return fail!("geohash length (--formatstr) must be from 1 to 12.");
+                    }
+                    #[allow(clippy::let_underscore_untyped)]
+                    let _ = GEOHASH_PRECISION.set(precision);
+                }
+            }
             Operations::Round => {
-                if ROUND_PLACES
-                    .set(
-                        flag_formatstr
-                            .parse::<u32>()
-                            .unwrap_or(DEFAULT_ROUND_PLACES),
-                    )
-                    .is_err()
+                if ROUND_PLACES.get().is_none()
+                    && ROUND_PLACES
+                        .set(
+                            flag_formatstr
+                                .parse::<u32>()
+                                .unwrap_or(DEFAULT_ROUND_PLACES),
+                        )
+                        .is_err()
                 {
                     return fail!("Cannot initialize Round precision.");
                 };
@@ -939,6 +1520,7 @@ fn validate_operations(
                 }
 
                 if whatlang_invokes == 0
+                    && WHATLANG_CONFIDENCE_THRESHOLD.get().is_none()
                     && WHATLANG_CONFIDENCE_THRESHOLD
                         .set(if flag_comparand.is_empty() {
                             DEFAULT_THRESHOLD
@@ -1056,6 +1638,49 @@ fn apply_operations(
                     Err(e) => format!("decoding error: {e:?}"),
                 };
             }
+            Operations::Urlencode => {
+                *cell = urlencoding::encode(cell).into_owned();
+            }
+            Operations::E164 => {
+                if let Ok(number) = phonenumber::parse(*PHONE_REGION.get().unwrap(), cell.trim()) {
+                    if phonenumber::is_valid(&number) {
+                        *cell = number.format().mode(phonenumber::Mode::E164).to_string();
+                    }
+                }
+            }
+            Operations::Email => {
+                let email = cell.trim();
+                if is_valid_email(email) {
+                    *cell = email.to_lowercase();
+                }
+            }
+            Operations::Email_Check => {
+                *cell = is_valid_email(cell.trim()).to_string();
+            }
+            Operations::Urldecode => {
+                *cell = match urlencoding::decode(cell) {
+                    Ok(decoded) => decoded.into_owned(),
+                    Err(e) => format!("decoding error: {e:?}"),
+                };
+            }
+            Operations::Md5 => {
+                *cell = HEXLOWER.encode(&Md5::digest(cell.as_bytes()));
+            }
+            Operations::Sha1 => {
+                *cell = HEXLOWER.encode(&Sha1::digest(cell.as_bytes()));
+            }
+            Operations::Sha256 => {
+                *cell = HEXLOWER.encode(&Sha256::digest(cell.as_bytes()));
+            }
+            Operations::Blake3 => {
+                *cell = blake3::hash(cell.as_bytes()).to_hex().to_string();
+            }
+            Operations::Hmac => {
+                // HMAC takes keys of any length, so this can't fail
+                let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(comparand.as_bytes()).unwrap();
+                mac.update(cell.as_bytes());
+                *cell = HEXLOWER.encode(&mac.finalize().into_bytes());
+            }
             Operations::Escape => {
                 *cell = cell.escape_default().to_string();
             }
@@ -1148,6 +1773,19 @@ fn apply_operations(
                     }
                 }
             }
+            Operations::Geohash => {
+                if let Some(location) = parse_location(cell) {
+                    *cell = geohash_encode(location, *GEOHASH_PRECISION.get().unwrap());
+                }
+            }
+            Operations::Geohash_Decode => {
+                if let Some((lat, long)) = geohash_decode(cell.trim()) {
+                    *cell = format!("{}, {}", util::round_num(lat, 6), util::round_num(long, 6));
+                }
+            }
+            Operations::Currencycode => {
+                *cell = currency_code(cell).unwrap_or_default();
+            }
             Operations::Numtocurrency => {
                 // same 3 decimal place workaround as currencytonum
                 let fract_3digits2: &'static Regex = regex_once_cell!(r"\.\d\d\d$");
@@ -1219,73 +1857,253 @@ fn apply_operations(
                     }
                 }
             }
+            Operations::Dns_Lookup => {
+                *cell = dns_lookup_cached(cell);
+            }
+            Operations::Reverse_Dns => {
+                *cell = reverse_dns_cached(cell);
+            }
             Operations::Copy => {} // copy is a noop
         }
     }
 }
 
+// failed lookups are cached too, as they are usually the slowest ones
+#[cached(size = 100_000, key = "String", convert = r#"{ host.to_string() }"#)]
+fn dns_lookup_cached(host: &str) -> String {
+    let host = host.trim();
+    if host.is_empty() {
+        return String::new();
+    }
+    match (host, 0).to_socket_addrs() {
+        Ok(mut addrs) => addrs
+            .next()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    }
+}
+
+// a pragmatic email address check - a dot-atom local part & a domain with at least one dot
+#[inline]
+fn is_valid_email(email: &str) -> bool {
+    let email_re: &'static Regex = regex_once_cell!(
+        r"(?i)^[a-z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-z0-9]+(-+[a-z0-9]+)*(\.[a-z0-9]+(-+[a-z0-9]+)*)+$"
+    );
+    email.len() <= 254 && email_re.is_match(email)
+}
+
+// get the ISO 4217 currency code of a currency value, preferring an explicit
+// code (e.g. "CAD $10") over a currency symbol. The amount can use point, comma
+// and space separators, and apart from it, only a code and/or symbol are allowed.
+fn currency_code(cell: &str) -> Option<String> {
+    let amount_re: &'static Regex = regex_once_cell!(r"[+-]?\d(?:[\d.,\s]*\d)?");
+    let amount = amount_re.find(cell)?;
+
+    let (letters, symbols): (String, String) = cell[..amount.start()]
+        .chars()
+        .chain(cell[amount.end()..].chars())
+        .filter(|c| !c.is_whitespace())
+        .partition(char::is_ascii_alphabetic);
+    if symbols.chars().count() > 1 {
+        return None;
+    }
+    match letters.len() {
+        0 => {}
+        3 => {
+            let code = letters.to_ascii_uppercase();
+            return Some(if code == "RMB" {
+                "CNY".to_string()
+            } else {
+                code
+            });
+        }
+        _ => return None,
+    }
+
+    symbols
+        .chars()
+        .next()
+        .and_then(|c| match c {
+            '$' => Some("USD"),
+            '€' => Some("EUR"),
+            '£' => Some("GBP"),
+            '¥' => Some("JPY"),
+            '₹' => Some("INR"),
+            '֏' => Some("AMD"),
+            '₱' => Some("PHP"),
+            '₽' => Some("RUB"),
+            '₪' => Some("ILS"),
+            '₩' => Some("KRW"),
+            'ƒ' => Some("ANG"),
+            '฿' => Some("THB"),
+            '₫' => Some("VND"),
+            '₺' => Some("TRY"),
+            '₴' => Some("UAH"),
+            '₦' => Some("NGN"),
+            _ => None,
+        })
+        .map(str::to_string)
+}
+
+#[cached(size = 100_000, key = "String", convert = r#"{ ip.to_string() }"#)]
+fn reverse_dns_cached(ip: &str) -> String {
+    let Ok(ip_addr) = ip.trim().parse::<IpAddr>() else {
+        return String::new();
+    };
+    match dns_lookup::lookup_addr(&ip_addr) {
+        // without a PTR record, the resolver returns the address itself
+        Ok(hostname) if hostname != ip_addr.to_string() => hostname,
+        _ => String::new(),
+    }
+}
+
 #[cached(
     key = "String",
-    convert = r#"{ format!("{}", cell) }"#,
+    convert = r#"{ format!("{cell}{formatstr}") }"#,
     option = true,
     sync_writes = false
 )]
-fn search_cached(cell: &str, formatstr: &str) -> Option<String> {
+pub(crate) fn search_cached(cell: &str, formatstr: &str) -> Option<String> {
     let geocoder =
         GEOCODER.get_or_init(|| ReverseGeocoder::new(LOCS.get_or_init(Locations::from_memory)));
 
-    // regex for Location field. Accepts (lat, long) & lat, long
+    parse_location(cell).and_then(|(lat, long)| {
+        let search_result = geocoder.search((lat, long));
+        search_result.map(|locdetails| format_place(locdetails.record, formatstr))
+    })
+}
+
+// format a Geonames place record per the geocode --formatstr
+pub(crate) fn format_place(record: &Record, formatstr: &str) -> String {
+    #[allow(clippy::match_same_arms)]
+    // match arms are evaluated in order,
+    // so we're optimizing for the most common cases first
+    match formatstr {
+        "%+" | "city-state" => format!(
+            "{name}, {admin1}",
+            name = record.name,
+            admin1 = record.admin1,
+        ),
+        "city-country" => format!("{name}, {cc}", name = record.name, cc = record.cc),
+        "city-state-country" | "city-admin1-country" => format!(
+            "{name}, {admin1} {cc}",
+            name = record.name,
+            admin1 = record.admin1,
+            cc = record.cc
+        ),
+        "city" => record.name.to_string(),
+        "county" | "admin2" => record.admin2.to_string(),
+        "state" | "admin1" => record.admin1.to_string(),
+        "county-country" | "admin2-country" => {
+            format!("{admin2}, {cc}", admin2 = record.admin2, cc = record.cc)
+        }
+        "county-state-country" | "admin2-admin1-country" => format!(
+            "{admin2}, {admin1} {cc}",
+            admin2 = record.admin2,
+            admin1 = record.admin1,
+            cc = record.cc
+        ),
+        "country" => record.cc.to_string(),
+        "city-location" => format!("({lat}, {lon})", lat = record.lat, lon = record.lon),
+        _ => record.name.to_string(),
+    }
+}
+
+// parse a WGS84 location field. Accepts (lat, long) & lat, long
+pub(crate) fn parse_location(cell: &str) -> Option<(f64, f64)> {
     let locregex: &'static Regex = regex_once_cell!(
         r"(?-u)([+-]?[0-9]+\.?[0-9]*|\.[0-9]+),\s*([+-]?[0-9]+\.?[0-9]*|\.[0-9]+)"
     );
 
-    let loccaps = locregex.captures(cell);
-    loccaps.and_then(|loccaps| {
-        let lat = loccaps[1].to_string().parse::<f64>().unwrap_or_default();
-        let long = loccaps[2].to_string().parse::<f64>().unwrap_or_default();
-        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&long) {
-            let search_result = geocoder.search((lat, long));
-            search_result.map(|locdetails| {
-                #[allow(clippy::match_same_arms)]
-                // match arms are evaluated in order,
-                // so we're optimizing for the most common cases first
-                match formatstr {
-                    "%+" | "city-state" => format!(
-                        "{name}, {admin1}",
-                        name = locdetails.record.name,
-                        admin1 = locdetails.record.admin1,
-                    ),
-                    "city-country" => format!(
-                        "{name}, {cc}",
-                        name = locdetails.record.name,
-                        cc = locdetails.record.cc
-                    ),
-                    "city-state-country" | "city-admin1-country" => format!(
-                        "{name}, {admin1} {cc}",
-                        name = locdetails.record.name,
-                        admin1 = locdetails.record.admin1,
-                        cc = locdetails.record.cc
-                    ),
-                    "city" => locdetails.record.name.to_string(),
-                    "county" | "admin2" => locdetails.record.admin2.to_string(),
-                    "state" | "admin1" => locdetails.record.admin1.to_string(),
-                    "county-country" | "admin2-country" => format!(
-                        "{admin2}, {cc}",
-                        admin2 = locdetails.record.admin2,
-                        cc = locdetails.record.cc
-                    ),
-                    "county-state-country" | "admin2-admin1-country" => format!(
-                        "{admin2}, {admin1} {cc}",
-                        admin2 = locdetails.record.admin2,
-                        admin1 = locdetails.record.admin1,
-                        cc = locdetails.record.cc
-                    ),
-                    "country" => locdetails.record.cc.to_string(),
-                    _ => locdetails.record.name.to_string(),
-                }
-            })
+    let loccaps = locregex.captures(cell)?;
+    parse_lat_long(&loccaps[1], &loccaps[2])
+}
+
+// parse separate latitude & longitude fields, checking they're in range
+fn parse_lat_long(lat: &str, long: &str) -> Option<(f64, f64)> {
+    let lat = lat.trim().parse::<f64>().ok()?;
+    let long = long.trim().parse::<f64>().ok()?;
+    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&long) {
+        Some((lat, long))
+    } else {
+        None
+    }
+}
+
+// haversine great-circle distance in kilometers
+fn haversine_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+    let delta_lat = lat2 - lat1;
+    let delta_long = (to.1 - from.1).to_radians();
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * (delta_long / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+fn geohash_encode((lat, long): (f64, f64), precision: usize) -> String {
+    let mut lat_range = (-90.0, 90.0);
+    let mut long_range = (-180.0, 180.0);
+    let mut geohash = String::with_capacity(precision);
+    let mut is_long = true;
+    let mut bits = 0_u8;
+    let mut idx = 0_usize;
+
+    while geohash.len() < precision {
+        let (range, value) = if is_long {
+            (&mut long_range, long)
         } else {
-            None
+            (&mut lat_range, lat)
+        };
+        let mid = (range.0 + range.1) / 2.0;
+        idx <<= 1;
+        if value >= mid {
+            idx |= 1;
+            range.0 = mid;
+        } else {
+            range.1 = mid;
         }
-    })
+        is_long = !is_long;
+        bits += 1;
+        if bits == 5 {
+            geohash.push(GEOHASH_BASE32[idx] as char);
+            bits = 0;
+            idx = 0;
+        }
+    }
+    geohash
+}
+
+// decode a geohash to the (lat, long) center of its cell
+fn geohash_decode(geohash: &str) -> Option<(f64, f64)> {
+    if geohash.is_empty() || geohash.len() > 12 {
+        return None;
+    }
+    let mut lat_range = (-90.0, 90.0);
+    let mut long_range = (-180.0, 180.0);
+    let mut is_long = true;
+
+    for c in geohash.bytes() {
+        let idx = GEOHASH_BASE32
+            .iter()
+            .position(|&b| b == c.to_ascii_lowercase())?;
+        for shift in (0..5).rev() {
+            let range = if is_long {
+                &mut long_range
+            } else {
+                &mut lat_range
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            if (idx >> shift) & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            is_long = !is_long;
+        }
+    }
+    Some((
+        (lat_range.0 + lat_range.1) / 2.0,
+        (long_range.0 + long_range.1) / 2.0,
+    ))
 }
@@ -0,0 +1,200 @@
+static USAGE: &str = r#"
+Sniffs a CSV for its delimiter, quote character & preamble lines, and converts it
+to a clean RFC 4180 CSV - UTF-8 encoded, comma-delimited, with '"' quotes - in one step.
+
+It chains what `qsv sniff` and `qsv input` do, so that messy files (e.g. semicolon or
+tab-delimited exports with a few lines of notes before the header) can be canonicalized
+with a single invocation.
+
+The input is transcoded to UTF-8 if it starts with a byte order mark, or if its
+encoding is given with --encoding. Otherwise, invalid UTF-8 sequences are replaced with �.
+Records with fewer fields than the first record are padded with empty fields.
+
+The detected settings are reported to stderr, unless --quiet is set.
+
+Examples:
+Convert a semicolon-delimited export with a preamble to a clean CSV.
+
+  $ qsv autoconvert export.txt -o clean.csv
+
+Convert a Windows-1252 encoded file, sniffing its first 1000 records.
+
+  $ qsv autoconvert --encoding windows-1252 --sample 1000 legacy.csv > clean.csv
+
+Usage:
+    qsv autoconvert [options] [<input>]
+    qsv autoconvert --help
+
+autoconvert options:
+    --sample <n>           The number of records to sniff. [default: 100]
+    --encoding <label>     The encoding of the input (e.g. latin1, windows-1252,
+                           utf-16le, shift_jis). Uses the WHATWG encoding labels.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -d, --delimiter <arg>  The field delimiter of the input, if sniffing picks
+                           the wrong one. Must be a single character.
+    -q, --quiet            Do not report the detected settings to stderr.
+"#;
+
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+use encoding_rs::Encoding;
+use qsv_sniffer::{metadata::Quote, SampleSize, Sniffer};
+use serde::Deserialize;
+
+use crate::{
+    cmd::input::TranscodingReader,
+    config::{Config, Delimiter},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:      Option<String>,
+    flag_sample:    usize,
+    flag_encoding:  Option<String>,
+    flag_output:    Option<String>,
+    flag_delimiter: Option<Delimiter>,
+    flag_quiet:     bool,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    if args.flag_sample == 0 {
+        return fail_clierror!("--sample must be greater than zero.");
+    }
+
+    let mut encoding = match args.flag_encoding {
+        Some(ref label) => match Encoding::for_label(label.as_bytes()) {
+            Some(encoding) => Some(encoding),
+            None => return fail_clierror!("Unknown encoding: {label}"),
+        },
+        None => None,
+    };
+    let mut input: Box<dyn Read> = match args.arg_input {
+        Some(ref path) => Box::new(fs::File::open(path)?),
+        None => Box::new(io::stdin()),
+    };
+    if encoding.is_none() {
+        let mut bom = [0_u8; 3];
+        let len = read_prefix(&mut input, &mut bom)?;
+        // transcoding also strips the BOM, including a UTF-8 one
+        encoding = Encoding::for_bom(&bom[..len]).map(|(encoding, _)| encoding);
+        input = Box::new(io::Cursor::new(bom[..len].to_vec()).chain(input));
+    }
+
+    // the sniffer needs a seekable UTF-8 file, so stdin & transcoded input are staged
+    // in a temporary file first
+    let staged = match (&args.arg_input, encoding) {
+        (Some(_), None) => {
+            drop(input);
+            None
+        }
+        (_, Some(encoding)) => {
+            let mut tmpfile = tempfile::NamedTempFile::new()?;
+            io::copy(
+                &mut TranscodingReader::new(input, encoding, false),
+                &mut tmpfile,
+            )?;
+            Some(tmpfile)
+        }
+        (None, None) => {
+            let mut tmpfile = tempfile::NamedTempFile::new()?;
+            io::copy(&mut input, &mut tmpfile)?;
+            Some(tmpfile)
+        }
+    };
+    let sniff_path = match staged {
+        Some(ref tmpfile) => tmpfile.path().to_string_lossy().to_string(),
+        // safety: the input is only not staged when it's a file
+        None => args.arg_input.clone().unwrap(),
+    };
+
+    let mut sniffer = Sniffer::new();
+    sniffer.sample_size(SampleSize::Records(args.flag_sample));
+    if let Some(ref delimiter) = args.flag_delimiter {
+        sniffer.delimiter(delimiter.as_byte());
+    }
+    let metadata = match sniffer.sniff_path(&sniff_path) {
+        Ok(metadata) => metadata,
+        Err(e) => return fail_clierror!("Could not sniff the input: {e}"),
+    };
+    let dialect = metadata.dialect;
+
+    let mut rconfig = Config::new(&Some(sniff_path))
+        .delimiter(Some(Delimiter(dialect.delimiter)))
+        .no_headers(true)
+        .flexible(true);
+    rconfig = match dialect.quote {
+        Quote::Some(quote) => rconfig.quote(quote),
+        Quote::None => rconfig.quoting(false),
+    };
+    // always write commas, even if --output has a .tsv extension
+    let wconfig = Config::new(&args.flag_output).delimiter(Some(Delimiter(b',')));
+
+    if !args.flag_quiet {
+        winfo!(
+            "delimiter: {:?}, quote: {}, preamble rows: {}, header row: {}, encoding: {}",
+            dialect.delimiter as char,
+            match dialect.quote {
+                Quote::Some(quote) => format!("{:?}", quote as char),
+                Quote::None => "none".to_string(),
+            },
+            dialect.header.num_preamble_rows,
+            dialect.header.has_header_row,
+            encoding.map_or("UTF-8", Encoding::name),
+        );
+    }
+
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = wconfig.writer()?;
+    let mut record = csv::ByteRecord::new();
+    for _ in 0..dialect.header.num_preamble_rows {
+        if !rdr.read_byte_record(&mut record)? {
+            break;
+        }
+    }
+
+    let mut num_fields = None;
+    let mut str_record = csv::StringRecord::new();
+    let mut idx = 0_u64;
+    while rdr.read_byte_record(&mut record)? {
+        idx += 1;
+        let width = *num_fields.get_or_insert(record.len());
+        if record.len() > width {
+            return fail_clierror!(
+                "Record {idx} has {} fields, but the first record has {width}. Use `qsv \
+                 fixlengths` to fix the input first.",
+                record.len()
+            );
+        }
+
+        str_record.clear();
+        for field in &record {
+            str_record.push_field(&String::from_utf8_lossy(field));
+        }
+        for _ in record.len()..width {
+            str_record.push_field("");
+        }
+        wtr.write_record(&str_record)?;
+    }
+
+    Ok(wtr.flush()?)
+}
+
+/// Reads up to `buf.len()` bytes, as a single read may return fewer bytes than available.
+fn read_prefix(rdr: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match rdr.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(len)
+}
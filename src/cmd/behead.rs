@@ -1,10 +1,17 @@
 static USAGE: &str = r#"
 Drop a CSV file's header.
 
+Preamble lines before the header (e.g. titles or notes) can also be dropped
+with the --skip-lines option.
+
 Usage:
     qsv behead [options] [<input>]
     qsv behead --help
 
+behead options:
+    --skip-lines <arg>     The number of preamble lines before the header
+                           to drop as well.
+
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
@@ -16,18 +23,34 @@ use crate::{config::Config, util, CliResult};
 
 #[derive(Deserialize)]
 struct Args {
-    arg_input:   Option<String>,
-    flag_output: Option<String>,
+    arg_input:       Option<String>,
+    flag_skip_lines: Option<u64>,
+    flag_output:     Option<String>,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
-    let conf = Config::new(&args.arg_input).no_headers(false);
+    let skip_lines = args.flag_skip_lines.unwrap_or_default();
+    let mut conf = Config::new(&args.arg_input).no_headers(false);
+    if skip_lines > 0 {
+        // preamble lines rarely have the same number of fields as the data,
+        // so we read everything as data and skip the preamble & header ourselves
+        conf = conf.no_headers(true).flexible(true);
+    }
 
     let mut rdr = conf.reader()?;
     let mut wtr = Config::new(&args.flag_output).writer()?;
     let mut record = csv::ByteRecord::new();
 
+    if skip_lines > 0 {
+        // skip the preamble lines and the header row
+        for _i in 0..=skip_lines {
+            if !rdr.read_byte_record(&mut record)? {
+                break;
+            }
+        }
+    }
+
     while rdr.read_byte_record(&mut record)? {
         wtr.write_byte_record(&record)?;
     }
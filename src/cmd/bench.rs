@@ -0,0 +1,188 @@
+static USAGE: &str = r#"
+Runs a suite of qsv commands against a CSV and reports how long each one takes,
+so that qsv builds, feature flags & hardware can be compared.
+
+If <input> is not given, a synthetic CSV with --rows records is generated.
+The input is copied to a temporary directory first, so the commands that
+create files (e.g. index) do not touch the original file.
+
+Each command is run --repeat times, with its output discarded. The results are
+written to stdout as a CSV with the columns:
+  command, runs, min_secs, mean_secs, mb_per_sec, rows_per_sec
+where the throughputs are computed from the fastest run.
+
+The available benchmarks are:
+  count      - qsv count
+  index      - qsv index
+  stats      - qsv stats --everything
+  frequency  - qsv frequency
+  sort       - qsv sort
+  dedup      - qsv dedup
+  search     - qsv search on all columns
+  select     - qsv select the first column
+  slice      - qsv slice the last 10 records
+  join       - qsv join --left-semi of the CSV with itself, on the first column
+
+Examples:
+Benchmark all the commands against a generated CSV with 1 million records.
+
+  $ qsv bench --rows 1000000
+
+Benchmark count & stats against data.csv, running each command 5 times.
+
+  $ qsv bench --commands count,stats --repeat 5 data.csv
+
+Usage:
+    qsv bench [options] [<input>]
+    qsv bench --help
+
+bench options:
+    --commands <list>      The comma-separated list of benchmarks to run.
+                           [default: count,index,stats,frequency,sort,dedup,search,select,slice,join]
+    --rows <n>             The number of records of the generated CSV, when
+                           <input> is not given.
+                           [default: 100000]
+    --repeat <n>           The number of times each command is run.
+                           [default: 3]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+"#;
+
+use std::{env, fs, path::Path, process, time::Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::{config::Config, util, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:     Option<String>,
+    flag_commands: String,
+    flag_rows:     usize,
+    flag_repeat:   usize,
+    flag_output:   Option<String>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    if args.flag_repeat == 0 {
+        return fail_clierror!("--repeat must be greater than zero.");
+    }
+
+    let tmpdir = tempfile::tempdir()?;
+    let input_path = tmpdir.path().join("bench.csv");
+    match args.arg_input {
+        Some(ref input) => {
+            fs::copy(input, &input_path)?;
+        }
+        None => generate_csv(&input_path, args.flag_rows)?,
+    }
+    let input = input_path.to_string_lossy().to_string();
+    let output = tmpdir
+        .path()
+        .join("output.csv")
+        .to_string_lossy()
+        .to_string();
+
+    let file_size = fs::metadata(&input_path)?.len();
+    let row_count = util::count_rows(&Config::new(&Some(input.clone())))?;
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    wtr.write_record([
+        "command",
+        "runs",
+        "min_secs",
+        "mean_secs",
+        "mb_per_sec",
+        "rows_per_sec",
+    ])?;
+
+    // docopt would parse "--start -10" as two flags, so use the --start=-10 form
+    let slice_start = format!("--start=-{}", row_count.min(10));
+    for command in args.flag_commands.split(',').map(str::trim) {
+        let qsv_args: Vec<&str> = match command {
+            "count" => vec!["count", &input],
+            "index" => vec!["index", &input, "-o", &output],
+            "stats" => vec!["stats", "--everything", &input, "-o", &output],
+            "frequency" => vec!["frequency", &input, "-o", &output],
+            "sort" => vec!["sort", &input, "-o", &output],
+            "dedup" => vec!["dedup", &input, "-o", &output],
+            "search" => vec!["search", "[aeiou]", &input, "-o", &output],
+            "select" => vec!["select", "1", &input, "-o", &output],
+            "slice" => vec!["slice", &slice_start, &input, "-o", &output],
+            "join" => vec![
+                "join",
+                "--left-semi",
+                "1",
+                &input,
+                "1",
+                &input,
+                "-o",
+                &output,
+            ],
+            _ => return fail_clierror!("Unknown benchmark: {command}"),
+        };
+
+        let mut timings = Vec::with_capacity(args.flag_repeat);
+        for _ in 0..args.flag_repeat {
+            let start = Instant::now();
+            let status = process::Command::new(env::current_exe()?)
+                .args(&qsv_args)
+                .stdout(process::Stdio::null())
+                .stderr(process::Stdio::null())
+                .status()?;
+            timings.push(start.elapsed().as_secs_f64());
+            if !status.success() {
+                return fail_clierror!("qsv {} failed.", qsv_args.join(" "));
+            }
+        }
+
+        let min_secs = timings.iter().copied().fold(f64::INFINITY, f64::min);
+        let mean_secs = timings.iter().sum::<f64>() / timings.len() as f64;
+        wtr.write_record([
+            command,
+            &timings.len().to_string(),
+            &util::round_num(min_secs, 4),
+            &util::round_num(mean_secs, 4),
+            &util::round_num(file_size as f64 / 1_000_000.0 / min_secs, 2),
+            &util::round_num(row_count as f64 / min_secs, 0),
+        ])?;
+        // show the results as we go, as a full benchmark run can take a while
+        wtr.flush()?;
+    }
+
+    Ok(wtr.flush()?)
+}
+
+/// Generates a CSV with a mix of integer, string, low cardinality, float & date columns.
+fn generate_csv(path: &Path, rows: usize) -> CliResult<()> {
+    const NAMES: [&str; 8] = [
+        "Alice", "Bob", "Carol", "Dave", "Eve", "Frank", "Grace", "Heidi",
+    ];
+    const CATEGORIES: [&str; 4] = ["red", "green", "blue", "yellow"];
+
+    // use a fixed seed so benchmark runs are comparable
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut wtr = Config::new(&Some(path.to_string_lossy().to_string())).writer()?;
+    wtr.write_record(["id", "name", "category", "amount", "date", "notes"])?;
+    for id in 0..rows {
+        let name = NAMES[rng.gen_range(0..NAMES.len())];
+        wtr.write_record([
+            id.to_string().as_str(),
+            &format!("{name} {}", rng.gen_range(0..10_000)),
+            CATEGORIES[rng.gen_range(0..CATEGORIES.len())],
+            &format!("{:.2}", rng.gen_range(0.0..10_000.0)),
+            &format!(
+                "20{:02}-{:02}-{:02}",
+                rng.gen_range(0..24),
+                rng.gen_range(1..=12),
+                rng.gen_range(1..=28)
+            ),
+            &format!("record {id} for {name}, in a quoted field"),
+        ])?;
+    }
+    Ok(wtr.flush()?)
+}
@@ -0,0 +1,92 @@
+static USAGE: &str = r#"
+Computes a checksum of the contents of a CSV that does not depend on the order of
+its rows or on how its fields are quoted, so that two CSVs produced differently
+(e.g. exported by two different tools, or sorted differently) can be checked to
+contain the same data.
+
+Each record is parsed and hashed with BLAKE3, and the record hashes are combined
+with a commutative operation. The header row (unless --no-headers is set) and the
+number of records are also part of the checksum, so column order and duplicate
+records matter.
+
+The checksum is written to stdout as a 64-character hexadecimal string.
+
+Examples:
+Check that two exports contain the same data.
+
+  $ qsv checksum export1.csv
+  $ qsv checksum export2.tsv
+
+Usage:
+    qsv checksum [options] [<input>]
+    qsv checksum --help
+
+Common options:
+    -h, --help             Display this message
+    -n, --no-headers       When set, the first row will be hashed like any other
+                           row, instead of being treated as the header row.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    let mut rdr = rconfig.reader()?;
+
+    let mut final_hasher = blake3::Hasher::new();
+    if !rconfig.no_headers {
+        final_hasher.update(hash_record(rdr.byte_headers()?).as_bytes());
+    }
+
+    // the record hashes are summed lane by lane, as addition is commutative
+    let mut sum = [0_u64; 4];
+    let mut record_count = 0_u64;
+    let mut record = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        let hash = hash_record(&record);
+        for (lane, chunk) in sum.iter_mut().zip(hash.as_bytes().chunks_exact(8)) {
+            let chunk: [u8; 8] = chunk
+                .try_into()
+                .expect("chunks_exact(8) returns 8-byte chunks");
+            *lane = lane.wrapping_add(u64::from_le_bytes(chunk));
+        }
+        record_count += 1;
+    }
+
+    for lane in sum {
+        final_hasher.update(&lane.to_le_bytes());
+    }
+    final_hasher.update(&record_count.to_le_bytes());
+
+    woutinfo!("{}", final_hasher.finalize().to_hex());
+    Ok(())
+}
+
+/// Hashes the fields of a record, prefixing each field with its length
+/// so that e.g. ["ab", "c"] and ["a", "bc"] hash differently.
+fn hash_record(record: &csv::ByteRecord) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&(record.len() as u64).to_le_bytes());
+    for field in record {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize()
+}
@@ -0,0 +1,61 @@
+static USAGE: &str = r#"
+Provides input from the clipboard or saves output to the clipboard.
+
+Note that when running this command on Linux, the clipboard contents are only
+available while qsv is running, as the clipboard is owned by the application
+that set it.
+
+Examples:
+Pipe the CSV data copied from a spreadsheet into qsv and show its stats.
+
+  $ qsv clipboard | qsv stats
+
+Sort a CSV and copy the result to the clipboard, ready to be pasted into a spreadsheet.
+
+  $ qsv sort -s name data.csv | qsv clipboard --save
+
+Usage:
+    qsv clipboard [options]
+    qsv clipboard --help
+
+clipboard options:
+    -s, --save             Save stdin to the clipboard instead of writing
+                           the clipboard contents to stdout.
+
+Common options:
+    -h, --help             Display this message
+"#;
+
+use std::io::{self, Read, Write};
+
+use arboard::Clipboard;
+use serde::Deserialize;
+
+use crate::{util, CliError, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    flag_save: bool,
+}
+
+impl From<arboard::Error> for CliError {
+    fn from(err: arboard::Error) -> CliError {
+        CliError::Other(format!("Clipboard error: {err}"))
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let mut clipboard = Clipboard::new()?;
+
+    if args.flag_save {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        clipboard.set_text(buffer)?;
+    } else {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(clipboard.get_text()?.as_bytes())?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
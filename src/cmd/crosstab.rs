@@ -0,0 +1,212 @@
+static USAGE: &str = r#"
+Compute a two-way contingency table (a crosstab) of two columns of CSV data.
+
+There is a row for each distinct value of <row-column> and a column for each
+distinct value of <col-column>, both sorted in ascending order. Each cell holds
+the number of records with that pair of values:
+
+    region/product,apples,pears
+    east,10,3
+    west,4,7
+
+The first header is "<row-column>/<col-column>". Empty values are shown as (NULL).
+
+Since this computes an exact table, memory proportional to the number of distinct
+pairs of values is required.
+
+Examples:
+Count the sales of each product per region, with totals.
+
+  $ qsv crosstab region product --margins sales.csv
+
+Show the share of each product within each region, as percentages.
+
+  $ qsv crosstab region product --normalize row sales.csv
+
+Usage:
+    qsv crosstab [options] <row-column> <col-column> [<input>]
+    qsv crosstab --help
+
+crosstab options:
+    --normalize <mode>     Show percentages instead of counts. The modes are:
+                             none   - counts
+                             all    - percentage of all records
+                             row    - percentage of the records in the row
+                             column - percentage of the records in the column
+                           [default: none]
+    --margins              Add a (Total) column and a (Total) row.
+    --round <places>       The number of decimal places to round percentages to.
+                           [default: 2]
+    --no-nulls             Don't count records where either value is empty.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be treated as headers.
+                           The columns are then selected by their 1-based index.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_row_column:  SelectColumns,
+    arg_col_column:  SelectColumns,
+    arg_input:       Option<String>,
+    flag_normalize:  String,
+    flag_margins:    bool,
+    flag_round:      u32,
+    flag_no_nulls:   bool,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+enum Normalize {
+    None,
+    All,
+    Row,
+    Column,
+}
+
+const NULL_VALUE: &[u8] = b"(NULL)";
+const TOTAL_LABEL: &str = "(Total)";
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let normalize = match args.flag_normalize.as_str() {
+        "none" => Normalize::None,
+        "all" => Normalize::All,
+        "row" => Normalize::Row,
+        "column" => Normalize::Column,
+        mode => return fail_clierror!("Invalid --normalize mode: {mode}"),
+    };
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+
+    let row_idx = single_column(&args.arg_row_column, &headers, &rconfig, "<row-column>")?;
+    let col_idx = single_column(&args.arg_col_column, &headers, &rconfig, "<col-column>")?;
+    let column_name = |idx: usize| {
+        if rconfig.no_headers {
+            (idx + 1).to_string()
+        } else {
+            String::from_utf8_lossy(&headers[idx]).to_string()
+        }
+    };
+
+    let mut counts: HashMap<(Vec<u8>, Vec<u8>), u64> = HashMap::new();
+    let mut row_totals: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+    let mut col_totals: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+    let mut grand_total = 0_u64;
+
+    let mut record = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        let row_value = record.get(row_idx).unwrap_or_default();
+        let col_value = record.get(col_idx).unwrap_or_default();
+        if args.flag_no_nulls && (row_value.is_empty() || col_value.is_empty()) {
+            continue;
+        }
+        let row_value = if row_value.is_empty() {
+            NULL_VALUE
+        } else {
+            row_value
+        };
+        let col_value = if col_value.is_empty() {
+            NULL_VALUE
+        } else {
+            col_value
+        };
+
+        *counts
+            .entry((row_value.to_vec(), col_value.to_vec()))
+            .or_insert(0) += 1;
+        *row_totals.entry(row_value.to_vec()).or_insert(0) += 1;
+        *col_totals.entry(col_value.to_vec()).or_insert(0) += 1;
+        grand_total += 1;
+    }
+
+    let cell = |count: u64, row_total: u64, col_total: u64| -> String {
+        let denominator = match normalize {
+            Normalize::None => return count.to_string(),
+            Normalize::All => grand_total,
+            Normalize::Row => row_total,
+            Normalize::Column => col_total,
+        };
+        if denominator == 0 {
+            return "0".to_string();
+        }
+        util::round_num(count as f64 * 100.0 / denominator as f64, args.flag_round)
+    };
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    let mut header = csv::ByteRecord::new();
+    header.push_field(format!("{}/{}", column_name(row_idx), column_name(col_idx)).as_bytes());
+    for col_value in col_totals.keys() {
+        header.push_field(col_value);
+    }
+    if args.flag_margins {
+        header.push_field(TOTAL_LABEL.as_bytes());
+    }
+    wtr.write_byte_record(&header)?;
+
+    let mut output = csv::ByteRecord::new();
+    for (row_value, row_total) in &row_totals {
+        output.clear();
+        output.push_field(row_value);
+        for (col_value, col_total) in &col_totals {
+            let count = counts
+                .get(&(row_value.clone(), col_value.clone()))
+                .copied()
+                .unwrap_or(0);
+            output.push_field(cell(count, *row_total, *col_total).as_bytes());
+        }
+        if args.flag_margins {
+            output.push_field(cell(*row_total, *row_total, grand_total).as_bytes());
+        }
+        wtr.write_byte_record(&output)?;
+    }
+
+    if args.flag_margins {
+        output.clear();
+        output.push_field(TOTAL_LABEL.as_bytes());
+        for col_total in col_totals.values() {
+            output.push_field(cell(*col_total, grand_total, *col_total).as_bytes());
+        }
+        output.push_field(cell(grand_total, grand_total, grand_total).as_bytes());
+        wtr.write_byte_record(&output)?;
+    }
+
+    Ok(wtr.flush()?)
+}
+
+/// Resolves a column selection that must select exactly one column.
+fn single_column(
+    sel: &SelectColumns,
+    headers: &csv::ByteRecord,
+    rconfig: &Config,
+    arg_name: &str,
+) -> CliResult<usize> {
+    let selection = sel.selection(headers, !rconfig.no_headers)?;
+    if selection.len() != 1 {
+        return fail_clierror!(
+            "{arg_name} must select exactly one column. {} columns selected.",
+            selection.len()
+        );
+    }
+    Ok(selection[0])
+}
@@ -0,0 +1,217 @@
+static USAGE: &str = r#"
+Formats recognized dates in the selected columns to a specified date format.
+
+Dates are parsed with fuzzy multi-format detection, recognizing many
+date & datetime formats (e.g. "2021-08-03", "Aug 3, 2021", "3/8/2021 5:30 PM",
+"1627982400" - epoch timestamps, etc.), and are formatted to ISO 8601 / RFC 3339
+format by default.
+
+Examples:
+Format dates in the Open Date column to ISO 8601/RFC 3339 format:
+
+  $ qsv datefmt 'Open Date' file.csv
+
+Format multiple date columns in file.csv to ISO 8601/RFC 3339 format:
+
+  $ qsv datefmt 'Open Date,Modified Date,Closed Date' file.csv
+
+Format dates in the OpenDate column using '%Y-%m-%d' format, parsing ambiguous
+dates like 03/08/2021 as the 3rd of August:
+
+  $ qsv datefmt OpenDate --formatstr '%Y-%m-%d' --prefer-dmy file.csv
+
+Put the formatted OpenDate & CloseDate into new columns, failing on invalid dates:
+
+  $ qsv datefmt OpenDate,CloseDate --new-column open_iso,close_iso --invalid error file.csv
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_datefmt.rs.
+
+Usage:
+    qsv datefmt [--formatstr=<string>] [options] <column> [<input>]
+    qsv datefmt --help
+
+datefmt arguments:
+    <column>                    The column/s to format. See 'qsv select --help'
+                                for the format details.
+    <input>                     The input file to read from. If not specified, reads from stdin.
+
+datefmt options:
+    -c, --new-column <name>     Put the formatted values in new columns instead.
+                                Set a comma-separated list of names, one for each
+                                selected column.
+    -r, --rename <name>         New names for the formatted columns, as a
+                                comma-separated list, one for each selected column.
+    -f, --formatstr=<string>    The date format to use. For formats, see
+                                https://docs.rs/chrono/latest/chrono/format/strftime/
+                                Defaults to ISO 8601 / RFC 3339 date & time format.
+                                [default: %+]
+    --prefer-dmy                Prefer to parse dates in dmy format. Otherwise, use mdy format.
+    --keep-zero-time            If a formatted date ends with "T00:00:00+00:00", keep the time
+                                instead of removing it.
+    --invalid <policy>          What to do with values that cannot be parsed as dates.
+                                  keep  - keep the original value.
+                                  empty - replace it with an empty string.
+                                  error - stop and return an error.
+                                [default: keep]
+
+Common options:
+    -h, --help                  Display this message
+    -o, --output <file>         Write output to <file> instead of stdout.
+    -n, --no-headers            When set, the first row will not be interpreted
+                                as headers.
+    -d, --delimiter <arg>       The field delimiter for reading CSV data.
+                                Must be a single character. (default: ,)
+    -p, --progressbar           Show progress bars. Not valid for stdin.
+"#;
+
+use indicatif::{ProgressBar, ProgressDrawTarget};
+use qsv_dateparser::parse_with_preference;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_column:          SelectColumns,
+    arg_input:           Option<String>,
+    flag_new_column:     Option<String>,
+    flag_rename:         Option<String>,
+    flag_formatstr:      String,
+    flag_prefer_dmy:     bool,
+    flag_keep_zero_time: bool,
+    flag_invalid:        String,
+    flag_output:         Option<String>,
+    flag_no_headers:     bool,
+    flag_delimiter:      Option<Delimiter>,
+    flag_progressbar:    bool,
+}
+
+#[derive(PartialEq)]
+enum InvalidPolicy {
+    Keep,
+    Empty,
+    Error,
+}
+
+fn replace_column_value(
+    record: &csv::StringRecord,
+    column_index: usize,
+    new_value: &str,
+) -> csv::StringRecord {
+    record
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| if i == column_index { new_value } else { v })
+        .collect()
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let invalid_policy = match args.flag_invalid.to_lowercase().as_str() {
+        "keep" => InvalidPolicy::Keep,
+        "empty" => InvalidPolicy::Empty,
+        "error" => InvalidPolicy::Error,
+        _ => return fail_clierror!("Invalid --invalid policy: {}", args.flag_invalid),
+    };
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.arg_column);
+
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    let headers = rdr.byte_headers()?.clone();
+    let sel = rconfig.selection(&headers)?;
+
+    let mut headers = rdr.headers()?.clone();
+    if let Some(new_name) = args.flag_rename {
+        let new_col_names = util::ColumnNameParser::new(&new_name).parse()?;
+        if new_col_names.len() != sel.len() {
+            return fail!("Number of new columns does not match input column selection.");
+        }
+        for (i, col_index) in sel.iter().enumerate() {
+            headers = replace_column_value(&headers, *col_index, &new_col_names[i]);
+        }
+    }
+    if let Some(ref new_column) = args.flag_new_column {
+        let new_col_names = util::ColumnNameParser::new(new_column).parse()?;
+        if new_col_names.len() != sel.len() {
+            return fail!("Number of new columns does not match input column selection.");
+        }
+        for new_col_name in new_col_names {
+            headers.push_field(&new_col_name);
+        }
+    }
+    if !rconfig.no_headers {
+        wtr.write_record(&headers)?;
+    }
+
+    // prep progress bar
+    let show_progress =
+        (args.flag_progressbar || std::env::var("QSV_PROGRESSBAR").is_ok()) && !rconfig.is_stdin();
+    let progress = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr_with_hz(5));
+    if show_progress {
+        util::prep_progress(&progress, util::count_rows(&rconfig)?);
+    } else {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
+    let prefer_dmy = args.flag_prefer_dmy || rconfig.get_dmy_preference();
+
+    let mut record = csv::StringRecord::new();
+    let mut cell = String::new();
+    let mut formatted_cells: Vec<String> = Vec::with_capacity(sel.len());
+    let mut row_number = 0_u64;
+    while rdr.read_record(&mut record)? {
+        row_number += 1;
+        if show_progress {
+            progress.inc(1);
+        }
+
+        formatted_cells.clear();
+        for col_index in sel.iter() {
+            record[*col_index].clone_into(&mut cell);
+            if !cell.is_empty() {
+                if let Ok(parsed_date) = parse_with_preference(&cell, prefer_dmy) {
+                    let formatted_date = parsed_date.format(&args.flag_formatstr).to_string();
+                    if !args.flag_keep_zero_time && formatted_date.ends_with("T00:00:00+00:00") {
+                        cell = formatted_date[..10].to_string();
+                    } else {
+                        cell = formatted_date;
+                    }
+                } else if invalid_policy == InvalidPolicy::Empty {
+                    cell.clear();
+                } else if invalid_policy == InvalidPolicy::Error {
+                    return fail_clierror!(
+                        "Invalid date in column {} of record {row_number}: {cell}",
+                        col_index + 1
+                    );
+                }
+            }
+            formatted_cells.push(cell.clone());
+        }
+
+        if args.flag_new_column.is_some() {
+            for formatted_cell in &formatted_cells {
+                record.push_field(formatted_cell);
+            }
+        } else {
+            for (col_index, formatted_cell) in sel.iter().zip(formatted_cells.iter()) {
+                record = replace_column_value(&record, *col_index, formatted_cell);
+            }
+        }
+        wtr.write_record(&record)?;
+    }
+
+    if show_progress {
+        util::finish_progress(&progress);
+    }
+    Ok(wtr.flush()?)
+}
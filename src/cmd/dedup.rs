@@ -147,11 +147,20 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         util::njobs(args.flag_jobs);
 
         let mut all = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
-        all.par_sort_by(|r1, r2| {
-            let a = sel.select(r1);
-            let b = sel.select(r2);
-            iter_cmp(a, b)
-        });
+        if ignore_case {
+            // sort case-insensitively first so that records that only differ in case
+            // end up next to each other, then case-sensitively for a stable order
+            all.par_sort_by(|r1, r2| {
+                iter_cmp_ignore_case(sel.select(r1), sel.select(r2))
+                    .then_with(|| iter_cmp(sel.select(r1), sel.select(r2)))
+            });
+        } else {
+            all.par_sort_by(|r1, r2| {
+                let a = sel.select(r1);
+                let b = sel.select(r2);
+                iter_cmp(a, b)
+            });
+        }
 
         for (current, current_record) in all.iter().enumerate() {
             let a = sel.select(current_record);
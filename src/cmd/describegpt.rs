@@ -0,0 +1,230 @@
+static USAGE: &str = r#"
+Infers a description, a data dictionary and tags for a CSV using a Large Language
+Model (LLM) through an OpenAI-compatible chat completions API.
+
+Only the summary statistics (from 'qsv stats') and the frequency distribution of the
+most common values of each column (from 'qsv frequency') are sent to the API. The
+data in the CSV itself never leaves your machine.
+
+Use --base-url to use another OpenAI-compatible API, e.g. a local model served by
+Ollama or llama.cpp. The API key is read from the --api-key option or the
+QSV_OPENAI_KEY environment variable. It's only required by the default OpenAI API.
+
+Examples:
+Infer all the metadata of data.csv:
+
+  $ qsv describegpt data.csv --all
+
+Infer a data dictionary using a local model and save it as JSON:
+
+  $ qsv describegpt data.csv --dictionary --base-url http://localhost:11434/v1 \
+      --model llama2 --json -o dictionary.json
+
+Usage:
+    qsv describegpt [options] <input>
+    qsv describegpt --help
+
+describegpt arguments:
+    <input>                The CSV file to describe. Stdin is not supported.
+
+describegpt options:
+    -A, --all              Infer the description, data dictionary & tags.
+    --description          Infer a short description of the dataset.
+    --dictionary           Infer a data dictionary of the columns of the dataset.
+    --tags                 Infer tags (keywords) describing the dataset.
+    -k, --api-key <key>    The API key to use. If not set, the QSV_OPENAI_KEY
+                           environment variable is used.
+    -u, --base-url <url>   The base URL of the OpenAI-compatible API.
+                           [default: https://api.openai.com/v1]
+    -m, --model <model>    The model to use.
+                           [default: gpt-3.5-turbo]
+    --max-tokens <n>       The maximum number of tokens of each inferred result.
+                           [default: 500]
+    --timeout <secs>       Timeout for each API request in seconds.
+                           [default: 120]
+    --json                 Return the inferred results as JSON.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+"#;
+
+use std::{env, fs, io::Write, path::Path, process, time::Duration};
+
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{util, CliError, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:        String,
+    flag_all:         bool,
+    flag_description: bool,
+    flag_dictionary:  bool,
+    flag_tags:        bool,
+    flag_api_key:     Option<String>,
+    flag_base_url:    String,
+    flag_model:       String,
+    flag_max_tokens:  u32,
+    flag_timeout:     u64,
+    flag_json:        bool,
+    flag_output:      Option<String>,
+}
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    if !(args.flag_all || args.flag_description || args.flag_dictionary || args.flag_tags) {
+        return fail_clierror!(
+            "Set at least one of --description, --dictionary, --tags or --all to infer."
+        );
+    }
+    let api_key = args
+        .flag_api_key
+        .clone()
+        .or_else(|| env::var("QSV_OPENAI_KEY").ok())
+        .unwrap_or_default();
+    if api_key.is_empty() && args.flag_base_url.trim_end_matches('/') == DEFAULT_BASE_URL {
+        return fail_clierror!(
+            "An API key is required by the OpenAI API. Set it with --api-key or the \
+             QSV_OPENAI_KEY environment variable."
+        );
+    }
+    if !Path::new(&args.arg_input).is_file() {
+        return fail_clierror!("Input file '{}' does not exist.", args.arg_input);
+    }
+
+    let stats = run_qsv(&[
+        "stats",
+        &args.arg_input,
+        "--infer-dates",
+        "--dates-whitelist",
+        "all",
+    ])?;
+    let frequency = run_qsv(&["frequency", &args.arg_input, "--limit", "10"])?;
+    let summary = format!(
+        "Here are the summary statistics of each column of a CSV file, as a \
+         CSV:\n\n{stats}\n\nHere is the frequency distribution of the 10 most common values of \
+         each column, as a CSV:\n\n{frequency}\n\n"
+    );
+
+    let client = match Client::builder()
+        .user_agent(util::DEFAULT_USER_AGENT)
+        .use_rustls_tls()
+        .timeout(Duration::from_secs(args.flag_timeout))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return fail_clierror!("Cannot build reqwest client: {e}.");
+        }
+    };
+
+    let mut inferred = serde_json::Map::new();
+    let prompts = [
+        (
+            "description",
+            args.flag_description,
+            "Write a short description of the dataset, in a paragraph of no more than 5 \
+             sentences. Do not list the summary statistics.",
+        ),
+        (
+            "dictionary",
+            args.flag_dictionary,
+            "Write a data dictionary of the dataset, as a Markdown table with the columns Name, \
+             Type, Label and Description, with one row for each column of the dataset.",
+        ),
+        (
+            "tags",
+            args.flag_tags,
+            "Write a comma-separated list of no more than 10 keywords/tags describing the \
+             dataset. Only output the list.",
+        ),
+    ];
+    for (kind, wanted, instruction) in prompts {
+        if !(wanted || args.flag_all) {
+            continue;
+        }
+        log::debug!("inferring {kind}");
+        let result = chat_completion(&client, &args, &api_key, &format!("{summary}{instruction}"))?;
+        inferred.insert(kind.to_string(), serde_json::Value::String(result));
+    }
+
+    let mut output = String::new();
+    if args.flag_json {
+        output = serde_json::to_string_pretty(&inferred)
+            .map_err(|e| CliError::Other(format!("Cannot serialize results to JSON: {e}")))?;
+        output.push('\n');
+    } else {
+        for (kind, result) in &inferred {
+            let title = kind[..1].to_uppercase() + &kind[1..];
+            output.push_str(&format!(
+                "{title}:\n\n{}\n\n",
+                result.as_str().unwrap_or_default()
+            ));
+        }
+    }
+
+    match args.flag_output {
+        Some(output_file) => fs::write(output_file, output)?,
+        None => std::io::stdout().write_all(output.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Runs a qsv command with the current qsv binary, returning its stdout.
+fn run_qsv(qsv_args: &[&str]) -> CliResult<String> {
+    let qsv_output = process::Command::new(env::current_exe()?)
+        .args(qsv_args)
+        .output()?;
+    if !qsv_output.status.success() {
+        return fail_clierror!(
+            "qsv {} failed: {}",
+            qsv_args[0],
+            String::from_utf8_lossy(&qsv_output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&qsv_output.stdout).to_string())
+}
+
+/// Sends the prompt to the chat completions API and returns the content of the reply.
+fn chat_completion(client: &Client, args: &Args, api_key: &str, prompt: &str) -> CliResult<String> {
+    let request_body = json!({
+        "model": args.flag_model,
+        "max_tokens": args.flag_max_tokens,
+        "messages": [
+            {"role": "user", "content": prompt}
+        ],
+    });
+
+    let mut request = client
+        .post(format!(
+            "{}/chat/completions",
+            args.flag_base_url.trim_end_matches('/')
+        ))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(request_body.to_string());
+    if !api_key.is_empty() {
+        request = request.bearer_auth(api_key);
+    }
+
+    let response = match request.send() {
+        Ok(response) => response,
+        Err(e) => return fail_clierror!("Cannot send request to {}: {e}", args.flag_base_url),
+    };
+    let status = response.status();
+    let response_text = response.text().unwrap_or_default();
+    if !status.is_success() {
+        return fail_clierror!("The API returned an error ({status}): {response_text}");
+    }
+
+    let response_json: serde_json::Value = serde_json::from_str(&response_text).unwrap_or_default();
+    match response_json["choices"][0]["message"]["content"].as_str() {
+        Some(content) => Ok(content.trim().to_string()),
+        None => fail_clierror!("Unexpected API response: {response_text}"),
+    }
+}
@@ -30,6 +30,13 @@ diff options:
                                 but have different content) will always be kept together
                                 in the sorted diff result and so won't be sorted
                                 independently from each other.
+    --ignore-columns <arg...>   The column indices to ignore when diffing, as a comma
+                                separated list of indices, e.g. 2,3. Ignored columns
+                                are not compared and are left out of the diff result.
+                                Key columns cannot be ignored.
+    --trim                      Trim leading & trailing whitespace of all fields
+                                (including headers) before diffing, so that fields
+                                only differing in whitespace are considered equal.
     -j, --jobs <arg>            The number of jobs to run in parallel.
                                 When not set, the number of jobs is set to the number
                                 of CPUs detected.
@@ -62,6 +69,8 @@ struct Args {
     flag_delimiter_right:  Option<Delimiter>,
     flag_key:              Option<String>,
     flag_sort_columns:     Option<String>,
+    flag_ignore_columns:   Option<String>,
+    flag_trim:             bool,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -81,25 +90,45 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         );
     }
 
-    let primary_key_cols = match args.flag_key {
+    let mut primary_key_cols = match args.flag_key {
         None => vec![0],
-        Some(s) => s
-            .split(',')
-            .map(str::parse::<usize>)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|err| CliError::Other(err.to_string()))?,
+        Some(s) => parse_column_indices(&s)?,
     };
 
-    let sort_cols = args
+    let mut sort_cols = args
         .flag_sort_columns
-        .map(|s| {
-            s.split(',')
-                .map(str::parse::<usize>)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|err| CliError::Other(err.to_string()))
-        })
+        .map(|s| parse_column_indices(&s))
         .transpose()?;
 
+    let ignore_cols = args
+        .flag_ignore_columns
+        .map(|s| parse_column_indices(&s))
+        .transpose()?
+        .unwrap_or_default();
+    if let Some(key_col) = primary_key_cols.iter().find(|k| ignore_cols.contains(k)) {
+        return fail_clierror!("Key column {key_col} cannot be ignored.");
+    }
+
+    // when ignoring columns or whitespace, we diff normalized copies of the inputs instead.
+    // The temp files need to outlive the readers, so we keep them around until we're done.
+    let (rconfig_left, rconfig_right, _tmpfiles) = if ignore_cols.is_empty() && !args.flag_trim {
+        (rconfig_left, rconfig_right, vec![])
+    } else {
+        // the column indices shift to the left for every ignored column before them
+        let shift = |col: usize| col - ignore_cols.iter().filter(|&&i| i < col).count();
+        primary_key_cols = primary_key_cols.into_iter().map(shift).collect();
+        if let Some(ref mut sort_cols) = sort_cols {
+            if let Some(sort_col) = sort_cols.iter().find(|c| ignore_cols.contains(c)) {
+                return fail_clierror!("Sort column {sort_col} cannot be ignored.");
+            }
+            *sort_cols = sort_cols.iter().map(|&c| shift(c)).collect();
+        }
+
+        let (left_tmp, left_config) = normalize(&rconfig_left, &ignore_cols, args.flag_trim)?;
+        let (right_tmp, right_config) = normalize(&rconfig_right, &ignore_cols, args.flag_trim)?;
+        (left_config, right_config, vec![left_tmp, right_tmp])
+    };
+
     let wtr = Config::new(&args.flag_output).writer()?;
     let mut csv_rdr_left = rconfig_left.reader()?;
     let mut csv_rdr_right = rconfig_right.reader()?;
@@ -134,6 +163,56 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(csv_diff_writer.write_diff_byte_records(diff_byte_records)?)
 }
 
+fn parse_column_indices(s: &str) -> CliResult<Vec<usize>> {
+    s.split(',')
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| CliError::Other(err.to_string()))
+}
+
+/// Writes a copy of the input to a temp file without the ignored columns and,
+/// if `trim` is set, with all fields trimmed. Returns the temp file along with
+/// the Config to read it.
+fn normalize(
+    rconfig: &Config,
+    ignore_cols: &[usize],
+    trim: bool,
+) -> CliResult<(tempfile::NamedTempFile, Config)> {
+    let tmpfile = tempfile::NamedTempFile::new()?;
+    let tmp_path = Some(tmpfile.path().to_string_lossy().to_string());
+
+    // read the header row (if any) as a regular record, so it's normalized as well
+    let no_headers = rconfig.no_headers;
+    let mut rdr = Config::new(
+        &rconfig
+            .path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string()),
+    )
+    .delimiter(Some(Delimiter(rconfig.get_delimiter())))
+    .no_headers(true)
+    .reader()?;
+    let mut wtr = Config::new(&tmp_path).writer()?;
+
+    let mut record = csv::ByteRecord::new();
+    let mut normalized = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        if trim {
+            record.trim();
+        }
+        normalized.clear();
+        for (i, field) in record.iter().enumerate() {
+            if !ignore_cols.contains(&i) {
+                normalized.push_field(field);
+            }
+        }
+        wtr.write_byte_record(&normalized)?;
+    }
+    wtr.flush()?;
+
+    Ok((tmpfile, Config::new(&tmp_path).no_headers(no_headers)))
+}
+
 struct CsvDiffWriter<W: Write> {
     csv_writer: csv::Writer<W>,
 }
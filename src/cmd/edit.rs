@@ -0,0 +1,361 @@
+static USAGE: &str = r#"
+Replaces the value of a single cell, or of a batch of cells listed in a patch file,
+by streaming the CSV through. The CSV is never loaded into memory, so this works on
+arbitrarily large files.
+
+Everything but the edited cells is copied byte for byte, keeping the original quoting
+and line endings. Only the edited cells are quoted, and only when needed.
+
+The row is the 0-based index of the record, not counting the header row (the same
+numbering used by 'qsv slice'). The column can be a column name or a 1-based column
+index (see 'qsv select --help' for the format details).
+
+An error is returned if a row or column is out of bounds.
+
+Examples:
+Set the value of the "city" column of the first record to "Boston":
+
+  $ qsv edit data.csv city 0 Boston
+
+Set the value of the 3rd column of the 11th record, editing data.csv in place:
+
+  $ qsv edit data.csv 3 10 'New value' --in-place
+
+Apply the edits in patch.csv, a CSV with the columns row, column & value:
+
+  $ qsv edit data.csv --patch patch.csv
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_edit.rs.
+
+Usage:
+    qsv edit [options] <input> <column> <row> <value>
+    qsv edit [options] --patch <file> <input>
+    qsv edit --help
+
+edit arguments:
+    <input>                The CSV file to edit. Stdin is supported (use -).
+    <column>               The column of the cell to edit.
+    <row>                  The 0-based row index of the cell to edit.
+    <value>                The new value of the cell.
+
+edit options:
+    --patch <file>         Apply all the edits in <file>, a CSV with the columns
+                           row, column & value (in that order, with a header row).
+    -i, --in-place         Overwrite the input file with the edited CSV, keeping
+                           its permissions. Cannot be used with --output.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers. The first row is then row 0.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    rc::Rc,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    arg_column:      Option<String>,
+    arg_row:         Option<u64>,
+    arg_value:       Option<String>,
+    flag_patch:      Option<String>,
+    flag_in_place:   bool,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+/// The edits to apply, keyed by row index, with the column index & new value of each edit.
+type Edits = BTreeMap<u64, Vec<(usize, String)>>;
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    if args.flag_in_place && rconfig.is_stdin() {
+        return fail_clierror!("--in-place cannot be used with stdin.");
+    }
+    if args.flag_in_place && args.flag_output.is_some() {
+        return fail!("--in-place cannot be used with --output.");
+    }
+
+    // keep the raw bytes of the input, so the records can be copied verbatim
+    let raw = Rc::new(RefCell::new(RawBytes::default()));
+    let mut rdr = rconfig.from_reader(RawTee {
+        inner: rconfig.io_reader()?,
+        raw:   Rc::clone(&raw),
+    });
+    let headers = rdr.byte_headers()?.clone();
+
+    // (row, column, value) of each edit, before resolving the columns
+    let raw_edits: Vec<(u64, String, String)> = if let Some(ref patch) = args.flag_patch {
+        let mut patch_rdr = Config::new(&Some(patch.clone())).reader()?;
+        let mut raw_edits = Vec::new();
+        for (i, patch_record) in patch_rdr.records().enumerate() {
+            let patch_record = patch_record?;
+            if patch_record.len() != 3 {
+                return fail_clierror!(
+                    "Patch file record {} does not have exactly 3 columns (row, column, value).",
+                    i + 1
+                );
+            }
+            let Ok(row) = patch_record[0].trim().parse::<u64>() else {
+                return fail_clierror!(
+                    "Patch file record {} has an invalid row: {}",
+                    i + 1,
+                    &patch_record[0]
+                );
+            };
+            raw_edits.push((
+                row,
+                patch_record[1].to_string(),
+                patch_record[2].to_string(),
+            ));
+        }
+        raw_edits
+    } else {
+        // docopt guarantees these are set when --patch is not
+        vec![(
+            args.arg_row.unwrap_or_default(),
+            args.arg_column.unwrap_or_default(),
+            args.arg_value.unwrap_or_default(),
+        )]
+    };
+
+    let mut edits = Edits::new();
+    for (row, column, value) in raw_edits {
+        let sel = SelectColumns::parse(&column)?.selection(&headers, !rconfig.no_headers)?;
+        if sel.len() != 1 {
+            return fail_clierror!("Column '{column}' does not select exactly one column.");
+        }
+        edits.entry(row).or_default().push((sel[0], value));
+    }
+
+    // when editing in place, write to a temp file in the same directory as the input,
+    // so we can rename it over the input when we're done
+    let input_path = args.arg_input.clone().unwrap_or_default();
+    let tmpfile = if args.flag_in_place {
+        let input_dir = Path::new(&input_path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        Some(tempfile::NamedTempFile::new_in(input_dir)?)
+    } else {
+        None
+    };
+    let output = match tmpfile {
+        Some(ref tmpfile) => Some(tmpfile.path().to_string_lossy().to_string()),
+        None => args.flag_output,
+    };
+    let mut wtr = io::BufWriter::new(Config::new(&output).io_writer()?);
+    let delimiter = rconfig.get_delimiter();
+
+    // the raw bytes of a record run up to the start of the next one, so each record
+    // (and the header row) is written once the following record is read
+    let mut record = csv::ByteRecord::new();
+    let mut prev_record = csv::ByteRecord::new();
+    let mut prev_edits: Option<Vec<(usize, String)>> = None;
+    let mut row_idx = 0_u64;
+    loop {
+        let more = rdr.read_byte_record(&mut record)?;
+        let end = if more {
+            record.position().map_or(0, csv::Position::byte)
+        } else {
+            u64::MAX
+        };
+        let raw_record = raw.borrow_mut().take(end);
+        match prev_edits.take() {
+            Some(row_edits) => {
+                write_edited(&mut wtr, &raw_record, &prev_record, &row_edits, delimiter)?;
+            }
+            None => wtr.write_all(&raw_record)?,
+        }
+        if !more {
+            break;
+        }
+
+        if let Some(row_edits) = edits.remove(&row_idx) {
+            if let Some((col_idx, _)) = row_edits
+                .iter()
+                .find(|(col_idx, _)| *col_idx >= record.len())
+            {
+                return fail_clierror!(
+                    "Column {} is out of bounds for row {row_idx}, which only has {} columns.",
+                    col_idx + 1,
+                    record.len()
+                );
+            }
+            prev_edits = Some(row_edits);
+            std::mem::swap(&mut prev_record, &mut record);
+        }
+        row_idx += 1;
+    }
+    wtr.flush()?;
+
+    if let Some((row, _)) = edits.first_key_value() {
+        return fail_clierror!("Row {row} is out of bounds. The CSV only has {row_idx} rows.");
+    }
+
+    if let Some(tmpfile) = tmpfile {
+        fs::set_permissions(tmpfile.path(), fs::metadata(&input_path)?.permissions())?;
+        tmpfile.persist(&input_path).map_err(|e| e.error)?;
+    }
+    Ok(())
+}
+
+/// The raw bytes read from the input that haven't been written out yet.
+#[derive(Default)]
+struct RawBytes {
+    buf:    Vec<u8>,
+    /// the byte position in the input of the start of `buf`
+    offset: u64,
+}
+
+impl RawBytes {
+    /// Removes & returns the buffered bytes up to the byte position `end`.
+    fn take(&mut self, end: u64) -> Vec<u8> {
+        let len = usize::try_from(end.saturating_sub(self.offset))
+            .unwrap_or(usize::MAX)
+            .min(self.buf.len());
+        self.offset += len as u64;
+        self.buf.drain(..len).collect()
+    }
+}
+
+/// Passes the input through to the CSV reader, keeping a copy of the raw bytes.
+struct RawTee<R> {
+    inner: R,
+    raw:   Rc<RefCell<RawBytes>>,
+}
+
+impl<R: Read> Read for RawTee<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.raw.borrow_mut().buf.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Writes an edited record, copying its untouched fields & line terminator verbatim.
+/// If the raw fields can't be matched with the parsed ones (e.g. with comment lines),
+/// all its fields are written quoted as needed instead.
+fn write_edited<W: Write>(
+    wtr: &mut W,
+    raw_record: &[u8],
+    record: &csv::ByteRecord,
+    row_edits: &[(usize, String)],
+    delimiter: u8,
+) -> io::Result<()> {
+    let content_start = raw_record
+        .iter()
+        .position(|b| !matches!(b, b'\r' | b'\n'))
+        .unwrap_or(raw_record.len());
+    let content_end = raw_record
+        .iter()
+        .rposition(|b| !matches!(b, b'\r' | b'\n'))
+        .map_or(content_start, |i| i + 1);
+    let content = &raw_record[content_start..content_end];
+
+    let raw_fields = split_raw_fields(content, delimiter);
+    let verbatim = raw_fields.len() == record.len()
+        && raw_fields
+            .iter()
+            .zip(record.iter())
+            .all(|(raw_field, field)| unquote(raw_field) == field);
+
+    wtr.write_all(&raw_record[..content_start])?;
+    for (col_idx, field) in record.iter().enumerate() {
+        if col_idx > 0 {
+            wtr.write_all(&[delimiter])?;
+        }
+        // if the same cell is edited several times, the last edit wins
+        match row_edits
+            .iter()
+            .rev()
+            .find(|(edit_col, _)| *edit_col == col_idx)
+        {
+            Some((_, value)) => {
+                wtr.write_all(&quote_field(value.as_bytes(), delimiter, record.len()))?;
+            }
+            None if verbatim => wtr.write_all(raw_fields[col_idx])?,
+            None => wtr.write_all(&quote_field(field, delimiter, record.len()))?,
+        }
+    }
+    wtr.write_all(&raw_record[content_end..])
+}
+
+/// Splits the raw bytes of a record on the delimiters that are not inside quotes.
+fn split_raw_fields(content: &[u8], delimiter: u8) -> Vec<&[u8]> {
+    let mut fields = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, &b) in content.iter().enumerate() {
+        if b == b'"' {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            fields.push(&content[start..i]);
+            start = i + 1;
+        }
+    }
+    fields.push(&content[start..]);
+    fields
+}
+
+/// Returns the value of a raw field, removing its quotes & unescaping doubled quotes.
+fn unquote(raw_field: &[u8]) -> Cow<[u8]> {
+    let [b'"', inner @ .., b'"'] = raw_field else {
+        return Cow::Borrowed(raw_field);
+    };
+    let mut value = Vec::with_capacity(inner.len());
+    let mut bytes = inner.iter().peekable();
+    while let Some(&b) = bytes.next() {
+        value.push(b);
+        if b == b'"' && bytes.peek() == Some(&&b'"') {
+            bytes.next();
+        }
+    }
+    Cow::Owned(value)
+}
+
+/// Quotes a field only if needed, like the CSV writer of the other commands.
+fn quote_field(value: &[u8], delimiter: u8, num_fields: usize) -> Cow<[u8]> {
+    // an empty record with a single field would otherwise be an empty line
+    let needs_quotes = (value.is_empty() && num_fields == 1)
+        || value
+            .iter()
+            .any(|&b| b == delimiter || matches!(b, b'"' | b'\r' | b'\n'));
+    if !needs_quotes {
+        return Cow::Borrowed(value);
+    }
+    let mut quoted = Vec::with_capacity(value.len() + 2);
+    quoted.push(b'"');
+    for &b in value {
+        if b == b'"' {
+            quoted.push(b'"');
+        }
+        quoted.push(b);
+    }
+    quoted.push(b'"');
+    Cow::Owned(quoted)
+}
@@ -8,6 +8,9 @@ The enum function can currently be used to perform the following tasks:
   Add an incremental identifier to each of the lines:
     $ qsv enum file.csv
 
+  Add an incremental identifier starting at 1000, incrementing by 10:
+    $ qsv enum --start 1000 --increment 10 file.csv
+
   Add a uuid v4 to each of the lines:
     $ qsv enum --uuid file.csv
 
@@ -28,6 +31,10 @@ Usage:
 enum options:
     -c, --new-column <name>  Name of the column to create.
                              Will default to "index".
+    --start <value>          The value to start the enumeration from.
+                             [default: 0]
+    --increment <value>      The value to increment the enumeration by.
+                             [default: 1]
     --constant <value>       Fill a new column with the given value.
                              Changes the default column name to "constant".
                              To specify a null value, pass the literal "<NULL>".
@@ -61,6 +68,8 @@ const NULL_VALUE: &str = "<NULL>";
 struct Args {
     arg_input:       Option<String>,
     flag_new_column: Option<String>,
+    flag_start:      u64,
+    flag_increment:  u64,
     flag_constant:   Option<String>,
     flag_copy:       Option<SelectColumns>,
     flag_uuid:       bool,
@@ -111,7 +120,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
 
     let mut record = csv::ByteRecord::new();
-    let mut counter: u64 = 0;
+    // None once the next identifier would overflow
+    let mut counter = Some(args.flag_start);
+    let increment = args.flag_increment;
 
     while rdr.read_byte_record(&mut record)? {
         if let Some(constant_value) = &args.flag_constant {
@@ -131,8 +142,18 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     .as_bytes(),
             );
         } else {
-            record.push_field(counter.to_string().as_bytes());
-            counter += 1;
+            let Some(value) = counter else {
+                return fail_clierror!(
+                    "The identifier overflowed: it can't be more than {} with --start {} & \
+                     --increment {}.",
+                    u64::MAX,
+                    args.flag_start,
+                    increment
+                );
+            };
+            let mut buffer = itoa::Buffer::new();
+            record.push_field(buffer.format(value).as_bytes());
+            counter = value.checked_add(increment);
         }
         wtr.write_byte_record(&record)?;
     }
@@ -73,6 +73,12 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let headers = rdr.byte_headers()?.clone();
     let sel = rconfig.selection(&headers)?;
+    if sel.len() != 1 {
+        return fail_clierror!(
+            "Only one column can be exploded at a time. {} columns selected.",
+            sel.len()
+        );
+    }
     let column_index = *sel.iter().next().unwrap();
 
     let mut headers = rdr.headers()?.clone();
@@ -25,7 +25,8 @@ extdedup options:
                                duplicate separated by a tab from the duplicate line itself.
     -H, --human-readable       Comma separate duplicate count.
     --memory-limit <arg>       The maximum amount of memory to buffer the on-disk hash table.
-                               This is a percentage of total memory. [default: 10]
+                               This is a percentage of total memory, capped at 50%.
+                               [default: 10]
 
 Common options:
     -h, --help                 Display this message
@@ -66,7 +67,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     let mem_limited_buffer = if System::IS_SUPPORTED {
         let mut sys = System::new_all();
         sys.refresh_memory();
-        (sys.total_memory() * 1000) / u8::min(args.flag_memory_limit.unwrap_or(10), 50) as u64
+        // total_memory() is in bytes
+        let mem_pct = u64::from(u8::min(args.flag_memory_limit.unwrap_or(10), 50));
+        (sys.total_memory() / 100) * mem_pct
     } else {
         MEMORY_LIMITED_BUFFER
     };
@@ -168,6 +171,6 @@ fn test_extdedup_mem_check() {
     // check to see if sysinfo return meminfo without segfaulting
     let mut sys = System::new_all();
     sys.refresh_memory();
-    let mem10percent = (sys.total_memory() * 1000) / 10; // 10 percent of total memory
+    let mem10percent = (sys.total_memory() / 100) * 10; // 10 percent of total memory
     assert!(mem10percent > 0);
 }
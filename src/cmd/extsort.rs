@@ -10,8 +10,13 @@ Usage:
     qsv extsort --help
 
 External sort option:
-    --memory-limit <arg>   The maximum amount of memory to buffer the on-disk hash table.
-                           This is a percentage of total memory. [default: 10]
+    --memory-limit <arg>   The maximum amount of memory to buffer the sorted runs
+                           in memory before spilling them to disk.
+                           This is a percentage of total memory, capped at 50%.
+                           [default: 10]
+    --tmp-dir <arg>        The directory to use for the temporary files of the
+                           sorted runs. Make sure it has enough free space to
+                           hold a copy of the input. [default: ./]
     -j, --jobs <arg>       The number of jobs to run in parallel.
                            When not set, the number of jobs is set to the
                            number of CPUs detected.
@@ -42,6 +47,7 @@ struct Args {
     arg_output:        Option<String>,
     flag_jobs:         Option<usize>,
     flag_memory_limit: Option<u8>,
+    flag_tmp_dir:      String,
     flag_no_headers:   bool,
 }
 
@@ -58,7 +64,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     let mem_limited_buffer = if System::IS_SUPPORTED {
         let mut sys = System::new_all();
         sys.refresh_memory();
-        (sys.total_memory() * 1000) / u8::min(args.flag_memory_limit.unwrap_or(10), 50) as u64
+        // total_memory() is in bytes
+        let mem_pct = u64::from(u8::min(args.flag_memory_limit.unwrap_or(10), 50));
+        (sys.total_memory() / 100) * mem_pct
     } else {
         MEMORY_LIMITED_BUFFER
     };
@@ -86,9 +94,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         )),
     };
 
+    let tmp_dir = path::Path::new(&args.flag_tmp_dir);
+    if !tmp_dir.is_dir() {
+        return fail_clierror!("tmp-dir '{}' is not a directory.", args.flag_tmp_dir);
+    }
+
     let sorter: ExternalSorter<String, io::Error, MemoryLimitedBufferBuilder> =
         match ExternalSorterBuilder::new()
-            .with_tmp_dir(path::Path::new("./"))
+            .with_tmp_dir(tmp_dir)
             .with_buffer(MemoryLimitedBufferBuilder::new(mem_limited_buffer))
             .with_rw_buf_size(RW_BUFFER_CAPACITY)
             .with_threads_number(util::njobs(args.flag_jobs))
@@ -125,6 +138,6 @@ fn test_mem_check() {
     // check to see if sysinfo return meminfo without segfaulting
     let mut sys = System::new_all();
     sys.refresh_memory();
-    let mem10percent = (sys.total_memory() * 1000) / 10; // 10 percent of total memory
+    let mem10percent = (sys.total_memory() / 100) * 10; // 10 percent of total memory
     assert!(mem10percent > 0);
 }
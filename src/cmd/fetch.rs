@@ -377,10 +377,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     if args.flag_url_template.is_none() {
         rconfig = rconfig.select(args.arg_url_column);
         let sel = rconfig.selection(&headers)?;
-        column_index = *sel.iter().next().unwrap();
         if sel.len() != 1 {
             return fail!("Only a single URL column may be selected.");
         }
+        column_index = *sel.iter().next().unwrap();
     }
 
     let mut dynfmt_url_template = String::new();
@@ -396,10 +396,20 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         let (safe_headers, _) = util::safe_header_names(&str_headers, false, false, None, "");
         let formatstr_re: &'static Regex = regex_once_cell!(r"\{(?P<key>\w+)?\}");
         for format_fields in formatstr_re.captures_iter(url_template) {
-            dynfmt_fields.push(format_fields.name("key").unwrap().as_str());
+            if let Some(key) = format_fields.name("key") {
+                dynfmt_fields.push(key.as_str());
+            }
         }
         // we sort the fields so we can do binary_search
         dynfmt_fields.sort_unstable();
+        // make sure all the fields used in the url template are actual columns
+        for field in &dynfmt_fields {
+            if !safe_headers.iter().any(|h| h == field) {
+                return fail_clierror!(
+                    r#"--url-template: "{field}" is not a valid column name. Note that non-alphanumeric characters in column names are replaced with _."#
+                );
+            }
+        }
         // now, get the indices of the columns for the lookup vec
         for (i, field) in safe_headers.into_iter().enumerate() {
             if dynfmt_fields.binary_search(&field.as_str()).is_ok() {
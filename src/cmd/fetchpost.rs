@@ -122,6 +122,9 @@ Fetchpost options:
     -H, --http-header <k:v>    Append custom header(s) to the HTTP header. Pass multiple key-value pairs
                                by adding this option multiple times, once for each pair. The key and value 
                                should be separated by a colon.
+    --json-body                Send the columns in the column-list as a JSON object in the HTTP Post body,
+                               with the "application/json" content type. Otherwise, they're sent as
+                               form data ("application/x-www-form-urlencoded").
     --compress                 Compress the HTTP request body using gzip. Note that most servers do not support
                                compressed request bodies unless they are specifically configured to do so. This
                                should only be enabled for trusted scenarios where "zip bombs" are not a concern.
@@ -215,6 +218,7 @@ struct Args {
     flag_rate_limit:  u32,
     flag_timeout:     u64,
     flag_http_header: Vec<String>,
+    flag_json_body:   bool,
     flag_compress:    bool,
     flag_max_retries: u8,
     flag_max_errors:  u64,
@@ -443,7 +447,12 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         );
         map.append(
             reqwest::header::CONTENT_TYPE,
-            HeaderValue::from_str("application/x-www-form-urlencoded").unwrap(),
+            HeaderValue::from_str(if args.flag_json_body {
+                "application/json"
+            } else {
+                "application/x-www-form-urlencoded"
+            })
+            .unwrap(),
         );
         if args.flag_compress {
             map.append(
@@ -640,6 +649,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 &jql_selector,
                 args.flag_store_error,
                 args.flag_pretty,
+                args.flag_json_body,
                 args.flag_compress,
                 include_existing_columns,
                 args.flag_max_retries,
@@ -659,12 +669,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             };
             if !args.flag_cache_error && final_response.status_code != 200 {
                 let key = format!(
-                    "{}{:?}{:?}{}{}{}{}",
+                    "{}{:?}{:?}{}{}{}{}{}",
                     url,
                     form_body_jsonmap,
                     args.flag_jql,
                     args.flag_store_error,
                     args.flag_pretty,
+                    args.flag_json_body,
                     args.flag_compress,
                     include_existing_columns
                 );
@@ -683,6 +694,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 &jql_selector,
                 args.flag_store_error,
                 args.flag_pretty,
+                args.flag_json_body,
                 args.flag_compress,
                 include_existing_columns,
                 args.flag_max_retries,
@@ -814,6 +826,7 @@ fn get_cached_response(
     flag_jql: &Option<String>,
     flag_store_error: bool,
     flag_pretty: bool,
+    flag_json_body: bool,
     flag_compress: bool,
     include_existing_columns: bool,
     flag_max_retries: u8,
@@ -826,6 +839,7 @@ fn get_cached_response(
         flag_jql,
         flag_store_error,
         flag_pretty,
+        flag_json_body,
         flag_compress,
         include_existing_columns,
         flag_max_retries,
@@ -838,7 +852,7 @@ fn get_cached_response(
 #[io_cached(
     type = "cached::RedisCache<String, String>",
     key = "String",
-    convert = r#"{ format!("{}{:?}{:?}{}{}{}{}", url, form_body_jsonmap, flag_jql, flag_store_error, flag_pretty, flag_compress, include_existing_columns) }"#,
+    convert = r#"{ format!("{}{:?}{:?}{}{}{}{}{}", url, form_body_jsonmap, flag_jql, flag_store_error, flag_pretty, flag_json_body, flag_compress, include_existing_columns) }"#,
     create = r##" {
         RedisCache::new("fp", REDISCONFIG.ttl_secs)
             .set_namespace("q")
@@ -859,6 +873,7 @@ fn get_redis_response(
     flag_jql: &Option<String>,
     flag_store_error: bool,
     flag_pretty: bool,
+    flag_json_body: bool,
     flag_compress: bool,
     include_existing_columns: bool,
     flag_max_retries: u8,
@@ -872,6 +887,7 @@ fn get_redis_response(
             flag_jql,
             flag_store_error,
             flag_pretty,
+            flag_json_body,
             flag_compress,
             include_existing_columns,
             flag_max_retries,
@@ -890,6 +906,7 @@ fn get_response(
     flag_jql: &Option<String>,
     flag_store_error: bool,
     flag_pretty: bool,
+    flag_json_body: bool,
     flag_compress: bool,
     include_existing_columns: bool,
     flag_max_retries: u8,
@@ -957,10 +974,14 @@ fn get_response(
         }
 
         // send the actual request
-        let form_body_raw = serde_urlencoded::to_string(form_body_jsonmap)
-            .unwrap()
-            .as_bytes()
-            .to_owned();
+        let form_body_raw = if flag_json_body {
+            serde_json::to_vec(form_body_jsonmap).unwrap()
+        } else {
+            serde_urlencoded::to_string(form_body_jsonmap)
+                .unwrap()
+                .as_bytes()
+                .to_owned()
+        };
         let resp_result = if flag_compress {
             // gzip the request body
             let mut gz_enc = GzEncoder::new(Vec::new(), Compression::default());
@@ -348,8 +348,13 @@ where
     where
         F: FnMut(B) -> B,
     {
+        // MapSelected walks the selection in lockstep with the record,
+        // so the selected indices need to be in ascending order
+        let mut selection: Vec<usize> = selector.iter().copied().collect();
+        selection.sort_unstable();
+        selection.dedup();
         MapSelected {
-            selection: selector.iter().copied().collect(),
+            selection,
             selection_index: 0,
             index: 0,
             iterator: self,
@@ -15,6 +15,14 @@ Same as above but with an additional column containing the current value:
 
   $ qsv foreach query -c from_query 'search {}' queries.csv > results.csv
 
+Capturing the output of a command into a new column of the input CSV:
+
+  $ qsv foreach filename --capture checksum 'sha256sum {}' assets.csv > assets_checksums.csv
+
+Running 8 commands at a time. The output is still in the order of the input rows:
+
+  $ qsv foreach filename --jobs 8 --capture checksum 'sha256sum {}' assets.csv > assets_checksums.csv
+
 Usage:
     qsv foreach [options] <column> <command> [<input>]
     qsv foreach --help
@@ -25,6 +33,17 @@ foreach options:
                              subsequent command.
     -c, --new-column <name>  If unifying, add a new column with given name
                              and copying the value of the current input file line.
+    --capture <name>         Capture the output of the command into a new column
+                             with the given name, outputting the input CSV with the
+                             new column added. Trailing newlines of the output are
+                             trimmed. Mutually exclusive with --unify.
+    -j, --jobs <arg>         The number of commands to run in parallel. The output
+                             of each command is buffered, and written in the order
+                             of the input rows. When not set, the commands are run
+                             one at a time, with their output streamed.
+    -b, --batch <size>       With --jobs, the number of rows per batch to load into
+                             memory, before running their commands in parallel.
+                             [default: 1000]
 
 Common options:
     -h, --help             Display this message
@@ -37,12 +56,13 @@ Common options:
 
 use std::{
     ffi::OsStr,
-    io::BufReader,
+    io::{self, BufReader, Write},
     os::unix::ffi::OsStrExt,
     process::{Command, Stdio},
 };
 
 use indicatif::{ProgressBar, ProgressDrawTarget};
+use rayon::prelude::*;
 use regex::bytes::{NoExpand, Regex};
 use serde::Deserialize;
 
@@ -59,6 +79,9 @@ struct Args {
     arg_input:        Option<String>,
     flag_unify:       bool,
     flag_new_column:  Option<String>,
+    flag_capture:     Option<String>,
+    flag_jobs:        Option<usize>,
+    flag_batch:       usize,
     flag_no_headers:  bool,
     flag_delimiter:   Option<Delimiter>,
     flag_progressbar: bool,
@@ -71,18 +94,35 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         .no_headers(args.flag_no_headers)
         .select(args.arg_column);
 
+    if args.flag_unify && args.flag_capture.is_some() {
+        return fail!("--unify and --capture are mutually exclusive.");
+    }
+
+    if args.flag_batch == 0 {
+        return fail!("--batch must be greater than zero.");
+    }
+
     let mut rdr = rconfig.reader()?;
     let mut wtr = Config::new(&None).writer()?;
 
-    #[allow(clippy::trivial_regex)]
-    let template_pattern = Regex::new(r"\{\}")?;
-    let splitter_pattern = Regex::new(r#"(?:[\w-]+|"[^"]*"|'[^']*'|`[^`]*`)"#)?;
-    let cleaner_pattern = Regex::new(r#"(?:^["'`]|["'`]$)"#)?;
+    let command_template = CommandTemplate::new(&args.arg_command)?;
+    let delimiter = args.flag_delimiter.map_or(b',', Delimiter::as_byte);
 
     let headers = rdr.byte_headers()?.clone();
     let sel = rconfig.selection(&headers)?;
+    if sel.len() != 1 {
+        return fail!("Only one column can be selected.");
+    }
     let column_index = *sel.iter().next().unwrap();
 
+    if let Some(ref capture_column) = args.flag_capture {
+        if !rconfig.no_headers {
+            let mut capture_headers = headers.clone();
+            capture_headers.push_field(capture_column.as_bytes());
+            wtr.write_byte_record(&capture_headers)?;
+        }
+    }
+
     let mut record = csv::ByteRecord::new();
     let mut output_headers_written = false;
 
@@ -97,84 +137,207 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         progress.set_draw_target(ProgressDrawTarget::hidden());
     }
 
-    while rdr.read_byte_record(&mut record)? {
-        if show_progress {
-            progress.inc(1);
+    if let Some(jobs) = args.flag_jobs.filter(|jobs| *jobs > 1) {
+        // set RAYON_NUM_THREADS
+        util::njobs(Some(jobs));
+
+        // reuse batch buffers
+        let batchsize = args.flag_batch;
+        let mut batch = Vec::with_capacity(batchsize);
+        let mut batch_outputs = Vec::with_capacity(batchsize);
+        let mut stdout = io::stdout();
+
+        // the commands of each batch are run via Rayon parallel iterator,
+        // buffering their output so it can be written in the order of the input rows.
+        // loop exits when batch is empty.
+        'batch_loop: loop {
+            for _ in 0..batchsize {
+                if !rdr.read_byte_record(&mut record)? {
+                    break;
+                }
+                batch.push(record.clone());
+            }
+
+            if batch.is_empty() {
+                // break out of infinite loop when at EOF
+                break 'batch_loop;
+            }
+
+            batch
+                .par_iter()
+                .map(|batch_record| {
+                    command_template
+                        .command(&batch_record[column_index])
+                        .stdin(Stdio::null())
+                        .stderr(Stdio::inherit())
+                        .output()
+                })
+                .collect_into_vec(&mut batch_outputs);
+
+            // rayon collect() guarantees original order, so we can just write the outputs
+            for (batch_record, output) in batch.iter_mut().zip(batch_outputs.drain(..)) {
+                let output = output?;
+                if args.flag_unify {
+                    write_unified(
+                        output.stdout.as_slice(),
+                        delimiter,
+                        args.flag_new_column.as_deref(),
+                        &batch_record[column_index],
+                        &mut output_headers_written,
+                        &mut wtr,
+                    )?;
+                } else if args.flag_capture.is_some() {
+                    batch_record.push_field(trim_trailing_newlines(&output.stdout));
+                    wtr.write_byte_record(batch_record)?;
+                } else {
+                    stdout.write_all(&output.stdout)?;
+                }
+            }
+
+            if show_progress {
+                progress.inc(batch.len() as u64);
+            }
+
+            batch.clear();
+        } // end batch loop
+        stdout.flush()?;
+    } else {
+        while rdr.read_byte_record(&mut record)? {
+            if show_progress {
+                progress.inc(1);
+            }
+            let current_value = &record[column_index];
+            let mut command = command_template.command(current_value);
+
+            if args.flag_unify {
+                let mut cmd = command
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::inherit())
+                    .spawn()?;
+
+                {
+                    let stdout = cmd.stdout.as_mut().unwrap();
+                    write_unified(
+                        BufReader::new(stdout),
+                        delimiter,
+                        args.flag_new_column.as_deref(),
+                        current_value,
+                        &mut output_headers_written,
+                        &mut wtr,
+                    )?;
+                }
+
+                cmd.wait().unwrap();
+            } else if args.flag_capture.is_some() {
+                let output = command.stderr(Stdio::inherit()).output()?;
+
+                record.push_field(trim_trailing_newlines(&output.stdout));
+                wtr.write_byte_record(&record)?;
+            } else {
+                let mut cmd = command
+                    .stdout(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .spawn()
+                    .unwrap();
+
+                cmd.wait().unwrap();
+            }
         }
-        let current_value = &record[column_index];
+    }
+    if show_progress {
+        util::finish_progress(&progress);
+    }
+    Ok(wtr.flush()?)
+}
 
-        let templated_command = template_pattern
-            .replace_all(args.arg_command.as_bytes(), current_value)
+/// The command to run for each row, with `{}` replaced by the value of the selected column.
+struct CommandTemplate {
+    command:          String,
+    template_pattern: Regex,
+    splitter_pattern: Regex,
+    cleaner_pattern:  Regex,
+}
+
+impl CommandTemplate {
+    fn new(command: &str) -> CliResult<Self> {
+        #[allow(clippy::trivial_regex)]
+        let template_pattern = Regex::new(r"\{\}")?;
+        let splitter_pattern = Regex::new(r#"(?:[\w-]+|"[^"]*"|'[^']*'|`[^`]*`)"#)?;
+        let cleaner_pattern = Regex::new(r#"(?:^["'`]|["'`]$)"#)?;
+        Ok(Self {
+            command: command.to_string(),
+            template_pattern,
+            splitter_pattern,
+            cleaner_pattern,
+        })
+    }
+
+    fn command(&self, value: &[u8]) -> Command {
+        let templated_command = self
+            .template_pattern
+            .replace_all(self.command.as_bytes(), value)
             .to_vec();
 
-        #[allow(unused_mut)]
-        let mut command_pieces = splitter_pattern.find_iter(&templated_command);
+        let mut command_pieces = self.splitter_pattern.find_iter(&templated_command);
         let prog = OsStr::from_bytes(command_pieces.next().unwrap().as_bytes());
 
         let cmd_args: Vec<String> = command_pieces
             .map(|piece| {
-                let clean_piece = cleaner_pattern.replace_all(piece.as_bytes(), NoExpand(b""));
+                let clean_piece = self
+                    .cleaner_pattern
+                    .replace_all(piece.as_bytes(), NoExpand(b""));
 
                 String::from_utf8(clean_piece.into_owned()).unwrap_or_default()
             })
             .collect();
 
-        if args.flag_unify {
-            let mut cmd = Command::new(prog)
-                .args(cmd_args)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::inherit())
-                .spawn()?;
-
-            {
-                let stdout = cmd.stdout.as_mut().unwrap();
-                let stdout_reader = BufReader::new(stdout);
-                // let stdout_lines = stdout_reader.lines();
-
-                let mut stdout_rdr = csv::ReaderBuilder::new()
-                    .delimiter(match &args.flag_delimiter {
-                        Some(delimiter) => delimiter.as_byte(),
-                        None => b',',
-                    })
-                    .has_headers(true)
-                    .from_reader(stdout_reader);
-
-                let mut output_record = csv::ByteRecord::new();
-
-                if !output_headers_written {
-                    let mut headers = stdout_rdr.byte_headers()?.clone();
-
-                    if let Some(name) = &args.flag_new_column {
-                        headers.push_field(name.as_bytes());
-                    }
-
-                    wtr.write_byte_record(&headers)?;
-                    output_headers_written = true;
-                }
-
-                while stdout_rdr.read_byte_record(&mut output_record)? {
-                    if args.flag_new_column.is_some() {
-                        output_record.push_field(current_value);
-                    }
+        let mut command = Command::new(prog);
+        command.args(cmd_args);
+        command
+    }
+}
 
-                    wtr.write_byte_record(&output_record)?;
-                }
-            }
+/// Writes the CSV output of a command, only writing the headers of the first command.
+fn write_unified<R: io::Read, W: Write>(
+    output: R,
+    delimiter: u8,
+    new_column: Option<&str>,
+    current_value: &[u8],
+    output_headers_written: &mut bool,
+    wtr: &mut csv::Writer<W>,
+) -> CliResult<()> {
+    let mut output_rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(true)
+        .from_reader(output);
+
+    let mut output_record = csv::ByteRecord::new();
+
+    if !*output_headers_written {
+        let mut headers = output_rdr.byte_headers()?.clone();
+
+        if let Some(name) = new_column {
+            headers.push_field(name.as_bytes());
+        }
 
-            cmd.wait().unwrap();
-        } else {
-            let mut cmd = Command::new(prog)
-                .args(cmd_args)
-                .stdout(Stdio::inherit())
-                .stderr(Stdio::inherit())
-                .spawn()
-                .unwrap();
+        wtr.write_byte_record(&headers)?;
+        *output_headers_written = true;
+    }
 
-            cmd.wait().unwrap();
+    while output_rdr.read_byte_record(&mut output_record)? {
+        if new_column.is_some() {
+            output_record.push_field(current_value);
         }
-    }
-    if show_progress {
-        util::finish_progress(&progress);
+
+        wtr.write_byte_record(&output_record)?;
     }
     Ok(())
 }
+
+/// Trims the trailing newlines of a captured output.
+fn trim_trailing_newlines(mut captured: &[u8]) -> &[u8] {
+    while let [rest @ .., b'\n' | b'\r'] = captured {
+        captured = rest;
+    }
+    captured
+}
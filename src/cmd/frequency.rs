@@ -5,9 +5,41 @@ The frequency table is formatted as CSV data:
 
     field,value,count
 
+With --pct and --cum-pct, the percentage and the cumulative percentage of each
+count in the total count of its field are added as "pct" and "cum_pct" columns.
+
 By default, there is a row for the N most frequent values for each field in the
 data. The order and number of values can be tweaked with --asc and --limit,
-respectively.
+respectively. With --other-label, the values beyond --limit are aggregated into a
+single row with their combined count.
+
+With --ignore-case, the values that only differ in case (e.g. "Yes", "yes" & "YES")
+are counted as one value, reported in its most frequent form. Note that the values
+are always trimmed of leading & trailing whitespace before being counted.
+
+With --weight, each row adds the value of its weight column to the counts of its
+values instead of one, e.g. to count pre-aggregated or survey-weighted data.
+The counts are then the sums of the weights, rounded to 4 decimal places.
+
+Columns with more unique values than --unq-limit (e.g. ids) are summarized in a
+single "(<N> UNIQUE VALUES)" row with the total count of the column, instead of
+listing their values. When the input has a valid stats cache with the cardinalities of
+its columns (see 'qsv stats --cache --cardinality'), the Integer & Float columns it shows
+to have more unique values than --unq-limit are summarized without counting their values.
+
+With --combo, the distinct combinations of the values of the selected columns are
+counted together instead (like a group-by count), with a column for each selected
+column followed by the count:
+
+    <column 1>,<column 2>,...,count
+
+With --json or --pretty-json, the frequency tables are returned as a JSON object
+with an array of the counts of each field, like sniff's JSON output:
+
+    {"<field>": [{"value": "a", "count": 3, "pct": 50}, ...], ...}
+
+NULLs are returned as null values. With --combo, the field is the comma-separated
+list of the selected columns and each value is an array of the column values.
 
 Since this computes an exact frequency table, memory proportional to the
 cardinality of each column is required.
@@ -27,9 +59,31 @@ frequency options:
     -l, --limit <arg>      Limit the frequency table to the N most common
                            items. Set to '0' to disable a limit.
                            [default: 10]
+    --other-label <arg>    Aggregate the values beyond --limit into a single row
+                           with this value, e.g. "Other".
+    --unq-limit <arg>      Summarize the columns with more than <arg> unique values
+                           in a single row. Set to '0' to disable the limit.
+                           [default: 0]
     -a, --asc              Sort the frequency tables in ascending order by
                            count. The default is descending order.
+    -i, --ignore-case      Count the values case-insensitively.
     --no-nulls             Don't include NULLs in the frequency table.
+                           With --combo, the combinations with a NULL are skipped.
+    --combo                Count the combinations of the values of the selected
+                           columns, instead of each column independently.
+                           --jobs is ignored with --combo.
+    --weight <column>      Add the value of <column> to the counts instead of one.
+                           Rows with a missing, negative or invalid weight are
+                           skipped. --jobs is ignored with --weight.
+    --pct                  Add a "pct" column with the percentage of each count
+                           in the total count of its field.
+    --cum-pct              Add a "cum_pct" column with the cumulative percentage
+                           of the counts, in the order of the frequency table.
+    --pct-decimals <arg>   The number of decimal places to round the percentages to.
+                           [default: 2]
+    --json                 Return the frequency tables in JSON format.
+                           The "pct" of each count is always included.
+    --pretty-json          Return the frequency tables in pretty JSON format.
     -j, --jobs <arg>       The number of jobs to run in parallel.
                            This works better when the given CSV data has
                            an index already created. Note that a file handle
@@ -50,8 +104,14 @@ Common options:
                            entire CSV into memory.
 "#;
 
-use std::{fs, io};
+use std::{
+    fs,
+    hash::Hash,
+    io::{self, Write},
+};
 
+use ahash::AHashMap;
+use indexmap::IndexMap;
 use serde::Deserialize;
 use stats::{merge_all, Frequencies};
 use threadpool::ThreadPool;
@@ -65,16 +125,26 @@ use crate::{
 
 #[derive(Clone, Deserialize)]
 pub struct Args {
-    pub arg_input:        Option<String>,
-    pub flag_select:      SelectColumns,
-    pub flag_limit:       usize,
-    pub flag_asc:         bool,
-    pub flag_no_nulls:    bool,
-    pub flag_jobs:        Option<usize>,
-    pub flag_output:      Option<String>,
-    pub flag_no_headers:  bool,
-    pub flag_delimiter:   Option<Delimiter>,
-    pub flag_no_memcheck: bool,
+    pub arg_input:         Option<String>,
+    pub flag_select:       SelectColumns,
+    pub flag_limit:        usize,
+    pub flag_other_label:  Option<String>,
+    pub flag_unq_limit:    usize,
+    pub flag_asc:          bool,
+    pub flag_ignore_case:  bool,
+    pub flag_no_nulls:     bool,
+    pub flag_combo:        bool,
+    pub flag_weight:       Option<SelectColumns>,
+    pub flag_pct:          bool,
+    pub flag_cum_pct:      bool,
+    pub flag_pct_decimals: u32,
+    pub flag_json:         bool,
+    pub flag_pretty_json:  bool,
+    pub flag_jobs:         Option<usize>,
+    pub flag_output:       Option<String>,
+    pub flag_no_headers:   bool,
+    pub flag_delimiter:    Option<Delimiter>,
+    pub flag_no_memcheck:  bool,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -86,33 +156,199 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         util::mem_file_check(&path, false, args.flag_no_memcheck)?;
     }
 
-    let mut wtr = Config::new(&args.flag_output).writer()?;
-    let (headers, tables) = match args.rconfig().indexed()? {
-        Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => args.parallel_ftables(idx),
-        _ => args.sequential_ftables(),
-    }?;
+    let mut output = if args.flag_json || args.flag_pretty_json {
+        Output::Json(serde_json::Map::new())
+    } else {
+        Output::Csv(Config::new(&args.flag_output).writer()?)
+    };
 
-    wtr.write_record(vec!["field", "value", "count"])?;
-    let head_ftables = headers.into_iter().zip(tables.into_iter());
-    for (i, (header, ftab)) in head_ftables.enumerate() {
-        let header = if rconfig.no_headers {
-            (i + 1).to_string().into_bytes()
+    if args.flag_combo {
+        let (headers, ftab) = args.combo_ftable(args.flag_weight.as_ref())?;
+        let columns: Vec<ByteString> = if rconfig.no_headers {
+            (1..=headers.len())
+                .map(|i| i.to_string().into_bytes())
+                .collect()
         } else {
-            header.to_vec()
+            headers.iter().map(<[u8]>::to_vec).collect()
         };
-        for (value, count) in args.counts(&ftab) {
-            let count = count.to_string();
-            let row = vec![&*header, &*value, count.as_bytes()];
-            wtr.write_record(row)?;
+        if let Output::Csv(wtr) = &mut output {
+            wtr.write_record(args.out_headers(columns.clone()))?;
+        }
+        let field = columns.join(&b","[..]);
+        args.write_table(&mut output, &field, None, columns.len(), &ftab)?;
+    } else {
+        if let Output::Csv(wtr) = &mut output {
+            wtr.write_record(args.out_headers(vec![b"field".to_vec(), b"value".to_vec()]))?;
+        }
+        if let Some(weight) = args.flag_weight.as_ref() {
+            let (headers, tables) = args.weighted_ftables(weight)?;
+            args.write_field_tables(&mut output, &headers, &tables, &[])?;
+        } else {
+            let summaries = args.cached_unq_summaries()?;
+            let skip: Vec<bool> = summaries.iter().map(Option::is_some).collect();
+            let (headers, tables) = match args.rconfig().indexed()? {
+                Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => {
+                    args.parallel_ftables(idx, &skip)
+                }
+                _ => args.sequential_ftables(&skip),
+            }?;
+            args.write_field_tables(&mut output, &headers, &tables, &summaries)?;
+        }
+    }
+
+    match output {
+        Output::Csv(mut wtr) => wtr.flush()?,
+        Output::Json(tables) => {
+            let tables = serde_json::Value::Object(tables);
+            let json = if args.flag_pretty_json {
+                serde_json::to_string_pretty(&tables)
+            } else {
+                serde_json::to_string(&tables)
+            }
+            .map_err(|e| e.to_string())?;
+            let mut wtr = Config::new(&args.flag_output).io_writer()?;
+            writeln!(wtr, "{json}")?;
+            wtr.flush()?;
         }
     }
     Ok(())
 }
 
+/// The frequency tables are written as CSV, or collected in a JSON object
+enum Output {
+    Csv(csv::Writer<Box<dyn io::Write + 'static>>),
+    Json(serde_json::Map<String, serde_json::Value>),
+}
+
+/// A frequency table row is either for a value, or for a label
+/// (the --other-label or unique values rows).
+enum Key<T> {
+    Value(T),
+    Label(String),
+}
+
+/// NULLs are shown as "(NULL)"
+fn null_label(value: &[u8]) -> ByteString {
+    if value.is_empty() {
+        b"(NULL)".to_vec()
+    } else {
+        value.to_vec()
+    }
+}
+
+/// trims the field if it's valid utf8
+fn normalize(field: &[u8]) -> ByteString {
+    match String::from_utf8(field.to_vec()) {
+        Ok(s) => s.trim().as_bytes().to_vec(),
+        Err(bs) => bs.into_bytes(),
+    }
+}
+
+/// converts a formatted number to a JSON number, which is empty (null) if the
+/// number couldn't be computed
+fn json_number(number: &str) -> serde_json::Value {
+    if let Ok(n) = number.parse::<u64>() {
+        return serde_json::Value::from(n);
+    }
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map_or(serde_json::Value::Null, serde_json::Value::Number)
+}
+
+/// The counts of the values of a frequency table, which are sums of weights with --weight
+trait Tallies<T> {
+    /// the values & their counts, sorted by descending count
+    fn tallies(&self) -> Vec<(T, f64)>;
+}
+
+impl<T: Hash + Eq + Clone> Tallies<T> for Frequencies<T> {
+    #[allow(clippy::cast_precision_loss)]
+    fn tallies(&self) -> Vec<(T, f64)> {
+        self.most_frequent()
+            .into_iter()
+            .map(|(value, count)| (value.clone(), count as f64))
+            .collect()
+    }
+}
+
+impl<T: Hash + Eq + Clone> Tallies<T> for WTable<T> {
+    fn tallies(&self) -> Vec<(T, f64)> {
+        let mut tallies: Vec<(T, f64)> = self
+            .iter()
+            .map(|(value, weight)| (value.clone(), *weight))
+            .collect();
+        tallies.sort_unstable_by(|(_, a), (_, b)| b.total_cmp(a));
+        tallies
+    }
+}
+
+/// parses a weight, returning None if it's not a valid, non-negative number
+fn parse_weight(field: &[u8]) -> Option<f64> {
+    std::str::from_utf8(field)
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|w| w.is_finite() && *w >= 0.0)
+}
+
+/// The values of a frequency table: a column value, or a combination of column values
+/// with --combo
+trait FreqValue: Hash + Eq + Clone {
+    /// the value to count case-insensitively with --ignore-case
+    fn fold_case(&self) -> Self;
+    /// the CSV fields of the value
+    fn csv_fields(&self) -> Vec<ByteString>;
+    fn to_json(&self) -> serde_json::Value;
+}
+
+impl FreqValue for ByteString {
+    fn fold_case(&self) -> Self {
+        match std::str::from_utf8(self) {
+            Ok(s) => s.to_lowercase().into_bytes(),
+            Err(_) => self.to_ascii_lowercase(),
+        }
+    }
+
+    fn csv_fields(&self) -> Vec<ByteString> {
+        vec![null_label(self)]
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        if self.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::Value::String(String::from_utf8_lossy(self).into_owned())
+        }
+    }
+}
+
+impl FreqValue for Vec<ByteString> {
+    fn fold_case(&self) -> Self {
+        self.iter().map(FreqValue::fold_case).collect()
+    }
+
+    fn csv_fields(&self) -> Vec<ByteString> {
+        self.iter().map(|value| null_label(value)).collect()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(self.iter().map(FreqValue::to_json).collect())
+    }
+}
+
+// the number of decimal places of the sums of weights
+const WEIGHTED_COUNT_PLACES: u32 = 4;
+
 type ByteString = Vec<u8>;
 type Headers = csv::ByteRecord;
-type FTable = Frequencies<Vec<u8>>;
 type FTables = Vec<Frequencies<Vec<u8>>>;
+/// the unique values count & total count of a column summarized from the stats cache
+type UnqSummary = Option<(u64, f64)>;
+/// a frequency table with the sums of the weights of its values
+type WTable<T> = AHashMap<T, f64>;
 
 impl Args {
     pub fn rconfig(&self) -> Config {
@@ -122,36 +358,263 @@ impl Args {
             .select(self.flag_select.clone())
     }
 
-    fn counts(&self, ftab: &FTable) -> Vec<(ByteString, u64)> {
-        let mut counts = if self.flag_asc {
-            ftab.least_frequent()
+    /// the output headers: the key columns, followed by the count & percentage columns
+    fn out_headers(&self, mut headers: Vec<ByteString>) -> Vec<ByteString> {
+        headers.push(b"count".to_vec());
+        if self.flag_pct {
+            headers.push(b"pct".to_vec());
+        }
+        if self.flag_cum_pct {
+            headers.push(b"cum_pct".to_vec());
+        }
+        headers
+    }
+
+    /// writes the (limited) counts of the frequency table of `field`.
+    /// The CSV records start with the `prefix` field if any, followed by the `width`
+    /// fields of the value.
+    fn write_table<T, M>(
+        &self,
+        output: &mut Output,
+        field: &[u8],
+        prefix: Option<&[u8]>,
+        width: usize,
+        ftab: &M,
+    ) -> CliResult<()>
+    where
+        T: FreqValue,
+        M: Tallies<T>,
+    {
+        let mut counts = self.counts(ftab);
+        // the percentages are relative to all the values, not just the limited ones
+        let total: f64 = counts.iter().map(|(_, c)| c).sum();
+
+        let mut rows = Vec::with_capacity(counts.len() + 1);
+        if self.flag_unq_limit > 0 && counts.len() > self.flag_unq_limit {
+            let unique_values = format!("({} UNIQUE VALUES)", counts.len());
+            rows.push((Key::Label(unique_values), total));
         } else {
-            ftab.most_frequent()
-        };
-        if self.flag_limit > 0 {
-            counts = counts.into_iter().take(self.flag_limit).collect();
+            let mut other_count = 0.0;
+            if self.flag_limit > 0 && counts.len() > self.flag_limit {
+                other_count = counts[self.flag_limit..].iter().map(|(_, c)| c).sum();
+                counts.truncate(self.flag_limit);
+            }
+            rows.extend(
+                counts
+                    .into_iter()
+                    .map(|(value, count)| (Key::Value(value), count)),
+            );
+            if let Some(other_label) = self.flag_other_label.as_ref() {
+                if other_count > 0.0 {
+                    rows.push((Key::Label(other_label.clone()), other_count));
+                }
+            }
+        }
+        self.write_rows(output, field, prefix, width, rows, total)
+    }
+
+    /// writes the rows of the frequency table of `field`, whose counts add up to `total`
+    fn write_rows<T: FreqValue>(
+        &self,
+        output: &mut Output,
+        field: &[u8],
+        prefix: Option<&[u8]>,
+        width: usize,
+        rows: Vec<(Key<T>, f64)>,
+        total: f64,
+    ) -> CliResult<()> {
+        let mut cum_count = 0.0;
+        let mut json_rows = Vec::new();
+        for (key, count) in rows {
+            cum_count += count;
+            let count_str = self.format_count(count);
+            let pct = self.percentage(count, total);
+            let cum_pct = self.percentage(cum_count, total);
+            match output {
+                Output::Csv(wtr) => {
+                    let mut row: Vec<ByteString> = prefix.map(<[u8]>::to_vec).into_iter().collect();
+                    match key {
+                        Key::Value(value) => row.extend(value.csv_fields()),
+                        // the label goes in the first field of the value, the others are empty
+                        Key::Label(label) => {
+                            row.push(label.into_bytes());
+                            row.extend((1..width).map(|_| vec![]));
+                        }
+                    }
+                    row.push(count_str.into_bytes());
+                    if self.flag_pct {
+                        row.push(pct.into_bytes());
+                    }
+                    if self.flag_cum_pct {
+                        row.push(cum_pct.into_bytes());
+                    }
+                    wtr.write_record(row)?;
+                }
+                Output::Json(_) => {
+                    let value = match key {
+                        Key::Value(value) => value.to_json(),
+                        Key::Label(label) => serde_json::Value::String(label),
+                    };
+                    let mut json_row = serde_json::Map::new();
+                    json_row.insert("value".to_string(), value);
+                    json_row.insert("count".to_string(), json_number(&count_str));
+                    json_row.insert("pct".to_string(), json_number(&pct));
+                    if self.flag_cum_pct {
+                        json_row.insert("cum_pct".to_string(), json_number(&cum_pct));
+                    }
+                    json_rows.push(serde_json::Value::Object(json_row));
+                }
+            }
+        }
+        if let Output::Json(tables) = output {
+            tables.insert(
+                String::from_utf8_lossy(field).into_owned(),
+                serde_json::Value::Array(json_rows),
+            );
+        }
+        Ok(())
+    }
+
+    /// the counts of a frequency table, sorted by count in the --asc order.
+    /// With --ignore-case, the counts of the values that only differ in case are combined.
+    fn counts<T, M>(&self, ftab: &M) -> Vec<(T, f64)>
+    where
+        T: FreqValue,
+        M: Tallies<T>,
+    {
+        let mut counts = ftab.tallies();
+        if self.flag_ignore_case {
+            // the values are sorted by descending count, so the first form of a value
+            // we see is its most frequent one, which is the one we report
+            let mut folded: IndexMap<T, (T, f64)> = IndexMap::new();
+            for (value, count) in counts {
+                folded
+                    .entry(value.fold_case())
+                    .or_insert_with(|| (value, 0.0))
+                    .1 += count;
+            }
+            counts = folded.into_iter().map(|(_, folded)| folded).collect();
+            // a stable sort, so values with the same count keep their order
+            counts.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        }
+        if self.flag_asc {
+            counts.reverse();
         }
         counts
-            .into_iter()
-            .map(|(bs, c)| {
-                if b"" == &**bs {
-                    (b"(NULL)"[..].to_vec(), c)
-                } else {
-                    (bs.clone(), c)
+    }
+
+    fn percentage(&self, count: f64, total: f64) -> String {
+        if total == 0.0 {
+            return String::new();
+        }
+        util::round_num(count * 100.0 / total, self.flag_pct_decimals)
+    }
+
+    /// the counts are integers, unless they're sums of weights
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn format_count(&self, count: f64) -> String {
+        if self.flag_weight.is_some() {
+            util::round_num(count, WEIGHTED_COUNT_PLACES)
+        } else {
+            (count as u64).to_string()
+        }
+    }
+
+    /// writes the frequency table of each selected column,
+    /// or its summary from the stats cache if it has one
+    fn write_field_tables<M>(
+        &self,
+        output: &mut Output,
+        headers: &Headers,
+        tables: &[M],
+        summaries: &[UnqSummary],
+    ) -> CliResult<()>
+    where
+        M: Tallies<ByteString>,
+    {
+        for (i, (header, ftab)) in headers.iter().zip(tables.iter()).enumerate() {
+            let header = if self.flag_no_headers {
+                (i + 1).to_string().into_bytes()
+            } else {
+                header.to_vec()
+            };
+            if let Some(Some((unique_count, total))) = summaries.get(i) {
+                let unique_values = format!("({unique_count} UNIQUE VALUES)");
+                let rows: Vec<(Key<ByteString>, f64)> = vec![(Key::Label(unique_values), *total)];
+                self.write_rows(output, &header, Some(&header), 1, rows, *total)?;
+            } else {
+                self.write_table(output, &header, Some(&header), 1, ftab)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the summaries of the selected columns with more unique values than --unq-limit,
+    /// from the stats cache of the input. Only Integer & Float columns are summarized, as
+    /// their values have no whitespace to trim nor case to ignore, so their cached
+    /// cardinality is exactly their number of distinct values.
+    #[allow(clippy::cast_precision_loss)]
+    fn cached_unq_summaries(&self) -> CliResult<Vec<UnqSummary>> {
+        if self.flag_unq_limit == 0 || self.flag_ignore_case {
+            return Ok(vec![]);
+        }
+        let stats_args = crate::cmd::stats::Args::new(
+            self.arg_input.clone(),
+            self.flag_delimiter,
+            self.flag_no_headers,
+        );
+        let (Some((stat_headers, cached_stats)), Some(record_count)) =
+            (stats_args.cached_stats(), stats_args.cached_record_count())
+        else {
+            return Ok(vec![]);
+        };
+        let stat_idx = |name: &str| stat_headers.iter().position(|h| h == name.as_bytes());
+        let (Some(type_idx), Some(nullcount_idx), Some(cardinality_idx)) = (
+            stat_idx("type"),
+            stat_idx("nullcount"),
+            stat_idx("cardinality"),
+        ) else {
+            return Ok(vec![]);
+        };
+        let parse_count = |field: Option<&[u8]>| -> Option<u64> {
+            std::str::from_utf8(field?).ok()?.parse().ok()
+        };
+
+        let mut rdr = self.rconfig().reader()?;
+        let (_, sel) = self.sel_headers(&mut rdr)?;
+        log::info!("using the stats cache");
+        Ok(sel
+            .iter()
+            .map(|&col_idx| {
+                let stat = cached_stats.get(col_idx)?;
+                if !matches!(stat.get(type_idx)?, b"Integer" | b"Float") {
+                    return None;
                 }
+                let nullcount = parse_count(stat.get(nullcount_idx))?;
+                let mut unique_count = parse_count(stat.get(cardinality_idx))?;
+                let mut total = record_count;
+                // the NULLs are one of the unique values
+                if self.flag_no_nulls && nullcount > 0 {
+                    unique_count -= 1;
+                    total -= nullcount;
+                }
+                (unique_count > self.flag_unq_limit as u64).then_some((unique_count, total as f64))
             })
-            .collect()
+            .collect())
     }
 
-    pub fn sequential_ftables(&self) -> CliResult<(Headers, FTables)> {
+    /// the frequency tables of the selected columns.
+    /// The values of the `skip`ped columns are not counted.
+    pub fn sequential_ftables(&self, skip: &[bool]) -> CliResult<(Headers, FTables)> {
         let mut rdr = self.rconfig().reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
-        Ok((headers, self.ftables(&sel, rdr.byte_records())?))
+        Ok((headers, self.ftables(&sel, rdr.byte_records(), skip)?))
     }
 
     pub fn parallel_ftables(
         &self,
         idx: &mut Indexed<fs::File, fs::File>,
+        skip: &[bool],
     ) -> CliResult<(Headers, FTables)> {
         let mut rdr = self.rconfig().reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
@@ -166,19 +629,19 @@ impl Args {
         let pool = ThreadPool::new(util::njobs(self.flag_jobs));
         let (send, recv) = channel::bounded(0);
         for i in 0..nchunks {
-            let (send, args, sel) = (send.clone(), self.clone(), sel.clone());
+            let (send, args, sel, skip) = (send.clone(), self.clone(), sel.clone(), skip.to_vec());
             pool.execute(move || {
                 let mut idx = args.rconfig().indexed().unwrap().unwrap();
                 idx.seek((i * chunk_size) as u64).unwrap();
                 let it = idx.byte_records().take(chunk_size);
-                send.send(args.ftables(&sel, it).unwrap()).unwrap();
+                send.send(args.ftables(&sel, it, &skip).unwrap()).unwrap();
             });
         }
         drop(send);
         Ok((headers, merge_all(recv.iter()).unwrap()))
     }
 
-    fn ftables<I>(&self, sel: &Selection, it: I) -> CliResult<FTables>
+    fn ftables<I>(&self, sel: &Selection, it: I, skip: &[bool]) -> CliResult<FTables>
     where
         I: Iterator<Item = csv::Result<csv::ByteRecord>>,
     {
@@ -192,12 +655,10 @@ impl Args {
         for row in it {
             row_work = row?;
             for (i, field) in nsel.select(row_work.into_iter()).enumerate() {
-                field_work = {
-                    match String::from_utf8(field.to_vec()) {
-                        Ok(s) => s.trim().as_bytes().to_vec(),
-                        Err(bs) => bs.into_bytes(),
-                    }
-                };
+                if skip.get(i) == Some(&true) {
+                    continue;
+                }
+                field_work = normalize(field);
                 if !field_work.is_empty() {
                     tabs[i].add(field_work);
                 } else if !self.flag_no_nulls {
@@ -208,6 +669,73 @@ impl Args {
         Ok(tabs)
     }
 
+    /// the weighted frequency tables of the selected columns
+    fn weighted_ftables(
+        &self,
+        weight: &SelectColumns,
+    ) -> CliResult<(Headers, Vec<WTable<ByteString>>)> {
+        let mut rdr = self.rconfig().reader()?;
+        let weight = self.weight_index(weight, rdr.byte_headers()?)?;
+        let (headers, sel) = self.sel_headers(&mut rdr)?;
+        let mut tabs: Vec<WTable<ByteString>> = (0..sel.len()).map(|_| WTable::new()).collect();
+        for row in rdr.byte_records() {
+            let row = row?;
+            let Some(row_weight) = row.get(weight).and_then(parse_weight) else {
+                continue;
+            };
+            for (i, field) in sel.select(&row).enumerate() {
+                let field = normalize(field);
+                if !field.is_empty() || !self.flag_no_nulls {
+                    *tabs[i].entry(field).or_insert(0.0) += row_weight;
+                }
+            }
+        }
+        Ok((headers, tabs))
+    }
+
+    /// the frequency table of the combinations of the values of the selected columns,
+    /// optionally weighted
+    fn combo_ftable(
+        &self,
+        weight: Option<&SelectColumns>,
+    ) -> CliResult<(Headers, WTable<Vec<ByteString>>)> {
+        let mut rdr = self.rconfig().reader()?;
+        let weight = match weight {
+            Some(weight) => Some(self.weight_index(weight, rdr.byte_headers()?)?),
+            None => None,
+        };
+        let (headers, sel) = self.sel_headers(&mut rdr)?;
+        let mut ftab = WTable::new();
+        for row in rdr.byte_records() {
+            let row = row?;
+            let row_weight = match weight {
+                Some(weight) => match row.get(weight).and_then(parse_weight) {
+                    Some(row_weight) => row_weight,
+                    None => continue,
+                },
+                None => 1.0,
+            };
+            let combo: Vec<ByteString> = sel.select(&row).map(normalize).collect();
+            if self.flag_no_nulls && combo.iter().any(Vec::is_empty) {
+                continue;
+            }
+            *ftab.entry(combo).or_insert(0.0) += row_weight;
+        }
+        Ok((headers, ftab))
+    }
+
+    /// returns the index of the --weight column
+    fn weight_index(&self, weight: &SelectColumns, headers: &csv::ByteRecord) -> CliResult<usize> {
+        let sel = weight.selection(headers, !self.flag_no_headers)?;
+        if sel.len() != 1 {
+            return fail_clierror!(
+                "--weight must select exactly one column. {} columns selected.",
+                sel.len()
+            );
+        }
+        Ok(sel[0])
+    }
+
     fn sel_headers<R: io::Read>(
         &self,
         rdr: &mut csv::Reader<R>,
@@ -0,0 +1,87 @@
+static USAGE: &str = r#"
+Converts files of other formats to CSV, so they can be sliced, searched, sampled, etc.
+with the other qsv commands.
+
+It has one subcommand:
+ * parquet - convert a Parquet file to CSV, using the Pola.rs engine.
+             With --limit, only the first rows of the file are read.
+             Null values are written as empty fields.
+
+Examples:
+Convert data.parquet to data.csv.
+
+  $ qsv from parquet data.parquet -o data.csv
+
+Sample 100 rows of the first million rows of data.parquet.
+
+  $ qsv from parquet data.parquet --limit 1000000 | qsv sample 100
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_from.rs.
+
+Usage:
+    qsv from parquet [options] <input>
+    qsv from --help
+
+from arguments:
+    <input>                The file to convert. Stdin is not supported.
+
+from options:
+    -l, --limit <n>        Only convert the first <n> rows.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -d, --delimiter <arg>  The field delimiter for writing CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use polars::prelude::*;
+use serde::Deserialize;
+
+use crate::{config::Delimiter, util, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    cmd_parquet:    bool,
+    arg_input:      String,
+    flag_limit:     Option<usize>,
+    flag_output:    Option<String>,
+    flag_delimiter: Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    if !Path::new(&args.arg_input).exists() {
+        return fail_clierror!("Input file '{}' does not exist.", args.arg_input);
+    }
+
+    let mut df = if args.cmd_parquet {
+        let scan_args = ScanArgsParquet {
+            n_rows: args.flag_limit,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(&args.arg_input, scan_args)?.collect()?
+    } else {
+        unreachable!();
+    };
+
+    // no need to use buffered writer here, as CsvWriter already does that
+    let mut out_writer = match args.flag_output {
+        Some(output_file) => Box::new(File::create(output_file)?) as Box<dyn Write>,
+        None => Box::new(io::stdout()) as Box<dyn Write>,
+    };
+
+    CsvWriter::new(&mut out_writer)
+        .has_header(true)
+        .with_delimiter(args.flag_delimiter.map_or(b',', Delimiter::as_byte))
+        .finish(&mut df)?;
+
+    Ok(())
+}
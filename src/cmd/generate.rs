@@ -6,6 +6,20 @@ Also allows you to create and use Data Sample Parser (DSP) profiles
 to generate test data without access to the original profiled CSV.
 See https://docs.rs/test-data-generation/ for more info.
 
+Alternatively, test data can be generated from the column types & distributions
+of a CSV (--mimic), or from a JSON Schema (--schema):
+  * with --mimic, integer, float & date columns are generated within the same range
+    as in <input>, boolean & low cardinality columns (at most 20 distinct values)
+    with the same value frequencies, email columns as fake emails, columns whose
+    name contains "name" as fake names, and other columns as random words of
+    similar length. Empty values are generated as often as in <input>.
+  * with --schema, the "type", "enum", "minimum", "maximum", "minLength", "maxLength"
+    & "format" (date, date-time & email) keywords of each property of a JSON Schema
+    (like the ones created by 'qsv schema') are used. Columns whose type includes
+    "null" are empty 10% of the time.
+Both modes are much faster than profiling with the Markov decision process, and
+generate the same data for the same --seed.
+
 Examples:
 
 Generate 100 rows of test data based on prod-data.csv and 
@@ -28,10 +42,20 @@ Create a DSP profile (prod-profile.json) based on prod-data.csv.
 
 $ qsv generate prod-data.csv --outdsp prod-profile
 
+Generate 1000 rows that have the same column types & distributions as prod-data.csv,
+generating the same rows on every run.
+
+$ qsv generate -r 1000 --mimic --seed 42 prod-data.csv
+
+Generate 50 rows from the JSON Schema created by 'qsv schema prod-data.csv'.
+
+$ qsv generate -r 50 --schema prod-data.csv.schema.json
+
 Usage:
     qsv generate [options] [--rows=<count>] <input>
     qsv generate [options] [--rows=<count>] (--indsp=<file>)
     qsv generate [options] (--outdsp=<file>) [<input>]
+    qsv generate [options] [--rows=<count>] (--schema=<file>)
     qsv generate --help
 
 generate options:
@@ -42,6 +66,13 @@ generate options:
                            .json file extension automatically added.
     --indsp <file>         Use a DSP JSON file to generate test data.
                            .json file extension assumed.
+    --mimic                Generate test data with the column types & distributions
+                           of <input>, instead of profiling it with the Markov
+                           decision process.
+    --schema <file>        Generate test data from a JSON Schema.
+    --seed <n>             The seed of the random number generator, to generate
+                           the same test data on every run. Only used with
+                           --mimic & --schema.
 
 Common options:
     -h, --help             Display this message
@@ -56,7 +87,10 @@ use std::{
     io::{self, Write},
 };
 
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
 use serde::Deserialize;
+use serde_json::Value;
 use test_data_generation::data_sample_parser::DataSampleParser;
 use uuid::Uuid;
 
@@ -72,13 +106,99 @@ struct Args {
     flag_output:    Option<String>,
     flag_outdsp:    Option<String>,
     flag_indsp:     Option<String>,
+    flag_mimic:     bool,
+    flag_schema:    Option<String>,
+    flag_seed:      Option<u64>,
     flag_delimiter: Option<Delimiter>,
 }
 
+/// Low cardinality columns are generated from their observed values.
+const MAX_CHOICES: usize = 20;
+/// How often nullable JSON Schema columns are empty.
+const SCHEMA_NULL_RATIO: f64 = 0.1;
+
+const FIRST_NAMES: [&str; 16] = [
+    "James", "Mary", "Robert", "Patricia", "John", "Jennifer", "Michael", "Linda", "Wei", "Fatima",
+    "Carlos", "Aiko", "Olga", "Kwame", "Priya", "Liam",
+];
+const LAST_NAMES: [&str; 16] = [
+    "Smith",
+    "Johnson",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Lee",
+    "Nguyen",
+    "Kim",
+    "Patel",
+    "Okafor",
+    "Ivanova",
+    "Tanaka",
+    "Silva",
+    "Brown",
+];
+const EMAIL_DOMAINS: [&str; 3] = ["example.com", "example.org", "example.net"];
+const WORDS: [&str; 24] = [
+    "lorem", "ipsum", "dolor", "sit", "amet", "data", "table", "river", "stone", "blue", "quick",
+    "report", "city", "north", "value", "record", "green", "field", "market", "light", "open",
+    "sample", "paper", "tree",
+];
+
+enum Generator {
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    Float {
+        min:      f64,
+        max:      f64,
+        decimals: usize,
+    },
+    Date {
+        min: i32,
+        max: i32,
+    },
+    DateTime {
+        min: i64,
+        max: i64,
+    },
+    Boolean,
+    Email,
+    Name,
+    Choice {
+        values:  Vec<String>,
+        weights: WeightedIndex<u64>,
+    },
+    Text {
+        min_len: usize,
+        max_len: usize,
+    },
+}
+
+struct Column {
+    name:       String,
+    generator:  Generator,
+    null_ratio: f64,
+}
+
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
     let conf = Config::new(&args.arg_input).delimiter(args.flag_delimiter);
 
+    if let Some(ref schema) = args.flag_schema {
+        let schema: Value = serde_json::from_str(&fs::read_to_string(schema)?)
+            .map_err(|e| format!("Cannot parse JSON Schema: {e}"))?;
+        return generate_typed(&args, columns_from_schema(&schema)?);
+    }
+    if args.flag_mimic {
+        if args.flag_outdsp.is_some() {
+            return fail_clierror!("--mimic cannot be used with --outdsp.");
+        }
+        return generate_typed(&args, columns_from_csv(&conf)?);
+    }
+
     let tdir = temp_dir();
     let mut dsp = DataSampleParser::new();
 
@@ -141,3 +261,354 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
     Ok(())
 }
+
+/// Generates test data with the --mimic or --schema column generators.
+fn generate_typed(args: &Args, columns: Vec<Column>) -> CliResult<()> {
+    if columns.is_empty() {
+        return fail_clierror!("No columns to generate.");
+    }
+
+    let mut rng = match args.flag_seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    wtr.write_record(columns.iter().map(|c| &c.name))?;
+    let mut record = csv::StringRecord::with_capacity(256, columns.len());
+    for _ in 0..args.flag_rows {
+        record.clear();
+        for column in &columns {
+            if column.null_ratio > 0.0 && rng.gen_bool(column.null_ratio.min(1.0)) {
+                record.push_field("");
+            } else {
+                record.push_field(&column.generator.generate(&mut rng));
+            }
+        }
+        wtr.write_record(&record)?;
+    }
+    Ok(wtr.flush()?)
+}
+
+impl Generator {
+    fn generate(&self, rng: &mut StdRng) -> String {
+        match self {
+            Generator::Integer { min, max } => rng.gen_range(*min..=*max).to_string(),
+            Generator::Float { min, max, decimals } => {
+                let n = if min < max {
+                    rng.gen_range(*min..*max)
+                } else {
+                    *min
+                };
+                format!("{n:.decimals$}")
+            }
+            Generator::Date { min, max } => {
+                NaiveDate::from_num_days_from_ce_opt(rng.gen_range(*min..=*max))
+                    .unwrap_or_default()
+                    .format("%Y-%m-%d")
+                    .to_string()
+            }
+            Generator::DateTime { min, max } => {
+                NaiveDateTime::from_timestamp_opt(rng.gen_range(*min..=*max), 0)
+                    .unwrap_or_default()
+                    .format("%Y-%m-%dT%H:%M:%S")
+                    .to_string()
+            }
+            Generator::Boolean => rng.gen_bool(0.5).to_string(),
+            Generator::Email => format!(
+                "{}.{}{}@{}",
+                FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_lowercase(),
+                LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())].to_lowercase(),
+                rng.gen_range(1..1000),
+                EMAIL_DOMAINS[rng.gen_range(0..EMAIL_DOMAINS.len())]
+            ),
+            Generator::Name => format!(
+                "{} {}",
+                FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())],
+                LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())]
+            ),
+            Generator::Choice { values, weights } => values[weights.sample(rng)].clone(),
+            Generator::Text { min_len, max_len } => {
+                let target_len = rng.gen_range(*min_len..=*max_len);
+                let mut text = String::with_capacity(target_len + 8);
+                while text.len() < target_len {
+                    if !text.is_empty() {
+                        text.push(' ');
+                    }
+                    text.push_str(WORDS[rng.gen_range(0..WORDS.len())]);
+                }
+                text.truncate(target_len);
+                text.trim_end().to_string()
+            }
+        }
+    }
+}
+
+fn choice(values: Vec<String>, weights: Vec<u64>) -> CliResult<Generator> {
+    let weights = WeightedIndex::new(weights).map_err(|e| format!("Invalid choices: {e}"))?;
+    Ok(Generator::Choice { values, weights })
+}
+
+fn days_from_ce(year: i32, month: u32, day: u32) -> i32 {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap_or_default()
+        .num_days_from_ce()
+}
+
+/// Builds the columns from the properties of a JSON Schema.
+fn columns_from_schema(schema: &Value) -> CliResult<Vec<Column>> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return fail_clierror!("The JSON Schema has no \"properties\".");
+    };
+
+    let mut columns = Vec::with_capacity(properties.len());
+    for (name, def) in properties {
+        let types: Vec<&str> = match def.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec!["string"],
+        };
+        let null_ratio = if types.contains(&"null") {
+            SCHEMA_NULL_RATIO
+        } else {
+            0.0
+        };
+        let main_type = types
+            .iter()
+            .find(|t| **t != "null")
+            .copied()
+            .unwrap_or("string");
+        let number = |key: &str| def.get(key).and_then(Value::as_f64);
+
+        let generator = if let Some(Value::Array(values)) = def.get("enum") {
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    Value::Null => String::new(),
+                    v => v.to_string(),
+                })
+                .collect();
+            if values.is_empty() {
+                return fail_clierror!("The \"enum\" of {name} is empty.");
+            }
+            let weights = vec![1; values.len()];
+            choice(values, weights)?
+        } else {
+            match main_type {
+                "integer" => {
+                    let min = number("minimum").unwrap_or(0.0) as i64;
+                    let max = number("maximum").unwrap_or(1000.0) as i64;
+                    Generator::Integer {
+                        min,
+                        max: max.max(min),
+                    }
+                }
+                "number" => {
+                    let min = number("minimum").unwrap_or(0.0);
+                    let max = number("maximum").unwrap_or(1000.0);
+                    Generator::Float {
+                        min,
+                        max: max.max(min),
+                        decimals: 2,
+                    }
+                }
+                "boolean" => Generator::Boolean,
+                _ => match def.get("format").and_then(Value::as_str) {
+                    Some("date") => Generator::Date {
+                        min: days_from_ce(2000, 1, 1),
+                        max: days_from_ce(2030, 12, 31),
+                    },
+                    Some("date-time") => Generator::DateTime {
+                        // 2000-01-01T00:00:00 to 2030-12-31T23:59:59
+                        min: 946_684_800,
+                        max: 1_924_991_999,
+                    },
+                    Some("email") => Generator::Email,
+                    _ if name.to_lowercase().contains("name") => Generator::Name,
+                    _ => {
+                        let min_len = number("minLength").unwrap_or(1.0) as usize;
+                        let max_len = number("maxLength").unwrap_or(20.0) as usize;
+                        Generator::Text {
+                            min_len,
+                            max_len: max_len.max(min_len),
+                        }
+                    }
+                },
+            }
+        };
+        columns.push(Column {
+            name: name.clone(),
+            generator,
+            null_ratio,
+        });
+    }
+    Ok(columns)
+}
+
+/// What we've seen of the values of a column of the CSV to mimic.
+struct ColumnProfile {
+    nulls:        u64,
+    values:       u64,
+    all_integer:  bool,
+    all_float:    bool,
+    all_date:     bool,
+    all_bool:     bool,
+    all_email:    bool,
+    min_number:   f64,
+    max_number:   f64,
+    max_decimals: usize,
+    min_date:     i32,
+    max_date:     i32,
+    min_len:      usize,
+    max_len:      usize,
+    /// the frequencies of the distinct values, until there are more than MAX_CHOICES
+    choices:      Option<HashMap<String, u64>>,
+}
+
+impl Default for ColumnProfile {
+    fn default() -> Self {
+        ColumnProfile {
+            nulls:        0,
+            values:       0,
+            all_integer:  true,
+            all_float:    true,
+            all_date:     true,
+            all_bool:     true,
+            all_email:    true,
+            min_number:   f64::INFINITY,
+            max_number:   f64::NEG_INFINITY,
+            max_decimals: 0,
+            min_date:     i32::MAX,
+            max_date:     i32::MIN,
+            min_len:      usize::MAX,
+            max_len:      0,
+            choices:      Some(HashMap::new()),
+        }
+    }
+}
+
+impl ColumnProfile {
+    fn add(&mut self, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.nulls += 1;
+            return;
+        }
+        self.values += 1;
+        self.min_len = self.min_len.min(value.len());
+        self.max_len = self.max_len.max(value.len());
+
+        self.all_integer = self.all_integer && value.parse::<i64>().is_ok();
+        if self.all_float {
+            // "NaN" & "inf" parse as floats, but aren't numbers we can generate
+            if let Some(n) = value.parse::<f64>().ok().filter(|n| n.is_finite()) {
+                self.min_number = self.min_number.min(n);
+                self.max_number = self.max_number.max(n);
+                let decimals = value.split_once('.').map_or(0, |(_, d)| d.len());
+                self.max_decimals = self.max_decimals.max(decimals);
+            } else {
+                self.all_float = false;
+            }
+        }
+        if self.all_date {
+            if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                let days = date.num_days_from_ce();
+                self.min_date = self.min_date.min(days);
+                self.max_date = self.max_date.max(days);
+            } else {
+                self.all_date = false;
+            }
+        }
+        self.all_bool = self.all_bool && matches!(value, "true" | "false");
+        self.all_email = self.all_email && value.contains('@');
+
+        if let Some(ref mut choices) = self.choices {
+            *choices.entry(value.to_string()).or_default() += 1;
+            if choices.len() > MAX_CHOICES {
+                self.choices = None;
+            }
+        }
+    }
+
+    fn into_column(self, name: String) -> CliResult<Column> {
+        let total = self.nulls + self.values;
+        let null_ratio = if total == 0 {
+            0.0
+        } else {
+            self.nulls as f64 / total as f64
+        };
+
+        let generator = if self.values == 0 {
+            // an all-empty column
+            Generator::Text {
+                min_len: 0,
+                max_len: 0,
+            }
+        } else if self.all_bool {
+            let mut choices: Vec<(String, u64)> =
+                self.choices.unwrap_or_default().into_iter().collect();
+            choices.sort_unstable();
+            let (values, weights) = choices.into_iter().unzip();
+            choice(values, weights)?
+        } else if self.all_integer {
+            Generator::Integer {
+                min: self.min_number as i64,
+                max: self.max_number as i64,
+            }
+        } else if self.all_float {
+            Generator::Float {
+                min:      self.min_number,
+                max:      self.max_number,
+                decimals: self.max_decimals,
+            }
+        } else if self.all_date {
+            Generator::Date {
+                min: self.min_date,
+                max: self.max_date,
+            }
+        } else if self.all_email {
+            // never reuse the emails & names of <input>, even in low cardinality columns
+            Generator::Email
+        } else if name.to_lowercase().contains("name") {
+            Generator::Name
+        } else if let Some(choices) = self.choices {
+            // sort the choices, so the same seed generates the same data
+            let mut choices: Vec<(String, u64)> = choices.into_iter().collect();
+            choices.sort_unstable();
+            let (values, weights) = choices.into_iter().unzip();
+            choice(values, weights)?
+        } else {
+            Generator::Text {
+                min_len: self.min_len,
+                max_len: self.max_len,
+            }
+        };
+        Ok(Column {
+            name,
+            generator,
+            null_ratio,
+        })
+    }
+}
+
+/// Builds the columns by profiling the columns of a CSV.
+fn columns_from_csv(rconfig: &Config) -> CliResult<Vec<Column>> {
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.headers()?.clone();
+
+    let mut profiles: Vec<ColumnProfile> =
+        headers.iter().map(|_| ColumnProfile::default()).collect();
+    let mut record = csv::StringRecord::new();
+    while rdr.read_record(&mut record)? {
+        for (profile, value) in profiles.iter_mut().zip(record.iter()) {
+            profile.add(value);
+        }
+    }
+
+    headers
+        .iter()
+        .zip(profiles)
+        .map(|(name, profile)| profile.into_column(name.to_string()))
+        .collect()
+}
@@ -0,0 +1,419 @@
+static USAGE: &str = r#"
+Geocodes a location against an embedded copy of the Geonames cities database.
+
+It has two subcommands:
+ * reverse - given a location column [i.e. a column which contains a latitude,
+   longitude WGS84 coordinate, e.g. "40.7128, -74.0060" or "(40.7128, -74.0060)"],
+   returns the nearest city center.
+ * forward - given a place name column (e.g. "Brooklyn", "Brooklyn, New York",
+   "Paris, FR" or "London, England GB"), returns its latitude & longitude.
+
+The geocoded information is formatted based on --formatstr. reverse returns it in
+'city-state' format if not specified, while forward returns the '(lat, long)'
+location of the place.
+
+Geocoding is done offline and results are cached, so it's much faster than
+using an online geocoding service with `fetch`.
+
+The embedded index only supports reverse geocoding. Forward geocoding looks up
+place names in a Geonames cities index file, set with --index or the
+QSV_GEOCODE_INDEX environment variable. It's a CSV with the columns
+lat,lon,name,admin1,admin2,cc - the same format as the embedded index, whose
+cities.csv can be used as is. reverse uses it too when it's set.
+
+Place names are matched case-insensitively. The name can be qualified by the
+state/admin1, county/admin2 and/or country code, separated by commas. When several
+places still match, the place that is also the name of its state or county is
+preferred (e.g. "Paris" is Paris, FR and "London" is London, GB), otherwise the
+first one in the index. Qualify the name to pick another (e.g. "Paris, Texas").
+
+With --online, the place names not found offline (or all of them, without an
+index) are looked up with a Nominatim-compatible geocoding service - by default,
+OpenStreetMap's. Per its usage policy, requests are throttled to one per second.
+Online results only have coordinates, so the place formats are resolved by
+reverse geocoding them.
+
+Examples:
+Reverse geocode file.csv Location column and set the geocoded value to a
+new column named City.
+
+$ qsv geocode reverse Location --new-column City file.csv
+
+Reverse geocode file.csv Location column, replacing its value with the county, state & country.
+
+$ qsv geocode reverse Location --formatstr county-state-country file.csv
+
+Get the latitude & longitude of the nearest city center into a new column.
+
+$ qsv geocode reverse Location --formatstr city-location --new-column CityCenter file.csv
+
+Forward geocode file.csv Place column into a new Location column, using the
+cities.csv index.
+
+$ qsv geocode forward Place --index cities.csv --new-column Location file.csv
+
+Forward geocode the places that are not in the index online, getting the latitude only.
+
+$ qsv geocode forward Place --index cities.csv --online --formatstr lat -c Lat file.csv
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_geocode.rs.
+
+Usage:
+    qsv geocode reverse [--formatstr=<string>] [options] <column> [<input>]
+    qsv geocode forward [--formatstr=<string>] [options] <column> [<input>]
+    qsv geocode --help
+
+geocode arguments:
+    <column>                    The column to geocode.
+    <input>                     The input file to read from. If not specified, reads from stdin.
+
+geocode options:
+    -c, --new-column <name>     Put the geocoded values in a new column instead
+                                of replacing the values of the location column.
+    -r, --rename <name>         New name for the geocoded column.
+    -f, --formatstr=<string>    The place format to use. The available formats are:
+                                  - 'city-state' (reverse default) - e.g. Brooklyn, New York
+                                  - 'city-country' - Brooklyn, US
+                                  - 'city-state-country' | 'city-admin1-country' - Brooklyn, New York US
+                                  - 'city' - Brooklyn
+                                  - 'county' | 'admin2' - Kings County
+                                  - 'state' | 'admin1' - New York
+                                  - 'county-country' | 'admin2-country' - Kings County, US
+                                  - 'county-state-country' | 'admin2-admin1-country' - Kings County, New York US
+                                  - 'country' - US
+                                  - 'city-location' - (40.65, -73.95) - the city center's coordinates
+                                forward also has these formats:
+                                  - 'location' (forward default) - (40.6501, -73.94958)
+                                  - 'lat-long' - 40.6501, -73.94958
+                                  - 'lat' - 40.6501
+                                  - 'long' - -73.94958
+    --invalid-result <string>   The string to return when the location is not valid or
+                                cannot be geocoded. If not set, the original value is kept.
+    --index <file>              The Geonames cities index (lat,lon,name,admin1,admin2,cc
+                                columns) to geocode against. Required for forward,
+                                unless --online is set. Overrides QSV_GEOCODE_INDEX.
+    --online                    forward only. Look up the place names not found in the
+                                index with an online geocoding service.
+    --provider-url <url>        The search URL of the Nominatim-compatible service
+                                used by --online.
+                                [default: https://nominatim.openstreetmap.org/search]
+
+Common options:
+    -h, --help                  Display this message
+    -o, --output <file>         Write output to <file> instead of stdout.
+    -n, --no-headers            When set, the first row will not be interpreted
+                                as headers.
+    -d, --delimiter <arg>       The field delimiter for reading CSV data.
+                                Must be a single character. (default: ,)
+    -p, --progressbar           Show progress bars. Not valid for stdin.
+"#;
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use cached::proc_macro::cached;
+use indicatif::{ProgressBar, ProgressDrawTarget};
+use once_cell::sync::OnceCell;
+use reqwest::blocking::Client;
+use reverse_geocoder::{Locations, Record};
+use serde::Deserialize;
+
+use crate::{
+    cmd::apply::{format_place, replace_column_value, search_cached, LOCS, SEARCH_CACHED},
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_column:          SelectColumns,
+    cmd_reverse:         bool,
+    cmd_forward:         bool,
+    arg_input:           Option<String>,
+    flag_new_column:     Option<String>,
+    flag_rename:         Option<String>,
+    flag_formatstr:      Option<String>,
+    flag_invalid_result: Option<String>,
+    flag_index:          Option<String>,
+    flag_online:         bool,
+    flag_provider_url:   String,
+    flag_output:         Option<String>,
+    flag_no_headers:     bool,
+    flag_delimiter:      Option<Delimiter>,
+    flag_progressbar:    bool,
+}
+
+// the places of the forward geocoding index, keyed by their lowercased name
+static PLACES: OnceCell<HashMap<String, Vec<Record>>> = OnceCell::new();
+static ONLINE_CLIENT: OnceCell<Client> = OnceCell::new();
+static LAST_ONLINE_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+// the minimum interval between online requests, per the Nominatim usage policy
+const ONLINE_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+const VALID_FORMATS: [&str; 15] = [
+    "city-state",
+    "city-country",
+    "city-state-country",
+    "city-admin1-country",
+    "city",
+    "county",
+    "admin2",
+    "state",
+    "admin1",
+    "county-country",
+    "admin2-country",
+    "county-state-country",
+    "admin2-admin1-country",
+    "country",
+    "city-location",
+];
+
+const LOCATION_FORMATS: [&str; 4] = ["location", "lat-long", "lat", "long"];
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    if !args.cmd_reverse && !args.cmd_forward {
+        return fail!("Unknown geocode subcommand.");
+    }
+    let formatstr = match args.flag_formatstr {
+        Some(formatstr) => formatstr,
+        None if args.cmd_forward => "location".to_string(),
+        None => "city-state".to_string(),
+    };
+    if !(VALID_FORMATS.contains(&formatstr.as_str())
+        || (args.cmd_forward && LOCATION_FORMATS.contains(&formatstr.as_str())))
+    {
+        return fail_clierror!("Invalid --formatstr: {formatstr}");
+    }
+    if args.flag_online && !args.cmd_forward {
+        return fail!("--online is only valid for forward geocoding.");
+    }
+
+    let index_path = args
+        .flag_index
+        .or_else(|| std::env::var("QSV_GEOCODE_INDEX").ok());
+    if let Some(ref index_path) = index_path {
+        let locs = Locations::from_path(index_path)
+            .map_err(|e| format!("Cannot load the geocode index '{index_path}': {e}"))?;
+        // the index is loaded once per run, so LOCS is always empty here
+        let _ = LOCS.set(locs);
+        if args.cmd_forward {
+            let _ = PLACES.set(load_places(index_path)?);
+        }
+    }
+    if args.cmd_forward && index_path.is_none() && !args.flag_online {
+        return fail!(
+            "Forward geocoding needs a geocode index (--index or QSV_GEOCODE_INDEX) or --online."
+        );
+    }
+    if args.flag_online {
+        let client = Client::builder()
+            .user_agent(util::DEFAULT_USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Cannot build the online geocoding client: {e}"))?;
+        let _ = ONLINE_CLIENT.set(client);
+    }
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.arg_column);
+
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    let headers = rdr.byte_headers()?.clone();
+    let sel = rconfig.selection(&headers)?;
+    if sel.len() != 1 {
+        return fail!("Only one column can be geocoded at a time.");
+    }
+    let column_index = *sel.iter().next().unwrap();
+
+    let mut headers = rdr.headers()?.clone();
+    if let Some(new_name) = args.flag_rename {
+        let new_col_names = util::ColumnNameParser::new(&new_name).parse()?;
+        if new_col_names.len() != sel.len() {
+            return fail!("Number of new columns does not match input column selection.");
+        }
+        headers = replace_column_value(&headers, column_index, &new_col_names[0]);
+    }
+    if !rconfig.no_headers {
+        if let Some(new_column) = &args.flag_new_column {
+            headers.push_field(new_column);
+        }
+        wtr.write_record(&headers)?;
+    }
+
+    // prep progress bar
+    let show_progress =
+        (args.flag_progressbar || std::env::var("QSV_PROGRESSBAR").is_ok()) && !rconfig.is_stdin();
+    let progress = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr_with_hz(5));
+    if show_progress {
+        util::prep_progress(&progress, util::count_rows(&rconfig)?);
+    } else {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
+    let mut record = csv::StringRecord::new();
+    let mut cell = String::new();
+    while rdr.read_record(&mut record)? {
+        if show_progress {
+            progress.inc(1);
+        }
+
+        record[column_index].clone_into(&mut cell);
+        if !cell.is_empty() {
+            let geocoded_result = if args.cmd_forward {
+                forward_geocode(&cell, &formatstr, args.flag_online, &args.flag_provider_url)
+            } else {
+                search_cached(&cell, &formatstr)
+            };
+            if let Some(geocoded_result) = geocoded_result {
+                cell = geocoded_result;
+            } else if let Some(ref invalid_result) = args.flag_invalid_result {
+                invalid_result.clone_into(&mut cell);
+            }
+        }
+
+        if args.flag_new_column.is_some() {
+            record.push_field(&cell);
+        } else {
+            record = replace_column_value(&record, column_index, &cell);
+        }
+        wtr.write_record(&record)?;
+    }
+
+    if show_progress {
+        if args.cmd_forward {
+            util::update_cache_info!(progress, ONLINE_SEARCH_CACHED);
+        } else {
+            util::update_cache_info!(progress, SEARCH_CACHED);
+        }
+        util::finish_progress(&progress);
+    }
+    Ok(wtr.flush()?)
+}
+
+// load the places of a Geonames cities index, keyed by their lowercased name
+fn load_places(index_path: &str) -> CliResult<HashMap<String, Vec<Record>>> {
+    let mut rdr = csv::Reader::from_path(index_path)?;
+    let mut places: HashMap<String, Vec<Record>> = HashMap::new();
+    for place in rdr.deserialize() {
+        let place: Record = place?;
+        places
+            .entry(place.name.to_lowercase())
+            .or_default()
+            .push(place);
+    }
+    Ok(places)
+}
+
+fn forward_geocode(
+    cell: &str,
+    formatstr: &str,
+    online: bool,
+    provider_url: &str,
+) -> Option<String> {
+    if let Some(place) = PLACES.get().and_then(|places| search_place(places, cell)) {
+        return Some(
+            format_location(place.lat, place.lon, formatstr)
+                .unwrap_or_else(|| format_place(place, formatstr)),
+        );
+    }
+    if !online {
+        return None;
+    }
+    let (lat, long) = online_search_cached(cell, provider_url)?;
+    format_location(lat, long, formatstr)
+        .or_else(|| search_cached(&format!("{lat}, {long}"), formatstr))
+}
+
+// find a place by its name, optionally qualified by its admin1, admin2 and/or country code,
+// e.g. "Brooklyn", "Brooklyn, New York", "Brooklyn, New York US" or "Brooklyn, US"
+fn search_place<'a>(places: &'a HashMap<String, Vec<Record>>, cell: &str) -> Option<&'a Record> {
+    let mut parts = cell.split(',').map(|part| part.trim().to_lowercase());
+    let candidates = places.get(&parts.next()?)?;
+    let qualifiers: Vec<String> = parts.filter(|part| !part.is_empty()).collect();
+
+    let mut matches = candidates.iter().filter(|place| {
+        let admin1 = place.admin1.to_lowercase();
+        let admin2 = place.admin2.to_lowercase();
+        let cc = place.cc.to_lowercase();
+        qualifiers.iter().all(|qualifier| {
+            *qualifier == admin1
+                || *qualifier == admin2
+                || *qualifier == cc
+                || *qualifier == format!("{admin1} {cc}")
+                || *qualifier == format!("{admin2} {cc}")
+        })
+    });
+    let first = matches.next()?;
+    // prefer the place that is also the name of its state or county, e.g. Paris, FR
+    let is_seat = |place: &Record| {
+        let name = place.name.to_lowercase();
+        place.admin1.to_lowercase().contains(&name) || place.admin2.to_lowercase().contains(&name)
+    };
+    if is_seat(first) {
+        return Some(first);
+    }
+    Some(matches.find(|place| is_seat(place)).unwrap_or(first))
+}
+
+// format the coordinates of a place, if formatstr is a location format
+fn format_location(lat: f64, long: f64, formatstr: &str) -> Option<String> {
+    match formatstr {
+        "location" => Some(format!("({lat}, {long})")),
+        "lat-long" => Some(format!("{lat}, {long}")),
+        "lat" => Some(lat.to_string()),
+        "long" => Some(long.to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize)]
+struct OnlinePlace {
+    lat: String,
+    lon: String,
+}
+
+// look up a place name with a Nominatim-compatible geocoding service.
+// the Option is cached, so failed lookups are cached too and are not retried
+#[cached(
+    key = "String",
+    convert = r#"{ format!("{provider_url}\t{cell}") }"#,
+    sync_writes = false
+)]
+fn online_search_cached(cell: &str, provider_url: &str) -> Option<(f64, f64)> {
+    let client = ONLINE_CLIENT.get()?;
+
+    // throttle the requests, so we don't go over the usage policy of the service
+    if let Ok(mut last_request) = LAST_ONLINE_REQUEST.lock() {
+        if let Some(elapsed) = last_request.map(|instant| instant.elapsed()) {
+            if elapsed < ONLINE_REQUEST_INTERVAL {
+                thread::sleep(ONLINE_REQUEST_INTERVAL - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    let places: Vec<OnlinePlace> = client
+        .get(provider_url)
+        .query(&[("q", cell), ("format", "jsonv2"), ("limit", "1")])
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::json)
+        .map_err(|e| log::warn!("Cannot geocode '{cell}' online: {e}"))
+        .ok()?;
+    let place = places.first()?;
+    let lat = place.lat.parse::<f64>().ok()?;
+    let long = place.lon.parse::<f64>().ok()?;
+    Some((lat, long))
+}
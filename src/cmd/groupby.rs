@@ -0,0 +1,352 @@
+static USAGE: &str = r#"
+Groups CSV data on one or more key columns and computes aggregates over the
+records of each group. There is one output row per distinct combination of keys,
+with the key columns followed by a column for each aggregate.
+
+The aggregates are given with --agg as a comma-separated list of <func>:<column>,
+where <column> is a column name or a 1-based index. The functions are:
+  count         - the number of records in the group (no column needed)
+  sum           - the sum of the numeric values of <column>
+  mean          - the mean of the numeric values of <column>
+  min, max      - the smallest/largest value of <column>. Values are compared as
+                  numbers if all of them are numeric, as strings otherwise.
+  first, last   - the first/last value of <column>
+  concat        - the values of <column> joined with --separator
+The output columns are named "count" and "<func>_<column>" (e.g. "sum_amount").
+Empty values are ignored by all the functions except count, first & last.
+
+By default, the groups are kept in memory in a hash table and are written in the
+order they are first seen. If the input is sorted on the key columns, use --sorted
+to aggregate in a single streaming pass with constant memory instead. With --sorted,
+unsorted input results in a group being split into several rows.
+
+Examples:
+Count the orders and sum their amount per customer.
+
+  $ qsv groupby customer --agg count,sum:amount orders.csv
+
+Compute per-region & product statistics on a file already sorted on those columns.
+
+  $ qsv groupby region,product --sorted --agg mean:price,min:price,max:price sales.csv
+
+List the order ids of each customer.
+
+  $ qsv groupby customer --agg concat:id --separator ';' orders.csv
+
+Usage:
+    qsv groupby [options] <keys> [<input>]
+    qsv groupby --help
+
+groupby options:
+    -a, --agg <specs>      The comma-separated aggregates to compute.
+                           [default: count]
+    --sorted               The input is sorted on the key columns, so aggregate
+                           in a single streaming pass.
+    --separator <sep>      The separator for the concat function. [default: |]
+    --round <places>       The number of decimal places to round sums & means to.
+                           [default: 4]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be treated as headers.
+                           The key & aggregate columns are then 1-based indices.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_keys:        SelectColumns,
+    arg_input:       Option<String>,
+    flag_agg:        String,
+    flag_sorted:     bool,
+    flag_separator:  String,
+    flag_round:      u32,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+#[derive(Clone, Copy)]
+enum AggFn {
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    First,
+    Last,
+    Concat,
+}
+
+struct Aggregate {
+    func:   AggFn,
+    column: usize,
+    name:   String,
+}
+
+/// The running state of an aggregate for a single group.
+enum AggState {
+    Count(u64),
+    Sum(f64),
+    Mean { sum: f64, count: u64 },
+    Extreme(Extreme),
+    First(Option<String>),
+    Last(String),
+    Concat(Vec<String>),
+}
+
+/// Tracks the smallest or largest value of a column, both as a number and as a string,
+/// as whether the column is numeric is only known once all its values are seen.
+struct Extreme {
+    numeric: Option<(f64, String)>,
+    text:    Option<String>,
+    all_num: bool,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.arg_keys);
+
+    let mut rdr = rconfig.reader()?;
+    let byte_headers = rdr.byte_headers()?.clone();
+    let keys = rconfig.selection(&byte_headers)?;
+    let column_name = |idx: usize| {
+        if rconfig.no_headers {
+            (idx + 1).to_string()
+        } else {
+            String::from_utf8_lossy(&byte_headers[idx]).to_string()
+        }
+    };
+    let aggregates = parse_aggregates(&args.flag_agg, &byte_headers, &rconfig, column_name)?;
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    let mut header = csv::StringRecord::new();
+    for &key in keys.iter() {
+        header.push_field(&column_name(key));
+    }
+    for aggregate in &aggregates {
+        header.push_field(&aggregate.name);
+    }
+    wtr.write_record(&header)?;
+
+    let mut write_group = |key: &[String], states: &[AggState]| -> CliResult<()> {
+        let mut output = csv::StringRecord::new();
+        for value in key {
+            output.push_field(value);
+        }
+        for state in states {
+            output.push_field(&state.result(&args.flag_separator, args.flag_round));
+        }
+        Ok(wtr.write_record(&output)?)
+    };
+
+    let mut record = csv::StringRecord::new();
+    if args.flag_sorted {
+        let mut current: Option<(Vec<String>, Vec<AggState>)> = None;
+        while rdr.read_record(&mut record)? {
+            let key = group_key(&record, &keys);
+            match current {
+                Some((ref current_key, ref mut states)) if *current_key == key => {
+                    update(states, &aggregates, &record);
+                }
+                _ => {
+                    if let Some((current_key, states)) = current.take() {
+                        write_group(&current_key, &states)?;
+                    }
+                    let mut states = new_states(&aggregates);
+                    update(&mut states, &aggregates, &record);
+                    current = Some((key, states));
+                }
+            }
+        }
+        if let Some((current_key, states)) = current {
+            write_group(&current_key, &states)?;
+        }
+    } else {
+        let mut groups: IndexMap<Vec<String>, Vec<AggState>> = IndexMap::new();
+        while rdr.read_record(&mut record)? {
+            let states = groups
+                .entry(group_key(&record, &keys))
+                .or_insert_with(|| new_states(&aggregates));
+            update(states, &aggregates, &record);
+        }
+        for (key, states) in &groups {
+            write_group(key, states)?;
+        }
+    }
+
+    Ok(wtr.flush()?)
+}
+
+/// Parses the --agg specs, e.g. "count,sum:amount,concat:2".
+fn parse_aggregates(
+    specs: &str,
+    headers: &csv::ByteRecord,
+    rconfig: &Config,
+    column_name: impl Fn(usize) -> String,
+) -> CliResult<Vec<Aggregate>> {
+    let mut aggregates = Vec::new();
+    for spec in specs.split(',').map(str::trim) {
+        let (func_name, column) = match spec.split_once(':') {
+            Some((func_name, column)) => (func_name, Some(column)),
+            None => (spec, None),
+        };
+        let func = match func_name.to_lowercase().as_str() {
+            "count" => AggFn::Count,
+            "sum" => AggFn::Sum,
+            "mean" => AggFn::Mean,
+            "min" => AggFn::Min,
+            "max" => AggFn::Max,
+            "first" => AggFn::First,
+            "last" => AggFn::Last,
+            "concat" => AggFn::Concat,
+            _ => return fail_clierror!("Unknown aggregate function: {func_name}"),
+        };
+
+        let aggregate = match (func, column) {
+            (AggFn::Count, None) => Aggregate {
+                func,
+                column: 0,
+                name: "count".to_string(),
+            },
+            (AggFn::Count, Some(_)) => {
+                return fail_clierror!("The count function does not take a column: {spec}")
+            }
+            (_, None) => return fail_clierror!("The {func_name} function needs a column: {spec}"),
+            (_, Some(column)) => {
+                let selection =
+                    SelectColumns::parse(column)?.selection(headers, !rconfig.no_headers)?;
+                if selection.len() != 1 {
+                    return fail_clierror!("{spec} must select exactly one column.");
+                }
+                Aggregate {
+                    func,
+                    column: selection[0],
+                    name: format!("{}_{}", func_name.to_lowercase(), column_name(selection[0])),
+                }
+            }
+        };
+        aggregates.push(aggregate);
+    }
+    Ok(aggregates)
+}
+
+fn group_key(record: &csv::StringRecord, keys: &[usize]) -> Vec<String> {
+    keys.iter()
+        .map(|&idx| record.get(idx).unwrap_or_default().to_string())
+        .collect()
+}
+
+fn new_states(aggregates: &[Aggregate]) -> Vec<AggState> {
+    aggregates
+        .iter()
+        .map(|aggregate| match aggregate.func {
+            AggFn::Count => AggState::Count(0),
+            AggFn::Sum => AggState::Sum(0.0),
+            AggFn::Mean => AggState::Mean {
+                sum:   0.0,
+                count: 0,
+            },
+            AggFn::Min | AggFn::Max => AggState::Extreme(Extreme {
+                numeric: None,
+                text:    None,
+                all_num: true,
+            }),
+            AggFn::First => AggState::First(None),
+            AggFn::Last => AggState::Last(String::new()),
+            AggFn::Concat => AggState::Concat(Vec::new()),
+        })
+        .collect()
+}
+
+fn update(states: &mut [AggState], aggregates: &[Aggregate], record: &csv::StringRecord) {
+    for (state, aggregate) in states.iter_mut().zip(aggregates) {
+        let value = record.get(aggregate.column).unwrap_or_default();
+        match state {
+            AggState::Count(count) => *count += 1,
+            AggState::First(first) => {
+                if first.is_none() {
+                    *first = Some(value.to_string());
+                }
+            }
+            AggState::Last(last) => value.clone_into(last),
+            _ if value.is_empty() => {}
+            AggState::Sum(sum) => {
+                if let Ok(number) = value.trim().parse::<f64>() {
+                    *sum += number;
+                }
+            }
+            AggState::Mean { sum, count } => {
+                if let Ok(number) = value.trim().parse::<f64>() {
+                    *sum += number;
+                    *count += 1;
+                }
+            }
+            AggState::Extreme(extreme) => {
+                extreme.update(value, matches!(aggregate.func, AggFn::Max));
+            }
+            AggState::Concat(values) => values.push(value.to_string()),
+        }
+    }
+}
+
+impl Extreme {
+    fn update(&mut self, value: &str, is_max: bool) {
+        let better = |ordering: std::cmp::Ordering| {
+            if is_max {
+                ordering.is_gt()
+            } else {
+                ordering.is_lt()
+            }
+        };
+
+        match value.trim().parse::<f64>() {
+            Ok(number) if self.all_num => match self.numeric {
+                Some((current, _)) if !better(number.total_cmp(&current)) => {}
+                _ => self.numeric = Some((number, value.to_string())),
+            },
+            Ok(_) => {}
+            Err(_) => self.all_num = false,
+        }
+        match self.text {
+            Some(ref current) if !better(value.cmp(current.as_str())) => {}
+            _ => self.text = Some(value.to_string()),
+        }
+    }
+}
+
+impl AggState {
+    fn result(&self, separator: &str, round_places: u32) -> String {
+        match self {
+            AggState::Count(count) => count.to_string(),
+            AggState::Sum(sum) => util::round_num(*sum, round_places),
+            AggState::Mean { count: 0, .. } => String::new(),
+            AggState::Mean { sum, count } => util::round_num(*sum / *count as f64, round_places),
+            AggState::Extreme(extreme) => {
+                let value = if extreme.all_num {
+                    extreme.numeric.as_ref().map(|(_, value)| value)
+                } else {
+                    extreme.text.as_ref()
+                };
+                value.cloned().unwrap_or_default()
+            }
+            AggState::First(first) => first.clone().unwrap_or_default(),
+            AggState::Last(last) => last.clone(),
+            AggState::Concat(values) => values.join(separator),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+static USAGE: &str = r#"
+Returns the first N rows of a CSV. A shortcut for 'qsv slice --len N'.
+
+Only the returned rows are parsed, so this is instantaneous even on very large files.
+
+See also 'qsv tail' to return the last N rows, and 'qsv slice' for arbitrary ranges.
+
+Usage:
+    qsv head [options] [<input>]
+    qsv head --help
+
+head options:
+    -N, --rows <n>         The number of rows to return.
+                           [default: 10]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers. Otherwise, the first row will always
+                           appear in the output as the header row.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    flag_rows:       usize,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    rconfig.write_headers(&mut rdr, &mut wtr)?;
+
+    let mut record = csv::ByteRecord::new();
+    let mut rows = 0;
+    while rows < args.flag_rows && rdr.read_byte_record(&mut record)? {
+        wtr.write_byte_record(&record)?;
+        rows += 1;
+    }
+    Ok(wtr.flush()?)
+}
@@ -10,7 +10,8 @@ Also, CSVs with preamble lines can have them skipped with the --skip-lines & --a
 options. Similarly, --skip-lastlines allows epilogue lines to be skipped.
 
 Finally, non-UTF8 encoded files are transcoded to UTF-8 with this command, replacing all
-invalid UTF-8 sequences with �.
+invalid UTF-8 sequences with �. If the encoding of the file is known (e.g. Latin-1,
+Windows-1252, UTF-16LE/BE, Shift-JIS), use --encoding to transcode it properly instead.
 
 This command is typically used at the beginning of a data pipeline (thus the name `input`)
 to normalize & prepare CSVs for further processing with other qsv commands.
@@ -38,6 +39,16 @@ input options:
     --skip-lastlines <arg>   The number of epilogue lines to skip.
     --trim-headers           Trim leading & trailing whitespace & quotes from header values.
     --trim-fields            Trim leading & trailing whitespace from field values.
+    --encoding <label>       The encoding of the input, transcoded to UTF-8.
+                             Any WHATWG encoding label is supported, e.g. latin1,
+                             windows-1252, utf-16le, utf-16be, shift_jis, euc-kr, gbk.
+                             See https://encoding.spec.whatwg.org/#names-and-labels
+                             When not set, the input is assumed to be UTF-8.
+                             Only available with the full qsv variant.
+    --encoding-errors <arg>  What to do with byte sequences that are invalid in
+                             the --encoding. "replace" replaces them with �,
+                             "strict" stops with an error.
+                             [default: replace]
 
 Common options:
     -h, --help               Display this message
@@ -46,6 +57,10 @@ Common options:
                              Must be a single character. (default: ,)
 "#;
 
+use std::io::{self, Read};
+
+#[cfg(feature = "full")]
+use encoding_rs::{CoderResult, Decoder, DecoderResult, Encoding};
 use log::info;
 use serde::Deserialize;
 
@@ -56,22 +71,44 @@ use crate::{
 
 #[derive(Deserialize)]
 struct Args {
-    arg_input:           Option<String>,
-    flag_output:         Option<String>,
-    flag_delimiter:      Option<Delimiter>,
-    flag_quote:          Delimiter,
-    flag_escape:         Option<Delimiter>,
-    flag_no_quoting:     bool,
-    flag_skip_lines:     Option<u64>,
-    flag_skip_lastlines: Option<u64>,
-    flag_auto_skip:      bool,
-    flag_trim_headers:   bool,
-    flag_trim_fields:    bool,
+    arg_input:            Option<String>,
+    flag_output:          Option<String>,
+    flag_delimiter:       Option<Delimiter>,
+    flag_quote:           Delimiter,
+    flag_escape:          Option<Delimiter>,
+    flag_no_quoting:      bool,
+    flag_skip_lines:      Option<u64>,
+    flag_skip_lastlines:  Option<u64>,
+    flag_auto_skip:       bool,
+    flag_trim_headers:    bool,
+    flag_trim_fields:     bool,
+    flag_encoding:        Option<String>,
+    flag_encoding_errors: String,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
 
+    #[cfg(feature = "full")]
+    let encoding = match args.flag_encoding {
+        Some(ref label) => match Encoding::for_label(label.as_bytes()) {
+            Some(encoding) => Some(encoding),
+            None => return fail_clierror!("Unknown encoding: {label}"),
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "full"))]
+    let encoding: Option<()> = match args.flag_encoding {
+        Some(_) => return fail_clierror!("--encoding is only available in the full qsv variant."),
+        None => None,
+    };
+    #[cfg_attr(not(feature = "full"), allow(unused_variables))]
+    let strict = match args.flag_encoding_errors.as_str() {
+        "replace" => false,
+        "strict" => true,
+        policy => return fail_clierror!("Invalid --encoding-errors policy: {policy}"),
+    };
+
     let trim_setting = match (args.flag_trim_headers, args.flag_trim_fields) {
         (false, false) => csv::Trim::None,
         (true, true) => csv::Trim::All,
@@ -104,9 +141,29 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         rconfig = rconfig.flexible(true);
     }
 
+    // transcode the input to UTF-8 before parsing it, if its encoding is given
+    let open_reader =
+        || -> io::Result<csv::Reader<Box<dyn Read + Send + 'static>>> {
+            match encoding {
+                #[cfg(feature = "full")]
+                Some(encoding) => {
+                    let transcoder: Box<dyn Read + Send + 'static> = Box::new(
+                        TranscodingReader::new(rconfig.io_reader()?, encoding, strict),
+                    );
+                    Ok(rconfig.from_reader(transcoder))
+                }
+                _ => rconfig.reader(),
+            }
+        };
+
     let mut total_lines = 0_u64;
     if let Some(skip_llines) = args.flag_skip_lastlines {
-        let row_count = util::count_rows(&rconfig)?;
+        let row_count = if encoding.is_some() {
+            // the raw bytes of the input can't be counted, e.g. with UTF-16
+            open_reader()?.byte_records().count() as u64
+        } else {
+            util::count_rows(&rconfig)?
+        };
         if skip_llines > row_count {
             return fail_clierror!(
                 "--skip-lastlines: {skip_llines} is greater than row_count: {row_count}."
@@ -116,7 +173,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         total_lines = row_count - skip_llines;
     }
 
-    let mut rdr = rconfig.reader()?;
+    let mut rdr = open_reader()?;
     let mut wtr = wconfig.writer()?;
     let mut row = csv::ByteRecord::new();
     let mut str_row = csv::StringRecord::new();
@@ -181,3 +238,98 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     wtr.flush()?;
     Ok(())
 }
+
+/// Transcodes a reader from the given encoding to UTF-8, replacing invalid byte
+/// sequences with U+FFFD (or failing on them, if strict).
+#[cfg(feature = "full")]
+pub(crate) struct TranscodingReader<R> {
+    inner:     R,
+    decoder:   Decoder,
+    strict:    bool,
+    in_buf:    Vec<u8>,
+    in_start:  usize,
+    in_end:    usize,
+    out_buf:   Vec<u8>,
+    out_start: usize,
+    out_end:   usize,
+    consumed:  u64,
+    eof:       bool,
+    finished:  bool,
+}
+
+#[cfg(feature = "full")]
+impl<R: Read> TranscodingReader<R> {
+    pub(crate) fn new(inner: R, encoding: &'static Encoding, strict: bool) -> TranscodingReader<R> {
+        TranscodingReader {
+            inner,
+            // also strips the BOM, if any
+            decoder: encoding.new_decoder(),
+            strict,
+            in_buf: vec![0; 32 * 1024],
+            in_start: 0,
+            in_end: 0,
+            out_buf: vec![0; 64 * 1024],
+            out_start: 0,
+            out_end: 0,
+            consumed: 0,
+            eof: false,
+            finished: false,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl<R: Read> Read for TranscodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.out_start < self.out_end {
+                let n = buf.len().min(self.out_end - self.out_start);
+                buf[..n].copy_from_slice(&self.out_buf[self.out_start..self.out_start + n]);
+                self.out_start += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            if self.in_start == self.in_end && !self.eof {
+                let n = self.inner.read(&mut self.in_buf)?;
+                self.in_start = 0;
+                self.in_end = n;
+                self.eof = n == 0;
+            }
+
+            let src = &self.in_buf[self.in_start..self.in_end];
+            let (read, written, input_empty) = if self.strict {
+                let (result, read, written) = self.decoder.decode_to_utf8_without_replacement(
+                    src,
+                    &mut self.out_buf,
+                    self.eof,
+                );
+                if let DecoderResult::Malformed(..) = result {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "invalid {} byte sequence near byte {}",
+                            self.decoder.encoding().name(),
+                            self.consumed + read as u64
+                        ),
+                    ));
+                }
+                (read, written, result == DecoderResult::InputEmpty)
+            } else {
+                let (result, read, written, _) =
+                    self.decoder
+                        .decode_to_utf8(src, &mut self.out_buf, self.eof);
+                (read, written, result == CoderResult::InputEmpty)
+            };
+            self.in_start += read;
+            self.consumed += read as u64;
+            self.out_start = 0;
+            self.out_end = written;
+            if self.eof && input_empty {
+                self.finished = true;
+            }
+        }
+    }
+}
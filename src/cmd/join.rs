@@ -6,7 +6,8 @@ intersection of rows on the keys specified.
 
 Joins are always done by ignoring leading and trailing whitespace. By default,
 joins are done case sensitively, but this can be disabled with the --ignore-case
-flag.
+flag. Keys can be further normalized with --trim-keys and --normalize-unicode,
+so that e.g. "ACME  Corp " joins with "acme corp" when combined with -i.
 
 The columns arguments specify the columns to join for each input. Columns can
 be referenced by name or index, starting at 1. Specify multiple columns by
@@ -14,10 +15,48 @@ separating them with a comma. Specify a range of columns with `-`. Both
 columns1 and columns2 must specify exactly the same number of columns.
 (See 'qsv select --help' for the full syntax.)
 
+Alternatively, the key columns can be given as explicit pairs with --on, which
+is handy when the key columns have different names in each input,
+e.g. '--on cust_id=id,region=area' joins cust_id with id and region with area.
+
+With --fuzzy, keys don't have to be equal to match. Instead, each row of the
+first data set is compared against every key of the second data set, and all
+pairs with a similarity score at or above the threshold are joined, with the
+score added as a 'fuzzy_score' column. This is useful for record linkage of
+messy name/address data, but note that it compares N * M keys. Only inner,
+--left, --left-anti and --left-semi joins are supported in fuzzy mode.
+
+With --range, a row of the first data set is joined with every row of the
+second data set whose [start, end] range contains the value of its single
+<columns1> column, e.g. to join IP addresses, dates or prices with the IP
+ranges, date ranges or price tiers they fall within. Both ends of the range
+are inclusive. Numbers and IP addresses are compared by value, other values as
+text (so dates should be in ISO 8601 format). The ranges are indexed in an
+interval tree. Only inner, --left, --left-anti and --left-semi joins are
+supported in range mode.
+
+With --asof, the last key column is compared by order instead of equality (an
+'as of' join), and each row of the first data set is joined with at most one
+row of the second data set: the one whose key is the closest at or before its
+key (--direction backward), at or after it (forward), or either way (nearest).
+Any other key columns have to match exactly, e.g. 'symbol,time' aligns each
+trade with the latest quote for the same symbol. Numbers and IP addresses are
+compared by value and dates by time, other values as text. Only inner, --left,
+--left-anti and --left-semi joins are supported in asof mode.
+
+The hash joins index the keys of one data set in memory (<input2>, or <input1>
+for --right). When that data set is larger than --memory-limit, both data sets
+are instead split by key hash into partitions on disk (a Grace hash join), and
+each pair of partitions is joined in turn. The joined rows are the same, but
+they are written partition by partition, so the output row order differs,
+though it's the same from one run to the next.
+
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_join.rs.
 
 Usage:
     qsv join [options] <columns1> <input1> <columns2> <input2>
+    qsv join [options] --on <pairs> <input1> <input2>
+    qsv join [options] --range <bounds> <columns1> <input1> <input2>
     qsv join --help
 
 input parameters:
@@ -25,18 +64,58 @@ input parameters:
     e.g. 'qsv frequency -s Agency nyc311.csv | qsv join value - id nycagencyinfo.csv'
 
 join options:
+    --on <pairs>           Join on explicit pairs of key columns instead of
+                           <columns1> and <columns2>. Each pair is written as
+                           'left=right' and pairs are separated by commas.
     -i, --ignore-case      When set, joins are done case insensitively.
+    --trim-keys            When set, runs of whitespace inside key values are
+                           collapsed to a single space before comparing, in
+                           addition to the leading/trailing whitespace that
+                           is always ignored.
+    --normalize-unicode    When set, key values are normalized to Unicode
+                           Normalization Form C (NFC) before comparing, so
+                           composed and decomposed forms of the same
+                           characters (e.g. "é" and "e\u{301}") match.
+                           Only available with the full qsv variant.
+    --fuzzy <threshold>    Match keys by similarity instead of equality.
+                           Key pairs with a similarity score between 0.0 and
+                           1.0 that is at or above <threshold> are joined.
+                           With multiple key columns, the key values are
+                           joined with a space before comparing.
+                           Only available with the full qsv variant.
+    --fuzzy-metric <arg>   The similarity metric used by --fuzzy. Either
+                           'jaro-winkler' or 'levenshtein' (normalized
+                           Levenshtein distance).
+                           [default: jaro-winkler]
+    --range <bounds>       Do a range join. <bounds> selects the start and
+                           end columns of the ranges in <input2>,
+                           e.g. 'ip_from,ip_to'.
+    --asof                 Do an asof join on the last key column.
+    --direction <arg>      The direction --asof looks for the closest key in.
+                           Either 'backward', 'forward' or 'nearest'.
+                           [default: backward]
+    --tolerance <arg>      With --asof, only join rows whose keys are at most
+                           this far apart. For dates, this is in seconds.
+    --prefer-dmy           With --asof, parse dates in dmy format. Otherwise,
+                           use mdy format. Can also be set with the
+                           QSV_PREFER_DMY environment variable.
+    --numeric-keys         When set, key values that are numbers are compared
+                           by numeric value, so that "007" matches "7" and
+                           "1.50" matches "1.5". Non-numeric key values are
+                           compared as text.
     --left                 Do a 'left outer' join. This returns all rows in
                            first CSV data set, including rows with no
                            corresponding row in the second data set. When no
                            corresponding row exists, it is padded out with
                            empty fields.
     --left-anti            Do a 'left anti' join. This returns all rows in
-                           first CSV data set that has no match with the 
-                           second data set.
+                           first CSV data set that has no match with the
+                           second data set. Only the columns of the first
+                           data set are returned.
     --left-semi            Do a 'left semi' join. This returns all rows in
-                           first CSV data set that has a match with the 
-                           second data set.
+                           first CSV data set that has a match with the
+                           second data set, once each. Only the columns of the
+                           first data set are returned.
     --right                Do a 'right outer' join. This returns all rows in
                            second CSV data set, including rows with no
                            corresponding row in the first data set. When no
@@ -52,10 +131,30 @@ join options:
                            data sets given. The number of rows return is
                            equal to N * M, where N and M correspond to the
                            number of rows in the given data sets, respectively.
+    --memory-limit <arg>   Spill to disk when the data set indexed in memory
+                           is larger than this percentage of total memory,
+                           capped at 50%. When the indexed data set is read
+                           from stdin, it is always spilled. Not supported
+                           with --fuzzy, and has no effect on --cross.
     --nulls                When set, joins will work on empty fields.
                            Otherwise, empty fields are completely ignored.
                            (In fact, any row that has an empty field in the
-                           key specified is ignored.)
+                           key specified never matches. With the outer joins,
+                           such rows are padded out with empty fields like any
+                           other unmatched row, and the anti join returns them.)
+
+output options:
+    --prefix-left <arg>    Prefix the names of the columns of the first data
+                           set that also occur in the second data set with
+                           <arg>, e.g. 'left_'.
+    --prefix-right <arg>   Prefix the names of the columns of the second data
+                           set that also occur in the first data set with
+                           <arg>, e.g. 'right_'.
+    --output-select <arg>  Only write these columns of the joined output,
+                           instead of writing all of them and selecting them
+                           in another pass. The columns are selected from the
+                           output headers, after any prefixes are added.
+                           (See 'qsv select --help' for the full syntax.)
 
 Common options:
     -h, --help             Display this message
@@ -67,11 +166,25 @@ Common options:
                            Must be a single character. (default: ,)
 "#;
 
-use std::{collections::hash_map::Entry, fmt, io, iter::repeat, str};
+use std::{
+    cmp::Ordering,
+    collections::hash_map::Entry,
+    fmt, fs,
+    hash::{BuildHasher, Hash, Hasher},
+    io,
+    iter::{once, repeat},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str,
+};
 
 use ahash::AHashMap;
 use byteorder::{BigEndian, WriteBytesExt};
+use qsv_dateparser::parse_with_preference;
 use serde::Deserialize;
+use sysinfo::{System, SystemExt};
+#[cfg(feature = "full")]
+use unicode_normalization::UnicodeNormalization;
 
 use crate::{
     config::{Config, Delimiter, SeekRead},
@@ -82,28 +195,114 @@ use crate::{
     CliResult,
 };
 
+// the memory limit used when the total memory can't be detected
+const MEMORY_LIMITED_BUFFER: u64 = 100 * 1_000_000; // 100 MB
+
+// the maximum number of partition files per input when spilling to disk
+const MAX_SPILL_PARTITIONS: u64 = 64;
+
+// the fixed seeds of the partitioning hasher, so a spilled join always writes
+// its rows in the same order for the same inputs
+const SPILL_HASH_SEEDS: (u64, u64, u64, u64) = (
+    0x243f_6a88_85a3_08d3,
+    0x1319_8a2e_0370_7344,
+    0xa409_3822_299f_31d0,
+    0x082e_fa98_ec4e_6c89,
+);
+
 #[derive(Deserialize)]
 struct Args {
-    arg_columns1:     SelectColumns,
-    arg_input1:       String,
-    arg_columns2:     SelectColumns,
-    arg_input2:       String,
-    flag_left:        bool,
-    flag_left_anti:   bool,
-    flag_left_semi:   bool,
-    flag_right:       bool,
-    flag_full:        bool,
-    flag_cross:       bool,
-    flag_output:      Option<String>,
-    flag_no_headers:  bool,
-    flag_ignore_case: bool,
-    flag_nulls:       bool,
-    flag_delimiter:   Option<Delimiter>,
+    arg_columns1:           SelectColumns,
+    arg_input1:             String,
+    arg_columns2:           SelectColumns,
+    arg_input2:             String,
+    flag_on:                Option<String>,
+    flag_left:              bool,
+    flag_left_anti:         bool,
+    flag_left_semi:         bool,
+    flag_right:             bool,
+    flag_full:              bool,
+    flag_cross:             bool,
+    flag_output:            Option<String>,
+    flag_prefix_left:       Option<String>,
+    flag_prefix_right:      Option<String>,
+    flag_output_select:     Option<SelectColumns>,
+    flag_no_headers:        bool,
+    flag_ignore_case:       bool,
+    flag_trim_keys:         bool,
+    flag_normalize_unicode: bool,
+    flag_numeric_keys:      bool,
+    flag_fuzzy:             Option<f64>,
+    flag_fuzzy_metric:      String,
+    flag_range:             Option<String>,
+    flag_asof:              bool,
+    flag_direction:         String,
+    flag_tolerance:         Option<f64>,
+    flag_prefer_dmy:        bool,
+    flag_memory_limit:      Option<u8>,
+    flag_nulls:             bool,
+    flag_delimiter:         Option<Delimiter>,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
+    #[cfg(not(feature = "full"))]
+    if args.flag_fuzzy.is_some() || args.flag_normalize_unicode {
+        return fail!(
+            "--fuzzy and --normalize-unicode are only available in the full qsv variant."
+        );
+    }
     let mut state = args.new_io_state()?;
+    if args.flag_asof {
+        if args.flag_range.is_some()
+            || args.flag_fuzzy.is_some()
+            || args.flag_memory_limit.is_some()
+        {
+            return fail!("--asof cannot be combined with --range, --fuzzy or --memory-limit.");
+        }
+        let kind = args.join_kind("--asof")?;
+        let direction = match args.flag_direction.to_lowercase().as_str() {
+            "backward" => AsofDirection::Backward,
+            "forward" => AsofDirection::Forward,
+            "nearest" => AsofDirection::Nearest,
+            other => {
+                return fail_clierror!(
+                    "Unknown --direction \"{other}\". Use backward, forward or nearest."
+                )
+            }
+        };
+        if args.flag_tolerance.map_or(false, |t| t.is_nan() || t < 0.0) {
+            return fail!("--tolerance must be a non-negative number.");
+        }
+        return state.asof_join(kind, direction, args.flag_tolerance);
+    }
+    if args.flag_range.is_some() {
+        if args.flag_on.is_some() || args.flag_fuzzy.is_some() || args.flag_memory_limit.is_some() {
+            return fail!("--range cannot be combined with --on, --fuzzy or --memory-limit.");
+        }
+        let kind = args.join_kind("--range")?;
+        return state.range_join(kind);
+    }
+    if let Some(threshold) = args.flag_fuzzy {
+        if !(0.0..=1.0).contains(&threshold) {
+            return fail_clierror!("--fuzzy threshold must be between 0.0 and 1.0.");
+        }
+        let metric = match args.flag_fuzzy_metric.to_lowercase().as_str() {
+            "jaro-winkler" => FuzzyMetric::JaroWinkler,
+            "levenshtein" => FuzzyMetric::Levenshtein,
+            other => {
+                return fail_clierror!(
+                    "Unknown --fuzzy-metric \"{other}\". Use jaro-winkler or levenshtein."
+                )
+            }
+        };
+        let kind = args.join_kind("--fuzzy")?;
+        if args.flag_memory_limit.is_some() {
+            return fail!("--memory-limit is not supported with --fuzzy.");
+        }
+        return state.fuzzy_join(kind, metric, threshold);
+    }
+    let spill = args.spill_partitions()?;
     match (
         args.flag_left,
         args.flag_left_anti,
@@ -114,23 +313,23 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     ) {
         (true, false, false, false, false, false) => {
             state.write_headers()?;
-            state.outer_join(false)
+            state.hash_join(spill, |s| s.outer_join(false))
         }
         (false, true, false, false, false, false) => {
             state.write_headers1()?;
-            state.left_join(true)
+            state.hash_join(spill, |s| s.left_join(true))
         }
         (false, false, true, false, false, false) => {
             state.write_headers1()?;
-            state.left_join(false)
+            state.hash_join(spill, |s| s.left_join(false))
         }
         (false, false, false, true, false, false) => {
             state.write_headers()?;
-            state.outer_join(true)
+            state.hash_join(spill, |s| s.outer_join(true))
         }
         (false, false, false, false, true, false) => {
             state.write_headers()?;
-            state.full_outer_join()
+            state.hash_join(spill, IoState::full_outer_join)
         }
         (false, false, false, false, false, true) => {
             state.write_headers()?;
@@ -138,47 +337,122 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
         (false, false, false, false, false, false) => {
             state.write_headers()?;
-            state.inner_join()
+            state.hash_join(spill, IoState::inner_join)
         }
         _ => fail!("Please pick exactly one join operation."),
     }
 }
 
+/// The output of a join, which renames colliding column names with
+/// --prefix-left/--prefix-right and only writes the --output-select columns.
+struct JoinWriter<W: io::Write> {
+    wtr:          csv::Writer<W>,
+    prefix_left:  Option<String>,
+    prefix_right: Option<String>,
+    select:       Option<SelectColumns>,
+    // the resolved --output-select columns, once the headers are known
+    sel:          Option<Selection>,
+    record:       csv::ByteRecord,
+}
+
+impl<W: io::Write> JoinWriter<W> {
+    fn new(wtr: csv::Writer<W>) -> JoinWriter<W> {
+        JoinWriter {
+            wtr,
+            prefix_left: None,
+            prefix_right: None,
+            select: None,
+            sel: None,
+            record: csv::ByteRecord::new(),
+        }
+    }
+
+    /// Returns the headers of both inputs combined, with the prefixes added to
+    /// the names that occur in both.
+    fn joined_headers(
+        &self,
+        headers1: &csv::ByteRecord,
+        headers2: &csv::ByteRecord,
+    ) -> csv::ByteRecord {
+        let prefixed = |name: &[u8], other: &csv::ByteRecord, prefix: Option<&str>| match prefix {
+            Some(prefix) if other.iter().any(|h| h == name) => [prefix.as_bytes(), name].concat(),
+            _ => name.to_vec(),
+        };
+        let mut headers = csv::ByteRecord::with_capacity(0, headers1.len() + headers2.len());
+        for name in headers1 {
+            headers.push_field(&prefixed(name, headers2, self.prefix_left.as_deref()));
+        }
+        for name in headers2 {
+            headers.push_field(&prefixed(name, headers1, self.prefix_right.as_deref()));
+        }
+        headers
+    }
+
+    /// Resolves --output-select against the output headers, and writes them
+    /// unless `no_headers` is set.
+    fn write_headers(&mut self, headers: &csv::ByteRecord, no_headers: bool) -> CliResult<()> {
+        if let Some(ref select) = self.select {
+            self.sel = Some(select.selection(headers, !no_headers)?);
+        }
+        if !no_headers {
+            self.write_record(headers)?;
+        }
+        Ok(())
+    }
+
+    fn write_record<I, T>(&mut self, record: I) -> csv::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        match self.sel {
+            None => self.wtr.write_record(record),
+            Some(ref sel) => {
+                self.record.clear();
+                for field in record {
+                    self.record.push_field(field.as_ref());
+                }
+                self.wtr.write_record(sel.select(&self.record))
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.wtr.flush()
+    }
+}
+
 struct IoState<R, W: io::Write> {
-    wtr:        csv::Writer<W>,
+    wtr:        JoinWriter<W>,
     rdr1:       csv::Reader<R>,
     sel1:       Selection,
     rdr2:       csv::Reader<R>,
     sel2:       Selection,
     no_headers: bool,
-    casei:      bool,
+    keynorm:    KeyNorm,
     nulls:      bool,
+    prefer_dmy: bool,
 }
 
 impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     fn write_headers(&mut self) -> CliResult<()> {
-        if !self.no_headers {
-            let mut headers = self.rdr1.byte_headers()?.clone();
-            headers.extend(self.rdr2.byte_headers()?.iter());
-            self.wtr.write_record(&headers)?;
-        }
-        Ok(())
+        let headers = self
+            .wtr
+            .joined_headers(self.rdr1.byte_headers()?, self.rdr2.byte_headers()?);
+        self.wtr.write_headers(&headers, self.no_headers)
     }
 
     fn write_headers1(&mut self) -> CliResult<()> {
-        if !self.no_headers {
-            let headers = self.rdr1.byte_headers()?;
-            self.wtr.write_record(headers)?;
-        }
-        Ok(())
+        let headers = self.rdr1.byte_headers()?;
+        self.wtr.write_headers(headers, self.no_headers)
     }
 
     fn inner_join(mut self) -> CliResult<()> {
         let mut scratch = csv::ByteRecord::new();
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.keynorm, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
+            let key = get_row_key(&self.sel1, &row, self.keynorm);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     validx.idx.seek(rowi as u64)?;
@@ -200,10 +474,10 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
 
         let mut scratch = csv::ByteRecord::new();
         let (_, pad2) = self.get_padding()?;
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.keynorm, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
+            let key = get_row_key(&self.sel1, &row, self.keynorm);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     validx.idx.seek(rowi as u64)?;
@@ -225,24 +499,13 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     }
 
     fn left_join(mut self, anti: bool) -> CliResult<()> {
-        let validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
-        let mut first_row: bool = true;
+        let validx = ValueIndex::new(self.rdr2, &self.sel2, self.keynorm, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
-            if validx.values.get(&key).is_none() {
-                if anti {
-                    self.wtr.write_record(&row)?;
-                }
-            } else if !anti {
-                // semi_join
-                if first_row {
-                    first_row = false;
-                } else {
-                    // since the first row in a left-semi is
-                    // the header, even if no-header is on
-                    self.wtr.write_record(&row)?;
-                }
+            let key = get_row_key(&self.sel1, &row, self.keynorm);
+            // the header row is never returned by byte_records, so every row is data
+            if validx.values.contains_key(&key) != anti {
+                self.wtr.write_record(&row)?;
             }
         }
         Ok(())
@@ -251,13 +514,13 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     fn full_outer_join(mut self) -> CliResult<()> {
         let mut scratch = csv::ByteRecord::new();
         let (pad1, pad2) = self.get_padding()?;
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.keynorm, self.nulls)?;
 
         // Keep track of which rows we've written from rdr2.
         let mut rdr2_written: Vec<_> = repeat(false).take(validx.num_rows).collect();
         for row1 in self.rdr1.byte_records() {
             let row1 = row1?;
-            let key = get_row_key(&self.sel1, &row1, self.casei);
+            let key = get_row_key(&self.sel1, &row1, self.keynorm);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     rdr2_written[rowi] = true;
@@ -302,6 +565,172 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
         Ok(())
     }
 
+    fn fuzzy_join(mut self, kind: JoinKind, metric: FuzzyMetric, threshold: f64) -> CliResult<()> {
+        if kind == JoinKind::Inner || kind == JoinKind::Left {
+            let mut headers = self
+                .wtr
+                .joined_headers(self.rdr1.byte_headers()?, self.rdr2.byte_headers()?);
+            headers.push_field(b"fuzzy_score");
+            self.wtr.write_headers(&headers, self.no_headers)?;
+        } else {
+            self.write_headers1()?;
+        }
+
+        let mut scratch = csv::ByteRecord::new();
+        let (_, pad2) = self.get_padding()?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.keynorm, self.nulls)?;
+
+        // the candidate keys of the second data set, in order of first appearance
+        let mut candidates: Vec<(String, Vec<usize>)> = validx
+            .values
+            .drain()
+            .map(|(key, rows)| (fuzzy_key(&key), rows))
+            .collect();
+        candidates.sort_unstable_by_key(|(_, rows)| rows[0]);
+
+        for row in self.rdr1.byte_records() {
+            let row = row?;
+            let key = get_row_key(&self.sel1, &row, self.keynorm);
+            let mut matched = false;
+            if self.nulls || !key.iter().any(Vec::is_empty) {
+                let key = fuzzy_key(&key);
+                for (candidate, rows) in &candidates {
+                    let score = metric.score(&key, candidate);
+                    if score < threshold {
+                        continue;
+                    }
+                    matched = true;
+                    if kind == JoinKind::LeftAnti || kind == JoinKind::LeftSemi {
+                        break;
+                    }
+                    let score = util::round_num(score, 4);
+                    for &rowi in rows {
+                        validx.idx.seek(rowi as u64)?;
+                        validx.idx.read_byte_record(&mut scratch)?;
+                        self.wtr.write_record(
+                            row.iter().chain(&scratch).chain(once(score.as_bytes())),
+                        )?;
+                    }
+                }
+            }
+            match kind {
+                JoinKind::Left if !matched => {
+                    self.wtr
+                        .write_record(row.iter().chain(&pad2).chain(once(&b""[..])))?;
+                }
+                JoinKind::LeftAnti if !matched => self.wtr.write_record(&row)?,
+                JoinKind::LeftSemi if matched => self.wtr.write_record(&row)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn write_kind_headers(&mut self, kind: JoinKind) -> CliResult<()> {
+        if kind == JoinKind::Inner || kind == JoinKind::Left {
+            self.write_headers()
+        } else {
+            self.write_headers1()
+        }
+    }
+
+    fn range_join(mut self, kind: JoinKind) -> CliResult<()> {
+        self.write_kind_headers(kind)?;
+
+        let (_, pad2) = self.get_padding()?;
+        let mut rows2 = Vec::new();
+        let mut intervals = Vec::new();
+        for row in self.rdr2.byte_records() {
+            let row = row?;
+            let bounds = get_row_key(&self.sel2, &row, self.keynorm);
+            // rows with a missing bound or an empty range never match
+            if let (Some(start), Some(end)) =
+                (RangeValue::parse(&bounds[0]), RangeValue::parse(&bounds[1]))
+            {
+                if start <= end {
+                    intervals.push((start, end, rows2.len()));
+                }
+            }
+            rows2.push(row);
+        }
+        let tree = IntervalTree::new(intervals);
+
+        let mut matches = Vec::new();
+        for row in self.rdr1.byte_records() {
+            let row = row?;
+            matches.clear();
+            let key = get_row_key(&self.sel1, &row, self.keynorm);
+            if let Some(value) = RangeValue::parse(&key[0]) {
+                tree.stab(&value, &mut matches);
+                // write the matches in the order of the second data set
+                matches.sort_unstable();
+            }
+            match kind {
+                JoinKind::Inner | JoinKind::Left => {
+                    for &rowi in &matches {
+                        self.wtr.write_record(row.iter().chain(&rows2[rowi]))?;
+                    }
+                    if kind == JoinKind::Left && matches.is_empty() {
+                        self.wtr.write_record(row.iter().chain(&pad2))?;
+                    }
+                }
+                JoinKind::LeftAnti if matches.is_empty() => self.wtr.write_record(&row)?,
+                JoinKind::LeftSemi if !matches.is_empty() => self.wtr.write_record(&row)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn asof_join(
+        mut self,
+        kind: JoinKind,
+        direction: AsofDirection,
+        tolerance: Option<f64>,
+    ) -> CliResult<()> {
+        self.write_kind_headers(kind)?;
+
+        // the rows of the second data set, grouped by the columns that have to
+        // match exactly, and sorted by the asof key within each group
+        let (_, pad2) = self.get_padding()?;
+        let mut rows2 = Vec::new();
+        let mut groups: AHashMap<Vec<ByteString>, Vec<(RangeValue, usize)>> = AHashMap::new();
+        for row in self.rdr2.byte_records() {
+            let row = row?;
+            let mut key = get_row_key(&self.sel2, &row, self.keynorm);
+            if let Some(value) = key.pop().and_then(|v| asof_value(&v, self.prefer_dmy)) {
+                if self.nulls || !key.iter().any(Vec::is_empty) {
+                    groups.entry(key).or_default().push((value, rows2.len()));
+                }
+            }
+            rows2.push(row);
+        }
+        for group in groups.values_mut() {
+            // a stable sort, so rows with equal asof keys stay in file order
+            group.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        }
+
+        for row in self.rdr1.byte_records() {
+            let row = row?;
+            let mut key = get_row_key(&self.sel1, &row, self.keynorm);
+            let matched = key
+                .pop()
+                .and_then(|v| asof_value(&v, self.prefer_dmy))
+                .and_then(|value| direction.find(groups.get(&key)?, &value, tolerance));
+            match (kind, matched) {
+                (JoinKind::Inner | JoinKind::Left, Some(rowi)) => {
+                    self.wtr.write_record(row.iter().chain(&rows2[rowi]))?;
+                }
+                (JoinKind::Left, None) => self.wtr.write_record(row.iter().chain(&pad2))?,
+                (JoinKind::LeftSemi, Some(_)) | (JoinKind::LeftAnti, None) => {
+                    self.wtr.write_record(&row)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     fn get_padding(&mut self) -> CliResult<(csv::ByteRecord, csv::ByteRecord)> {
         let len1 = self.rdr1.byte_headers()?.len();
         let len2 = self.rdr2.byte_headers()?.len();
@@ -312,35 +741,204 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     }
 }
 
+type BoxedIoState = IoState<Box<dyn SeekRead + 'static>, Box<dyn io::Write + 'static>>;
+
+impl BoxedIoState {
+    /// Runs a hash join, either directly or, when `partitions` is set, as a
+    /// Grace hash join: both inputs are split by key hash into that many
+    /// partitions on disk, and `join` is run on each pair of partitions in turn.
+    fn hash_join(
+        mut self,
+        partitions: Option<usize>,
+        join: fn(BoxedIoState) -> CliResult<()>,
+    ) -> CliResult<()> {
+        let Some(partitions) = partitions else {
+            return join(self);
+        };
+        let tmpdir = tempfile::tempdir()?;
+        let (k0, k1, k2, k3) = SPILL_HASH_SEEDS;
+        let hasher = ahash::RandomState::with_seeds(k0, k1, k2, k3);
+        let parts1 = self.partition(true, partitions, &hasher, tmpdir.path())?;
+        let parts2 = self.partition(false, partitions, &hasher, tmpdir.path())?;
+
+        let mut record = csv::ByteRecord::new();
+        for (i, (part1, part2)) in parts1.into_iter().zip(parts2).enumerate() {
+            let out_path = tmpdir.path().join(format!("out_{i}.csv"));
+            let out: Box<dyn io::Write> = Box::new(fs::File::create(&out_path)?);
+            let part_reader = |path: PathBuf| -> CliResult<csv::Reader<Box<dyn SeekRead>>> {
+                let file: Box<dyn SeekRead> = Box::new(fs::File::open(path)?);
+                Ok(csv::ReaderBuilder::new().flexible(true).from_reader(file))
+            };
+            let state = IoState {
+                wtr:        JoinWriter::new(
+                    csv::WriterBuilder::new().flexible(true).from_writer(out),
+                ),
+                rdr1:       part_reader(part1)?,
+                sel1:       self.sel1.clone(),
+                rdr2:       part_reader(part2)?,
+                sel2:       self.sel2.clone(),
+                no_headers: true,
+                keynorm:    self.keynorm,
+                nulls:      self.nulls,
+                prefer_dmy: self.prefer_dmy,
+            };
+            join(state)?;
+
+            let mut rdr = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_path(&out_path)?;
+            while rdr.read_byte_record(&mut record)? {
+                self.wtr.write_record(&record)?;
+            }
+        }
+        Ok(self.wtr.flush()?)
+    }
+
+    /// Writes the rows of the first (`first = true`) or second input into
+    /// `partitions` files, by the hash of their key. The header row (or, with
+    /// --no-headers, the first row) is written to every file, so the joins
+    /// can work out the padding of unmatched rows even for empty partitions.
+    fn partition(
+        &mut self,
+        first: bool,
+        partitions: usize,
+        hasher: &ahash::RandomState,
+        dir: &Path,
+    ) -> CliResult<Vec<PathBuf>> {
+        let (rdr, sel) = if first {
+            (&mut self.rdr1, &self.sel1)
+        } else {
+            (&mut self.rdr2, &self.sel2)
+        };
+        let headers = rdr.byte_headers()?.clone();
+        let mut paths = Vec::with_capacity(partitions);
+        let mut wtrs = Vec::with_capacity(partitions);
+        for i in 0..partitions {
+            let path = dir.join(format!("{}_{i}.csv", if first { "left" } else { "right" }));
+            let mut wtr = csv::WriterBuilder::new().flexible(true).from_path(&path)?;
+            wtr.write_byte_record(&headers)?;
+            paths.push(path);
+            wtrs.push(wtr);
+        }
+
+        let mut row = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut row)? {
+            let key = get_row_key(sel, &row, self.keynorm);
+            // rows with empty keys never match, so any partition will do
+            let part = if !self.nulls && key.iter().any(Vec::is_empty) {
+                0
+            } else {
+                let mut h = hasher.build_hasher();
+                key.hash(&mut h);
+                (h.finish() % partitions as u64) as usize
+            };
+            wtrs[part].write_byte_record(&row)?;
+        }
+        for mut wtr in wtrs {
+            wtr.flush()?;
+        }
+        Ok(paths)
+    }
+}
+
 impl Args {
+    /// Returns the join type of a fuzzy or range join, which only support
+    /// inner, left, left anti and left semi joins.
+    fn join_kind(&self, mode: &str) -> CliResult<JoinKind> {
+        match (
+            self.flag_left,
+            self.flag_left_anti,
+            self.flag_left_semi,
+            self.flag_right,
+            self.flag_full,
+            self.flag_cross,
+        ) {
+            (false, false, false, false, false, false) => Ok(JoinKind::Inner),
+            (true, false, false, false, false, false) => Ok(JoinKind::Left),
+            (false, true, false, false, false, false) => Ok(JoinKind::LeftAnti),
+            (false, false, true, false, false, false) => Ok(JoinKind::LeftSemi),
+            _ => fail_clierror!(
+                "{mode} only supports inner, --left, --left-anti and --left-semi joins."
+            ),
+        }
+    }
+
+    /// Returns the number of partitions to spill the hash join into, or
+    /// `None` if the indexed data set fits within --memory-limit.
+    fn spill_partitions(&self) -> CliResult<Option<usize>> {
+        let Some(mem_pct) = self.flag_memory_limit else {
+            return Ok(None);
+        };
+        let limit = if System::IS_SUPPORTED {
+            let mut sys = System::new();
+            sys.refresh_memory();
+            // total_memory() is in bytes
+            (sys.total_memory() / 100) * u64::from(u8::min(mem_pct, 50))
+        } else {
+            MEMORY_LIMITED_BUFFER
+        };
+        // --right indexes the first input, all the other hash joins the second
+        let indexed = if self.flag_right {
+            &self.arg_input1
+        } else {
+            &self.arg_input2
+        };
+        let size = if indexed == "-" {
+            u64::MAX
+        } else {
+            fs::metadata(indexed)?.len()
+        };
+        if size <= limit {
+            return Ok(None);
+        }
+        let partitions = (size / limit.max(1)).saturating_add(1);
+        Ok(Some(partitions.clamp(2, MAX_SPILL_PARTITIONS) as usize))
+    }
+
     fn new_io_state(
         &self,
     ) -> CliResult<IoState<Box<dyn SeekRead + 'static>, Box<dyn io::Write + 'static>>> {
+        let (columns1, columns2) = match (&self.flag_on, &self.flag_range) {
+            (Some(pairs), _) => parse_key_pairs(pairs)?,
+            (None, Some(bounds)) => (self.arg_columns1.clone(), SelectColumns::parse(bounds)?),
+            (None, None) => (self.arg_columns1.clone(), self.arg_columns2.clone()),
+        };
         let rconf1 = Config::new(&Some(self.arg_input1.clone()))
             .delimiter(self.flag_delimiter)
             .no_headers(self.flag_no_headers)
-            .select(self.arg_columns1.clone());
+            .select(columns1);
         let rconf2 = Config::new(&Some(self.arg_input2.clone()))
             .delimiter(self.flag_delimiter)
             .no_headers(self.flag_no_headers)
-            .select(self.arg_columns2.clone());
+            .select(columns2);
 
         let mut rdr1 = rconf1.reader_file_stdin()?;
         let mut rdr2 = rconf2.reader_file_stdin()?;
         let (sel1, sel2) = self.get_selections(&rconf1, &mut rdr1, &rconf2, &mut rdr2)?;
         Ok(IoState {
-            wtr: Config::new(&self.flag_output).writer()?,
+            wtr: JoinWriter {
+                prefix_left: self.flag_prefix_left.clone(),
+                prefix_right: self.flag_prefix_right.clone(),
+                select: self.flag_output_select.clone(),
+                ..JoinWriter::new(Config::new(&self.flag_output).writer()?)
+            },
             rdr1,
             sel1,
             rdr2,
             sel2,
             no_headers: rconf1.no_headers,
-            casei: self.flag_ignore_case,
+            keynorm: KeyNorm {
+                casei:   self.flag_ignore_case,
+                trim:    self.flag_trim_keys,
+                unicode: self.flag_normalize_unicode,
+                numeric: self.flag_numeric_keys,
+            },
             nulls: self.flag_nulls,
+            prefer_dmy: self.flag_prefer_dmy || rconf1.get_dmy_preference(),
         })
     }
 
-    #[allow(clippy::unused_self)]
     fn get_selections<R: io::Read>(
         &self,
         rconf1: &Config,
@@ -352,7 +950,16 @@ impl Args {
         let headers2 = rdr2.byte_headers()?;
         let select1 = rconf1.selection(headers1)?;
         let select2 = rconf2.selection(headers2)?;
-        if select1.len() != select2.len() {
+        if self.flag_range.is_some() && self.flag_on.is_none() {
+            if select1.len() != 1 || select2.len() != 2 {
+                return fail_clierror!(
+                    "--range needs a single column in <columns1> and exactly two (start and end) \
+                     columns in <bounds>, but found {} and {} columns.",
+                    select1.len(),
+                    select2.len()
+                );
+            }
+        } else if select1.len() != select2.len() {
             return fail_clierror!(
                 "Column selections must have the same number of columns, but found column \
                  selections with {} and {} columns.",
@@ -364,6 +971,226 @@ impl Args {
     }
 }
 
+/// The join types supported by fuzzy and range joins.
+#[derive(Clone, Copy, PartialEq)]
+enum JoinKind {
+    Inner,
+    Left,
+    LeftAnti,
+    LeftSemi,
+}
+
+#[derive(Clone, Copy)]
+enum FuzzyMetric {
+    JaroWinkler,
+    Levenshtein,
+}
+
+impl FuzzyMetric {
+    #[cfg(feature = "full")]
+    fn score(self, a: &str, b: &str) -> f64 {
+        match self {
+            FuzzyMetric::JaroWinkler => strsim::jaro_winkler(a, b),
+            FuzzyMetric::Levenshtein => strsim::normalized_levenshtein(a, b),
+        }
+    }
+
+    /// --fuzzy is rejected without the full feature, so this is never called.
+    #[cfg(not(feature = "full"))]
+    fn score(self, _a: &str, _b: &str) -> f64 {
+        unreachable!("--fuzzy is only available in the full qsv variant")
+    }
+}
+
+/// A value compared by range joins. Numbers and IP addresses are compared by
+/// value, anything else (e.g. ISO 8601 dates) as text.
+#[derive(Clone, PartialEq, PartialOrd)]
+enum RangeValue {
+    Number(f64),
+    Ip(u128),
+    Text(String),
+}
+
+impl RangeValue {
+    fn parse(bs: &[u8]) -> Option<RangeValue> {
+        let s = String::from_utf8_lossy(bs);
+        if s.is_empty() {
+            return None;
+        }
+        if let Ok(n) = s.parse::<f64>() {
+            if !n.is_nan() {
+                return Some(RangeValue::Number(n));
+            }
+        }
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            return Some(RangeValue::Ip(match ip {
+                IpAddr::V4(v4) => u128::from(u32::from(v4)),
+                IpAddr::V6(v6) => u128::from(v6),
+            }));
+        }
+        Some(RangeValue::Text(s.into_owned()))
+    }
+}
+
+impl RangeValue {
+    /// Returns how far apart two values are, if they can be subtracted.
+    #[allow(clippy::cast_precision_loss)]
+    fn distance(&self, other: &RangeValue) -> Option<f64> {
+        match (self, other) {
+            (RangeValue::Number(a), RangeValue::Number(b)) => Some((a - b).abs()),
+            (RangeValue::Ip(a), RangeValue::Ip(b)) => Some(a.abs_diff(*b) as f64),
+            _ if self == other => Some(0.0),
+            _ => None,
+        }
+    }
+}
+
+/// Parses an asof key. Unlike range joins, dates are compared by time, as
+/// seconds since the Unix epoch, so that --tolerance can be given in seconds.
+#[allow(clippy::cast_precision_loss)]
+fn asof_value(bs: &[u8], prefer_dmy: bool) -> Option<RangeValue> {
+    match RangeValue::parse(bs)? {
+        RangeValue::Text(s) => match parse_with_preference(&s, prefer_dmy) {
+            Ok(dt) => Some(RangeValue::Number(dt.timestamp_millis() as f64 / 1000.0)),
+            Err(_) => Some(RangeValue::Text(s)),
+        },
+        value => Some(value),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AsofDirection {
+    Backward,
+    Forward,
+    Nearest,
+}
+
+impl AsofDirection {
+    /// Returns the row of the closest entry to `value` in the direction, from
+    /// a group of entries sorted by key, or `None` if there is none within
+    /// `tolerance`.
+    fn find(
+        self,
+        group: &[(RangeValue, usize)],
+        value: &RangeValue,
+        tolerance: Option<f64>,
+    ) -> Option<usize> {
+        // of several equal keys, backward takes the last and forward the first
+        let backward = group
+            .partition_point(|(key, _)| key <= value)
+            .checked_sub(1)
+            .map(|i| &group[i]);
+        let forward = group.get(group.partition_point(|(key, _)| key < value));
+        let (key, rowi) = match self {
+            AsofDirection::Backward => backward,
+            AsofDirection::Forward => forward,
+            // ties, and keys that can't be subtracted, prefer the backward match
+            AsofDirection::Nearest => match (backward, forward) {
+                (Some(b), Some(f)) => match (b.0.distance(value), f.0.distance(value)) {
+                    (Some(db), Some(df)) if df < db => Some(f),
+                    _ => Some(b),
+                },
+                (b, f) => b.or(f),
+            },
+        }?;
+        if let Some(tolerance) = tolerance {
+            if !key.distance(value).map_or(false, |d| d <= tolerance) {
+                return None;
+            }
+        }
+        Some(*rowi)
+    }
+}
+
+/// A static interval tree over the [start, end] ranges of a range join.
+///
+/// The intervals are sorted by start, and the sorted slice is treated as an
+/// implicit balanced binary tree, where the node of the subslice `lo..hi` is
+/// its midpoint and `max_end[mid]` is the largest end within that subslice.
+/// This finds the k ranges containing a value in O(log n + k).
+struct IntervalTree {
+    intervals: Vec<(RangeValue, RangeValue, usize)>,
+    max_end:   Vec<RangeValue>,
+}
+
+impl IntervalTree {
+    fn new(mut intervals: Vec<(RangeValue, RangeValue, usize)>) -> IntervalTree {
+        intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let mut max_end: Vec<_> = intervals.iter().map(|(_, end, _)| end.clone()).collect();
+        IntervalTree::build(&mut max_end, 0, intervals.len());
+        IntervalTree { intervals, max_end }
+    }
+
+    fn build(max_end: &mut [RangeValue], lo: usize, hi: usize) -> Option<RangeValue> {
+        if lo >= hi {
+            return None;
+        }
+        let mid = (lo + hi) / 2;
+        let left = IntervalTree::build(max_end, lo, mid);
+        let right = IntervalTree::build(max_end, mid + 1, hi);
+        for child in [left, right].into_iter().flatten() {
+            if child > max_end[mid] {
+                max_end[mid] = child;
+            }
+        }
+        Some(max_end[mid].clone())
+    }
+
+    /// Adds the rows of all the ranges containing `value` to `out`.
+    fn stab(&self, value: &RangeValue, out: &mut Vec<usize>) {
+        self.stab_range(value, 0, self.intervals.len(), out);
+    }
+
+    fn stab_range(&self, value: &RangeValue, lo: usize, hi: usize, out: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if self.max_end[mid] < *value {
+            return;
+        }
+        self.stab_range(value, lo, mid, out);
+        let (start, end, rowi) = &self.intervals[mid];
+        // all the ranges right of mid start at or after this one
+        if start <= value {
+            if value <= end {
+                out.push(*rowi);
+            }
+            self.stab_range(value, mid + 1, hi, out);
+        }
+    }
+}
+
+/// Joins the (already normalized) values of a key into the string that is
+/// compared in fuzzy mode.
+fn fuzzy_key(key: &[ByteString]) -> String {
+    key.iter()
+        .map(|k| String::from_utf8_lossy(k))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits `--on` pairs like `a=x,b=y` into the left (`a,b`) and right (`x,y`)
+/// column selections.
+fn parse_key_pairs(pairs: &str) -> CliResult<(SelectColumns, SelectColumns)> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for pair in pairs.split(',') {
+        let Some((l, r)) = pair.split_once('=') else {
+            return fail_clierror!("Invalid --on pair \"{pair}\". Expected \"left=right\".");
+        };
+        if l.is_empty() || r.is_empty() {
+            return fail_clierror!("Invalid --on pair \"{pair}\". Expected \"left=right\".");
+        }
+        left.push(l);
+        right.push(r);
+    }
+    Ok((
+        SelectColumns::parse(&left.join(","))?,
+        SelectColumns::parse(&right.join(","))?,
+    ))
+}
+
 struct ValueIndex<R> {
     // This maps tuples of values to corresponding rows.
     values:   AHashMap<Vec<ByteString>, Vec<usize>>,
@@ -375,7 +1202,7 @@ impl<R: io::Read + io::Seek> ValueIndex<R> {
     fn new(
         mut rdr: csv::Reader<R>,
         sel: &Selection,
-        casei: bool,
+        keynorm: KeyNorm,
         nulls: bool,
     ) -> CliResult<ValueIndex<R>> {
         let mut val_idx = AHashMap::with_capacity(10000);
@@ -406,10 +1233,7 @@ impl<R: io::Read + io::Seek> ValueIndex<R> {
             // indexes in one pass.
             row_idx.write_u64::<BigEndian>(row.position().unwrap().byte())?;
 
-            let fields: Vec<_> = sel
-                .select(&row)
-                .map(|v| util::transform(v, casei))
-                .collect();
+            let fields = get_row_key(sel, &row, keynorm);
             if nulls || !fields.iter().any(std::vec::Vec::is_empty) {
                 match val_idx.entry(fields) {
                     Entry::Vacant(v) => {
@@ -453,7 +1277,59 @@ impl<R> fmt::Debug for ValueIndex<R> {
     }
 }
 
+/// How key values are normalized before they are compared.
+#[derive(Clone, Copy)]
+struct KeyNorm {
+    casei:   bool,
+    trim:    bool,
+    unicode: bool,
+    numeric: bool,
+}
+
+impl KeyNorm {
+    fn normalize(self, bs: &[u8]) -> ByteString {
+        if !self.trim && !self.unicode && !self.numeric {
+            return util::transform(bs, self.casei);
+        }
+        let Ok(s) = simdutf8::basic::from_utf8(bs) else {
+            return bs.to_vec();
+        };
+        if self.numeric {
+            if let Some(n) = numeric_key(s.trim()) {
+                return n.into_bytes();
+            }
+        }
+        let key = if self.trim {
+            s.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            s.trim().to_string()
+        };
+        // --normalize-unicode is rejected without the full feature
+        #[cfg(feature = "full")]
+        let key: String = if self.unicode {
+            key.nfc().collect()
+        } else {
+            key
+        };
+        util::transform(key.as_bytes(), self.casei)
+    }
+}
+
+/// Returns the canonical form of a numeric key value, e.g. "7" for "007"
+/// and "1.5" for "1.50", or `None` if the value isn't a finite number.
+fn numeric_key(s: &str) -> Option<String> {
+    if let Ok(i) = s.parse::<i64>() {
+        return Some(i.to_string());
+    }
+    let f = s.parse::<f64>().ok()?;
+    if !f.is_finite() {
+        return None;
+    }
+    // adding 0.0 turns -0.0 into 0.0, so "-0" matches "0"
+    Some((f + 0.0).to_string())
+}
+
 #[inline]
-fn get_row_key(sel: &Selection, row: &csv::ByteRecord, casei: bool) -> Vec<ByteString> {
-    sel.select(row).map(|v| util::transform(v, casei)).collect()
+fn get_row_key(sel: &Selection, row: &csv::ByteRecord, keynorm: KeyNorm) -> Vec<ByteString> {
+    sel.select(row).map(|v| keynorm.normalize(v)).collect()
 }
@@ -8,6 +8,10 @@ The columns arguments specify the columns to join for each input. Columns can
 be referenced by name. Specify multiple columns by separating them with a comma.
 Both columns1 and columns2 must specify exactly the same number of columns.
 
+Joins are computed with Polars' streaming engine where possible, so inputs larger
+than the available memory can be joined. Use --low-memory to further reduce memory
+usage at the expense of speed.
+
 Returns the shape of the join result (number of rows, number of columns) to stderr.
 
 Usage:
@@ -39,8 +43,21 @@ joinp options:
                            that do not have a corresponding row in the second
                            CSV data set. The output schema is the same as the
                            first dataset.
+    --asof                 Do an 'asof' join. This is similar to a left join,
+                           except that we match on the nearest key rather than
+                           on equal keys. Only one numeric column can be given
+                           for each input. Both inputs are sorted on their
+                           join column before joining.
+    --strategy <arg>       The asof join strategy. 'backward' matches each row
+                           of the first data set with the last row of the second
+                           data set whose key is less than or equal to its key.
+                           'forward' matches it with the first row whose key is
+                           greater than or equal to its key.
+                           [default: backward]
     --nulls                When set, joins will work on empty fields.
                            Otherwise, empty fields are completely ignored.
+    --low-memory           Use the low memory mode when reading the inputs.
+                           Slower, but uses less memory for very large files.
 
 Common options:
     -h, --help             Display this message
@@ -64,19 +81,22 @@ use crate::{config::Delimiter, util, CliError, CliResult};
 
 #[derive(Deserialize)]
 struct Args {
-    arg_columns1:   String,
-    arg_input1:     String,
-    arg_columns2:   String,
-    arg_input2:     String,
-    flag_left:      bool,
-    flag_outer:     bool,
-    flag_cross:     bool,
-    flag_semi:      bool,
-    flag_anti:      bool,
-    flag_output:    Option<String>,
-    flag_nulls:     bool,
-    flag_delimiter: Option<Delimiter>,
-    flag_quiet:     bool,
+    arg_columns1:    String,
+    arg_input1:      String,
+    arg_columns2:    String,
+    arg_input2:      String,
+    flag_left:       bool,
+    flag_outer:      bool,
+    flag_cross:      bool,
+    flag_semi:       bool,
+    flag_anti:       bool,
+    flag_asof:       bool,
+    flag_strategy:   String,
+    flag_low_memory: bool,
+    flag_output:     Option<String>,
+    flag_nulls:      bool,
+    flag_delimiter:  Option<Delimiter>,
+    flag_quiet:      bool,
 }
 
 impl From<polars::error::PolarsError> for CliError {
@@ -94,13 +114,28 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.flag_cross,
         args.flag_semi,
         args.flag_anti,
+        args.flag_asof,
     ) {
-        (false, false, false, false, false) => join.polars_join(JoinType::Inner),
-        (true, false, false, false, false) => join.polars_join(JoinType::Left),
-        (false, true, false, false, false) => join.polars_join(JoinType::Outer),
-        (false, false, true, false, false) => join.polars_join(JoinType::Cross),
-        (false, false, false, true, false) => join.polars_join(JoinType::Semi),
-        (false, false, false, false, true) => join.polars_join(JoinType::Anti),
+        (false, false, false, false, false, false) => join.polars_join(JoinType::Inner),
+        (true, false, false, false, false, false) => join.polars_join(JoinType::Left),
+        (false, true, false, false, false, false) => join.polars_join(JoinType::Outer),
+        (false, false, true, false, false, false) => join.polars_join(JoinType::Cross),
+        (false, false, false, true, false, false) => join.polars_join(JoinType::Semi),
+        (false, false, false, false, true, false) => join.polars_join(JoinType::Anti),
+        (false, false, false, false, false, true) => {
+            let strategy = match args.flag_strategy.to_lowercase().as_str() {
+                "backward" => AsofStrategy::Backward,
+                "forward" => AsofStrategy::Forward,
+                s => return fail_clierror!("Invalid asof strategy: {s}"),
+            };
+            join.polars_join(JoinType::AsOf(AsOfOptions {
+                strategy,
+                tolerance: None,
+                tolerance_str: None,
+                left_by: None,
+                right_by: None,
+            }))
+        }
         _ => fail!("Please pick exactly one join operation."),
     }?;
 
@@ -135,6 +170,19 @@ impl JoinStruct {
             );
         }
 
+        // asof joins require both inputs to be sorted on their join column
+        let (lf1, lf2) = if let JoinType::AsOf(_) = jointype {
+            if selcols1_len != 1 {
+                return fail_clierror!("asof joins require exactly one join column per input.");
+            }
+            (
+                self.lf1.sort(&self.sel1, SortOptions::default()),
+                self.lf2.sort(&self.sel2, SortOptions::default()),
+            )
+        } else {
+            (self.lf1, self.lf2)
+        };
+
         let optimize_all = polars::lazy::frame::OptState {
             projection_pushdown: true,
             predicate_pushdown:  true,
@@ -145,11 +193,10 @@ impl JoinStruct {
             streaming:           true,
         };
 
-        let mut join_results = self
-            .lf1
+        let mut join_results = lf1
             .with_optimizations(optimize_all)
             .join_builder()
-            .with(self.lf2.with_optimizations(optimize_all))
+            .with(lf2.with_optimizations(optimize_all))
             .left_on(selcols1)
             .right_on(selcols2)
             .how(jointype)
@@ -189,12 +236,14 @@ impl Args {
         let lf1 = LazyCsvReader::new(&self.arg_input1)
             .has_header(true)
             .with_missing_is_null(self.flag_nulls)
+            .low_memory(self.flag_low_memory)
             .with_delimiter(delim)
             .finish()?;
 
         let lf2 = LazyCsvReader::new(&self.arg_input2)
             .has_header(true)
             .with_missing_is_null(self.flag_nulls)
+            .low_memory(self.flag_low_memory)
             .with_delimiter(delim)
             .finish()?;
 
@@ -5,8 +5,12 @@ The command tries to do its best but since it is not possible to
 straightforwardly convert JSON lines to CSV, the process might lose some complex
 fields from the input.
 
-Also, it will fail if the JSON documents are not consistent with one another,
-as the first JSON line will be use to infer the headers of the CSV output.
+Also, by default, the first JSON line will be used to infer the headers of the CSV
+output, so keys that are not present in the first line will be ignored.
+Use the --union option to scan all the lines first and use the union of all their
+keys as headers instead.
+
+Nested objects are flattened, with the keys of each level joined by the --separator.
 
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_jsonl.rs.
 
@@ -16,6 +20,11 @@ Usage:
 
 jsonl options:
     --ignore-errors        Skip malformed input lines.
+    --union                Infer the headers from the union of the keys of all lines,
+                           instead of just the first line. This requires two passes
+                           over the input.
+    --separator <sep>      The separator to use when flattening the keys of
+                           nested objects into column names. [default: .]
 
 Common options:
     -h, --help             Display this message
@@ -23,12 +32,14 @@ Common options:
 "#;
 
 use std::{
+    collections::HashSet,
     fs,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, Seek},
 };
 
 use serde::Deserialize;
 use serde_json::Value;
+use tempfile::tempfile;
 
 use crate::{config::Config, util, CliResult};
 
@@ -37,6 +48,8 @@ struct Args {
     arg_input:          Option<String>,
     flag_output:        Option<String>,
     flag_ignore_errors: bool,
+    flag_union:         bool,
+    flag_separator:     String,
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -97,6 +110,20 @@ fn get_value_at_path(value: &Value, path: &[String]) -> Option<Value> {
     Some(current.clone())
 }
 
+fn write_headers<W: io::Write>(
+    wtr: &mut csv::Writer<W>,
+    headers: &[Vec<String>],
+    separator: &str,
+) -> CliResult<()> {
+    let headers_formatted = headers
+        .iter()
+        .map(|v| v.join(separator))
+        .collect::<Vec<String>>();
+    let headers_record = csv::StringRecord::from(headers_formatted);
+    wtr.write_record(&headers_record)?;
+    Ok(())
+}
+
 fn json_line_to_csv_record(value: &Value, headers: &[Vec<String>]) -> csv::StringRecord {
     let mut record = csv::StringRecord::new();
 
@@ -129,18 +156,70 @@ fn json_line_to_csv_record(value: &Value, headers: &[Vec<String>]) -> csv::Strin
     record
 }
 
+/// scan all the lines and return the union of their inferred headers,
+/// in order of first appearance
+fn infer_union_headers(rdr: impl BufRead, ignore_errors: bool) -> CliResult<Vec<Vec<String>>> {
+    let mut headers: Vec<Vec<String>> = Vec::new();
+    let mut seen: HashSet<Vec<String>> = HashSet::new();
+
+    for (rowidx, line) in rdr.lines().enumerate() {
+        let value: Value = match serde_json::from_str(&line?) {
+            Ok(v) => v,
+            Err(e) => {
+                if ignore_errors {
+                    continue;
+                }
+                let human_idx = rowidx + 1; // not zero based, for readability
+                return fail_clierror!(
+                    r#"Could not parse line {human_idx} as JSON!: {e}
+Use `--ignore-errors` option to skip malformed input lines."#,
+                );
+            }
+        };
+
+        for path in infer_headers(&value) {
+            if seen.insert(path.clone()) {
+                headers.push(path);
+            }
+        }
+    }
+
+    Ok(headers)
+}
+
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
     let mut wtr = Config::new(&args.flag_output).writer()?;
 
-    let rdr: Box<dyn BufRead> = match args.arg_input {
-        None => Box::new(BufReader::new(io::stdin())),
-        Some(p) => Box::new(BufReader::new(fs::File::open(p)?)),
-    };
-
     let mut headers: Vec<Vec<String>> = Vec::new();
     let mut headers_emitted: bool = false;
 
+    let rdr: Box<dyn BufRead> = if args.flag_union {
+        // we need to read the input twice, so if its stdin,
+        // we spool it to a temp file first as stdin is not seekable
+        let mut input_file = match args.arg_input {
+            None => {
+                let mut stdin_file = tempfile()?;
+                io::copy(&mut io::stdin().lock(), &mut stdin_file)?;
+                stdin_file
+            }
+            Some(ref p) => fs::File::open(p)?,
+        };
+        input_file.rewind()?;
+        headers = infer_union_headers(BufReader::new(&input_file), args.flag_ignore_errors)?;
+        input_file.rewind()?;
+
+        write_headers(&mut wtr, &headers, &args.flag_separator)?;
+        headers_emitted = true;
+
+        Box::new(BufReader::new(input_file))
+    } else {
+        match args.arg_input {
+            None => Box::new(BufReader::new(io::stdin())),
+            Some(p) => Box::new(BufReader::new(fs::File::open(p)?)),
+        }
+    };
+
     for (rowidx, line) in rdr.lines().enumerate() {
         let value: Value = match serde_json::from_str(&line?) {
             Ok(v) => v,
@@ -159,11 +238,7 @@ Use `tojsonl` command to convert _to_ jsonl instead of _from_ jsonl."#,
 
         if !headers_emitted {
             headers = infer_headers(&value);
-
-            let headers_formatted = headers.iter().map(|v| v.join(".")).collect::<Vec<String>>();
-            let headers_record = csv::StringRecord::from(headers_formatted);
-            wtr.write_record(&headers_record)?;
-
+            write_headers(&mut wtr, &headers, &args.flag_separator)?;
             headers_emitted = true;
         }
 
@@ -0,0 +1,107 @@
+static USAGE: &str = r#"
+Interactively view, search & filter a CSV using the csvlens (https://github.com/YS-L/csvlens)
+terminal viewer.
+
+The header row stays on top while scrolling, columns can be hidden with --columns,
+and rows can be searched (--find) or filtered (--filter) using a regex.
+
+Once in the viewer, press H for help on the available key bindings, and q to exit.
+
+Examples:
+View data.csv:
+
+  $ qsv lens data.csv
+
+View data.csv, only showing the columns whose names contain "date" or "amount":
+
+  $ qsv lens --columns 'date|amount' --ignore-case data.csv
+
+View the result of a pipeline, only showing the rows that mention "Boston":
+
+  $ qsv sort -s city data.csv | qsv lens --filter Boston
+
+Usage:
+    qsv lens [options] [<input>]
+    qsv lens --help
+
+lens options:
+    --columns <regex>      Only show the columns whose names match the regex.
+    --filter <regex>       Only show the rows that match the regex.
+    --find <regex>         Find & highlight the cells that match the regex.
+    -i, --ignore-case      Search & filter case-insensitively.
+    --echo-column <name>   Print the value of the selected cell of this column
+                           to stdout when selecting a row with Enter, and exit.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write the --echo-column value to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::io::Write;
+
+use csvlens::run_csvlens;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    util, CliError, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:        Option<String>,
+    flag_columns:     Option<String>,
+    flag_filter:      Option<String>,
+    flag_find:        Option<String>,
+    flag_ignore_case: bool,
+    flag_echo_column: Option<String>,
+    flag_output:      Option<String>,
+    flag_no_headers:  bool,
+    flag_delimiter:   Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    // use Config so we pick up the delimiter from the file extension & QSV_DEFAULT_DELIMITER
+    let rconfig = Config::new(&args.arg_input).delimiter(args.flag_delimiter);
+
+    let mut lens_args: Vec<String> = vec![
+        "csvlens".to_string(),
+        "--delimiter".to_string(),
+        (rconfig.get_delimiter() as char).to_string(),
+    ];
+    if let Some(input) = args.arg_input {
+        lens_args.push(input);
+    }
+    if args.flag_no_headers {
+        lens_args.push("--no-headers".to_string());
+    }
+    if args.flag_ignore_case {
+        lens_args.push("--ignore-case".to_string());
+    }
+    for (option, value) in [
+        ("--columns", args.flag_columns),
+        ("--filter", args.flag_filter),
+        ("--find", args.flag_find),
+        ("--echo-column", args.flag_echo_column),
+    ] {
+        if let Some(value) = value {
+            lens_args.push(option.to_string());
+            lens_args.push(value);
+        }
+    }
+
+    let selection =
+        run_csvlens(&lens_args).map_err(|e| CliError::Other(format!("csvlens error: {e}")))?;
+    if let Some(selected_cell) = selection {
+        let mut wtr = Config::new(&args.flag_output).io_writer()?;
+        writeln!(wtr, "{selected_cell}")?;
+        wtr.flush()?;
+    }
+    Ok(())
+}
@@ -218,12 +218,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.arg_main_script.clone()
     };
 
-    // in Luau, comments begin with two consecutive hyphens
-    // let's remove them, so we don't falsely trigger on commented special variables
-    let comment_remover_re = regex::Regex::new(r"--.*?$").unwrap();
-    comment_remover_re.replace_all(&luau_script, "");
-
-    let mut index_file_used = luau_script.contains("_INDEX") || luau_script.contains("_LASTROW");
+    let mut index_file_used = uses_index_vars(&luau_script);
 
     // check if the main script has BEGIN and END blocks
     // and if so, extract them and remove them from the main script
@@ -253,7 +248,6 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     };
 
     main_script.push_str(luau_script.trim());
-    comment_remover_re.replace_all(&main_script, "");
     debug!("MAIN script: {main_script:?}");
 
     // setup LUAU_PATH; create a temporary directory and add it to LUAU_PATH and copy date.lua
@@ -283,10 +277,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     } else {
         embedded_begin_script.trim().to_string()
     };
-    comment_remover_re.replace_all(&begin_script, "");
     // check if the BEGIN script uses _INDEX
-    index_file_used =
-        index_file_used || begin_script.contains("_INDEX") || begin_script.contains("_LASTROW");
+    index_file_used = index_file_used || uses_index_vars(&begin_script);
     debug!("BEGIN script: {begin_script:?}");
 
     // check if an END script was specified
@@ -302,10 +294,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     } else {
         embedded_end_script.trim().to_string()
     };
-    comment_remover_re.replace_all(&end_script, "");
     // check if the END script uses _INDEX
-    index_file_used =
-        index_file_used || end_script.contains("_INDEX") || end_script.contains("_LASTROW");
+    index_file_used = index_file_used || uses_index_vars(&end_script);
     debug!("END script: {end_script:?}");
 
     // -------- setup Luau environment --------
@@ -360,6 +350,16 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(())
 }
 
+/// checks if a script uses the _INDEX or _LASTROW special variables,
+/// which require an index for random access
+fn uses_index_vars(script: &str) -> bool {
+    // in Luau, comments begin with two consecutive hyphens
+    // let's ignore them, so we don't falsely trigger on commented special variables
+    let comment_remover_re = regex::Regex::new(r"(?m)--.*$").unwrap();
+    let uncommented_script = comment_remover_re.replace_all(script, "");
+    uncommented_script.contains("_INDEX") || uncommented_script.contains("_LASTROW")
+}
+
 fn setup_helpers(luau: &Lua) -> Result<(), CliError> {
     // this is a helper function that can be called from Luau scripts
     // to send log messages to the logfile
@@ -396,13 +396,17 @@ fn setup_helpers(luau: &Lua) -> Result<(), CliError> {
     luau.globals().set("qsv_log", qsv_log)?;
 
     // this is a helper function that can be called from Luau scripts
-    // to coalesce - return the first non-null value in a list
+    // to coalesce - return the first non-null, non-empty value in a list
     let qsv_coalesce = luau.create_function(|luau, mut args: mlua::MultiValue| {
         while let Some(val) = args.pop_front() {
-            let val = luau.from_value::<serde_json::Value>(val)?;
-            let val_str = &serde_json::to_string(&val).unwrap_or_default();
-            if !val_str.is_empty() {
-                return Ok(val_str.to_string());
+            match luau.from_value::<serde_json::Value>(val)? {
+                serde_json::Value::Null => continue,
+                serde_json::Value::String(val_str) => {
+                    if !val_str.is_empty() {
+                        return Ok(val_str);
+                    }
+                }
+                val => return Ok(val.to_string()),
             }
         }
         Ok(String::new())
@@ -0,0 +1,138 @@
+static USAGE: &str = r#"
+Melts (unpivots) a CSV from wide to long format, turning the selected value
+columns into variable/value row pairs, while repeating the id columns.
+
+For instance the following CSV:
+
+name,2021,2022
+John,10,12
+Mary,7,9
+
+Can be melted with "name" as the id column to:
+
+name,variable,value
+John,2021,10
+John,2022,12
+Mary,2021,7
+Mary,2022,9
+
+This command streams the CSV row by row, so it works on arbitrarily large files.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_melt.rs.
+
+Usage:
+    qsv melt [options] [<input>]
+    qsv melt --help
+
+melt options:
+    -i, --id <cols>            The id columns to repeat on each output row.
+                               See 'qsv select --help' for the format details.
+                               When not set, no id columns are output.
+    -v, --values <cols>        The value columns to melt.
+                               See 'qsv select --help' for the format details.
+                               When not set, all the columns that are not
+                               id columns are melted.
+    --variable-name <name>     The name of the variable column.
+                               [default: variable]
+    --value-name <name>        The name of the value column.
+                               [default: value]
+    --skip-empty               Do not output rows with empty values.
+
+Common options:
+    -h, --help                 Display this message
+    -o, --output <file>        Write output to <file> instead of stdout.
+    -n, --no-headers           When set, the first row will not be interpreted
+                               as headers. Column indices (starting from 1)
+                               are used as the variable values instead.
+    -d, --delimiter <arg>      The field delimiter for reading CSV data.
+                               Must be a single character. (default: ,)
+"#;
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:          Option<String>,
+    flag_id:            Option<SelectColumns>,
+    flag_values:        Option<SelectColumns>,
+    flag_variable_name: String,
+    flag_value_name:    String,
+    flag_skip_empty:    bool,
+    flag_output:        Option<String>,
+    flag_no_headers:    bool,
+    flag_delimiter:     Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    let headers = rdr.byte_headers()?.clone();
+    let use_names = !rconfig.no_headers;
+
+    let id_cols: Vec<usize> = match args.flag_id {
+        Some(ref id) => id.selection(&headers, use_names)?.to_vec(),
+        None => vec![],
+    };
+    let value_cols: Vec<usize> = match args.flag_values {
+        Some(ref values) => values.selection(&headers, use_names)?.to_vec(),
+        None => (0..headers.len())
+            .filter(|i| !id_cols.contains(i))
+            .collect(),
+    };
+    if value_cols.is_empty() {
+        return fail_clierror!("No value columns to melt.");
+    }
+
+    // the variable value for each value column
+    let variables: Vec<Vec<u8>> = value_cols
+        .iter()
+        .map(|&i| {
+            if use_names {
+                headers[i].to_vec()
+            } else {
+                (i + 1).to_string().into_bytes()
+            }
+        })
+        .collect();
+
+    let mut out_record = csv::ByteRecord::with_capacity(64, id_cols.len() + 2);
+    if use_names {
+        for &i in &id_cols {
+            out_record.push_field(&headers[i]);
+        }
+        out_record.push_field(args.flag_variable_name.as_bytes());
+        out_record.push_field(args.flag_value_name.as_bytes());
+        wtr.write_byte_record(&out_record)?;
+    }
+
+    let mut record = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        for (variable, &value_idx) in variables.iter().zip(value_cols.iter()) {
+            let value = record.get(value_idx).unwrap_or_default();
+            if args.flag_skip_empty && value.is_empty() {
+                continue;
+            }
+            out_record.clear();
+            for &i in &id_cols {
+                out_record.push_field(record.get(i).unwrap_or_default());
+            }
+            out_record.push_field(variable);
+            out_record.push_field(value);
+            wtr.write_byte_record(&out_record)?;
+        }
+    }
+
+    Ok(wtr.flush()?)
+}
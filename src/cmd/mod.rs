@@ -2,15 +2,31 @@
 pub mod apply;
 #[cfg(feature = "datapusher_plus")]
 pub mod applydp;
+#[cfg(feature = "full")]
+pub mod autoconvert;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod behead;
 #[cfg(any(feature = "full", feature = "lite"))]
+pub mod bench;
+#[cfg(any(feature = "full", feature = "lite"))]
 pub mod cat;
+#[cfg(feature = "full")]
+pub mod checksum;
+#[cfg(all(feature = "clipboard", feature = "full"))]
+pub mod clipboard;
 pub mod count;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod crosstab;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod datefmt;
 pub mod dedup;
 #[cfg(feature = "full")]
+pub mod describegpt;
+#[cfg(feature = "full")]
 pub mod diff;
 #[cfg(any(feature = "full", feature = "lite"))]
+pub mod edit;
+#[cfg(any(feature = "full", feature = "lite"))]
 pub mod enumerate;
 pub mod excel;
 pub mod exclude;
@@ -34,8 +50,16 @@ pub mod fmt;
 #[cfg(all(feature = "foreach", target_family = "unix", not(feature = "lite")))]
 pub mod foreach;
 pub mod frequency;
+#[cfg(all(feature = "polars", feature = "full"))]
+pub mod from;
 #[cfg(all(feature = "generate", feature = "full"))]
 pub mod generate;
+#[cfg(all(feature = "apply", feature = "full"))]
+pub mod geocode;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod groupby;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod head;
 pub mod headers;
 pub mod index;
 pub mod input;
@@ -45,10 +69,20 @@ pub mod join;
 pub mod joinp;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod jsonl;
+#[cfg(all(feature = "lens", feature = "full"))]
+pub mod lens;
 #[cfg(all(feature = "luau", feature = "full"))]
 pub mod luau;
 #[cfg(any(feature = "full", feature = "lite"))]
+pub mod melt;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod outliers;
+#[cfg(any(feature = "full", feature = "lite"))]
 pub mod partition;
+#[cfg(feature = "full")]
+pub mod plot;
+#[cfg(all(feature = "prompt", feature = "full"))]
+pub mod prompt;
 pub mod pseudo;
 #[cfg(all(feature = "python", feature = "full"))]
 pub mod python;
@@ -64,14 +98,22 @@ pub mod search;
 pub mod searchset;
 pub mod select;
 pub mod slice;
+#[cfg(feature = "full")]
+pub mod snappy;
 pub mod sniff;
 pub mod sort;
 pub mod sortcheck;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod split;
+#[cfg(all(feature = "polars", feature = "full"))]
+pub mod sqlp;
 pub mod stats;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod table;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod tail;
+#[cfg(feature = "full")]
+pub mod template;
 #[cfg(all(feature = "to", feature = "full"))]
 pub mod to;
 #[cfg(any(feature = "full", feature = "lite"))]
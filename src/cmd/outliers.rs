@@ -0,0 +1,280 @@
+static USAGE: &str = r#"
+Flags the numeric outliers of a CSV, column by column.
+
+The CSV is read twice: once to compute the statistics of each selected column,
+and once more to flag the records with at least one outlier value. Only numeric
+columns are considered - selected columns with non-numeric values are skipped.
+
+Two methods are supported:
+  iqr     - a value is an outlier if it falls outside the fences
+            q1 - (threshold * IQR) and q3 + (threshold * IQR), like the
+            lower/upper inner fences computed by 'qsv stats --quartiles'.
+            The default threshold is 1.5.
+  zscore  - a value is an outlier if the absolute value of its modified z-score
+            (0.6745 * (value - median) / MAD) is greater than the threshold.
+            The default threshold is 3.5. Columns whose median absolute
+            deviation (MAD) is zero have no outliers.
+
+When the input has a valid stats cache (see 'qsv stats --cache') with the quartiles
+(iqr) or the median & MAD (zscore) of its columns, the first read is skipped and the
+cached statistics are used instead. The numeric columns are then the ones typed as
+Integer or Float by stats, and the statistics are rounded to 4 decimal places.
+
+By default, a boolean column named "outlier" is added to each record.
+With --filter, only the records with at least one outlier value are returned.
+
+Examples:
+Add an "outlier" column flagging the records with an outlier in any numeric column.
+
+  $ qsv outliers data.csv
+
+Only return the records whose price is more than 3 IQRs beyond the quartiles.
+
+  $ qsv outliers --select price --threshold 3 --filter data.csv
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_outliers.rs.
+
+Usage:
+    qsv outliers [options] [<input>]
+    qsv outliers --help
+
+outliers options:
+    -s, --select <arg>      Select the columns to check for outliers.
+                            See 'qsv select --help' for the format details.
+                            By default, all the numeric columns are checked.
+    -m, --method <arg>      The outlier detection method - iqr or zscore.
+                            [default: iqr]
+    -t, --threshold <arg>   The IQR multiplier (iqr) or the modified z-score
+                            cutoff (zscore). Defaults to 1.5 for iqr & 3.5 for zscore.
+    -c, --new-column <name> The name of the added outlier flag column.
+                            [default: outlier]
+    -f, --filter            Only return the records with at least one outlier,
+                            instead of adding a flag column.
+
+Common options:
+    -h, --help              Display this message
+    -o, --output <file>     Write output to <file> instead of stdout.
+    -n, --no-headers        When set, the first row will not be interpreted
+                            as headers.
+    -d, --delimiter <arg>   The field delimiter for reading CSV data.
+                            Must be a single character. (default: ,)
+"#;
+
+use std::{io, str::from_utf8};
+
+use serde::Deserialize;
+use stats::Unsorted;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::{SelectColumns, Selection},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    flag_select:     Option<SelectColumns>,
+    flag_method:     Method,
+    flag_threshold:  Option<f64>,
+    flag_new_column: String,
+    flag_filter:     bool,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum Method {
+    Iqr,
+    Zscore,
+}
+
+/// The inclusive range of non-outlier values of a column.
+struct Bounds {
+    col_idx: usize,
+    lower:   f64,
+    upper:   f64,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let threshold = args.flag_threshold.unwrap_or(match args.flag_method {
+        Method::Iqr => 1.5,
+        Method::Zscore => 3.5,
+    });
+    if threshold.is_nan() || threshold < 0.0 {
+        return fail_clierror!("--threshold must be a non-negative number.");
+    }
+
+    // stdin is not seekable, and we need to read the CSV twice,
+    // so we copy it to a temporary file first
+    let stdin_tmpfile;
+    let input = if Config::new(&args.arg_input).is_stdin() {
+        stdin_tmpfile = tempfile::NamedTempFile::new()?;
+        io::copy(&mut io::stdin().lock(), &mut stdin_tmpfile.as_file())?;
+        Some(stdin_tmpfile.path().to_string_lossy().to_string())
+    } else {
+        args.arg_input
+    };
+
+    let mut rconfig = Config::new(&input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    if let Some(select) = args.flag_select {
+        rconfig = rconfig.select(select);
+    }
+
+    // first pass - collect the values of the selected columns,
+    // unless the stats cache has the statistics we need
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+    let sel = rconfig.selection(&headers)?;
+
+    let mut record = csv::ByteRecord::new();
+    let bounds = if let Some(bounds) = cached_bounds(&args, &input, &sel, threshold) {
+        log::info!("using the stats cache");
+        bounds
+    } else {
+        let mut values: Vec<Option<Unsorted<f64>>> =
+            sel.iter().map(|_| Some(Unsorted::default())).collect();
+        while rdr.read_byte_record(&mut record)? {
+            for (col_values, field) in values.iter_mut().zip(sel.select(&record)) {
+                let Some(unsorted) = col_values else {
+                    continue;
+                };
+                if field.is_empty() {
+                    continue;
+                }
+                match parse_number(field) {
+                    Some(n) if n.is_finite() => unsorted.add(n),
+                    // not a numeric column, skip it
+                    _ => *col_values = None,
+                }
+            }
+        }
+
+        sel.iter()
+            .zip(values)
+            .filter_map(|(&col_idx, col_values)| {
+                let (lower, upper) = compute_bounds(col_values?, args.flag_method, threshold)?;
+                Some(Bounds {
+                    col_idx,
+                    lower,
+                    upper,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    if bounds.is_empty() {
+        return fail_clierror!("No numeric columns selected.");
+    }
+
+    // second pass - flag the outliers
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    if !rconfig.no_headers {
+        let mut headers = rdr.byte_headers()?.clone();
+        if !args.flag_filter {
+            headers.push_field(args.flag_new_column.as_bytes());
+        }
+        wtr.write_byte_record(&headers)?;
+    }
+
+    while rdr.read_byte_record(&mut record)? {
+        let is_outlier = bounds.iter().any(|b| {
+            parse_number(&record[b.col_idx]).map_or(false, |n| n < b.lower || n > b.upper)
+        });
+        if args.flag_filter {
+            if is_outlier {
+                wtr.write_byte_record(&record)?;
+            }
+        } else {
+            record.push_field(if is_outlier { b"true" } else { b"false" });
+            wtr.write_byte_record(&record)?;
+        }
+    }
+
+    Ok(wtr.flush()?)
+}
+
+/// Returns the lower & upper bounds of the non-outlier values of a column,
+/// or None if the column has no values.
+fn compute_bounds(mut values: Unsorted<f64>, method: Method, threshold: f64) -> Option<(f64, f64)> {
+    match method {
+        Method::Iqr => {
+            let (q1, _, q3) = values.quartiles()?;
+            Some(iqr_bounds(q1, q3, threshold))
+        }
+        Method::Zscore => {
+            let median = values.median()?;
+            let mad = values.mad(Some(median))?;
+            Some(zscore_bounds(median, mad, threshold))
+        }
+    }
+}
+
+fn iqr_bounds(q1: f64, q3: f64, threshold: f64) -> (f64, f64) {
+    let iqr = q3 - q1;
+    (threshold.mul_add(-iqr, q1), threshold.mul_add(iqr, q3))
+}
+
+fn zscore_bounds(median: f64, mad: f64, threshold: f64) -> (f64, f64) {
+    if mad.abs() < f64::EPSILON {
+        return (f64::NEG_INFINITY, f64::INFINITY);
+    }
+    // |0.6745 * (x - median) / MAD| > threshold
+    let max_deviation = threshold * mad / 0.6745;
+    (median - max_deviation, median + max_deviation)
+}
+
+/// Returns the bounds of the selected numeric columns computed from the stats cache of
+/// the input, or None if there's no valid cache with the statistics of the method.
+fn cached_bounds(
+    args: &Args,
+    input: &Option<String>,
+    sel: &Selection,
+    threshold: f64,
+) -> Option<Vec<Bounds>> {
+    let stats_args =
+        crate::cmd::stats::Args::new(input.clone(), args.flag_delimiter, args.flag_no_headers);
+    let (stat_headers, cached_stats) = stats_args.cached_stats()?;
+    let stat_idx = |name: &str| stat_headers.iter().position(|h| h == name.as_bytes());
+    let type_idx = stat_idx("type")?;
+    let (first_idx, second_idx) = match args.flag_method {
+        Method::Iqr => (stat_idx("q1")?, stat_idx("q3")?),
+        Method::Zscore => (stat_idx("median")?, stat_idx("mad")?),
+    };
+
+    let mut bounds = Vec::with_capacity(sel.len());
+    for &col_idx in sel.iter() {
+        let stat = cached_stats.get(col_idx)?;
+        if !matches!(stat.get(type_idx)?, b"Integer" | b"Float") {
+            continue;
+        }
+        let (Some(first), Some(second)) = (
+            stat.get(first_idx).and_then(parse_number),
+            stat.get(second_idx).and_then(parse_number),
+        ) else {
+            continue;
+        };
+        let (lower, upper) = match args.flag_method {
+            Method::Iqr => iqr_bounds(first, second, threshold),
+            Method::Zscore => zscore_bounds(first, second, threshold),
+        };
+        bounds.push(Bounds {
+            col_idx,
+            lower,
+            upper,
+        });
+    }
+    Some(bounds)
+}
+
+#[inline]
+fn parse_number(field: &[u8]) -> Option<f64> {
+    from_utf8(field).ok()?.trim().parse().ok()
+}
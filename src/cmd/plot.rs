@@ -0,0 +1,402 @@
+static USAGE: &str = r#"
+Plots a quick chart of one or two columns of a CSV, either in the terminal
+(using unicode block characters) or as an SVG file.
+
+The following kinds of chart are supported:
+  hist  - a histogram of the numeric values of <column>, with --bins
+          equal-width bins between its minimum & maximum values.
+  bar   - a bar chart of the distinct values of <column>, in order of first
+          appearance. The bars show the number of records with each value,
+          or the sum of the --y column for each value if set.
+  line  - a line chart of the numeric values of <column> in record order.
+          If --y is set, its values are plotted instead, and <column> is used
+          for the labels of the x axis.
+          In the terminal, line charts are rendered as a sparkline.
+
+Empty values are ignored. Non-numeric values in a column that needs to be
+numeric are an error.
+
+Examples:
+Show a histogram of the prices in the terminal.
+
+  $ qsv plot price data.csv
+
+Save a bar chart of the total sales by region as an SVG.
+
+  $ qsv plot --kind bar region --y sales data.csv --svg sales.svg
+
+Show a sparkline of the daily temperatures.
+
+  $ qsv plot --kind line date --y temperature data.csv
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_plot.rs.
+
+Usage:
+    qsv plot [options] <column> [<input>]
+    qsv plot --help
+
+plot options:
+    -k, --kind <arg>       The kind of chart - hist, bar or line.
+                           [default: hist]
+    -y, --y <column>       The numeric column to plot against <column>
+                           for bar & line charts.
+    --bins <n>             The number of bins of histograms.
+                           [default: 10]
+    -w, --width <n>        The width of the chart - in characters in the terminal,
+                           in pixels for SVG charts.
+                           [default: 60]
+    --height <n>           The height of SVG charts, in pixels.
+                           [default: 400]
+    --svg <file>           Write the chart as an SVG to <file> instead of
+                           rendering it in the terminal.
+    --title <arg>          The title of the chart. Defaults to the name of
+                           the plotted column(s).
+
+Common options:
+    -h, --help             Display this message
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_column:      String,
+    arg_input:       Option<String>,
+    flag_kind:       Kind,
+    flag_y:          Option<String>,
+    flag_bins:       usize,
+    flag_width:      usize,
+    flag_height:     usize,
+    flag_svg:        Option<String>,
+    flag_title:      Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Hist,
+    Bar,
+    Line,
+}
+
+/// The labels & values of the bars or points of a chart.
+type Series = Vec<(String, f64)>;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    if args.flag_bins == 0 || args.flag_width == 0 || args.flag_height == 0 {
+        return fail_clierror!("--bins, --width & --height must be greater than zero.");
+    }
+    if args.flag_kind == Kind::Hist && args.flag_y.is_some() {
+        return fail_clierror!("--y cannot be used with histograms.");
+    }
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+
+    let column_index = |name: &str| -> CliResult<usize> {
+        let sel = SelectColumns::parse(name)?.selection(&headers, !rconfig.no_headers)?;
+        if sel.len() != 1 {
+            return fail_clierror!("'{name}' does not select exactly one column.");
+        }
+        Ok(sel[0])
+    };
+    let x_idx = column_index(&args.arg_column)?;
+    let y_idx = args.flag_y.as_deref().map(column_index).transpose()?;
+
+    let mut series = Series::new();
+    let mut categories: IndexMap<String, f64> = IndexMap::new();
+    let mut record = csv::StringRecord::new();
+    let mut row_number = 0_u64;
+    while rdr.read_record(&mut record)? {
+        row_number += 1;
+        let x = record.get(x_idx).unwrap_or_default().trim();
+        let y = match y_idx {
+            Some(y_idx) => {
+                let y = record.get(y_idx).unwrap_or_default().trim();
+                if y.is_empty() {
+                    continue;
+                }
+                Some(parse_number(y, row_number)?)
+            }
+            None => None,
+        };
+        match args.flag_kind {
+            Kind::Bar => *categories.entry(x.to_string()).or_default() += y.unwrap_or(1.0),
+            Kind::Hist | Kind::Line => match y {
+                Some(y) => series.push((x.to_string(), y)),
+                None if x.is_empty() => {}
+                None => series.push((row_number.to_string(), parse_number(x, row_number)?)),
+            },
+        }
+    }
+
+    let series = match args.flag_kind {
+        Kind::Hist => histogram(&series, args.flag_bins),
+        Kind::Bar => categories.into_iter().collect(),
+        Kind::Line => series,
+    };
+    if series.is_empty() {
+        return fail_clierror!("Nothing to plot.");
+    }
+
+    let title = args.flag_title.unwrap_or_else(|| match args.flag_y {
+        Some(ref y) => format!("{y} by {}", args.arg_column),
+        None => args.arg_column.clone(),
+    });
+
+    if let Some(svg_path) = args.flag_svg {
+        let svg = if args.flag_kind == Kind::Line {
+            svg_line(&series, &title, args.flag_width, args.flag_height)
+        } else {
+            svg_bars(&series, &title, args.flag_width, args.flag_height)
+        };
+        fs::write(svg_path, svg)?;
+        return Ok(());
+    }
+
+    let chart = if args.flag_kind == Kind::Line {
+        sparkline(&series, args.flag_width)
+    } else {
+        terminal_bars(&series, args.flag_width)
+    };
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{title}")?;
+    stdout.write_all(chart.as_bytes())?;
+    Ok(stdout.flush()?)
+}
+
+fn parse_number(value: &str, row_number: u64) -> CliResult<f64> {
+    match value.parse::<f64>() {
+        Ok(n) if n.is_finite() => Ok(n),
+        _ => fail_clierror!("Non-numeric value in record {row_number}: {value}"),
+    }
+}
+
+/// Counts the values of a series into equal-width bins.
+fn histogram(series: &Series, bins: usize) -> Series {
+    if series.is_empty() {
+        return Series::new();
+    }
+    let (min, max) = series
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+            (min.min(*v), max.max(*v))
+        });
+
+    let bin_width = (max - min) / bins as f64;
+    let mut counts = vec![0_u64; bins];
+    for (_, v) in series {
+        let bin = if bin_width > 0.0 {
+            (((v - min) / bin_width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let lower = (i as f64).mul_add(bin_width, min);
+            let upper = lower + bin_width;
+            (
+                format!(
+                    "{} - {}",
+                    util::round_num(lower, 2),
+                    util::round_num(upper, 2)
+                ),
+                count as f64,
+            )
+        })
+        .collect()
+}
+
+/// Renders a series as horizontal bars, one per line, with their label & value.
+fn terminal_bars(series: &Series, width: usize) -> String {
+    let label_width = series
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or_default();
+    let max_value = series.iter().map(|(_, v)| v.abs()).fold(0.0, f64::max);
+
+    let mut chart = String::new();
+    for (label, value) in series {
+        let bar_len = if max_value > 0.0 {
+            (value.abs() / max_value * width as f64).round() as usize
+        } else {
+            0
+        };
+        let _ = writeln!(
+            chart,
+            "{label:>label_width$} │{} {}",
+            "█".repeat(bar_len),
+            util::round_num(*value, 4)
+        );
+    }
+    chart
+}
+
+/// Renders a series as a sparkline, averaging consecutive values
+/// when there are more values than `width`.
+fn sparkline(series: &Series, width: usize) -> String {
+    let values: Vec<f64> = if series.len() > width {
+        let chunk_size = (series.len() + width - 1) / width;
+        series
+            .chunks(chunk_size)
+            .map(|chunk| chunk.iter().map(|(_, v)| v).sum::<f64>() / chunk.len() as f64)
+            .collect()
+    } else {
+        series.iter().map(|(_, v)| *v).collect()
+    };
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+
+    let mut chart: String = values
+        .iter()
+        .map(|v| {
+            if max > min {
+                SPARKS[(((v - min) / (max - min)) * 7.0).round() as usize]
+            } else {
+                SPARKS[0]
+            }
+        })
+        .collect();
+    let _ = writeln!(
+        chart,
+        "\nmin: {}  max: {}",
+        util::round_num(min, 4),
+        util::round_num(max, 4)
+    );
+    chart
+}
+
+const SVG_MARGIN: f64 = 40.0;
+
+fn svg_header(title: &str, width: usize, height: usize) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"12\">\n<rect \
+         width=\"100%\" height=\"100%\" fill=\"white\"/>\n<text x=\"{}\" y=\"20\" \
+         text-anchor=\"middle\" font-size=\"16\">{}</text>\n",
+        width / 2,
+        xml_escape(title)
+    )
+}
+
+/// Renders a series as vertical bars.
+fn svg_bars(series: &Series, title: &str, width: usize, height: usize) -> String {
+    let mut svg = svg_header(title, width, height);
+    let plot_width = width as f64 - 2.0 * SVG_MARGIN;
+    let plot_height = height as f64 - 2.0 * SVG_MARGIN;
+    let max_value = series.iter().map(|(_, v)| v.abs()).fold(0.0, f64::max);
+    let bar_width = plot_width / series.len() as f64;
+
+    for (i, (label, value)) in series.iter().enumerate() {
+        let bar_height = if max_value > 0.0 {
+            value.abs() / max_value * plot_height
+        } else {
+            0.0
+        };
+        let x = (i as f64).mul_add(bar_width, SVG_MARGIN);
+        let y = SVG_MARGIN + plot_height - bar_height;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x:.2}\" y=\"{y:.2}\" width=\"{:.2}\" height=\"{bar_height:.2}\" \
+             fill=\"steelblue\"><title>{}: {}</title></rect>",
+            bar_width * 0.9,
+            xml_escape(label),
+            util::round_num(*value, 4)
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a series as a polyline.
+fn svg_line(series: &Series, title: &str, width: usize, height: usize) -> String {
+    let mut svg = svg_header(title, width, height);
+    let plot_width = width as f64 - 2.0 * SVG_MARGIN;
+    let plot_height = height as f64 - 2.0 * SVG_MARGIN;
+    let (min, max) = series
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+            (min.min(*v), max.max(*v))
+        });
+    let x_step = if series.len() > 1 {
+        plot_width / (series.len() - 1) as f64
+    } else {
+        0.0
+    };
+
+    let points: Vec<String> = series
+        .iter()
+        .enumerate()
+        .map(|(i, (_, v))| {
+            let y_ratio = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            };
+            format!(
+                "{:.2},{:.2}",
+                (i as f64).mul_add(x_step, SVG_MARGIN),
+                SVG_MARGIN + plot_height * (1.0 - y_ratio)
+            )
+        })
+        .collect();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"steelblue\" stroke-width=\"2\"/>",
+        points.join(" ")
+    );
+    for (label, anchor, x) in [
+        (&series[0].0, "start", SVG_MARGIN),
+        (&series[series.len() - 1].0, "end", SVG_MARGIN + plot_width),
+    ] {
+        let _ = writeln!(
+            svg,
+            "<text x=\"{x:.2}\" y=\"{:.2}\" text-anchor=\"{anchor}\">{}</text>",
+            height as f64 - SVG_MARGIN / 2.0,
+            xml_escape(label)
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,99 @@
+static USAGE: &str = r#"
+Opens a native file dialog to select an input file, or to choose where to save
+the output of a pipeline. Handy for running saved qsv pipelines without typing
+file paths.
+
+By default, an open file dialog is shown, and the contents of the selected file
+are written to stdout, so it can be piped into other qsv commands. With --fd-output,
+the path of the selected file is written instead.
+
+With --save, a save file dialog is shown, and stdin is written to the chosen file.
+The path of the saved file is written to stderr.
+
+If the dialog is cancelled, qsv exits with an error exit code.
+
+Examples:
+Select a CSV and count its records.
+
+  $ qsv prompt | qsv count
+
+Select a CSV, sort it and save the result with a save dialog.
+
+  $ qsv prompt -m 'Select a CSV to sort' | qsv sort -s name | qsv prompt --save
+
+Usage:
+    qsv prompt [options]
+    qsv prompt --help
+
+prompt options:
+    -m, --msg <arg>          The title of the file dialog.
+                             [default: Select a File]
+    -F, --filters <arg>      The comma-separated file extensions that can be selected.
+                             [default: csv,tsv,tab,ssv,txt]
+    -w, --workdir <dir>      The directory the file dialog starts in.
+                             [default: .]
+    -f, --fd-output          Write the path of the selected file to stdout,
+                             instead of its contents.
+    --save                   Show a save file dialog and write stdin to the chosen file.
+    --save-fname <file>      The default filename of the save file dialog.
+                             [default: output.csv]
+
+Common options:
+    -h, --help               Display this message
+"#;
+
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+use rfd::FileDialog;
+use serde::Deserialize;
+
+use crate::{util, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    flag_msg:        String,
+    flag_filters:    String,
+    flag_workdir:    PathBuf,
+    flag_fd_output:  bool,
+    flag_save:       bool,
+    flag_save_fname: String,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let extensions: Vec<&str> = args.flag_filters.split(',').map(str::trim).collect();
+    let dialog = FileDialog::new()
+        .set_title(&args.flag_msg)
+        .add_filter("Filter", &extensions)
+        .set_directory(&args.flag_workdir);
+
+    if args.flag_save {
+        let Some(save_path) = dialog.set_file_name(&args.flag_save_fname).save_file() else {
+            return fail_clierror!("No file selected.");
+        };
+        let mut wtr = BufWriter::new(fs::File::create(&save_path)?);
+        io::copy(&mut io::stdin().lock(), &mut wtr)?;
+        wtr.flush()?;
+        winfo!("{}", save_path.display());
+        return Ok(());
+    }
+
+    let Some(selected_path) = dialog.pick_file() else {
+        return fail_clierror!("No file selected.");
+    };
+    let mut stdout = io::stdout().lock();
+    if args.flag_fd_output {
+        writeln!(stdout, "{}", selected_path.display())?;
+    } else {
+        io::copy(
+            &mut BufReader::new(fs::File::open(&selected_path)?),
+            &mut stdout,
+        )?;
+    }
+    Ok(stdout.flush()?)
+}
@@ -2,12 +2,22 @@ static USAGE: &str = r#"
 Pseudonymise the value of the given column by replacing them by an
 incremental identifier.
 
+The same value is always replaced by the same identifier, so records can still
+be grouped & joined on the pseudonymised column. To be able to reverse the
+pseudonymisation, save the mapping of the original values to their identifiers
+with --mapping.
+
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_pseudo.rs.
 
 Usage:
     qsv pseudo [options] <column> [<input>]
     qsv pseudo --help
 
+pseudo options:
+    --mapping <file>       Write the mapping of the original values to their
+                           identifiers to <file>, as a CSV with the columns
+                           "value" and "pseudonym".
+
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
@@ -30,6 +40,7 @@ use crate::{
 struct Args {
     arg_column:      SelectColumns,
     arg_input:       Option<String>,
+    flag_mapping:    Option<String>,
     flag_output:     Option<String>,
     flag_no_headers: bool,
     flag_delimiter:  Option<Delimiter>,
@@ -61,6 +72,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let headers = rdr.byte_headers()?.clone();
     let sel = rconfig.selection(&headers)?;
+    if sel.len() != 1 {
+        return fail_clierror!("Only one column can be pseudonymised at a time.");
+    }
     let column_index = *sel.iter().next().unwrap();
 
     if !rconfig.no_headers {
@@ -83,5 +97,24 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
         wtr.write_record(&record)?;
     }
-    Ok(wtr.flush()?)
+    wtr.flush()?;
+
+    if let Some(mapping) = args.flag_mapping {
+        write_mapping(&mapping, values)?;
+    }
+    Ok(())
+}
+
+/// Writes the value to pseudonym mapping as a CSV, ordered by pseudonym.
+fn write_mapping(mapping_path: &str, values: Values) -> CliResult<()> {
+    let mut mapping: Vec<(String, u64)> = values.into_iter().collect();
+    mapping.sort_unstable_by_key(|(_, id)| *id);
+
+    let mut mapping_wtr = Config::new(&Some(mapping_path.to_string())).writer()?;
+    mapping_wtr.write_record(["value", "pseudonym"])?;
+    let mut id_buffer = itoa::Buffer::new();
+    for (value, id) in mapping {
+        mapping_wtr.write_record([value.as_str(), id_buffer.format(id)])?;
+    }
+    Ok(mapping_wtr.flush()?)
 }
@@ -6,9 +6,9 @@ The executed Python has 4 ways to reference cell values (as strings):
   1. Directly by using column name (e.g. amount) as a local variable. If a column
      name has spaces and other special characters, they are replaced with underscores
      (e.g. "unit cost" -> unit_cost, "test-units/sec" -> test_units_sec)
-  2. Indexing cell value by column name as an attribute: row.amount
-  3. Indexing cell value by column name as a key: row["amount"]
-  4. Indexing cell value by column position: row[0]
+  2. Indexing cell value by column name as an attribute: col.amount
+  3. Indexing cell value by column name as a key: col["amount"]
+  4. Indexing cell value by column position: col[0]
 
 Of course, if your input has no headers, then 4. will be the only available
 option.
@@ -37,6 +37,12 @@ Some usage examples:
   Filter some lines based on numerical filtering
   $ qsv py filter "int(a) > 45"
 
+  Extract the year from a date column using the preloaded datetime module
+  $ qsv py map year "datetime.date.fromisoformat(date).year"
+
+  Extract the area code from a phone column using the preloaded re module
+  $ qsv py map area_code "re.search(r'\((\d{3})\)', phone).group(1)"
+
   Load helper file with function to compute Fibonacci sequence of the column "num_col"
   $ qsv py map --helper fibonacci.py fib qsv_uh.fibonacci(num_col) data.csv
 
@@ -71,7 +77,7 @@ Some usage examples:
   using the `py` command if Python's shared libraries are not found.
   
   Also, the following Python modules are automatically loaded and available to the user -
-  builtsin, math, random & datetime. The user can import additional modules with the --helper option,
+  builtins, math, random, datetime & re. The user can import additional modules with the --helper option,
   with the ability to use any python module that's installed in the current python virtualenv. 
 
   The python expression is evaluated on a per record basis.
@@ -283,11 +289,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             let math_module = PyModule::import(py, "math")?;
             let random_module = PyModule::import(py, "random")?;
             let datetime_module = PyModule::import(py, "datetime")?;
+            let re_module = PyModule::import(py, "re")?;
 
             batch_globals.set_item("__builtins__", builtins)?;
             batch_globals.set_item("math", math_module)?;
             batch_globals.set_item("random", random_module)?;
             batch_globals.set_item("datetime", datetime_module)?;
+            batch_globals.set_item("re", re_module)?;
 
             let py_row = helpers
                 .getattr("QSVRow")?
@@ -10,12 +10,28 @@ all of the headers, and separate them by a comma.
   Use column names that contains commas and conflict with the separator:
   $ qsv rename '"Date - Opening","Date - Actual Closing"'
 
+  Rename only some of the columns with old=new pairs:
+  $ qsv rename --pairs 'id=ID,Date - Opening=opening_date'
+
+  Replace all column names with generic ones (_col_1, _col_2, ... _col_N).
+  Typically used with --no-headers to add headers to a headerless CSV:
+  $ qsv rename --generic --no-headers
+
 For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_rename.rs.
 
 Usage:
+    qsv rename [options] --generic [<input>]
     qsv rename [options] [--] <headers> [<input>]
     qsv rename --help
 
+rename options:
+    --pairs                Interpret <headers> as a comma-separated list of
+                           old=new pairs, renaming only the matching columns.
+                           The other columns keep their current names.
+    --generic              Replace all the column names with generic ones
+                           (_col_1, _col_2, ... _col_N). For compatibility,
+                           <headers> can also be "_all_generic" instead.
+
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
@@ -35,6 +51,8 @@ use crate::{
 struct Args {
     arg_input:       Option<String>,
     arg_headers:     String,
+    flag_pairs:      bool,
+    flag_generic:    bool,
     flag_output:     Option<String>,
     flag_no_headers: bool,
     flag_delimiter:  Option<Delimiter>,
@@ -49,20 +67,34 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let mut rdr = rconfig.reader()?;
     let mut wtr = Config::new(&args.flag_output).writer()?;
-    let headers = rdr.byte_headers()?;
-
-    let mut new_rdr = csv::Reader::from_reader(args.arg_headers.as_bytes());
-    let new_headers = new_rdr.byte_headers()?;
-
-    if headers.len() != new_headers.len() {
-        return fail_clierror!(
-            "The length of the CSV headers ({}) is different from the provided one ({}).",
-            headers.len(),
-            new_headers.len()
-        );
-    }
-
-    wtr.write_record(new_headers)?;
+    let headers = rdr.byte_headers()?.clone();
+
+    let new_headers = if args.flag_generic || args.arg_headers == "_all_generic" {
+        (1..=headers.len())
+            .map(|i| format!("_col_{i}"))
+            .collect::<csv::ByteRecord>()
+    } else {
+        let mut new_rdr = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(args.arg_headers.as_bytes());
+        let mut new_names = csv::ByteRecord::new();
+        new_rdr.read_byte_record(&mut new_names)?;
+
+        if args.flag_pairs {
+            rename_pairs(&headers, &new_names)?
+        } else {
+            if headers.len() != new_names.len() {
+                return fail_clierror!(
+                    "The length of the CSV headers ({}) is different from the provided one ({}).",
+                    headers.len(),
+                    new_names.len()
+                );
+            }
+            new_names
+        }
+    };
+
+    wtr.write_record(&new_headers)?;
 
     let mut record = csv::ByteRecord::new();
     while rdr.read_byte_record(&mut record)? {
@@ -71,3 +103,21 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     wtr.flush()?;
     Ok(())
 }
+
+/// rename the headers using a list of old=new pairs
+fn rename_pairs(headers: &csv::ByteRecord, pairs: &csv::ByteRecord) -> CliResult<csv::ByteRecord> {
+    let mut new_headers: Vec<Vec<u8>> = headers.iter().map(<[u8]>::to_vec).collect();
+
+    for pair in pairs {
+        let pair_str = String::from_utf8_lossy(pair);
+        let Some((old_name, new_name)) = pair_str.split_once('=') else {
+            return fail_clierror!(r#"Invalid rename pair "{pair_str}". Expected old=new."#);
+        };
+        let Some(idx) = headers.iter().position(|h| h == old_name.as_bytes()) else {
+            return fail_clierror!(r#"Column "{old_name}" not found in the CSV headers."#);
+        };
+        new_headers[idx] = new_name.as_bytes().to_vec();
+    }
+
+    Ok(new_headers.into_iter().collect())
+}
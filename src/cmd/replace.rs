@@ -26,6 +26,10 @@ replace options:
                            prefixing the regex with '(?i)'.
     -s, --select <arg>     Select the columns to search. See 'qsv select -h'
                            for the full syntax.
+    --literal              Treat the pattern and the replacement as literal strings.
+                           Regex metacharacters in the pattern are matched as is,
+                           and capture group references (e.g. $1) in the
+                           replacement are not expanded.
     -u, --unicode          Enable unicode support. When enabled, character classes
                            will match all unicode word characters instead of only
                            ASCII word characters. Decreases performance.
@@ -54,7 +58,7 @@ use std::{borrow::Cow, env};
 
 #[cfg(any(feature = "full", feature = "lite"))]
 use indicatif::{HumanCount, ProgressBar, ProgressDrawTarget};
-use regex::bytes::RegexBuilder;
+use regex::bytes::{NoExpand, RegexBuilder};
 use serde::Deserialize;
 
 use crate::{
@@ -70,6 +74,7 @@ struct Args {
     arg_pattern:         String,
     arg_replacement:     String,
     flag_select:         SelectColumns,
+    flag_literal:        bool,
     flag_unicode:        bool,
     flag_output:         Option<String>,
     flag_no_headers:     bool,
@@ -89,7 +94,12 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         Ok(_) => true,
         Err(_) => args.flag_unicode,
     };
-    let pattern = RegexBuilder::new(&args.arg_pattern)
+    let pattern_str = if args.flag_literal {
+        regex::escape(&args.arg_pattern)
+    } else {
+        args.arg_pattern.clone()
+    };
+    let pattern = RegexBuilder::new(&pattern_str)
         .case_insensitive(args.flag_ignore_case)
         .unicode(regex_unicode)
         .size_limit(args.flag_size_limit * (1 << 20))
@@ -160,7 +170,11 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                         {
                             match_found = true;
                         }
-                        pattern.replace_all(v, replacement)
+                        if args.flag_literal {
+                            pattern.replace_all(v, NoExpand(replacement))
+                        } else {
+                            pattern.replace_all(v, replacement)
+                        }
                     } else {
                         Cow::Borrowed(v)
                     }
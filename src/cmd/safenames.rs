@@ -99,9 +99,7 @@ Common options:
                            Must be a single character. (default: ,)
 "#;
 
-use std::collections::HashMap;
-
-use ahash::RandomState;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -202,7 +200,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         // Verify or VerifyVerbose Mode
         let mut safenames_vec: Vec<String> = Vec::new();
         let mut unsafenames_vec: Vec<String> = Vec::new();
-        let mut checkednames_map: HashMap<String, u16, RandomState> = HashMap::default();
+        // use an IndexMap so duplicates are reported in the order they first appear
+        let mut checkednames_map: IndexMap<String, u16> = IndexMap::new();
         let mut temp_string;
 
         for header_name in headers.iter() {
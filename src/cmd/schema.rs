@@ -60,7 +60,7 @@ Common options:
 use std::{collections::HashSet, fs::File, io::Write, path::Path};
 
 use ahash::AHashMap;
-use csv::ByteRecord;
+use csv::{ByteRecord, StringRecord};
 use grex::RegExpBuilder;
 use itertools::Itertools;
 use log::{debug, error, info, warn};
@@ -69,7 +69,6 @@ use serde_json::{json, value::Number, Map, Value};
 use stats::Frequencies;
 
 use crate::{
-    cmd::stats::Stats,
     config::{Config, Delimiter},
     select::SelectColumns,
     util, CliResult,
@@ -153,7 +152,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     // create final JSON object for output
     let schema = json!({
-        "$schema": "https://json-schema.org/draft-07/schema",
+        "$schema": "http://json-schema.org/draft-07/schema#",
         "title": format!("JSON Schema for {input_filename}"),
         "description": "Inferred JSON Schema from QSV schema command",
         "type": "object",
@@ -230,7 +229,7 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
         let header_string = convert_to_string(header_byte_slice)?;
 
         // grab stats record for current column
-        let stats_record = csv_stats.get(i).unwrap().clone().to_record(4);
+        let stats_record = csv_stats.get(i).unwrap();
 
         if log::log_enabled!(log::Level::Debug) {
             debug!("stats[{header_string}]: {stats_record:?}");
@@ -379,9 +378,22 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
     Ok(properties_map)
 }
 
-/// get stats records from `cmd::stats`
+// the stats columns used to infer the schema
+const SCHEMA_STATS_COLUMNS: [&str; 7] = [
+    "type",
+    "nullcount",
+    "min_length",
+    "max_length",
+    "min",
+    "max",
+    "cardinality",
+];
+
+/// get stats records from `cmd::stats`, reusing the stats cache of the input if possible
 /// returns tuple (`csv_fields`, `csv_stats`, `stats_col_index_map`)
-fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap<String, usize>)> {
+fn get_stats_records(
+    args: &Args,
+) -> CliResult<(ByteRecord, Vec<StringRecord>, AHashMap<String, usize>)> {
     let stats_args = crate::cmd::stats::Args {
         arg_input:            args.arg_input.clone(),
         flag_select:          crate::select::SelectColumns::parse("").unwrap(),
@@ -389,14 +401,25 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
         flag_typesonly:       false,
         flag_mode:            false,
         flag_cardinality:     true,
+        flag_uniqueness:      false,
         flag_median:          false,
         flag_quartiles:       false,
+        flag_percentiles:     None,
+        flag_histogram:       None,
+        flag_quantile_bins:   false,
+        flag_quality:         false,
+        flag_approx:          false,
         flag_mad:             false,
         flag_nulls:           false,
         flag_round:           4,
+        flag_decimal_comma:   false,
+        flag_weight:          None,
+        flag_groupby:         None,
         flag_infer_dates:     true,
         flag_dates_whitelist: args.flag_dates_whitelist.to_string(),
         flag_prefer_dmy:      args.flag_prefer_dmy,
+        flag_force:           false,
+        flag_cache:           false,
         flag_jobs:            Some(util::njobs(args.flag_jobs)),
         flag_output:          None,
         flag_no_headers:      args.flag_no_headers,
@@ -404,6 +427,13 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
         flag_no_memcheck:     args.flag_no_memcheck,
     };
 
+    if !args.flag_no_headers {
+        if let Some(cached) = cached_stats_records(&stats_args) {
+            info!("using the stats cache");
+            return Ok(cached);
+        }
+    }
+
     let (csv_fields, csv_stats) = match stats_args.rconfig().indexed() {
         Ok(o) => {
             if let Some(idx) = o {
@@ -420,6 +450,7 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
             stats_args.sequential_stats(&stats_args.flag_dates_whitelist)
         }
     }?;
+    let csv_stats = stats_args.stats_to_records(csv_stats);
 
     let stats_columns = stats_args.stat_headers();
     debug!("stats columns: {stats_columns:?}");
@@ -436,12 +467,42 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
     Ok((csv_fields, csv_stats, stats_col_index_map))
 }
 
+/// get the stats records from the stats cache of the input, if its still valid and
+/// has all the stats columns we need
+fn cached_stats_records(
+    stats_args: &crate::cmd::stats::Args,
+) -> Option<(ByteRecord, Vec<StringRecord>, AHashMap<String, usize>)> {
+    let (cached_headers, cached_stats) = stats_args.cached_stats()?;
+
+    let mut stats_col_index_map = AHashMap::new();
+    for (i, col) in cached_headers.iter().enumerate().skip(1) {
+        // need offset by 1 due to extra "field" column in headers that's not in stats records
+        stats_col_index_map.insert(String::from_utf8_lossy(col).to_string(), i - 1);
+    }
+    if !SCHEMA_STATS_COLUMNS
+        .iter()
+        .all(|col| stats_col_index_map.contains_key(*col))
+    {
+        return None;
+    }
+
+    let csv_fields: ByteRecord = cached_stats.iter().map(|stat| &stat[0]).collect();
+    let csv_stats = cached_stats
+        .iter()
+        .map(|stat| {
+            let stat_record: ByteRecord = stat.iter().skip(1).collect();
+            StringRecord::from_byte_record(stat_record).ok()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((csv_fields, csv_stats, stats_col_index_map))
+}
+
 /// get column selector argument string for low cardinality columns
 fn build_low_cardinality_column_selector_arg(
     low_cardinality_column_indices: &mut Vec<usize>,
     enum_cardinality_threshold: usize,
     csv_fields: &ByteRecord,
-    csv_stats: &[Stats],
+    csv_stats: &[StringRecord],
     stats_col_index_map: &AHashMap<String, usize>,
 ) -> String {
     low_cardinality_column_indices.clear();
@@ -449,7 +510,7 @@ fn build_low_cardinality_column_selector_arg(
     // identify low cardinality columns
     for i in 0..csv_fields.len() {
         // grab stats record for current column
-        let stats_record = csv_stats.get(i).unwrap().clone().to_record(4);
+        let stats_record = csv_stats.get(i).unwrap();
 
         // get Cardinality
         let col_cardinality = match stats_record.get(stats_col_index_map["cardinality"]) {
@@ -482,21 +543,31 @@ fn get_unique_values(
 ) -> CliResult<AHashMap<String, Vec<String>>> {
     // prepare arg for invoking cmd::frequency
     let freq_args = crate::cmd::frequency::Args {
-        arg_input:        args.arg_input.clone(),
-        flag_select:      crate::select::SelectColumns::parse(column_select_arg).unwrap(),
-        flag_limit:       args.flag_enum_threshold,
-        flag_asc:         false,
-        flag_no_nulls:    true,
-        flag_jobs:        Some(util::njobs(args.flag_jobs)),
-        flag_output:      None,
-        flag_no_headers:  args.flag_no_headers,
-        flag_delimiter:   args.flag_delimiter,
-        flag_no_memcheck: args.flag_no_memcheck,
+        arg_input:         args.arg_input.clone(),
+        flag_select:       crate::select::SelectColumns::parse(column_select_arg).unwrap(),
+        flag_limit:        args.flag_enum_threshold,
+        flag_other_label:  None,
+        flag_unq_limit:    0,
+        flag_asc:          false,
+        flag_ignore_case:  false,
+        flag_no_nulls:     true,
+        flag_combo:        false,
+        flag_weight:       None,
+        flag_pct:          false,
+        flag_cum_pct:      false,
+        flag_pct_decimals: 2,
+        flag_json:         false,
+        flag_pretty_json:  false,
+        flag_jobs:         Some(util::njobs(args.flag_jobs)),
+        flag_output:       None,
+        flag_no_headers:   args.flag_no_headers,
+        flag_delimiter:    args.flag_delimiter,
+        flag_no_memcheck:  args.flag_no_memcheck,
     };
 
     let (headers, ftables) = match freq_args.rconfig().indexed()? {
-        Some(ref mut idx) => freq_args.parallel_ftables(idx),
-        _ => freq_args.sequential_ftables(),
+        Some(ref mut idx) => freq_args.parallel_ftables(idx, &[]),
+        _ => freq_args.sequential_ftables(&[]),
     }?;
 
     let unique_values_map = construct_map_of_unique_values(&headers, &ftables)?;
@@ -0,0 +1,153 @@
+static USAGE: &str = r#"
+Compresses, decompresses or checks files in the Snappy frame format
+(https://github.com/google/snappy/blob/main/framing_format.txt).
+
+Snappy trades some compression ratio for speed, which makes it a good fit for
+keeping intermediate files in a qsv pipeline small. Compression is multithreaded,
+with the input compressed in 1 MiB chunks in parallel, which are written in order.
+
+It has three subcommands:
+ * compress   - compress the input to the Snappy frame format.
+ * decompress - decompress a Snappy-compressed input.
+ * check      - check if the input is a valid Snappy-compressed file.
+                Returns an error exit code if it's not.
+
+Examples:
+Compress data.csv to data.csv.sz.
+
+  $ qsv snappy compress data.csv -o data.csv.sz
+
+Decompress data.csv.sz and count its records.
+
+  $ qsv snappy decompress data.csv.sz | qsv count
+
+Check if data.csv.sz is a valid Snappy file.
+
+  $ qsv snappy check data.csv.sz
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_snappy.rs.
+
+Usage:
+    qsv snappy compress [options] [<input>]
+    qsv snappy decompress [options] [<input>]
+    qsv snappy check [<input>]
+    qsv snappy --help
+
+snappy arguments:
+    <input>                The file to (de)compress or check. If not given, reads from stdin.
+
+snappy options:
+    -j, --jobs <arg>       The number of jobs to run in parallel when compressing.
+                           When not set, the number of jobs is set to the
+                           number of CPUs detected.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+"#;
+
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+};
+
+use rayon::prelude::*;
+use serde::Deserialize;
+
+use crate::{util, CliResult};
+
+#[derive(Deserialize)]
+struct Args {
+    cmd_compress:   bool,
+    cmd_decompress: bool,
+    cmd_check:      bool,
+    arg_input:      Option<String>,
+    flag_jobs:      Option<usize>,
+    flag_output:    Option<String>,
+}
+
+/// The uncompressed size of the chunks compressed in parallel. It's a multiple of
+/// the 64 KiB blocks of the Snappy frame format, so the output is the same as when
+/// compressing the whole input as a single stream.
+const CHUNK_SIZE: usize = 16 * 65_536;
+
+/// The stream identifier that starts every Snappy frame stream.
+const STREAM_IDENTIFIER: &[u8] = b"\xff\x06\x00\x00sNaPpY";
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let mut input: Box<dyn Read> = match args.arg_input {
+        Some(ref input) => Box::new(BufReader::new(fs::File::open(input)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    let output: Box<dyn Write> = match args.flag_output {
+        Some(ref output) => Box::new(BufWriter::new(fs::File::create(output)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    if args.cmd_compress {
+        compress(input, output, util::njobs(args.flag_jobs))?;
+    } else if args.cmd_decompress {
+        let mut wtr = output;
+        io::copy(&mut snap::read::FrameDecoder::new(input), &mut wtr)?;
+        wtr.flush()?;
+    } else if args.cmd_check {
+        let input_name = args.arg_input.as_deref().unwrap_or("<stdin>");
+        if io::copy(&mut snap::read::FrameDecoder::new(input), &mut io::sink()).is_err() {
+            return fail_clierror!("{input_name} is not a valid Snappy-compressed file.");
+        }
+    }
+    Ok(())
+}
+
+/// Compresses batches of `jobs` chunks in parallel, writing the compressed chunks
+/// in the order of the input.
+fn compress(mut input: Box<dyn Read>, mut output: Box<dyn Write>, jobs: usize) -> io::Result<()> {
+    let mut batch: Vec<Vec<u8>> = Vec::with_capacity(jobs);
+    let mut compressed_batch = Vec::with_capacity(jobs);
+    let mut wrote_stream_identifier = false;
+    loop {
+        for _ in 0..jobs {
+            let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+            input
+                .by_ref()
+                .take(CHUNK_SIZE as u64)
+                .read_to_end(&mut chunk)?;
+            if chunk.is_empty() {
+                break;
+            }
+            batch.push(chunk);
+        }
+        if batch.is_empty() {
+            break;
+        }
+
+        batch
+            .par_iter()
+            .map(|chunk| -> io::Result<Vec<u8>> {
+                let mut frame = Vec::with_capacity(chunk.len() / 2);
+                let mut wtr = snap::write::FrameEncoder::new(&mut frame);
+                wtr.write_all(chunk)?;
+                wtr.flush()?;
+                drop(wtr);
+                Ok(frame)
+            })
+            .collect_into_vec(&mut compressed_batch);
+
+        // each chunk is compressed as a separate stream, so only keep its first
+        // stream identifier
+        for frame in compressed_batch.drain(..) {
+            let frame = frame?;
+            let frame = if wrote_stream_identifier {
+                &frame[STREAM_IDENTIFIER.len()..]
+            } else {
+                wrote_stream_identifier = true;
+                &frame[..]
+            };
+            output.write_all(frame)?;
+        }
+        batch.clear();
+    }
+    output.flush()
+}
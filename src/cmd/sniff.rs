@@ -11,15 +11,65 @@ to adjust).
 If you want more robust, guaranteed schemata, use the "schema" or "stats" commands
 instead as they scan the entire file.
 
+Inputs compressed with gzip, zstd or snappy (framed format) are transparently
+decompressed before sniffing. Only the sample is decompressed when --sample is a
+number of rows, so the number of records & the uncompressed size of a larger input
+are estimated. With a URL, only the sample is downloaded & decompressed, except for
+snappy, which is always downloaded in full. Compressed inputs can only be sniffed
+with the full qsv variant.
+
+Before sniffing, the file type is detected from its signature ("magic bytes").
+If the input is not a CSV (e.g. JSON, JSONL, Excel, Parquet or an archive), sniff
+fails with an error that reports the detected MIME type.
+
+Several inputs can be sniffed in one invocation, in parallel. Glob patterns
+(e.g. "data/*.csv") are expanded even when the shell doesn't. With --json, the
+results are then written as JSONL - one JSON object per input, with errors
+reported as {"path": ..., "errors": [...]}. With --csv, a summary CSV with one
+row per input is written instead. If any input could not be sniffed, sniff
+fails after reporting the results of the other inputs.
+
+With --harvest, sniff walks a directory tree and sniffs every tabular file found
+(by extension: csv, tsv, tab, ssv & txt, optionally compressed with gz, zst or sz),
+e.g. to inventory a shared drive. The results are written as a JSONL catalog
+(or a CSV catalog with --csv) that also has the last modified time of each file.
+Files that could not be sniffed are reported, but don't make harvest fail.
+
+With --cache, the results of local files are cached in $XDG_CACHE_HOME/qsv/sniff
+(by default, ~/.cache/qsv/sniff), keyed by the path, size & modification time of the
+file and the options that change the results. So sniffing an unchanged file again with
+--cache is instant. Use --force to sniff it again anyway.
+
+The --require-utf8, --require-header, --max-preamble & --delimiter-must-be options
+make sniff a lightweight pre-ingestion gate, e.g. in CI. The results are still
+reported, but sniff then fails, listing the requirements each input doesn't meet.
+
+With --schema-output, the sniffed fields & types are also written as a schema, so
+they can seed the "validate" & "schema" workflows. The types are mapped as follows:
+  Unsigned, Signed -> integer      Boolean  -> boolean
+  Float            -> number       Date     -> date (string with a date format)
+  Text             -> string       DateTime -> datetime (string with a date-time format)
+As sniff only samples the input, review the schema before relying on it.
+
+Objects in Amazon S3 (s3://bucket/path), Google Cloud Storage (gs://bucket/path) &
+Azure Blob Storage (az://container/path) can be sniffed too. Like with http(s) URLs,
+only the sample is downloaded, using ranged GETs. The credentials are read from the
+usual environment variables of each provider (e.g. AWS_ACCESS_KEY_ID,
+AWS_SECRET_ACCESS_KEY & AWS_REGION, GOOGLE_SERVICE_ACCOUNT, or
+AZURE_STORAGE_ACCOUNT_NAME & AZURE_STORAGE_ACCOUNT_KEY).
+Sniffing these requires qsv to be built with the sniff_cloud feature.
+
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_sniff.rs.
 
 Usage:
-    qsv sniff [options] [<input>]
+    qsv sniff [options] [--http-header <k:v>...] [<input>...]
     qsv sniff --help
 
 sniff arguments:
-    <input>                  The CSV to sniff. This can be a local file, stdin 
-                             or a URL (http and https schemes supported).
+    <input>                  The CSV(s) to sniff. This can be a local file, stdin 
+                             or a URL (http, https, s3, gs and az schemes supported).
+                             Several inputs may be given, as well as glob
+                             patterns with the full qsv variant.
 
                              Note that when input is a URL, sniff will automatically
                              download the file to a temporary file and sniff it. It
@@ -28,6 +78,12 @@ sniff arguments:
                              This is done to increase the chances of sniffing the
                              correct schema.
 
+                             For http(s) URLs, only the sample is requested, using
+                             HTTP range requests sized from --sample, so the retrieved
+                             size is about the size of the sample. If the server
+                             doesn't support range requests, the file is streamed
+                             until there are enough samples.
+
 sniff options:
     --sample <size>          First n rows to sample to sniff out the metadata.
                              When sample size is between 0 and 1 exclusive, 
@@ -37,13 +93,57 @@ sniff options:
                              When the input is a URL, the sample size dictates
                              how many lines to sample without having to
                              download the entire file.
+                             Likewise, only the sample is read from stdin. If stdin
+                             has more rows than the sample, the number of records
+                             is estimated & the file size is the size read.
                              [default: 1000]
+    --confidence             Also report how confident sniff is about the detected
+                             delimiter & header row (from 0 to 1), and the other
+                             delimiters that could split the sampled records, with
+                             their scores. A delimiter scores higher when it splits
+                             more records into the same number of fields.
+    --stats-types            Also report the number of nulls (empty values) and
+                             up to 5 distinct sample values of each field, as
+                             observed in the sampled records.
+    --quick                  Only sniff the dialect: the delimiter, quote char,
+                             header row, preamble rows & flexibility. The rows are
+                             not counted & the fields & types are not reported,
+                             so it returns quickly even on huge files.
+                             --sample can't be a percentage with --quick.
     --prefer-dmy             Prefer to parse dates in dmy format.
                              Otherwise, use mdy format.
     --json                   Return results in JSON format.
     --pretty-json            Return results in pretty JSON format.
+    --csv                    Return results as a CSV, with one row per input.
+                             The fields & types are separated by '|'.
     --save-urlsample <file>  Save the URL sample to a file.
-                             Valid only when input is a URL.
+                             Valid only when the single input is a URL.
+    --schema-output <file>   Write the sniffed fields & types as a schema to <file>.
+                             Valid only with a single input.
+    --schema-format <fmt>    The format of the schema written with --schema-output.
+                             Either "jsonschema" (JSON Schema Draft 7) or
+                             "tableschema" (Frictionless Table Schema).
+                             [default: jsonschema]
+    --harvest <dir>          Sniff all the tabular files in <dir> & its
+                             subdirectories, writing a catalog of the results.
+    --require-utf8           Fail if the input is not UTF-8.
+    --require-header         Fail if the input has no header row.
+    --max-preamble <n>       Fail if the input has more than <n> preamble rows.
+    --delimiter-must-be <c>  Fail if the sniffed delimiter is not <c>.
+    --cache                  Use & update the cache of sniff results.
+                             Failing to write the cache is not an error.
+    --force                  With --cache, sniff again even if the results are cached.
+                             The cache is then updated.
+    --user-agent <agent>     Specify a custom user agent for http(s) requests.
+    -H, --http-header <k:v>  Append a custom header to http(s) requests. Pass multiple
+                             key-value pairs by adding this option multiple times.
+                             The key and value should be separated by a colon.
+    --basic-auth <user:pw>   Authenticate http(s) requests with HTTP basic auth.
+    --bearer-token <token>   Authenticate http(s) requests with a bearer token.
+    --proxy <url>            The proxy to use for http(s) requests.
+                             (e.g. http://proxy:3128 or https://user:pw@proxy:443)
+                             If not set, the HTTP_PROXY, HTTPS_PROXY & NO_PROXY
+                             env vars are honored.
     --timeout <secs>         Timeout for URL requests in seconds.
                              [default: 30]
 
@@ -57,14 +157,32 @@ Common options:
     -p, --progressbar        Show progress bars. Only valid for URL input.
 "#;
 
-use std::{cmp::min, fmt, fs, io::Write, time::Duration};
+use std::{
+    cell::Cell,
+    cmp::min,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
 
-use bytes::Bytes;
 use futures::executor::block_on;
 use futures_util::StreamExt;
 use indicatif::{HumanCount, ProgressBar, ProgressDrawTarget, ProgressStyle};
+#[cfg(feature = "sniff_cloud")]
+use object_store::{
+    aws::AmazonS3Builder, azure::MicrosoftAzureBuilder, gcp::GoogleCloudStorageBuilder,
+    path::Path as ObjectPath, ObjectStore,
+};
 use qsv_sniffer::{DatePreference, SampleSize, Sniffer};
-use reqwest::Client;
+use rayon::prelude::*;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_RANGE, RANGE},
+    Client, Proxy, RequestBuilder, StatusCode,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tabwriter::TabWriter;
@@ -74,46 +192,130 @@ use url::Url;
 
 use crate::{
     config::{Config, Delimiter},
-    util, CliResult,
+    util, CliError, CliResult,
 };
 
 #[derive(Deserialize)]
 struct Args {
-    arg_input:           Option<String>,
-    flag_sample:         f64,
-    flag_prefer_dmy:     bool,
-    flag_json:           bool,
-    flag_save_urlsample: Option<String>,
-    flag_pretty_json:    bool,
-    flag_delimiter:      Option<Delimiter>,
-    flag_progressbar:    bool,
-    flag_timeout:        u64,
+    arg_input:              Vec<String>,
+    flag_sample:            f64,
+    flag_prefer_dmy:        bool,
+    flag_json:              bool,
+    flag_save_urlsample:    Option<String>,
+    flag_pretty_json:       bool,
+    flag_csv:               bool,
+    flag_harvest:           Option<String>,
+    flag_quick:             bool,
+    flag_stats_types:       bool,
+    flag_confidence:        bool,
+    flag_require_utf8:      bool,
+    flag_require_header:    bool,
+    flag_max_preamble:      Option<usize>,
+    flag_delimiter_must_be: Option<Delimiter>,
+    flag_cache:             bool,
+    flag_force:             bool,
+    flag_user_agent:        Option<String>,
+    flag_http_header:       Vec<String>,
+    flag_basic_auth:        Option<String>,
+    flag_bearer_token:      Option<String>,
+    flag_proxy:             Option<String>,
+    flag_schema_output:     Option<String>,
+    flag_schema_format:     String,
+    flag_delimiter:         Option<Delimiter>,
+    flag_progressbar:       bool,
+    flag_timeout:           u64,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 struct SniffStruct {
-    path:            String,
-    sniff_timestamp: String,
-    delimiter_char:  char,
-    header_row:      bool,
-    preamble_rows:   usize,
-    quote_char:      String,
-    flexible:        bool,
-    is_utf8:         bool,
-    retrieved_size:  usize,
-    file_size:       usize,
-    sampled_records: usize,
-    estimated:       bool,
-    num_records:     usize,
-    avg_record_len:  usize,
-    num_fields:      usize,
-    fields:          Vec<String>,
-    types:           Vec<String>,
+    path:              String,
+    sniff_timestamp:   String,
+    detected_mime:     String,
+    compression:       String,
+    uncompressed_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified:     Option<String>,
+    delimiter_char:    char,
+    header_row:        bool,
+    preamble_rows:     usize,
+    quote_char:        String,
+    flexible:          bool,
+    is_utf8:           bool,
+    retrieved_size:    usize,
+    file_size:         usize,
+    sampled_records:   usize,
+    estimated:         bool,
+    num_records:       usize,
+    avg_record_len:    usize,
+    num_fields:        usize,
+    fields:            Vec<String>,
+    types:             Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    null_counts:       Option<Vec<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sample_values:     Option<Vec<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence:        Option<Confidence>,
+    #[serde(skip)]
+    quick:             bool,
+}
+
+/// How confident sniff is about the dialect it detected, from 0 to 1.
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct Confidence {
+    delimiter:  f64,
+    header:     f64,
+    /// the other delimiters that could split the sample, best first
+    candidates: Vec<DialectCandidate>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct DialectCandidate {
+    delimiter: char,
+    score:     f64,
+}
+
+/// The results reported with --quick, as only the dialect is sniffed.
+const QUICK_FIELDS: [&str; 12] = [
+    "path",
+    "sniff_timestamp",
+    "detected_mime",
+    "compression",
+    "last_modified",
+    "delimiter_char",
+    "header_row",
+    "preamble_rows",
+    "quote_char",
+    "flexible",
+    "is_utf8",
+    "confidence",
+];
+
+impl SniffStruct {
+    /// The results as JSON. With --quick, only the dialect is kept.
+    fn to_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let (true, serde_json::Value::Object(map)) = (self.quick, &mut value) {
+            map.retain(|key, _| QUICK_FIELDS.contains(&key.as_str()));
+        }
+        value
+    }
 }
+
 impl fmt::Display for SniffStruct {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "Path: {}", self.path)?;
         writeln!(f, "Sniff Timestamp: {}", self.sniff_timestamp)?;
+        writeln!(f, "Detected MIME: {}", self.detected_mime)?;
+        writeln!(f, "Compression: {}", self.compression)?;
+        writeln!(
+            f,
+            "Uncompressed Size (bytes): {}",
+            self.uncompressed_size.separate_with_commas()
+        )?;
+        if let Some(ref last_modified) = self.last_modified {
+            writeln!(f, "Last Modified: {last_modified}")?;
+        }
         writeln!(
             f,
             "Delimiter: {}",
@@ -132,6 +334,26 @@ impl fmt::Display for SniffStruct {
         writeln!(f, "Quote Char: {}", self.quote_char)?;
         writeln!(f, "Flexible: {}", self.flexible)?;
         writeln!(f, "Is UTF8: {}", self.is_utf8)?;
+        if let Some(ref confidence) = self.confidence {
+            writeln!(f, "Delimiter Confidence: {}", confidence.delimiter)?;
+            writeln!(f, "Header Confidence: {}", confidence.header)?;
+            let candidates = confidence
+                .candidates
+                .iter()
+                .map(|candidate| {
+                    let delimiter = if candidate.delimiter == '\t' {
+                        "tab".to_string()
+                    } else {
+                        format!("{:?}", candidate.delimiter)
+                    };
+                    format!("{delimiter} ({})", candidate.score)
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "Candidate Delimiters: {}", candidates.join(", "))?;
+        }
+        if self.quick {
+            return Ok(());
+        }
         writeln!(
             f,
             "Retrieved Size (bytes): {}",
@@ -164,7 +386,7 @@ impl fmt::Display for SniffStruct {
         let mut tabwtr = TabWriter::new(vec![]);
 
         for (i, ty) in self.types.iter().enumerate() {
-            writeln!(
+            write!(
                 &mut tabwtr,
                 "\t{}:\t{}\t{}",
                 i,
@@ -172,6 +394,21 @@ impl fmt::Display for SniffStruct {
                 self.fields.get(i).unwrap_or(&String::new())
             )
             .unwrap_or_default();
+            if let (Some(null_counts), Some(sample_values)) =
+                (&self.null_counts, &self.sample_values)
+            {
+                write!(
+                    &mut tabwtr,
+                    "\tnulls: {}\tsamples: {}",
+                    null_counts.get(i).copied().unwrap_or_default(),
+                    sample_values
+                        .get(i)
+                        .map(|v| v.join(", "))
+                        .unwrap_or_default()
+                )
+                .unwrap_or_default();
+            }
+            writeln!(&mut tabwtr).unwrap_or_default();
         }
         tabwtr.flush().unwrap();
 
@@ -190,6 +427,615 @@ struct SniffFileStruct {
     retrieved_size:     usize,
     file_size:          usize,
     downloaded_records: usize,
+    detected_mime:      &'static str,
+    compression:        Compression,
+    uncompressed_size:  usize,
+}
+
+// without the full feature, compressed inputs are not decompressed, so only None is used
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(feature = "full"), allow(dead_code))]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Snappy,
+}
+
+impl Compression {
+    fn from_mime(mime: &str) -> Compression {
+        match mime {
+            #[cfg(feature = "full")]
+            "application/gzip" => Compression::Gzip,
+            #[cfg(feature = "full")]
+            "application/zstd" => Compression::Zstd,
+            #[cfg(feature = "full")]
+            "application/x-snappy-framed" => Compression::Snappy,
+            _ => Compression::None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Snappy => "snappy",
+        }
+    }
+
+    fn decoder<R: Read + 'static>(self, reader: R) -> io::Result<Box<dyn Read>> {
+        Ok(match self {
+            #[cfg(feature = "full")]
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            #[cfg(feature = "full")]
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+            #[cfg(feature = "full")]
+            Compression::Snappy => Box::new(snap::read::FrameDecoder::new(reader)),
+            #[cfg(not(feature = "full"))]
+            Compression::Gzip | Compression::Zstd | Compression::Snappy => {
+                unreachable!("compressed inputs are only detected in the full qsv variant")
+            }
+            Compression::None => Box::new(reader),
+        })
+    }
+
+    /// Decompresses a whole file to a new temporary file, returning its path & size.
+    fn decompress_file(self, path: &Path) -> CliResult<(String, usize)> {
+        let mut decoder = self.decoder(fs::File::open(path)?)?;
+        let (mut decompressed, decompressed_path) = NamedTempFile::new()?
+            .keep()
+            .or(Err("Cannot keep temporary file".to_string()))?;
+        let size = io::copy(&mut decoder, &mut decompressed).map_err(|e| {
+            let _ = fs::remove_file(&decompressed_path);
+            format!("Cannot decompress {} input: {e}", self.name())
+        })?;
+        Ok((
+            decompressed_path.to_string_lossy().to_string(),
+            size as usize,
+        ))
+    }
+}
+
+/// Counts the bytes read from a compressed file, so we know how much of it
+/// was decompressed into the sample.
+struct ReadCounter {
+    file:  fs::File,
+    bytes: Rc<Cell<usize>>,
+}
+
+impl Read for ReadCounter {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.file.read(buf)?;
+        self.bytes.set(self.bytes.get() + n);
+        Ok(n)
+    }
+}
+
+/// Counts the lines & bytes of the (decompressed) URL sample as it's written,
+/// so the download can stop once there are enough lines.
+struct LineCounter {
+    file:  fs::File,
+    lines: usize,
+    bytes: usize,
+}
+
+impl Write for LineCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.file.write(buf)?;
+        self.lines += buf[..n].iter().filter(|&&b| b == b'\n').count();
+        self.bytes += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writes the downloaded chunks of a URL sample, decompressing them on the fly.
+/// Snappy has no streaming decompressor for writes, so it's written as is.
+enum SampleWriter {
+    Plain(LineCounter),
+    #[cfg(feature = "full")]
+    Gzip(flate2::write::MultiGzDecoder<LineCounter>),
+    #[cfg(feature = "full")]
+    Zstd(zstd::stream::write::Decoder<'static, LineCounter>),
+}
+
+impl SampleWriter {
+    fn new(compression: Compression, counter: LineCounter) -> io::Result<SampleWriter> {
+        Ok(match compression {
+            #[cfg(feature = "full")]
+            Compression::Gzip => SampleWriter::Gzip(flate2::write::MultiGzDecoder::new(counter)),
+            #[cfg(feature = "full")]
+            Compression::Zstd => SampleWriter::Zstd(zstd::stream::write::Decoder::new(counter)?),
+            _ => SampleWriter::Plain(counter),
+        })
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            SampleWriter::Plain(w) => w.write_all(buf),
+            #[cfg(feature = "full")]
+            SampleWriter::Gzip(w) => w.write_all(buf),
+            #[cfg(feature = "full")]
+            SampleWriter::Zstd(w) => w.write_all(buf),
+        }
+    }
+
+    fn counter(&self) -> &LineCounter {
+        match self {
+            SampleWriter::Plain(w) => w,
+            #[cfg(feature = "full")]
+            SampleWriter::Gzip(w) => w.get_ref(),
+            #[cfg(feature = "full")]
+            SampleWriter::Zstd(w) => w.get_ref(),
+        }
+    }
+
+    /// Flushes what could be decompressed. As the download usually stops mid-stream,
+    /// errors about the truncated input are expected and ignored.
+    fn finish(mut self) -> (usize, usize) {
+        let _ = match self {
+            SampleWriter::Plain(ref mut w) => w.flush(),
+            #[cfg(feature = "full")]
+            SampleWriter::Gzip(ref mut w) => w.flush(),
+            #[cfg(feature = "full")]
+            SampleWriter::Zstd(ref mut w) => w.flush(),
+        };
+        let counter = self.counter();
+        (counter.lines, counter.bytes)
+    }
+}
+
+/// A URL or stdin sample being downloaded to a temporary file, decompressing it on the fly
+/// and counting its lines so the download can stop once there are enough samples.
+struct SampleDownload {
+    sample_path:       String,
+    counter:           Option<LineCounter>,
+    writer:            Option<SampleWriter>,
+    compression:       Compression,
+    downloaded:        usize,
+    total_size:        usize,
+    lines_sample_size: usize,
+    progress:          ProgressBar,
+    show_progress:     bool,
+}
+
+impl SampleDownload {
+    /// total_size is usize::MAX when it's not known.
+    fn new(args: &Args, total_size: usize, show_progress: bool) -> CliResult<SampleDownload> {
+        #[allow(clippy::cast_precision_loss)]
+        let lines_sample_size = if args.flag_sample > 1.0 {
+            args.flag_sample.round() as usize
+        } else if args.flag_sample.abs() < f64::EPSILON {
+            // sample size is zero, so we want to download the entire file
+            usize::MAX
+        } else {
+            // sample size is a percentage, download percentage number of lines
+            // from the file. Since we don't know how wide the lines are, we
+            // just download a percentage of the bytes, assuming the lines are
+            // 100 characters wide as a rough estimate.
+            ((total_size / 100_usize) as f64 * args.flag_sample) as usize
+        };
+
+        // prep progress bar
+        let progress = ProgressBar::with_draw_target(
+            Some(total_size.try_into().unwrap_or(u64::MAX)),
+            ProgressDrawTarget::stderr_with_hz(5),
+        );
+        if show_progress {
+            progress.set_style(
+                ProgressStyle::default_bar()
+                    .template(
+                        "{msg}\n{spinner:.green} [{elapsed_precise}] [{wide_bar:.white/blue}] \
+                         {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+                    )
+                    .unwrap(),
+            );
+            progress.set_message(format!(
+                "Downloading {} samples...",
+                HumanCount(lines_sample_size as u64)
+            ));
+        } else {
+            progress.set_draw_target(ProgressDrawTarget::hidden());
+        }
+
+        // keep the temporary file around, we'll delete it when we're done
+        let (sample_file, sample_path) = NamedTempFile::new()?
+            .keep()
+            .or(Err("Cannot keep temporary file".to_string()))?;
+
+        Ok(SampleDownload {
+            sample_path: sample_path.to_string_lossy().to_string(),
+            counter: Some(LineCounter {
+                file:  sample_file,
+                lines: 0,
+                bytes: 0,
+            }),
+            writer: None,
+            compression: Compression::None,
+            downloaded: 0,
+            total_size,
+            lines_sample_size,
+            progress,
+            show_progress,
+        })
+    }
+
+    /// Writes a downloaded chunk. Returns true once we downloaded enough samples.
+    fn write_chunk(&mut self, chunk: &[u8]) -> CliResult<bool> {
+        if self.writer.is_none() {
+            // the first chunk tells us if the file is compressed
+            self.compression = Compression::from_mime(detect_mime_bytes(chunk));
+            // safety: the counter is only taken once, with the first chunk
+            self.writer = Some(SampleWriter::new(
+                self.compression,
+                self.counter.take().unwrap(),
+            )?);
+        }
+        // safety: the writer was set above
+        let sample_writer = self.writer.as_mut().unwrap();
+        sample_writer
+            .write_all(chunk)
+            .map_err(|e| format!("Error while writing to file: {e}"))?;
+        self.downloaded = min(self.downloaded + chunk.len(), self.total_size);
+        if self.show_progress {
+            self.progress.inc(chunk.len() as u64);
+        }
+
+        // the lines of a snappy file can only be counted once it's decompressed
+        Ok(self.compression != Compression::Snappy
+            && sample_writer.counter().lines > self.lines_sample_size)
+    }
+
+    /// Prepares the downloaded sample for sniffing. complete is true when the entire
+    /// input was downloaded, so its rows can be counted instead of estimated.
+    fn finish(self, args: &Args, url: String, complete: bool) -> CliResult<SniffFileStruct> {
+        let SampleDownload {
+            mut sample_path,
+            writer,
+            compression,
+            downloaded,
+            total_size,
+            lines_sample_size,
+            progress,
+            show_progress,
+            ..
+        } = self;
+        let (mut downloaded_lines, mut decompressed) = writer.map_or((0, 0), SampleWriter::finish);
+
+        if compression == Compression::Snappy {
+            let decompressed_file = compression.decompress_file(Path::new(&sample_path));
+            fs::remove_file(&sample_path)?;
+            (sample_path, decompressed) = decompressed_file?;
+            downloaded_lines = count_lines(Path::new(&sample_path))?;
+        }
+
+        let file_size = if total_size == usize::MAX {
+            // the server didn't give us content length, so we just
+            // downloaded the entire file
+            downloaded
+        } else {
+            total_size
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let uncompressed_size = if compression == Compression::None {
+            file_size
+        } else if downloaded >= file_size {
+            decompressed
+        } else {
+            // estimate it from the compression ratio of the sample
+            (file_size as f64 * decompressed as f64 / downloaded.max(1) as f64) as usize
+        };
+
+        let detected_mime = detect_mime(Path::new(&sample_path))?;
+        if detected_mime != CSV_MIME || complete {
+            // not a CSV, don't bother preparing a sample to sniff.
+            // Or we have the entire input, so there's no sample to prepare.
+            return Ok(SniffFileStruct {
+                display_path: url,
+                file_to_sniff: sample_path,
+                tempfile_flag: true,
+                retrieved_size: downloaded,
+                file_size,
+                downloaded_records: 0,
+                detected_mime,
+                compression,
+                uncompressed_size,
+            });
+        }
+
+        // we subtract 1 because we don't want to count the header row
+        downloaded_lines = downloaded_lines.saturating_sub(1);
+
+        if show_progress {
+            progress.finish_with_message(format!(
+                "Downloaded {} samples.",
+                HumanCount(downloaded_lines as u64)
+            ));
+        }
+
+        // now we downloaded the file, rewrite it so we only have the exact sample size
+        // and truncate potentially incomplete lines. We streamed the download
+        // and the downloaded file may be more than the sample size, and the final
+        // line may be incomplete
+        let config = Config::new(&Some(sample_path.clone()))
+            .delimiter(args.flag_delimiter)
+            // we say no_headers so we can just copy the downloaded file over
+            // including headers, to the exact sanple size file
+            .no_headers(true)
+            .flexible(true);
+
+        let mut rdr = config.reader()?;
+        let wtr_file = NamedTempFile::new()?;
+
+        // keep the temporary file around so we can sniff it later
+        // we'll delete it when we're done
+        let (_file, path) = wtr_file
+            .keep()
+            .or(Err("Cannot keep temporary file".to_string()))?;
+        let wtr_file_path = path.to_str().unwrap().to_string();
+
+        let mut wtr = Config::new(&Some(wtr_file_path.clone()))
+            .no_headers(false)
+            .flexible(true)
+            .quote_style(csv::QuoteStyle::NonNumeric)
+            .writer()?;
+        let mut downloaded_records = 0_usize;
+
+        // amortize allocation
+        #[allow(unused_assignments)]
+        let mut record = csv::ByteRecord::with_capacity(100, 20);
+
+        let header_row = rdr.byte_headers()?;
+        wtr.write_byte_record(header_row)?;
+        rdr.byte_records().next();
+
+        for rec in rdr.byte_records() {
+            record = rec?;
+            if downloaded_records >= lines_sample_size {
+                break;
+            }
+            downloaded_records += 1;
+            wtr.write_byte_record(&record)?;
+        }
+        wtr.flush()?;
+        drop(rdr);
+        fs::remove_file(&sample_path)?;
+
+        Ok(SniffFileStruct {
+            display_path: url,
+            file_to_sniff: wtr_file_path,
+            tempfile_flag: true,
+            retrieved_size: downloaded,
+            file_size,
+            downloaded_records,
+            detected_mime,
+            compression,
+            uncompressed_size,
+        })
+    }
+}
+
+/// The size of the first range downloaded from an object store, or from an http(s)
+/// URL when the sample size is a percentage. The following ranges double in size,
+/// up to MAX_RANGE_CHUNK_SIZE.
+const RANGE_CHUNK_SIZE: usize = 1024 * 1024;
+const MAX_RANGE_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+/// The first range of an http(s) URL is sized from the sample size,
+/// assuming records of about ESTIMATED_RECORD_LEN bytes.
+const ESTIMATED_RECORD_LEN: usize = 100;
+const MIN_RANGE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The size of the first range to get from an http(s) URL,
+/// or None when the sample size is zero, as the entire file is then needed.
+fn initial_range_size(args: &Args) -> Option<usize> {
+    if args.flag_sample > 1.0 {
+        // one more record for the header row
+        let records = args.flag_sample.round() as usize + 1;
+        Some(
+            records
+                .saturating_mul(ESTIMATED_RECORD_LEN)
+                .clamp(MIN_RANGE_CHUNK_SIZE, MAX_RANGE_CHUNK_SIZE),
+        )
+    } else if args.flag_sample.abs() < f64::EPSILON {
+        None
+    } else {
+        // the sample size is a percentage of the file size, which we don't know yet
+        Some(RANGE_CHUNK_SIZE)
+    }
+}
+
+/// Builds the http(s) client with the --user-agent, --http-header & --proxy options.
+/// Without --proxy, the HTTP_PROXY, HTTPS_PROXY & NO_PROXY env vars are honored.
+fn http_client(args: &Args) -> CliResult<Client> {
+    let user_agent = match args.flag_user_agent {
+        Some(ref ua) => match HeaderValue::from_str(ua) {
+            Ok(_) => ua.clone(),
+            Err(e) => return fail_clierror!("Invalid user-agent value: {e}"),
+        },
+        None => util::DEFAULT_USER_AGENT.to_string(),
+    };
+
+    let mut http_headers = HeaderMap::with_capacity(args.flag_http_header.len());
+    for header in &args.flag_http_header {
+        let Some((key, value)) = header.split_once(':') else {
+            return fail_clierror!(
+                "{header:?} is not a valid key-value pair. Expecting a key and a value separated \
+                 by a colon."
+            );
+        };
+        let header_name = match HeaderName::from_bytes(key.trim().as_bytes()) {
+            Ok(h) => h,
+            Err(e) => return fail_clierror!("Invalid header name: {e}"),
+        };
+        let header_val = match HeaderValue::from_str(value.trim()) {
+            Ok(v) => v,
+            Err(e) => return fail_clierror!("Invalid header value: {e}"),
+        };
+        http_headers.append(header_name, header_val);
+    }
+
+    let mut builder = Client::builder()
+        .user_agent(user_agent)
+        .default_headers(http_headers)
+        .brotli(true)
+        .gzip(true)
+        .deflate(true)
+        .use_rustls_tls()
+        .http2_adaptive_window(true);
+    if let Some(ref proxy) = args.flag_proxy {
+        match Proxy::all(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => return fail_clierror!("Invalid proxy {proxy}: {e}"),
+        }
+    }
+    match builder.build() {
+        Ok(c) => Ok(c),
+        Err(e) => fail_clierror!("Cannot build reqwest client: {e}."),
+    }
+}
+
+/// Adds the --basic-auth or --bearer-token credentials to a request.
+fn with_auth(request: RequestBuilder, args: &Args) -> RequestBuilder {
+    if let Some(ref token) = args.flag_bearer_token {
+        request.bearer_auth(token)
+    } else if let Some(ref credentials) = args.flag_basic_auth {
+        match credentials.split_once(':') {
+            Some((user, password)) => request.basic_auth(user, Some(password)),
+            None => request.basic_auth(credentials, None::<&str>),
+        }
+    } else {
+        request
+    }
+}
+
+/// The size of the chunks read from stdin.
+const STDIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Whether to show the progress of URL downloads.
+fn show_progress(args: &Args) -> bool {
+    args.flag_progressbar || std::env::var("QSV_PROGRESSBAR").is_ok()
+}
+
+fn is_object_store_url(url: &str) -> bool {
+    Url::parse(url).map_or(false, |url| matches!(url.scheme(), "s3" | "gs" | "az"))
+}
+
+/// Builds the object store of an s3://, gs:// or az:// URL. The credentials & settings
+/// are read from the usual environment variables of each cloud provider.
+#[cfg(feature = "sniff_cloud")]
+fn object_store_for(url: &Url) -> CliResult<(Box<dyn ObjectStore>, ObjectPath)> {
+    let store: Box<dyn ObjectStore> = match url.scheme() {
+        "s3" => Box::new(
+            AmazonS3Builder::from_env()
+                .with_url(url.as_str())
+                .build()
+                .map_err(|e| format!("Cannot access '{url}': {e}"))?,
+        ),
+        "gs" => Box::new(
+            GoogleCloudStorageBuilder::from_env()
+                .with_url(url.as_str())
+                .build()
+                .map_err(|e| format!("Cannot access '{url}': {e}"))?,
+        ),
+        "az" => Box::new(
+            MicrosoftAzureBuilder::from_env()
+                .with_url(url.as_str())
+                .build()
+                .map_err(|e| format!("Cannot access '{url}': {e}"))?,
+        ),
+        scheme => return fail_clierror!("Unsupported object store scheme: {scheme}"),
+    };
+    let path = ObjectPath::from_url_path(url.path())
+        .map_err(|e| format!("Invalid object path in '{url}': {e}"))?;
+    Ok((store, path))
+}
+
+const CSV_MIME: &str = "text/csv";
+const ODS_MIME: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// Detects the MIME type of a file from its signature ("magic bytes"), so that
+/// non-CSV files can be reported as such instead of being sniffed as CSVs.
+fn detect_mime(path: &Path) -> io::Result<&'static str> {
+    let mut buf = Vec::with_capacity(8192);
+    fs::File::open(path)?.take(8192).read_to_end(&mut buf)?;
+    Ok(detect_mime_bytes(&buf))
+}
+
+fn detect_mime_bytes(buf: &[u8]) -> &'static str {
+    match buf {
+        [0x50, 0x4B, 0x03, 0x04, ..] => {
+            // xlsx files are zip archives whose entries are in the xl directory
+            if buf.windows(3).any(|w| w == b"xl/") {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            } else if buf
+                .windows(ODS_MIME.len())
+                .any(|w| w == ODS_MIME.as_bytes())
+            {
+                // ods files store their MIME type uncompressed, as their first entry
+                ODS_MIME
+            } else {
+                "application/zip"
+            }
+        }
+        [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1, ..] => "application/vnd.ms-excel",
+        [b'P', b'A', b'R', b'1', ..] => "application/vnd.apache.parquet",
+        [b'A', b'R', b'R', b'O', b'W', b'1', ..] => "application/vnd.apache.arrow.file",
+        [0x1F, 0x8B, ..] => "application/gzip",
+        [0x28, 0xB5, 0x2F, 0xFD, ..] => "application/zstd",
+        [0xFF, 0x06, 0x00, 0x00, b's', b'N', b'a', b'P', b'p', b'Y', ..] => {
+            "application/x-snappy-framed"
+        }
+        [b'B', b'Z', b'h', ..] => "application/x-bzip2",
+        [0xFD, b'7', b'z', b'X', b'Z', 0x00, ..] => "application/x-xz",
+        [b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C, ..] => "application/x-7z-compressed",
+        [b'%', b'P', b'D', b'F', b'-', ..] => "application/pdf",
+        _ if buf.starts_with(b"SQLite format 3\0") => "application/vnd.sqlite3",
+        _ if buf.contains(&0) => "application/octet-stream",
+        _ => detect_text_mime(buf),
+    }
+}
+
+fn count_lines(path: &Path) -> io::Result<usize> {
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0_u8; 64 * 1024];
+    let mut lines = 0;
+    loop {
+        match file.read(&mut buf)? {
+            0 => return Ok(lines),
+            n => lines += buf[..n].iter().filter(|&&b| b == b'\n').count(),
+        }
+    }
+}
+
+/// Tells JSON & JSONL apart from CSV, as they are also text.
+/// The text is only JSON if it parses as JSON, up to the end of the (possibly truncated)
+/// sample, so CSVs whose first header starts with a bracket (e.g. `[id],name`) are CSVs.
+fn detect_text_mime(buf: &[u8]) -> &'static str {
+    let text = String::from_utf8_lossy(buf);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    if !text.starts_with('[') && !text.starts_with('{') {
+        return CSV_MIME;
+    }
+
+    // JSONL has one value per line, while a JSON value can span several lines
+    let mut values = 0;
+    for value in serde_json::Deserializer::from_str(text).into_iter::<serde::de::IgnoredAny>() {
+        match value {
+            Ok(_) => values += 1,
+            // the sample ends mid-value
+            Err(e) if e.is_eof() => break,
+            Err(_) => return CSV_MIME,
+        }
+    }
+    if values > 1 {
+        "application/x-ndjson"
+    } else {
+        "application/json"
+    }
 }
 
 const fn rowcount(
@@ -203,7 +1049,7 @@ const fn rowcount(
         // didn't provide a Content-Length header, so we estimate the rowcount by
         // dividing the file_size by avg_rec_len
         estimated = true;
-        sniff_file_info.file_size / metadata.avg_record_len
+        sniff_file_info.uncompressed_size / metadata.avg_record_len
     } else {
         count
     };
@@ -220,33 +1066,74 @@ const fn rowcount(
     (final_rowcount, estimated)
 }
 
-async fn get_file_to_sniff(args: &Args) -> CliResult<SniffFileStruct> {
-    if let Some(uri) = args.arg_input.clone() {
+async fn get_file_to_sniff(args: &Args, input: Option<String>) -> CliResult<SniffFileStruct> {
+    if let Some(uri) = input {
         match uri {
             // its a URL, download sample to temp file
             url if Url::parse(&url).is_ok() && url.starts_with("http") => {
-                let client = match Client::builder()
-                    .user_agent(util::DEFAULT_USER_AGENT)
-                    .brotli(true)
-                    .gzip(true)
-                    .deflate(true)
-                    .use_rustls_tls()
-                    .http2_adaptive_window(true)
-                    .build()
-                {
-                    Ok(c) => c,
-                    Err(e) => {
-                        return fail_clierror!("Cannot build reqwest client: {e}.");
-                    }
-                };
-
-                let res = client
-                    .get(url.clone())
-                    .timeout(Duration::from_secs(args.flag_timeout))
+                let client = http_client(args)?;
+
+                // ask for a range sized from the sample size. If the server doesn't
+                // support range requests, it sends the whole file & we stop streaming it
+                // once we have enough samples.
+                let mut range_size = initial_range_size(args);
+                let mut request = with_auth(client.get(url.clone()), args)
+                    .timeout(Duration::from_secs(args.flag_timeout));
+                if let Some(range_size) = range_size {
+                    request = request.header(RANGE, format!("bytes=0-{}", range_size - 1));
+                }
+                let mut res = request
                     .send()
                     .await
                     .or(Err(format!("Failed to GET from '{url}'")))?;
 
+                if res.status() == StatusCode::PARTIAL_CONTENT {
+                    // the total size is after the slash, e.g. "bytes 0-1023/146515"
+                    let total_size = res
+                        .headers()
+                        .get(CONTENT_RANGE)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.rsplit_once('/'))
+                        .and_then(|(_, total)| total.parse::<usize>().ok())
+                        .unwrap_or(usize::MAX);
+
+                    let mut download = SampleDownload::new(args, total_size, show_progress(args))?;
+                    let mut offset = 0_usize;
+
+                    // download ranges until we have the desired sample size
+                    loop {
+                        let chunk = res
+                            .bytes()
+                            .await
+                            .or(Err("Error while downloading file".to_string()))?;
+                        offset += chunk.len();
+                        if chunk.is_empty() || download.write_chunk(&chunk)? || offset >= total_size
+                        {
+                            break;
+                        }
+
+                        // get bigger ranges as the download goes on, so fewer requests are
+                        // needed. safety: range_size is set for range requests
+                        let next_range_size = min(range_size.unwrap() * 2, MAX_RANGE_CHUNK_SIZE);
+                        range_size = Some(next_range_size);
+                        res = with_auth(client.get(url.clone()), args)
+                            .header(
+                                RANGE,
+                                format!("bytes={offset}-{}", offset + next_range_size - 1),
+                            )
+                            .timeout(Duration::from_secs(args.flag_timeout))
+                            .send()
+                            .await
+                            .or(Err(format!("Failed to GET from '{url}'")))?;
+                        // a 416 (Range Not Satisfiable) means we're past the end of the file
+                        if res.status() != StatusCode::PARTIAL_CONTENT {
+                            break;
+                        }
+                    }
+                    drop(client);
+                    return download.finish(args, url, false);
+                }
+
                 let total_size = match res.content_length() {
                     Some(l) => l as usize,
                     None => {
@@ -256,147 +1143,55 @@ async fn get_file_to_sniff(args: &Args) -> CliResult<SniffFileStruct> {
                     }
                 };
 
-                #[allow(clippy::cast_precision_loss)]
-                let lines_sample_size = if args.flag_sample > 1.0 {
-                    args.flag_sample.round() as usize
-                } else if args.flag_sample.abs() < f64::EPSILON {
-                    // sample size is zero, so we want to download the entire file
-                    usize::MAX
-                } else {
-                    // sample size is a percentage, download percentage number of lines
-                    // from the file. Since we don't know how wide the lines are, we
-                    // just download a percentage of the bytes, assuming the lines are
-                    // 100 characters wide as a rough estimate.
-                    ((total_size / 100_usize) as f64 * args.flag_sample) as usize
-                };
-
-                // prep progress bar
-                let show_progress =
-                    args.flag_progressbar || std::env::var("QSV_PROGRESSBAR").is_ok();
-
-                let progress = ProgressBar::with_draw_target(
-                    Some(total_size.try_into().unwrap_or(u64::MAX)),
-                    ProgressDrawTarget::stderr_with_hz(5),
-                );
-                if show_progress {
-                    progress.set_style(
-                        ProgressStyle::default_bar()
-                            .template(
-                                "{msg}\n{spinner:.green} [{elapsed_precise}] \
-                                 [{wide_bar:.white/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, \
-                                 {eta})",
-                            )
-                            .unwrap(),
-                    );
-                    progress.set_message(format!(
-                        "Downloading {} samples...",
-                        HumanCount(lines_sample_size as u64)
-                    ));
-                } else {
-                    progress.set_draw_target(ProgressDrawTarget::hidden());
-                }
-
-                let mut file = NamedTempFile::new()?;
-                let mut downloaded = 0_usize;
+                let mut download = SampleDownload::new(args, total_size, show_progress(args))?;
                 let mut stream = res.bytes_stream();
-                let mut downloaded_lines = 0_usize;
-                #[allow(unused_assignments)]
-                let mut chunk = Bytes::new(); // amortize the allocation
 
                 // download chunks until we have the desired sample size
                 while let Some(item) = stream.next().await {
-                    chunk = item.or(Err("Error while downloading file".to_string()))?;
-                    file.write_all(&chunk)
-                        .map_err(|_| "Error while writing to file".to_string())?;
-                    let chunk_len = chunk.len();
-                    downloaded = min(downloaded + chunk_len, total_size);
-                    if show_progress {
-                        progress.inc(chunk_len as u64);
-                    }
-
-                    // scan chunk for newlines
-                    let num_lines = chunk.into_iter().filter(|&x| x == b'\n').count();
-                    // and keep track of the number of lines downloaded which is ~= sample_size
-                    downloaded_lines += num_lines;
-                    // we downloaded enough samples, stop downloading
-                    if downloaded_lines > lines_sample_size {
+                    let chunk = item.or(Err("Error while downloading file".to_string()))?;
+                    if download.write_chunk(&chunk)? {
                         break;
                     }
                 }
                 drop(client);
-
-                // we subtract 1 because we don't want to count the header row
-                downloaded_lines -= 1;
-
-                if show_progress {
-                    progress.finish_with_message(format!(
-                        "Downloaded {} samples.",
-                        HumanCount(downloaded_lines as u64)
-                    ));
-                }
-
-                // now we downloaded the file, rewrite it so we only have the exact sample size
-                // and truncate potentially incomplete lines. We streamed the download
-                // and the downloaded file may be more than the sample size, and the final
-                // line may be incomplete
-                let retrieved_name = file.path().to_str().unwrap().to_string();
-                let config = Config::new(&Some(retrieved_name))
-                    .delimiter(args.flag_delimiter)
-                    // we say no_headers so we can just copy the downloaded file over
-                    // including headers, to the exact sanple size file
-                    .no_headers(true)
-                    .flexible(true);
-
-                let mut rdr = config.reader()?;
-                let wtr_file = NamedTempFile::new()?;
-
-                // keep the temporary file around so we can sniff it later
-                // we'll delete it when we're done
-                let (_file, path) = wtr_file
-                    .keep()
-                    .or(Err("Cannot keep temporary file".to_string()))?;
-                let wtr_file_path = path.to_str().unwrap().to_string();
-
-                let mut wtr = Config::new(&Some(wtr_file_path.clone()))
-                    .no_headers(false)
-                    .flexible(true)
-                    .quote_style(csv::QuoteStyle::NonNumeric)
-                    .writer()?;
-                let mut downloaded_records = 0_usize;
-
-                // amortize allocation
-                #[allow(unused_assignments)]
-                let mut record = csv::ByteRecord::with_capacity(100, 20);
-
-                let header_row = rdr.byte_headers()?;
-                wtr.write_byte_record(header_row)?;
-                rdr.byte_records().next();
-
-                for rec in rdr.byte_records() {
-                    record = rec?;
-                    if downloaded_records >= lines_sample_size {
+                download.finish(args, url, false)
+            }
+            // its an object store URL, download sample to temp file with ranged GETs
+            #[cfg(feature = "sniff_cloud")]
+            url if is_object_store_url(&url) => {
+                let parsed_url = Url::parse(&url).expect("is_object_store_url already parsed it");
+                let (store, path) = object_store_for(&parsed_url)?;
+                let total_size = store
+                    .head(&path)
+                    .await
+                    .map_err(|e| format!("Cannot get the metadata of '{url}': {e}"))?
+                    .size;
+
+                let mut download = SampleDownload::new(args, total_size, show_progress(args))?;
+                let mut offset = 0_usize;
+                let mut chunk_size = RANGE_CHUNK_SIZE;
+
+                // download ranges until we have the desired sample size
+                while offset < total_size {
+                    let range_end = min(offset.saturating_add(chunk_size), total_size);
+                    let chunk = store
+                        .get_range(&path, offset..range_end)
+                        .await
+                        .map_err(|e| format!("Error while downloading '{url}': {e}"))?;
+                    offset = range_end;
+                    if download.write_chunk(&chunk)? {
                         break;
                     }
-                    downloaded_records += 1;
-                    wtr.write_byte_record(&record)?;
+                    // get bigger ranges as the download goes on, so fewer requests are needed
+                    chunk_size = min(chunk_size * 2, MAX_RANGE_CHUNK_SIZE);
                 }
-                wtr.flush()?;
-
-                Ok(SniffFileStruct {
-                    display_path: url,
-                    file_to_sniff: wtr_file_path,
-                    tempfile_flag: true,
-                    retrieved_size: downloaded,
-                    file_size: if total_size == usize::MAX {
-                        // the server didn't give us content length, so we just
-                        // downloaded the entire file. downloaded variable
-                        // is the total size of the file
-                        downloaded
-                    } else {
-                        total_size
-                    },
-                    downloaded_records,
-                })
+                download.finish(args, url, false)
+            }
+            #[cfg(not(feature = "sniff_cloud"))]
+            url if is_object_store_url(&url) => {
+                fail_clierror!(
+                    "Cannot sniff '{url}': qsv was built without the sniff_cloud feature."
+                )
             }
             // its a file, passthrough the path along with its size
             path => {
@@ -407,101 +1202,259 @@ async fn get_file_to_sniff(args: &Args) -> CliResult<SniffFileStruct> {
 
                 let canonical_path = fs::canonicalize(&path)?.to_str().unwrap().to_string();
 
+                let detected_mime = detect_mime(Path::new(&path))?;
+                let compression = Compression::from_mime(detected_mime);
+                if compression == Compression::None {
+                    return Ok(SniffFileStruct {
+                        display_path: canonical_path,
+                        detected_mime,
+                        file_to_sniff: path,
+                        tempfile_flag: false,
+                        retrieved_size: fsize,
+                        file_size: fsize,
+                        downloaded_records: 0,
+                        compression,
+                        uncompressed_size: fsize,
+                    });
+                }
+
+                if args.flag_sample > 1.0 {
+                    return sample_compressed_file(args, compression, &path, canonical_path, fsize);
+                }
+                let (decompressed_path, uncompressed_size) =
+                    compression.decompress_file(Path::new(&path))?;
                 Ok(SniffFileStruct {
-                    display_path:       canonical_path,
-                    file_to_sniff:      path,
-                    tempfile_flag:      false,
-                    retrieved_size:     fsize,
-                    file_size:          fsize,
+                    display_path: canonical_path,
+                    detected_mime: detect_mime(Path::new(&decompressed_path))?,
+                    file_to_sniff: decompressed_path,
+                    tempfile_flag: true,
+                    retrieved_size: fsize,
+                    file_size: fsize,
                     downloaded_records: 0,
+                    compression,
+                    uncompressed_size,
                 })
             }
         }
     } else {
-        // read from stdin and write to a temp file
-        let mut stdin_file = NamedTempFile::new()?;
+        // read stdin to a temp file, only up to the sample size, like a URL sample.
+        // So piping a huge stream doesn't require spooling all of it.
+        let mut download = SampleDownload::new(args, usize::MAX, false)?;
         let stdin = std::io::stdin();
         let mut stdin_handle = stdin.lock();
-        std::io::copy(&mut stdin_handle, &mut stdin_file)?;
+        let mut buffer = vec![0_u8; STDIN_CHUNK_SIZE];
+        let mut complete = false;
+        loop {
+            let n = match stdin_handle.read(&mut buffer) {
+                Ok(0) => {
+                    complete = true;
+                    break;
+                }
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return fail_clierror!("Cannot read stdin: {e}"),
+            };
+            if download.write_chunk(&buffer[..n])? {
+                break;
+            }
+        }
         drop(stdin_handle);
-        let (file, path) = stdin_file
-            .keep()
-            .or(Err("Cannot keep temporary file".to_string()))?;
-
-        let metadata = file
-            .metadata()
-            .map_err(|_| "Cannot get metadata for stdin file".to_string())?;
-
-        let fsize = metadata.len() as usize;
-        let path_string = path
-            .into_os_string()
-            .into_string()
-            .unwrap_or_else(|_| "???".to_string());
+        download.finish(args, "stdin".to_string(), complete)
+    }
+}
 
-        Ok(SniffFileStruct {
-            display_path:       "stdin".to_string(),
-            file_to_sniff:      path_string,
-            tempfile_flag:      true,
-            retrieved_size:     fsize,
-            file_size:          fsize,
-            downloaded_records: 0,
-        })
+/// Decompresses the sample of a local compressed file, like a URL sample.
+/// The rest of the file is only decompressed when all of it was already read, as the
+/// decoders read ahead, so the records of small files are still counted exactly.
+fn sample_compressed_file(
+    args: &Args,
+    compression: Compression,
+    path: &str,
+    display_path: String,
+    fsize: usize,
+) -> CliResult<SniffFileStruct> {
+    let compressed_read = Rc::new(Cell::new(0_usize));
+    let mut decoder = compression.decoder(ReadCounter {
+        file:  fs::File::open(path)?,
+        bytes: Rc::clone(&compressed_read),
+    })?;
+    let mut download = SampleDownload::new(args, usize::MAX, false)?;
+    let mut buffer = vec![0_u8; STDIN_CHUNK_SIZE];
+    let mut complete = false;
+    loop {
+        let n = match decoder.read(&mut buffer) {
+            Ok(0) => {
+                complete = true;
+                break;
+            }
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                let _ = fs::remove_file(&download.sample_path);
+                return fail_clierror!("Cannot decompress {} input: {e}", compression.name());
+            }
+        };
+        if download.write_chunk(&buffer[..n])? && compressed_read.get() < fsize {
+            break;
+        }
     }
+    drop(decoder);
+
+    let decompressed = download.downloaded;
+    let mut sniff_file = download.finish(args, display_path, complete)?;
+    let compressed_read = compressed_read.get().clamp(1, fsize.max(1));
+    sniff_file.retrieved_size = compressed_read;
+    sniff_file.file_size = fsize;
+    sniff_file.compression = compression;
+    #[allow(clippy::cast_precision_loss)]
+    if !complete {
+        // estimate it from the compression ratio of the sample
+        sniff_file.uncompressed_size =
+            (fsize as f64 * decompressed as f64 / compressed_read as f64) as usize;
+    }
+    Ok(sniff_file)
 }
 
-fn cleanup_tempfile(
-    tempfile_flag: bool,
-    tempfile: String,
-) -> Result<(), crate::clitypes::CliError> {
+fn cleanup_tempfile(tempfile_flag: bool, tempfile: String) -> Result<(), CliError> {
     if tempfile_flag {
         fs::remove_file(tempfile)?;
     }
     Ok(())
 }
 
-#[allow(clippy::unused_async)] // false positive lint
-pub async fn run(argv: &[&str]) -> CliResult<()> {
-    let args: Args = util::get_args(USAGE, argv)?;
+/// An error sniffing a file. With --json, it's reported as a JSON:API error object.
+struct SniffError {
+    title:  &'static str,
+    detail: String,
+    meta:   Option<serde_json::Value>,
+}
 
-    let mut sample_size = args.flag_sample;
-    if sample_size < 0.0 {
-        if args.flag_json || args.flag_pretty_json {
-            let json_result = json!({
-                "errors": [{
-                    "title": "sniff error",
-                    "detail": "Sample size must be greater than or equal to zero."
-                }]
-            });
-            return fail_clierror!("{json_result}");
+impl SniffError {
+    fn new(title: &'static str, detail: impl Into<String>) -> SniffError {
+        SniffError {
+            title,
+            detail: detail.into(),
+            meta: None,
+        }
+    }
+
+    fn to_json(&self, path: Option<&str>) -> serde_json::Value {
+        let mut error = json!({
+            "title": self.title,
+            "detail": self.detail
+        });
+        if let Some(ref meta) = self.meta {
+            error["meta"] = meta.clone();
+        }
+        match path {
+            Some(path) => json!({ "path": path, "errors": [error] }),
+            None => json!({ "errors": [error] }),
+        }
+    }
+}
+
+impl From<CliError> for SniffError {
+    fn from(err: CliError) -> SniffError {
+        SniffError::new("sniff error", err.to_string())
+    }
+}
+
+impl From<io::Error> for SniffError {
+    fn from(err: io::Error) -> SniffError {
+        SniffError::new("sniff error", err.to_string())
+    }
+}
+
+/// Expands the glob patterns among the inputs, e.g. for shells that don't.
+#[cfg(feature = "full")]
+fn expand_inputs(inputs: &[String]) -> CliResult<Vec<String>> {
+    let mut expanded = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let is_pattern = input.contains(['*', '?', '['])
+            && Url::parse(input).is_err()
+            && !Path::new(input).exists();
+        if !is_pattern {
+            expanded.push(input.clone());
+            continue;
+        }
+        let paths = glob::glob(input).map_err(|e| format!("Invalid glob pattern {input}: {e}"))?;
+        let num_expanded = expanded.len();
+        for path in paths {
+            let path = path.map_err(|e| format!("Cannot read {input} match: {e}"))?;
+            if path.is_file() {
+                expanded.push(path.to_string_lossy().to_string());
+            }
+        }
+        if expanded.len() == num_expanded {
+            return fail_clierror!("No files match {input}");
+        }
+    }
+    Ok(expanded)
+}
+
+/// Without the full feature, glob patterns are left for the shell to expand.
+#[cfg(not(feature = "full"))]
+fn expand_inputs(inputs: &[String]) -> CliResult<Vec<String>> {
+    Ok(inputs.to_vec())
+}
+
+async fn sniff_file(args: &Args, input: Option<String>) -> Result<SniffStruct, SniffError> {
+    // with --cache, the results of local files are cached
+    let cache_key = match input {
+        Some(ref path) if args.flag_cache && Path::new(path).is_file() => {
+            cache_key(args, Path::new(path))
+        }
+        _ => None,
+    };
+    if let (Some(key), false) = (&cache_key, args.flag_force) {
+        if let Some(mut cached) = read_cache(key) {
+            log::info!("Using the cached sniff results of {}", cached.path);
+            cached.quick = args.flag_quick;
+            // the timestamp is of this sniff, not of when the results were cached
+            cached.sniff_timestamp = chrono::Utc::now().to_rfc3339();
+            return Ok(cached);
         }
-        return fail_clierror!("Sample size must be greater than or equal to zero.");
     }
 
+    let sniffed = sniff_uncached(args, input).await?;
+    if let Some(ref key) = cache_key {
+        write_cache(key, &sniffed);
+    }
+    Ok(sniffed)
+}
+
+async fn sniff_uncached(args: &Args, input: Option<String>) -> Result<SniffStruct, SniffError> {
     let sniffed_ts = chrono::Utc::now().to_rfc3339();
 
-    let future = get_file_to_sniff(&args);
-    let sfile_info = block_on(future)?;
+    let sfile_info = get_file_to_sniff(args, input).await?;
     let tempfile_to_delete = sfile_info.file_to_sniff.clone();
 
+    if sfile_info.detected_mime != CSV_MIME {
+        cleanup_tempfile(sfile_info.tempfile_flag, tempfile_to_delete)?;
+
+        let mut sniff_error = SniffError::new(
+            "sniff error",
+            format!(
+                "{} is not a CSV. Detected MIME type: {}",
+                sfile_info.display_path, sfile_info.detected_mime
+            ),
+        );
+        sniff_error.meta = Some(json!({ "detected_mime": sfile_info.detected_mime }));
+        return Err(sniff_error);
+    }
+
     let conf = Config::new(&Some(sfile_info.file_to_sniff.clone()))
         .flexible(true)
         .delimiter(args.flag_delimiter);
-    let n_rows = if sfile_info.downloaded_records == 0 {
+    let n_rows = if args.flag_quick {
+        // with --quick, the rows are not counted
+        usize::MAX
+    } else if sfile_info.downloaded_records == 0 {
         match util::count_rows(&conf) {
             Ok(n) => n as usize,
             Err(e) => {
                 cleanup_tempfile(sfile_info.tempfile_flag, tempfile_to_delete)?;
-
-                if args.flag_json || args.flag_pretty_json {
-                    let json_result = json!({
-                        "errors": [{
-                            "title": "count rows error",
-                            "detail": e.to_string()
-                        }]
-                    });
-                    return fail_clierror!("{json_result}");
-                }
-                return fail_clierror!("{}", e);
+                return Err(SniffError::new("count rows error", e.to_string()));
             }
         }
     } else {
@@ -515,19 +1468,10 @@ pub async fn run(argv: &[&str]) -> CliResult<()> {
     // its an empty file, exit with an error
     if n_rows == 0 {
         cleanup_tempfile(sfile_info.tempfile_flag, tempfile_to_delete)?;
-
-        if args.flag_json || args.flag_pretty_json {
-            let json_result = json!({
-                "errors": [{
-                    "title": "sniff error",
-                    "detail": "Empty file"
-                }]
-            });
-            return fail_clierror!("{json_result}");
-        }
-        return fail_clierror!("Empty file");
+        return Err(SniffError::new("sniff error", "Empty file"));
     }
 
+    let mut sample_size = args.flag_sample;
     let mut sample_all = false;
     // its a percentage, get the actual sample size
     #[allow(clippy::cast_precision_loss)]
@@ -556,7 +1500,7 @@ pub async fn run(argv: &[&str]) -> CliResult<()> {
         DatePreference::MdyFormat
     };
 
-    if let Some(save_urlsample) = args.flag_save_urlsample {
+    if let Some(ref save_urlsample) = args.flag_save_urlsample {
         fs::copy(sfile_info.file_to_sniff.clone(), save_urlsample)?;
     }
 
@@ -595,82 +1539,711 @@ pub async fn run(argv: &[&str]) -> CliResult<()> {
         }
     };
 
-    let mut processed_results = SniffStruct::default();
-    let mut sniffing_error: Option<String> = None;
+    let num_samples = if sample_all {
+        usize::MAX
+    } else {
+        sampled_records
+    };
+    let confidence = match sniff_results {
+        Ok(ref metadata) if args.flag_confidence => Some(dialect_confidence(
+            &sfile_info.file_to_sniff,
+            metadata,
+            num_samples,
+        )),
+        _ => None,
+    };
+    let stats_types = match sniff_results {
+        Ok(ref metadata) if args.flag_stats_types => Some(stats_types(
+            &sfile_info.file_to_sniff,
+            metadata,
+            num_samples,
+        )),
+        _ => None,
+    };
 
-    match sniff_results {
-        Ok(metadata) => {
-            let (num_records, estimated) = rowcount(&metadata, &sfile_info, n_rows);
+    cleanup_tempfile(sfile_info.tempfile_flag, tempfile_to_delete)?;
 
-            let sniffedfields = metadata
+    let metadata = sniff_results.map_err(|e| SniffError::new("sniff error", e.to_string()))?;
+    let confidence = confidence.transpose()?;
+    let (null_counts, sample_values) = match stats_types.transpose()? {
+        Some((null_counts, sample_values)) => (Some(null_counts), Some(sample_values)),
+        None => (None, None),
+    };
+    let (num_records, estimated) = rowcount(&metadata, &sfile_info, n_rows);
+
+    let sniffedfields = metadata
+        .fields
+        .iter()
+        .map(std::string::ToString::to_string)
+        .collect();
+    let sniffedtypes = metadata
+        .types
+        .iter()
+        .map(std::string::ToString::to_string)
+        .collect();
+
+    Ok(SniffStruct {
+        path: sfile_info.display_path,
+        sniff_timestamp: sniffed_ts,
+        detected_mime: sfile_info.detected_mime.to_string(),
+        compression: sfile_info.compression.name().to_string(),
+        uncompressed_size: sfile_info.uncompressed_size,
+        last_modified: None,
+        delimiter_char: metadata.dialect.delimiter as char,
+        header_row: metadata.dialect.header.has_header_row,
+        preamble_rows: metadata.dialect.header.num_preamble_rows,
+        quote_char: match metadata.dialect.quote {
+            qsv_sniffer::metadata::Quote::Some(chr) => format!("{}", char::from(chr)),
+            qsv_sniffer::metadata::Quote::None => "none".into(),
+        },
+        flexible: metadata.dialect.flexible,
+        is_utf8: metadata.dialect.is_utf8,
+        retrieved_size: sfile_info.retrieved_size,
+        file_size: sfile_info.file_size,
+        sampled_records: if sampled_records > num_records {
+            num_records
+        } else {
+            sampled_records
+        },
+        estimated,
+        num_records,
+        avg_record_len: metadata.avg_record_len,
+        num_fields: metadata.num_fields,
+        fields: sniffedfields,
+        types: sniffedtypes,
+        null_counts,
+        sample_values,
+        confidence,
+        quick: args.flag_quick,
+    })
+}
+
+#[derive(Clone, Copy)]
+enum SchemaFormat {
+    JsonSchema,
+    TableSchema,
+}
+
+/// Builds a JSON Schema (Draft 7) or a Frictionless Table Schema from the sniffed fields & types.
+fn sniffed_to_schema(sniffed: &SniffStruct, format: SchemaFormat) -> serde_json::Value {
+    match format {
+        SchemaFormat::JsonSchema => {
+            let mut properties = serde_json::Map::new();
+            for (field, field_type) in sniffed.fields.iter().zip(sniffed.types.iter()) {
+                let property = match field_type.as_str() {
+                    "Unsigned" | "Signed" => json!({ "type": "integer" }),
+                    "Float" => json!({ "type": "number" }),
+                    "Boolean" => json!({ "type": "boolean" }),
+                    "Date" => json!({ "type": "string", "format": "date" }),
+                    "DateTime" => json!({ "type": "string", "format": "date-time" }),
+                    "NULL" => json!({ "type": "null" }),
+                    _ => json!({ "type": "string" }),
+                };
+                properties.insert(field.clone(), property);
+            }
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": format!("JSON Schema for {}", sniffed.path),
+                "description": "Inferred JSON Schema from QSV sniff command",
+                "type": "object",
+                "properties": properties,
+                "required": sniffed.fields
+            })
+        }
+        SchemaFormat::TableSchema => {
+            let fields = sniffed
                 .fields
                 .iter()
-                .map(std::string::ToString::to_string)
-                .collect();
-            let sniffedtypes = metadata
-                .types
+                .zip(sniffed.types.iter())
+                .map(|(field, field_type)| {
+                    let table_type = match field_type.as_str() {
+                        "Unsigned" | "Signed" => "integer",
+                        "Float" => "number",
+                        "Boolean" => "boolean",
+                        "Date" => "date",
+                        "DateTime" => "datetime",
+                        "NULL" => "any",
+                        _ => "string",
+                    };
+                    json!({ "name": field, "type": table_type })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "fields": fields,
+                "missingValues": [""]
+            })
+        }
+    }
+}
+
+/// The extensions of the files sniffed with --harvest, before any compression extension.
+const HARVEST_EXTENSIONS: [&str; 5] = ["csv", "tsv", "tab", "ssv", "txt"];
+
+/// Recursively lists the tabular files in dir, sorted by path.
+/// Symbolic links to directories are not followed.
+fn harvest_files(dir: &Path) -> CliResult<Vec<String>> {
+    fn is_tabular(path: &Path) -> bool {
+        let mut path = path.to_path_buf();
+        if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("gz" | "zst" | "sz")
+        ) {
+            path.set_extension("");
+        }
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| {
+                HARVEST_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+            })
+    }
+
+    fn walk(dir: &Path, files: &mut Vec<String>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                walk(&path, files)?;
+            } else if is_tabular(&path) && path.is_file() {
+                files.push(path.to_string_lossy().to_string());
+            }
+        }
+        Ok(())
+    }
+
+    if !dir.is_dir() {
+        return fail_clierror!("{} is not a directory.", dir.display());
+    }
+    let mut files = Vec::new();
+    walk(dir, &mut files)?;
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// The number of distinct sample values reported for each field with --stats-types.
+const NUM_SAMPLE_VALUES: usize = 5;
+
+/// Counts the null (empty) values of each field in the sampled records, and collects
+/// the first distinct non-empty values of each field as samples.
+fn stats_types(
+    path: &str,
+    metadata: &qsv_sniffer::metadata::Metadata,
+    num_samples: usize,
+) -> CliResult<(Vec<usize>, Vec<Vec<String>>)> {
+    let dialect = &metadata.dialect;
+    let mut rconfig = Config::new(&Some(path.to_string()))
+        .delimiter(Some(Delimiter(dialect.delimiter)))
+        .no_headers(true)
+        .flexible(true);
+    rconfig = match dialect.quote {
+        qsv_sniffer::metadata::Quote::Some(quote) => rconfig.quote(quote),
+        qsv_sniffer::metadata::Quote::None => rconfig.quoting(false),
+    };
+    let mut rdr = rconfig.reader()?;
+
+    let mut record = csv::StringRecord::new();
+    let num_skipped = dialect.header.num_preamble_rows + usize::from(dialect.header.has_header_row);
+    for _ in 0..num_skipped {
+        if !rdr.read_record(&mut record)? {
+            break;
+        }
+    }
+
+    let mut null_counts = vec![0_usize; metadata.num_fields];
+    let mut sample_values: Vec<Vec<String>> = vec![Vec::new(); metadata.num_fields];
+    let mut num_read = 0_usize;
+    while num_read < num_samples && rdr.read_record(&mut record)? {
+        num_read += 1;
+        for (i, (null_count, samples)) in null_counts
+            .iter_mut()
+            .zip(sample_values.iter_mut())
+            .enumerate()
+        {
+            let value = record.get(i).unwrap_or_default();
+            if value.trim().is_empty() {
+                *null_count += 1;
+            } else if samples.len() < NUM_SAMPLE_VALUES && !samples.iter().any(|s| s == value) {
+                samples.push(value.to_string());
+            }
+        }
+    }
+    Ok((null_counts, sample_values))
+}
+
+/// The delimiters scored with --confidence, besides the detected one.
+const CANDIDATE_DELIMITERS: [u8; 6] = [b',', b';', b'\t', b'|', b':', b' '];
+/// The maximum number of sampled records used to score the dialect.
+const MAX_CONFIDENCE_SAMPLES: usize = 10_000;
+
+/// Scores the detected delimiter against the candidate delimiters, and the detected
+/// header row decision.
+fn dialect_confidence(
+    path: &str,
+    metadata: &qsv_sniffer::metadata::Metadata,
+    num_samples: usize,
+) -> CliResult<Confidence> {
+    let num_samples = num_samples.min(MAX_CONFIDENCE_SAMPLES);
+    let detected = metadata.dialect.delimiter;
+
+    let mut scores = Vec::with_capacity(CANDIDATE_DELIMITERS.len() + 1);
+    scores.push((
+        detected,
+        delimiter_score(path, metadata, detected, num_samples)?,
+    ));
+    for delimiter in CANDIDATE_DELIMITERS {
+        if delimiter != detected {
+            scores.push((
+                delimiter,
+                delimiter_score(path, metadata, delimiter, num_samples)?,
+            ));
+        }
+    }
+    let total_score: f64 = scores.iter().map(|(_, score)| score).sum();
+    let delimiter_confidence = if total_score > 0.0 {
+        scores[0].1 / total_score
+    } else {
+        0.0
+    };
+
+    let mut candidates = scores
+        .iter()
+        .skip(1)
+        .filter(|(_, score)| *score > 0.0)
+        .map(|(delimiter, score)| DialectCandidate {
+            delimiter: *delimiter as char,
+            score:     round4(*score),
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    Ok(Confidence {
+        delimiter: round4(delimiter_confidence),
+        header: round4(header_score(path, metadata, num_samples)?),
+        candidates,
+    })
+}
+
+/// Opens the sample with the detected dialect, but the given delimiter,
+/// skipping the preamble rows.
+fn sample_reader(
+    path: &str,
+    metadata: &qsv_sniffer::metadata::Metadata,
+    delimiter: u8,
+) -> CliResult<csv::Reader<Box<dyn io::Read + Send>>> {
+    let mut rconfig = Config::new(&Some(path.to_string()))
+        .delimiter(Some(Delimiter(delimiter)))
+        .no_headers(true)
+        .flexible(true);
+    rconfig = match metadata.dialect.quote {
+        qsv_sniffer::metadata::Quote::Some(quote) => rconfig.quote(quote),
+        qsv_sniffer::metadata::Quote::None => rconfig.quoting(false),
+    };
+    let mut rdr = rconfig.reader()?;
+    let mut record = csv::ByteRecord::new();
+    for _ in 0..metadata.dialect.header.num_preamble_rows {
+        if !rdr.read_byte_record(&mut record)? {
+            break;
+        }
+    }
+    Ok(rdr)
+}
+
+/// Scores how consistently a delimiter splits the sampled records: the share of records
+/// with the most common number of fields, weighted so that more fields score higher.
+/// A delimiter that doesn't split the records scores 0.
+fn delimiter_score(
+    path: &str,
+    metadata: &qsv_sniffer::metadata::Metadata,
+    delimiter: u8,
+    num_samples: usize,
+) -> CliResult<f64> {
+    let mut rdr = sample_reader(path, metadata, delimiter)?;
+    let mut record = csv::ByteRecord::new();
+    let mut num_fields_counts: HashMap<usize, usize> = HashMap::new();
+    let mut num_records = 0_usize;
+    while num_records < num_samples && rdr.read_byte_record(&mut record)? {
+        num_records += 1;
+        *num_fields_counts.entry(record.len()).or_insert(0) += 1;
+    }
+
+    let Some((num_fields, count)) = num_fields_counts
+        .into_iter()
+        .max_by_key(|&(num_fields, count)| (count, num_fields))
+    else {
+        return Ok(0.0);
+    };
+    if num_fields < 2 {
+        return Ok(0.0);
+    }
+    Ok(count as f64 / num_records as f64 * (1.0 - 1.0 / num_fields as f64))
+}
+
+/// Scores the header row decision: the share of columns whose first row value doesn't look
+/// like the other values of the column if there's a header row, or does if there's none.
+fn header_score(
+    path: &str,
+    metadata: &qsv_sniffer::metadata::Metadata,
+    num_samples: usize,
+) -> CliResult<f64> {
+    let mut rdr = sample_reader(path, metadata, metadata.dialect.delimiter)?;
+    let mut first_row = csv::StringRecord::new();
+    if !rdr.read_record(&mut first_row)? || metadata.num_fields == 0 {
+        return Ok(0.0);
+    }
+
+    let mut column_values: Vec<HashSet<String>> = vec![HashSet::new(); metadata.num_fields];
+    let mut record = csv::StringRecord::new();
+    let mut num_records = 0_usize;
+    while num_records < num_samples && rdr.read_record(&mut record)? {
+        num_records += 1;
+        for (values, value) in column_values.iter_mut().zip(record.iter()) {
+            values.insert(value.to_string());
+        }
+    }
+
+    let looks_like_header = column_values
+        .iter()
+        .zip(metadata.types.iter())
+        .enumerate()
+        .filter(|(i, (values, column_type))| {
+            let value = first_row.get(*i).unwrap_or_default().trim();
+            match column_type.to_string().as_str() {
+                // a header of a text column is not one of its values
+                "Text" | "NULL" => !values.contains(value),
+                // a header of a typed column doesn't look like a number or a date
+                _ => !value.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.'),
+            }
+        })
+        .count();
+    let header_share = looks_like_header as f64 / metadata.num_fields as f64;
+
+    Ok(if metadata.dialect.header.has_header_row {
+        header_share
+    } else {
+        1.0 - header_share
+    })
+}
+
+fn round4(score: f64) -> f64 {
+    (score * 10_000.0).round() / 10_000.0
+}
+
+/// Checks the sniffed metadata against the --require-utf8, --require-header,
+/// --max-preamble & --delimiter-must-be options.
+fn requirement_violations(args: &Args, sniffed: &SniffStruct) -> Vec<String> {
+    let mut violations = Vec::new();
+    if args.flag_require_utf8 && !sniffed.is_utf8 {
+        violations.push("not UTF-8".to_string());
+    }
+    if args.flag_require_header && !sniffed.header_row {
+        violations.push("no header row".to_string());
+    }
+    if let Some(max_preamble) = args.flag_max_preamble {
+        if sniffed.preamble_rows > max_preamble {
+            violations.push(format!(
+                "{} preamble rows, more than {max_preamble}",
+                sniffed.preamble_rows
+            ));
+        }
+    }
+    if let Some(delimiter) = args.flag_delimiter_must_be {
+        let expected = delimiter.as_byte() as char;
+        if sniffed.delimiter_char != expected {
+            violations.push(format!(
+                "delimiter is {:?}, not {expected:?}",
+                sniffed.delimiter_char
+            ));
+        }
+    }
+    violations
+}
+
+/// The cached sniff results of a file, with the key they were cached with.
+#[derive(Deserialize)]
+struct CachedSniff {
+    key:     String,
+    results: SniffStruct,
+}
+
+/// The cache key of a local file: its path, size & modification time,
+/// and the options that change the sniff results.
+fn cache_key(args: &Args, path: &Path) -> Option<String> {
+    let canonical_path = fs::canonicalize(path).ok()?;
+    let metadata = fs::metadata(&canonical_path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some(format!(
+        "{}|{}|{modified}|sample={}|prefer_dmy={}|delimiter={:?\
+         }|quick={}|stats_types={}|confidence={}",
+        canonical_path.display(),
+        metadata.len(),
+        args.flag_sample,
+        // the same date preference as the sniff itself
+        args.flag_prefer_dmy || Config::new(&None).get_dmy_preference(),
+        args.flag_delimiter.map(Delimiter::as_byte),
+        args.flag_quick,
+        args.flag_stats_types,
+        args.flag_confidence,
+    ))
+}
+
+/// The sniff cache is in $XDG_CACHE_HOME/qsv/sniff, or ~/.cache/qsv/sniff.
+fn cache_file(key: &str) -> Option<PathBuf> {
+    let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    Some(
+        cache_dir
+            .join("qsv")
+            .join("sniff")
+            .join(format!("{:016x}.json", hasher.finish())),
+    )
+}
+
+fn read_cache(key: &str) -> Option<SniffStruct> {
+    let cached: CachedSniff = serde_json::from_slice(&fs::read(cache_file(key)?).ok()?).ok()?;
+    // the key is checked in case of a hash collision
+    (cached.key == key).then_some(cached.results)
+}
+
+/// Caches the sniff results. Failing to do so is not an error, as it only makes
+/// the next invocation slower.
+fn write_cache(key: &str, sniffed: &SniffStruct) {
+    let Some(cache_file) = cache_file(key) else {
+        return;
+    };
+    let cached = json!({ "key": key, "results": sniffed });
+    let written = cache_file
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&cache_file, cached.to_string()));
+    if let Err(e) = written {
+        log::warn!(
+            "Cannot cache the sniff results in {}: {e}",
+            cache_file.display()
+        );
+    }
+}
+
+/// Flattens the sniff results of a file to a CSV record, joining the fields & types with '|'.
+fn sniffed_to_csv(sniffed: &SniffStruct) -> CliResult<(Vec<String>, Vec<String>)> {
+    let serde_json::Value::Object(map) = sniffed.to_json() else {
+        return fail_clierror!("Cannot serialize results.");
+    };
+    let mut headers = Vec::with_capacity(map.len());
+    let mut values = Vec::with_capacity(map.len());
+    for (key, value) in map {
+        headers.push(key);
+        values.push(match value {
+            serde_json::Value::String(s) => s,
+            serde_json::Value::Array(items) => items
                 .iter()
-                .map(std::string::ToString::to_string)
-                .collect();
-
-            processed_results = SniffStruct {
-                path: sfile_info.display_path,
-                sniff_timestamp: sniffed_ts,
-                delimiter_char: metadata.dialect.delimiter as char,
-                header_row: metadata.dialect.header.has_header_row,
-                preamble_rows: metadata.dialect.header.num_preamble_rows,
-                quote_char: match metadata.dialect.quote {
-                    qsv_sniffer::metadata::Quote::Some(chr) => format!("{}", char::from(chr)),
-                    qsv_sniffer::metadata::Quote::None => "none".into(),
-                },
-                flexible: metadata.dialect.flexible,
-                is_utf8: metadata.dialect.is_utf8,
-                retrieved_size: sfile_info.retrieved_size,
-                file_size: sfile_info.file_size, // sfile_info.file_size,
-                sampled_records: if sampled_records > num_records {
-                    num_records
-                } else {
-                    sampled_records
-                },
-                estimated,
-                num_records,
-                avg_record_len: metadata.avg_record_len,
-                num_fields: metadata.num_fields,
-                fields: sniffedfields,
-                types: sniffedtypes,
-            };
+                .map(|item| {
+                    item.as_str()
+                        .map_or_else(|| item.to_string(), str::to_string)
+                })
+                .collect::<Vec<_>>()
+                .join("|"),
+            other => other.to_string(),
+        });
+    }
+    Ok((headers, values))
+}
+
+#[allow(clippy::unused_async)] // false positive lint
+pub async fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let json_output = args.flag_json || args.flag_pretty_json;
+
+    if args.flag_sample < 0.0 {
+        let sniff_error = SniffError::new(
+            "sniff error",
+            "Sample size must be greater than or equal to zero.",
+        );
+        if json_output {
+            return fail_clierror!("{}", sniff_error.to_json(None));
+        }
+        return fail_clierror!("{}", sniff_error.detail);
+    }
+
+    let mut inputs = expand_inputs(&args.arg_input)?;
+    let harvest = args.flag_harvest.is_some();
+    if let Some(ref dir) = args.flag_harvest {
+        if !inputs.is_empty() {
+            return fail_clierror!("<input> cannot be used with --harvest.");
         }
-        Err(e) => {
-            sniffing_error = Some(e.to_string());
+        inputs = harvest_files(Path::new(dir))?;
+        if inputs.is_empty() {
+            return fail_clierror!("No tabular files found in {dir}.");
         }
     }
+    let batch = harvest || inputs.len() > 1;
+    if batch && args.flag_save_urlsample.is_some() {
+        return fail_clierror!("--save-urlsample can only be used with a single input.");
+    }
+    if batch && args.flag_schema_output.is_some() {
+        return fail_clierror!("--schema-output can only be used with a single input.");
+    }
+    if args.flag_basic_auth.is_some() && args.flag_bearer_token.is_some() {
+        return fail_clierror!("--basic-auth and --bearer-token cannot be used together.");
+    }
+    if args.flag_quick {
+        if args.flag_schema_output.is_some() {
+            return fail_clierror!("--schema-output cannot be used with --quick.");
+        }
+        if args.flag_stats_types {
+            return fail_clierror!("--stats-types cannot be used with --quick.");
+        }
+        if args.flag_sample > 0.0 && args.flag_sample < 1.0 {
+            return fail_clierror!(
+                "--sample cannot be a percentage with --quick, as the rows are not counted."
+            );
+        }
+    }
+    let schema_format = match args.flag_schema_format.to_lowercase().as_str() {
+        "jsonschema" => SchemaFormat::JsonSchema,
+        "tableschema" => SchemaFormat::TableSchema,
+        _ => {
+            return fail_clierror!(
+                "Invalid --schema-format: {}. Use jsonschema or tableschema.",
+                args.flag_schema_format
+            )
+        }
+    };
+    // a harvest catalog is JSONL by default
+    let json_lines = args.flag_json || (harvest && !args.flag_csv && !args.flag_pretty_json);
 
-    cleanup_tempfile(sfile_info.tempfile_flag, tempfile_to_delete)?;
+    let mut results = if !batch {
+        vec![sniff_file(&args, inputs.first().cloned()).await]
+    } else {
+        // sniff the files in parallel. The URL downloads need the tokio runtime,
+        // so it's entered in each of the rayon threads.
+        let handle = tokio::runtime::Handle::current();
+        inputs
+            .par_iter()
+            .map(|input| {
+                let _guard = handle.enter();
+                block_on(sniff_file(&args, Some(input.clone())))
+            })
+            .collect::<Vec<_>>()
+    };
 
-    if args.flag_json || args.flag_pretty_json {
-        if sniffing_error.is_none() {
-            if args.flag_pretty_json {
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&processed_results).unwrap()
-                );
-            } else {
-                println!("{}", serde_json::to_string(&processed_results).unwrap());
-            };
-            Ok(())
-        } else {
-            let json_error = json!({
-                "errors": [{
-                    "title": "sniff error",
-                    "detail": sniffing_error.unwrap()
-                }]
-            });
-            fail_clierror!("{json_error}")
+    // with a single input, errors are returned as is
+    if !batch {
+        // safety: there is always exactly one result
+        if let Err(sniff_error) = &results[0] {
+            if json_output {
+                return fail_clierror!("{}", sniff_error.to_json(None));
+            }
+            return fail_clierror!("{}", sniff_error.detail);
         }
-    } else if sniffing_error.is_none() {
-        println!("{processed_results}");
-        return Ok(());
+    }
+
+    if let (Some(schema_output), Some(Ok(sniffed))) = (&args.flag_schema_output, results.first()) {
+        let schema = sniffed_to_schema(sniffed, schema_format);
+        let schema_pretty = match serde_json::to_string_pretty(&schema) {
+            Ok(s) => s,
+            Err(e) => return fail_clierror!("Cannot prettify schema json: {e}"),
+        };
+        fs::write(schema_output, schema_pretty)?;
+    }
+
+    // stdin has no input name
+    let names = if inputs.is_empty() {
+        vec![String::new()]
     } else {
-        return fail_clierror!("{}", sniffing_error.unwrap());
+        inputs.clone()
+    };
+    if harvest {
+        for (input, sniffed) in inputs.iter().zip(results.iter_mut()) {
+            if let Ok(sniffed) = sniffed {
+                sniffed.last_modified = fs::metadata(input)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(|modified| {
+                        let modified: chrono::DateTime<chrono::Utc> = modified.into();
+                        modified.to_rfc3339()
+                    });
+            }
+        }
     }
+
+    let mut csv_wtr = if args.flag_csv {
+        Some(Config::new(&None).writer()?)
+    } else {
+        None
+    };
+    let mut csv_headers_written = false;
+    let mut num_errors = 0_usize;
+    let mut violations = Vec::new();
+    for (input, result) in names.iter().zip(results.iter()) {
+        let sniffed = match result {
+            Ok(sniffed) => sniffed,
+            Err(sniff_error) => {
+                num_errors += 1;
+                if json_lines || args.flag_pretty_json {
+                    println!("{}", sniff_error.to_json(Some(input)));
+                } else {
+                    werr!("{input}: {}", sniff_error.detail);
+                }
+                continue;
+            }
+        };
+
+        if let Some(ref mut wtr) = csv_wtr {
+            let (headers, values) = sniffed_to_csv(sniffed)?;
+            if !csv_headers_written {
+                wtr.write_record(&headers)?;
+                csv_headers_written = true;
+            }
+            wtr.write_record(&values)?;
+        } else if args.flag_pretty_json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&sniffed.to_json()).unwrap()
+            );
+        } else if json_lines {
+            println!("{}", serde_json::to_string(&sniffed.to_json()).unwrap());
+        } else {
+            println!("{sniffed}");
+        }
+
+        let file_violations = requirement_violations(&args, sniffed);
+        if !file_violations.is_empty() {
+            violations.push(format!("{}: {}", sniffed.path, file_violations.join("; ")));
+        }
+    }
+    if let Some(mut wtr) = csv_wtr {
+        wtr.flush()?;
+    }
+
+    let mut failures = Vec::new();
+    if num_errors > 0 {
+        let message = format!(
+            "{num_errors} of {} files could not be sniffed.",
+            inputs.len()
+        );
+        if harvest {
+            werr!("{message}");
+        } else {
+            failures.push(message);
+        }
+    }
+    if !violations.is_empty() {
+        failures.push(format!(
+            "The sniffed metadata doesn't meet the requirements:\n{}",
+            violations.join("\n")
+        ));
+    }
+    if !failures.is_empty() {
+        return fail_clierror!("{}", failures.join("\n"));
+    }
+    Ok(())
 }
@@ -18,7 +18,8 @@ arbitrarily large files, can be used instead.
 Simply put, sortcheck allows you to make informed choices on how to compose pipelines that
 require sorted data.
 
-Returns exit code 0 if a CSV is sorted, and exit code 1 otherwise.
+Returns exit code 0 if a CSV is sorted, and exit code 1 otherwise, returning the
+position of the first out-of-order record to stderr.
 
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_sortcheck.rs.
 
@@ -30,6 +31,8 @@ sort options:
     -s, --select <arg>      Select a subset of columns to check for sort.
                             See 'qsv select --help' for the format details.
     -i, --ignore-case       Compare strings disregarding case
+    -N, --numeric           Compare according to string numerical value
+    -R, --reverse           Check for descending (reverse) order
     --all                   Check all records. Do not stop/short-circuit the check 
                             on the first unsorted record.
     --json                  Return results in JSON format, scanning --all records. 
@@ -62,7 +65,10 @@ use indicatif::{HumanCount, ProgressBar, ProgressDrawTarget};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cmd::{dedup, sort::iter_cmp},
+    cmd::{
+        dedup,
+        sort::{iter_cmp, iter_cmp_num},
+    },
     config::{Config, Delimiter},
     select::SelectColumns,
     util, CliResult,
@@ -74,6 +80,8 @@ struct Args {
     arg_input:        Option<String>,
     flag_select:      SelectColumns,
     flag_ignore_case: bool,
+    flag_numeric:     bool,
+    flag_reverse:     bool,
     flag_all:         bool,
     flag_no_headers:  bool,
     flag_delimiter:   Option<Delimiter>,
@@ -134,6 +142,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     let mut scan_ctr: u64 = 0;
     let mut dupe_count: u64 = 0;
     let mut unsorted_breaks: u64 = 0;
+    let mut first_unsorted: u64 = 0;
 
     rdr.read_byte_record(&mut record)?;
     loop {
@@ -148,11 +157,19 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         };
         let a = sel.select(&record);
         let b = sel.select(&next_record);
-        let comparison = if ignore_case {
+        let comparison = if args.flag_numeric {
+            iter_cmp_num(a, b)
+        } else if ignore_case {
             dedup::iter_cmp_ignore_case(a, b)
         } else {
             iter_cmp(a, b)
         };
+        // when checking for descending order, the comparison is flipped
+        let comparison = if args.flag_reverse {
+            comparison.reverse()
+        } else {
+            comparison
+        };
 
         match comparison {
             cmp::Ordering::Equal => {
@@ -162,6 +179,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 record.clone_from(&next_record);
             }
             cmp::Ordering::Greater => {
+                if sorted {
+                    // next_record is the first out-of-order record
+                    first_unsorted = scan_ctr + 1;
+                }
                 sorted = false;
                 if args.flag_all || do_json {
                     unsorted_breaks += 1;
@@ -222,7 +243,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
 
     if !sorted {
-        return fail!("not sorted");
+        return fail_clierror!("not sorted. First out-of-order record: {first_unsorted}");
     }
 
     Ok(())
@@ -4,6 +4,13 @@ Splits the given CSV data into chunks.
 The files are written to the directory given with the name '{start}.csv',
 where {start} is the index of the first record of the chunk (starting at 0).
 
+With --chunks, the CSV is split into exactly N files with roughly equal numbers
+of records (handy to shard work across N workers), named '{chunk}.csv', where
+{chunk} is the number of the chunk (starting at 0). If the CSV has an index,
+it is used to compute the chunk boundaries. Otherwise, the CSV is read twice -
+once to count its records, and once more to split it (stdin is first copied
+to a temporary file).
+
 Usage:
     qsv split [options] <outdir> [<input>]
     qsv split --help
@@ -11,6 +18,8 @@ Usage:
 split options:
     -s, --size <arg>       The number of records to write into each chunk.
                            [default: 500]
+    -c, --chunks <n>       Split into exactly <n> files of roughly equal size,
+                           instead of files of --size records.
     -j, --jobs <arg>       The number of splitting jobs to run in parallel.
                            This only works when the given CSV data has
                            an index already created. Note that a file handle
@@ -42,7 +51,6 @@ use threadpool::ThreadPool;
 
 use crate::{
     config::{Config, Delimiter},
-    index::Indexed,
     util::{self, FilenameTemplate},
     CliResult,
 };
@@ -52,6 +60,7 @@ struct Args {
     arg_input:       Option<String>,
     arg_outdir:      String,
     flag_size:       usize,
+    flag_chunks:     Option<usize>,
     flag_jobs:       Option<usize>,
     flag_filename:   FilenameTemplate,
     flag_pad:        usize,
@@ -64,14 +73,47 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     if args.flag_size == 0 {
         return fail!("--size must be greater than 0.");
     }
+    if args.flag_chunks == Some(0) {
+        return fail!("--chunks must be greater than 0.");
+    }
     fs::create_dir_all(&args.arg_outdir)?;
 
-    match args.rconfig().indexed()? {
-        Some(idx) => args.parallel_split(&idx),
-        None => args.sequential_split(),
+    match (args.rconfig().indexed()?, args.flag_chunks) {
+        (Some(idx), Some(nchunks)) => {
+            let chunks = chunk_ranges(idx.count() as usize, nchunks);
+            args.parallel_split(chunks)
+        }
+        (Some(idx), None) => {
+            let nchunks = util::num_of_chunks(idx.count() as usize, args.flag_size);
+            let chunks = (0..nchunks)
+                .map(|i| (i * args.flag_size, i * args.flag_size, args.flag_size))
+                .collect();
+            args.parallel_split(chunks)
+        }
+        (None, Some(nchunks)) => args.sequential_chunks_split(nchunks),
+        (None, None) => args.sequential_split(),
     }
 }
 
+/// Splits `count` records into `nchunks` chunks whose lengths differ by at most one.
+/// Returns the (chunk number, start, length) of each chunk.
+fn chunk_ranges(count: usize, nchunks: usize) -> Vec<(usize, usize, usize)> {
+    let (base_len, remainder) = (count / nchunks, count % nchunks);
+    let mut start = 0;
+    (0..nchunks)
+        .map(|i| {
+            let len = if i < remainder {
+                base_len + 1
+            } else {
+                base_len
+            };
+            let chunk = (i, start, len);
+            start += len;
+            chunk
+        })
+        .collect()
+}
+
 impl Args {
     fn sequential_split(&self) -> CliResult<()> {
         let rconfig = self.rconfig();
@@ -93,24 +135,56 @@ impl Args {
         Ok(())
     }
 
+    fn sequential_chunks_split(&self, nchunks: usize) -> CliResult<()> {
+        // stdin can only be read once, and we need to read the CSV twice,
+        // so we copy it to a temporary file first
+        let stdin_tmpfile;
+        let rconfig = if self.rconfig().is_stdin() {
+            stdin_tmpfile = tempfile::NamedTempFile::new()?;
+            io::copy(&mut io::stdin().lock(), &mut stdin_tmpfile.as_file())?;
+            Config::new(&Some(stdin_tmpfile.path().to_string_lossy().to_string()))
+                .delimiter(self.flag_delimiter)
+                .no_headers(self.flag_no_headers)
+        } else {
+            self.rconfig()
+        };
+        let count = util::count_rows(&rconfig)? as usize;
+        let mut rdr = rconfig.reader()?;
+        let headers = rdr.byte_headers()?.clone();
+
+        let mut row = csv::ByteRecord::new();
+        for (chunk, _, len) in chunk_ranges(count, nchunks) {
+            let mut wtr = self.new_writer(&headers, chunk, self.flag_pad)?;
+            for _ in 0..len {
+                if !rdr.read_byte_record(&mut row)? {
+                    break;
+                }
+                wtr.write_byte_record(&row)?;
+            }
+            wtr.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Splits an indexed CSV in parallel, given the (name, start, length) of each chunk.
     #[allow(clippy::unnecessary_wraps)]
-    fn parallel_split(&self, idx: &Indexed<fs::File, fs::File>) -> CliResult<()> {
-        let nchunks = util::num_of_chunks(idx.count() as usize, self.flag_size);
+    fn parallel_split(&self, chunks: Vec<(usize, usize, usize)>) -> CliResult<()> {
         let pool = ThreadPool::new(util::njobs(self.flag_jobs));
-        for i in 0..nchunks {
+        for (name, start, len) in chunks {
             let args = self.clone();
             pool.execute(move || {
                 let conf = args.rconfig();
                 let mut idx = conf.indexed().unwrap().unwrap();
                 let headers = idx.byte_headers().unwrap().clone();
-                let mut wtr = args
-                    .new_writer(&headers, i * args.flag_size, args.flag_pad)
-                    .unwrap();
-
-                idx.seek((i * args.flag_size) as u64).unwrap();
-                for row in idx.byte_records().take(args.flag_size) {
-                    let row = row.unwrap();
-                    wtr.write_byte_record(&row).unwrap();
+                let mut wtr = args.new_writer(&headers, name, args.flag_pad).unwrap();
+
+                // empty chunks (when there are more chunks than records) have nothing to seek to
+                if len > 0 {
+                    idx.seek(start as u64).unwrap();
+                    for row in idx.byte_records().take(len) {
+                        let row = row.unwrap();
+                        wtr.write_byte_record(&row).unwrap();
+                    }
                 }
                 wtr.flush().unwrap();
             });
@@ -0,0 +1,227 @@
+static USAGE: &str = r#"
+Run blazing-fast Polars SQL queries against several CSVs - replete with joins,
+aggregations, grouping, sorting, window functions and more - on larger than
+memory CSV files.
+
+Each input CSV is registered as a table named after its file stem (e.g. `data.csv`
+is queryable as `data`). The tables can also be referenced by their positional
+alias `_t_N`, where N is the 1-based position of the input (e.g. `_t_1` for the
+first input, `_t_2` for the second, etc.). This is handy when the file stem is not
+a valid SQL identifier.
+
+Example queries:
+
+   qsv sqlp data.csv 'select * from data where col1 > 10 order by col2 desc limit 20'
+
+   qsv sqlp data.csv 'select col1, count(*) as cnt, sum(col3) from data group by col1'
+
+   qsv sqlp data.csv data2.csv 'select * from data join data2 on data.colname = data2.colname'
+
+   qsv sqlp data.csv data2.csv 'select * from _t_1 join _t_2 on _t_1.colname = _t_2.colname'
+
+The query result can also be written as a Parquet file, with its schema inferred from the
+input CSVs. E.g. to convert data.csv to a zstd-compressed Parquet file:
+
+   qsv sqlp data.csv 'select * from data' --format parquet --compression zstd -o data.parquet
+
+Or as an Arrow IPC (Feather v2) file, which can be memory-mapped directly by Python/R:
+
+   qsv sqlp data.csv 'select * from data' --format arrow --batch-size 10000 -o data.arrow
+
+Parquet files (with a .parquet extension) can also be used as inputs, and are registered
+as tables the same way as CSVs. E.g. to convert the first 100 rows of data.parquet to CSV:
+
+   qsv sqlp data.parquet 'select * from data limit 100'
+
+Returns the shape of the query result (number of rows, number of columns) to stderr.
+
+Usage:
+    qsv sqlp [options] <input>... <sql>
+    qsv sqlp --help
+
+sqlp arguments:
+    input                  The CSV and/or Parquet file/s to query. Stdin is not supported.
+    sql                    The SQL query to run. Each input file will be available as a
+                           table named after the file stem, or by its `_t_N` alias.
+
+sqlp options:
+    --nulls                When set, empty fields are treated as NULLs.
+                           Otherwise, empty fields are treated as empty strings.
+    --format <arg>         The output format of the query result.
+                           Valid values are: csv, parquet, arrow.
+                           Parquet and arrow output require the --output option.
+                           [default: csv]
+    --compression <arg>    The compression codec to use when writing Parquet files.
+                           Valid values are: uncompressed, snappy, gzip, lz4raw, zstd.
+                           [default: snappy]
+    --row-group-size <arg> The number of rows per row group when writing Parquet files.
+                           When not set, the whole result is written as one row group.
+    --batch-size <arg>     The number of rows per record batch when writing Arrow files.
+                           When not set, the whole result is written as one batch.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+    -Q, --quiet            Do not return result shape to stderr.
+"#;
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use polars::{prelude::*, sql::SQLContext};
+use serde::Deserialize;
+
+use crate::{config::Delimiter, util, CliResult};
+
+enum OutputFormat {
+    Csv,
+    Parquet(ParquetCompression),
+    Arrow,
+}
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:           Vec<String>,
+    arg_sql:             String,
+    flag_nulls:          bool,
+    flag_format:         String,
+    flag_compression:    String,
+    flag_row_group_size: Option<usize>,
+    flag_batch_size:     Option<usize>,
+    flag_output:         Option<String>,
+    flag_delimiter:      Option<Delimiter>,
+    flag_quiet:          bool,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let delim = if let Some(delimiter) = args.flag_delimiter {
+        delimiter.as_byte()
+    } else {
+        b','
+    };
+
+    let output_format = match args.flag_format.to_lowercase().as_str() {
+        "csv" => OutputFormat::Csv,
+        "parquet" => OutputFormat::Parquet(parse_compression(&args.flag_compression)?),
+        "arrow" => OutputFormat::Arrow,
+        _ => return fail_clierror!("Invalid --format: {}", args.flag_format),
+    };
+    if !matches!(output_format, OutputFormat::Csv) && args.flag_output.is_none() {
+        return fail_clierror!("{} output requires the --output option.", args.flag_format);
+    }
+    if args.flag_batch_size == Some(0) {
+        return fail_clierror!("--batch-size must be greater than zero.");
+    }
+
+    let mut ctx = SQLContext::try_new()?;
+
+    for (idx, table) in args.arg_input.iter().enumerate() {
+        let table_path = Path::new(table);
+        if !table_path.exists() {
+            return fail_clierror!("Input file '{table}' does not exist.");
+        }
+        let table_name = table_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let is_parquet = table_path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("parquet"));
+        let lf = if is_parquet {
+            LazyFrame::scan_parquet(table, ScanArgsParquet::default())?
+        } else {
+            LazyCsvReader::new(table)
+                .has_header(true)
+                .with_missing_is_null(args.flag_nulls)
+                .with_delimiter(delim)
+                .finish()?
+        };
+
+        // register the table under both its file stem & its positional alias
+        let table_alias = format!("_t_{}", idx + 1);
+        log::debug!("registering table {table_name} ({table_alias}) from {table}");
+        ctx.register(&table_name, lf.clone());
+        ctx.register(&table_alias, lf);
+    }
+
+    log::debug!("executing query: {}", args.arg_sql);
+
+    let mut query_result = ctx.execute(&args.arg_sql)?.collect()?;
+
+    // shape is the number of rows and columns
+    let query_shape = query_result.shape();
+
+    match output_format {
+        OutputFormat::Csv => {
+            // no need to use buffered writer here, as CsvWriter already does that
+            let mut out_writer = match args.flag_output {
+                Some(output_file) => {
+                    let path = Path::new(&output_file);
+                    Box::new(File::create(path)?) as Box<dyn Write>
+                }
+                None => Box::new(io::stdout()) as Box<dyn Write>,
+            };
+
+            CsvWriter::new(&mut out_writer)
+                .has_header(true)
+                .with_delimiter(delim)
+                .finish(&mut query_result)?;
+        }
+        OutputFormat::Parquet(compression) => {
+            // checked above that we have an output file
+            let file = File::create(args.flag_output.unwrap_or_default())?;
+            ParquetWriter::new(file)
+                .with_compression(compression)
+                .with_row_group_size(args.flag_row_group_size)
+                .with_statistics(true)
+                .finish(&mut query_result)?;
+        }
+        OutputFormat::Arrow => {
+            // each chunk of the DataFrame is written as a separate record batch
+            let mut batched_result = match args.flag_batch_size {
+                Some(batch_size) => rebatch(&query_result, batch_size)?,
+                None => query_result.agg_chunks(),
+            };
+            let file = File::create(args.flag_output.unwrap_or_default())?;
+            IpcWriter::new(file).finish(&mut batched_result)?;
+        }
+    }
+
+    if !args.flag_quiet {
+        eprintln!("{query_shape:?}");
+    }
+
+    Ok(())
+}
+
+/// Returns a copy of the DataFrame with one chunk for every `batch_size` rows.
+fn rebatch(df: &DataFrame, batch_size: usize) -> CliResult<DataFrame> {
+    let mut batched = df.slice(0, batch_size);
+    let mut offset = batch_size;
+    while offset < df.height() {
+        // vstack_mut appends the slice as a new chunk, without rechunking
+        batched.vstack_mut(&df.slice(offset as i64, batch_size))?;
+        offset += batch_size;
+    }
+    Ok(batched)
+}
+
+fn parse_compression(compression: &str) -> CliResult<ParquetCompression> {
+    Ok(match compression.to_lowercase().as_str() {
+        "uncompressed" => ParquetCompression::Uncompressed,
+        "snappy" => ParquetCompression::Snappy,
+        "gzip" => ParquetCompression::Gzip(None),
+        "lz4raw" => ParquetCompression::Lz4Raw,
+        "zstd" => ParquetCompression::Zstd(None),
+        _ => return fail_clierror!("Invalid --compression: {compression}"),
+    })
+}
@@ -13,7 +13,18 @@ on a stream of data (i.e., constant memory) and can work with arbitrarily large
 
 The following additional statistics require loading the entire file into memory:
 cardinality, mode/antimode, median, MAD, quartiles and its related measures (IQR,
-lower/upper fences & skewness).
+lower/upper fences & skewness), percentiles and histograms.
+
+Quartiles & percentiles can also be approximated in constant memory with the --approx
+option, which uses a t-digest (https://arxiv.org/abs/1902.04023) instead of sorting all
+the values of a column. Likewise, cardinality is estimated with HyperLogLog++
+(https://research.google/pubs/pub40671/) when --approx is set.
+Approximate results are typically within 1% of the exact value.
+--approx is only available in the full qsv variant, not in qsvlite & qsvdp.
+
+With --uniqueness, a "uniqueness_ratio" column is added after the cardinality. It is
+the cardinality divided by the number of records. A column with a uniqueness_ratio of 1
+only has unique values and is a candidate key.
 
 Note that an Out-Of-Memory (OOM) check heuristic will prevent processing if the file
 is larger than the available memory minus a headroom buffer of 20% (adjustable using the
@@ -26,11 +37,18 @@ It returns "*ALL" if all the values are unique, and only returns a preview of th
 If you need all the antimode values of a column, run the `frequency` command with --limit set
 to zero. The resulting frequency table will have all the antimode values.
 
+Data quality indicators are also available with the --quality option:
+  whitespace_count    - the number of values with leading and/or trailing whitespace.
+  leading_zero_count  - the number of numeric-looking values with leading zeros (e.g. 007).
+                        Note that integers with leading zeros are inferred as Strings.
+  mixed_types         - true if the column has a mix of numeric, date & string values.
+                        Dates are only considered if --infer-dates is enabled for the column.
+
 Summary statistics for dates are also computed when --infer-dates is enabled, with DateTime
 results in rfc3339 format and Date results in "yyyy-mm-dd" format in the UTC timezone.
-Date range, stddev, MAD & IQR are returned in days, not timestamp milliseconds. Date variance
-is currently not computed as the current streaming variance algorithm is not well suited to 
-unix epoch timestamp values.
+The date range is the time span covered by the column. Like the stddev, MAD & IQR, it is
+returned in days, not timestamp milliseconds. Date variance is currently not computed as
+the current streaming variance algorithm is not well suited to unix epoch timestamp values.
 
 Each column's data type is also inferred (NULL, Integer, String, Float, Date & DateTime).
 Unlike the sniff command, stats' data type inferences are GUARANTEED, as the entire file
@@ -46,6 +64,13 @@ https://github.com/jqnatividad/belt/tree/main/dateparser#accepted-date-formats.
 Computing statistics on a large file can be made much faster if you create an index for it
 first with 'qsv index' to enable multithreading.
 
+With --cache, the statistics of a file are also cached in "<file>.stats.csv", along with
+its metadata in "<file>.stats.csv.json". The cache is reused as long as the file and the
+statistics options are unchanged. The `schema` and `tojsonl` commands also reuse the cache
+when it has the statistics they need (i.e. --cardinality with the same date inference
+options), as do `frequency` (--cardinality) and `outliers` (--quartiles for iqr,
+--median & --mad for zscore) when the cache has the default statistics options.
+
 For examples, see the "boston311" test files in https://github.com/jqnatividad/qsv/tree/master/resources/test
 and https://github.com/jqnatividad/qsv/blob/f7f9c4297fb3dea685b5d0f631932b6b2ca4a99a/tests/test_stats.rs#L544.
 
@@ -58,14 +83,18 @@ stats options:
                               See 'qsv select --help' for the format details.
                               This is provided here because piping 'qsv select'
                               into 'qsv stats' will disable the use of indexing.
-    --everything              Show all statistics available.
+    --everything              Show all statistics available, except for percentiles,
+                              histograms & data quality indicators.
     --typesonly               Infer data types only and do not compute statistics.
                               Note that if you want to infer dates, you'll still need to use
                               the --infer-dates and --dates-whitelist options.
     --mode                    Show the mode/s & antimode/s. Multimodal-aware.
                               This requires loading all CSV data in memory.
     --cardinality             Show the cardinality.
-                              This requires loading all CSV data in memory.
+                              This requires loading all CSV data in memory,
+                              unless --approx is set.
+    --uniqueness              Show the uniqueness ratio after the cardinality.
+                              Implies --cardinality. Not included in --everything.
     --median                  Show the median.
                               This requires loading all CSV data in memory.
     --mad                     Shows the median absolute deviation (MAD).
@@ -73,11 +102,47 @@ stats options:
     --quartiles               Show the quartiles, the IQR, the lower/upper inner/outer
                               fences and skewness.
                               This requires loading all CSV data in memory.
+    --percentiles <list>      Show the given comma-separated list of percentiles (0-100).
+                              e.g. "5,50,95,99". Each percentile is returned in its own
+                              "p<percentile>" column (e.g. p5, p50, p95, p99).
+                              This requires loading all CSV data in memory.
+    --histogram <bins>        Show a histogram of numeric & date columns with <bins> bins.
+                              The bin boundaries are returned in the "histogram_bins" column
+                              and the number of values in each bin in "histogram_counts".
+                              Each bin includes its lower boundary, but not its upper
+                              boundary - except for the last bin which includes both.
+                              This requires loading all CSV data in memory.
+    --quantile-bins           Use quantile (equal-frequency) histogram bins instead of
+                              equal-width bins.
+    --quality                 Show the data quality indicators (whitespace_count,
+                              leading_zero_count & mixed_types).
+    --approx                  Approximate the quartiles & percentiles using a t-digest
+                              and the cardinality using HyperLogLog++, so they can be
+                              computed in constant memory.
     --round <decimal_places>  Round statistics to <decimal_places>. Rounding is done following
                               Midpoint Nearest Even (aka "Bankers Rounding") rule.
                               For dates - range, stddev & IQR are always at least 5 decimal places as
                               they are reported in days, and 5 places gives us millisecond precision.
                               [default: 4]
+    --decimal-comma           Parse numbers with a decimal comma & optional period
+                              thousands separators (e.g. 1.234,56), as used in many
+                              European locales. Values that are not valid numbers in this
+                              format (e.g. dates like 15.02.2023) are left as is, and
+                              values with a period that is not a thousands separator
+                              (e.g. 1.5) are not numbers.
+    --weight <column>         Compute the weighted sum, mean, stddev, variance &
+                              percentiles of numeric columns, using the values of
+                              <column> as the weights (e.g. survey sampling weights).
+                              Rows with a missing, negative or invalid weight are
+                              excluded from the weighted statistics. The other
+                              statistics (e.g. the median & quartiles) are not weighted.
+                              Weighted percentiles are always exact.
+    --groupby <selection>     Compute the statistics of each group of rows with the same
+                              values in the <selection> key columns, instead of the whole
+                              file. The key columns are output before the "field" column
+                              and are not summarized themselves. The groups are output in
+                              the order they are first seen.
+                              Grouped statistics are computed sequentially & not cached.
     --nulls                   Include NULLs in the population size for computing
                               mean and standard deviation.
     --infer-dates             Infer date/datetime datatypes. This is an expensive
@@ -102,7 +167,13 @@ stats options:
                               to RFC3339 format.
                               [default: date,time,due,open,close,created]
     --prefer-dmy              Parse dates in dmy format. Otherwise, use mdy format.
+                              Can also be set with the QSV_PREFER_DMY environment
+                              variable, like the sniff & schema commands.
                               Ignored if --infer-dates is false.
+    --force                   Recompute the statistics, even if the stats cache is valid.
+    --cache                   Save the statistics in the stats cache of the input, for
+                              later runs & the commands that reuse it.
+                              Failing to write the cache is not an error.
     -j, --jobs <arg>          The number of jobs to run in parallel.
                               This works only when the given CSV has an index.
                               Note that a file handle is opened for each job.
@@ -141,19 +212,30 @@ with ~470 tests.
 
 use std::{
     borrow::ToOwned,
+    collections::hash_map::DefaultHasher,
     default::Default,
-    fmt, io,
-    iter::repeat,
+    fmt, fs,
+    hash::BuildHasherDefault,
+    io,
+    iter::{once, repeat},
+    path::{Path, PathBuf},
     str::{self, FromStr},
     sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, UNIX_EPOCH},
 };
 
+#[cfg(feature = "full")]
+use hyperloglogplus::{HyperLogLog, HyperLogLogPlus};
+use indexmap::IndexMap;
 use itertools::Itertools;
 use once_cell::sync::OnceCell;
 use qsv_dateparser::parse_with_preference;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use simdutf8::basic::from_utf8;
 use stats::{merge_all, Commute, MinMax, OnlineStats, Unsorted};
+#[cfg(feature = "full")]
+use tdigest::TDigest;
 use threadpool::ThreadPool;
 
 use self::FieldType::{TDate, TDateTime, TFloat, TInteger, TNull, TString};
@@ -172,14 +254,25 @@ pub struct Args {
     pub flag_typesonly:       bool,
     pub flag_mode:            bool,
     pub flag_cardinality:     bool,
+    pub flag_uniqueness:      bool,
     pub flag_median:          bool,
     pub flag_mad:             bool,
     pub flag_quartiles:       bool,
+    pub flag_percentiles:     Option<String>,
+    pub flag_histogram:       Option<usize>,
+    pub flag_quantile_bins:   bool,
+    pub flag_quality:         bool,
+    pub flag_approx:          bool,
     pub flag_round:           u32,
+    pub flag_decimal_comma:   bool,
+    pub flag_weight:          Option<SelectColumns>,
+    pub flag_groupby:         Option<SelectColumns>,
     pub flag_nulls:           bool,
     pub flag_infer_dates:     bool,
     pub flag_dates_whitelist: String,
     pub flag_prefer_dmy:      bool,
+    pub flag_force:           bool,
+    pub flag_cache:           bool,
     pub flag_jobs:            Option<usize>,
     pub flag_output:          Option<String>,
     pub flag_no_headers:      bool,
@@ -190,12 +283,22 @@ pub struct Args {
 static INFER_DATE_FLAGS: once_cell::sync::OnceCell<Vec<bool>> = OnceCell::new();
 static DMY_PREFERENCE: AtomicBool = AtomicBool::new(false);
 static RECORD_COUNT: once_cell::sync::OnceCell<u64> = OnceCell::new();
+static PERCENTILE_LIST: once_cell::sync::OnceCell<Vec<f64>> = OnceCell::new();
 
 // number of milliseconds per day
 const MS_IN_DAY: f64 = 86_400_000.0;
 // number of decimal places when rounding days
 // 5 decimal places give us millisecond precision
 const DAY_DECIMAL_PLACES: u32 = 5;
+// t-digest compression factor, higher values are more accurate but use more memory
+#[cfg(feature = "full")]
+const TDIGEST_SIZE: usize = 100;
+// number of values buffered before they're merged into the t-digest
+#[cfg(feature = "full")]
+const TDIGEST_BUFFER_SIZE: usize = 10_000;
+// HyperLogLog++ precision, 2^14 registers gives us a standard error of ~0.8%
+#[cfg(feature = "full")]
+const HLL_PRECISION: u8 = 14;
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let mut args: Args = util::get_args(USAGE, argv)?;
@@ -203,28 +306,98 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.flag_everything = false;
         args.flag_mode = false;
         args.flag_cardinality = false;
+        args.flag_uniqueness = false;
         args.flag_median = false;
         args.flag_quartiles = false;
+        args.flag_percentiles = None;
+        args.flag_histogram = None;
+        args.flag_quality = false;
         args.flag_mad = false;
     }
 
+    #[cfg(not(feature = "full"))]
+    if args.flag_approx {
+        return fail_clierror!("--approx is only available in the full qsv variant.");
+    }
+
+    // validate the percentiles early, as the stats cache may be used instead
+    parse_percentiles(args.flag_percentiles.as_deref())?;
+    if args.flag_histogram == Some(0) {
+        return fail_clierror!("--histogram must have at least one bin.");
+    }
+
     let mut wtr = Config::new(&args.flag_output).writer()?;
     let fconfig = args.rconfig();
+
+    if !args.flag_force && args.flag_groupby.is_none() {
+        if let Some((cached_headers, cached_stats)) = args.cached_stats() {
+            // the cache can be reused if it has all the requested statistics
+            let stat_headers = args.stat_headers();
+            let cached_columns = stat_headers
+                .iter()
+                .map(|h| cached_headers.iter().position(|c| c == h.as_bytes()))
+                .collect::<Option<Vec<usize>>>();
+            if let Some(cached_columns) = cached_columns {
+                log::info!("using the stats cache...");
+                wtr.write_record(&stat_headers)?;
+                for cached_stat in &cached_stats {
+                    wtr.write_record(cached_columns.iter().map(|&i| &cached_stat[i]))?;
+                }
+                wtr.flush()?;
+                return Ok(());
+            }
+        }
+    }
+
     let record_count = RECORD_COUNT.get_or_init(|| util::count_rows(&fconfig).unwrap());
 
     if let Some(path) = fconfig.path.clone() {
         // we're loading the entire file into memory, we need to check avail mem
         if args.flag_everything
             || args.flag_mode
-            || args.flag_cardinality
+            || (args.flag_cardinality && !args.flag_approx)
             || args.flag_median
-            || args.flag_quartiles
+            || (args.flag_quartiles && !args.flag_approx)
+            || (args.flag_percentiles.is_some()
+                && (!args.flag_approx || args.flag_weight.is_some()))
+            || args.flag_histogram.is_some()
             || args.flag_mad
         {
             util::mem_file_check(&path, false, args.flag_no_memcheck)?;
         }
     }
 
+    if let Some(groupby) = args.flag_groupby.as_ref() {
+        log::info!("scanning {record_count} records by group...");
+        let (key_headers, headers, groups) =
+            args.grouped_stats(groupby, &args.flag_dates_whitelist)?;
+        let stat_headers = args.stat_headers();
+        wtr.write_record(
+            key_headers
+                .iter()
+                .chain(stat_headers.iter().map(str::as_bytes)),
+        )?;
+        for (key, group_count, stats) in groups {
+            // the sparsity & uniqueness ratio are relative to the rows of the group
+            for (i, (header, mut stat)) in headers.iter().zip(stats).enumerate() {
+                let stat = stat.to_record(args.flag_round, group_count);
+                let header = if args.flag_no_headers {
+                    i.to_string().into_bytes()
+                } else {
+                    header.to_vec()
+                };
+                wtr.write_record(
+                    key.iter()
+                        .map(Vec::as_slice)
+                        .chain(once(&*header))
+                        .chain(stat.iter().map(str::as_bytes)),
+                )?;
+            }
+        }
+        wtr.flush()?;
+        return Ok(());
+    }
+
     log::info!("scanning {record_count} records...");
     let (headers, stats) = match fconfig.indexed()? {
         None => args.sequential_stats(&args.flag_dates_whitelist),
@@ -242,8 +415,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
     }?;
     let stats = args.stats_to_records(stats);
+    let stat_headers = args.stat_headers();
 
-    wtr.write_record(&args.stat_headers())?;
+    let mut stat_records = Vec::with_capacity(stats.len());
     let fields = headers.iter().zip(stats.into_iter());
     for (i, (header, stat)) in fields.enumerate() {
         let header = if args.flag_no_headers {
@@ -252,25 +426,104 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             header.to_vec()
         };
         let stat = stat.iter().map(str::as_bytes);
-        wtr.write_record(vec![&*header].into_iter().chain(stat))?;
+        stat_records.push(
+            vec![&*header]
+                .into_iter()
+                .chain(stat)
+                .collect::<csv::ByteRecord>(),
+        );
+    }
+
+    wtr.write_record(&stat_headers)?;
+    for stat_record in &stat_records {
+        wtr.write_byte_record(stat_record)?;
     }
     wtr.flush()?;
+
+    if args.flag_cache {
+        args.write_stats_cache(&stat_headers, &stat_records, *record_count);
+    }
     Ok(())
 }
 
+/// The metadata of a stats cache, used to check if the cache is still valid.
+#[derive(Serialize, Deserialize)]
+struct StatsCacheMeta {
+    qsv_version:   String,
+    file_size:     u64,
+    file_modified: Duration,
+    options:       serde_json::Value,
+    record_count:  u64,
+}
+
+/// The stats cache of `<file>` is saved in `<file>.stats.csv`, with its metadata in
+/// `<file>.stats.csv.json`.
+fn stats_cache_paths(path: &Path) -> (PathBuf, PathBuf) {
+    let cache_csv = path.with_extension("stats.csv");
+    let mut cache_meta = cache_csv.clone().into_os_string();
+    cache_meta.push(".json");
+    (cache_csv, PathBuf::from(cache_meta))
+}
+
+/// returns the size & the modified time of a file, which we use as its fingerprint
+fn file_fingerprint(path: &Path) -> Option<(u64, Duration)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((metadata.len(), modified))
+}
+
 impl Args {
+    /// the arguments of a plain `qsv stats <input>` run, e.g. to look up its stats cache.
+    pub fn new(arg_input: Option<String>, delimiter: Option<Delimiter>, no_headers: bool) -> Self {
+        Self {
+            arg_input,
+            flag_select: SelectColumns::parse("").unwrap(),
+            flag_everything: false,
+            flag_typesonly: false,
+            flag_mode: false,
+            flag_cardinality: false,
+            flag_uniqueness: false,
+            flag_median: false,
+            flag_mad: false,
+            flag_quartiles: false,
+            flag_percentiles: None,
+            flag_histogram: None,
+            flag_quantile_bins: false,
+            flag_quality: false,
+            flag_approx: false,
+            flag_round: 4,
+            flag_decimal_comma: false,
+            flag_weight: None,
+            flag_groupby: None,
+            flag_nulls: false,
+            flag_infer_dates: false,
+            flag_dates_whitelist: "all".to_string(),
+            flag_prefer_dmy: false,
+            flag_force: false,
+            flag_cache: false,
+            flag_jobs: None,
+            flag_output: None,
+            flag_no_headers: no_headers,
+            flag_delimiter: delimiter,
+            flag_no_memcheck: false,
+        }
+    }
+
     pub fn sequential_stats(&self, whitelist: &str) -> CliResult<(csv::ByteRecord, Vec<Stats>)> {
-        let mut rdr = self.rconfig().reader()?;
+        let rconfig = self.rconfig();
+        let mut rdr = rconfig.reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
 
         init_date_inference(
             self.flag_infer_dates,
-            self.flag_prefer_dmy,
+            self.flag_prefer_dmy || rconfig.get_dmy_preference(),
             &headers,
             whitelist,
         )?;
+        init_percentiles(self.flag_percentiles.as_deref())?;
+        let weight = self.weight_index(rdr.byte_headers()?)?;
 
-        let stats = self.compute(&sel, rdr.byte_records());
+        let stats = self.compute(&sel, weight, rdr.byte_records());
         Ok((headers, stats))
     }
 
@@ -285,15 +538,18 @@ impl Args {
             return self.sequential_stats(whitelist);
         }
 
-        let mut rdr = self.rconfig().reader()?;
+        let rconfig = self.rconfig();
+        let mut rdr = rconfig.reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
 
         init_date_inference(
             self.flag_infer_dates,
-            self.flag_prefer_dmy,
+            self.flag_prefer_dmy || rconfig.get_dmy_preference(),
             &headers,
             whitelist,
         )?;
+        init_percentiles(self.flag_percentiles.as_deref())?;
+        let weight = self.weight_index(rdr.byte_headers()?)?;
 
         let chunk_size = util::chunk_size(idx_count as usize, util::njobs(self.flag_jobs));
         let nchunks = util::num_of_chunks(idx_count as usize, chunk_size);
@@ -316,7 +572,7 @@ impl Args {
                     idx.seek((i * chunk_size) as u64)
                         .expect("File seek failed.");
                     let it = idx.byte_records().take(chunk_size);
-                    send.send(args.compute(&sel, it)).unwrap_unchecked();
+                    send.send(args.compute(&sel, weight, it)).unwrap_unchecked();
                 });
             }
         }
@@ -326,6 +582,7 @@ impl Args {
 
     pub fn stats_to_records(&self, stats: Vec<Stats>) -> Vec<csv::StringRecord> {
         let round_places = self.flag_round;
+        let record_count = *RECORD_COUNT.get().unwrap_or(&1);
         let mut records = Vec::with_capacity(stats.len());
         records.extend(repeat(csv::StringRecord::new()).take(stats.len()));
         let pool = ThreadPool::new(util::njobs(self.flag_jobs));
@@ -334,7 +591,10 @@ impl Args {
             let (send, recv) = channel::bounded(0);
             results.push(recv);
             pool.execute(move || {
-                unsafe { send.send(stat.to_record(round_places)).unwrap_unchecked() };
+                unsafe {
+                    send.send(stat.to_record(round_places, record_count))
+                        .unwrap_unchecked()
+                };
             });
         }
         for (i, recv) in results.into_iter().enumerate() {
@@ -344,12 +604,22 @@ impl Args {
     }
 
     #[inline]
-    fn compute<I>(&self, sel: &Selection, it: I) -> Vec<Stats>
+    fn compute<I>(&self, sel: &Selection, weight: Option<usize>, it: I) -> Vec<Stats>
     where
         I: Iterator<Item = csv::Result<csv::ByteRecord>>,
     {
         let mut stats = self.new_stats(sel.len());
 
+        if self.flag_decimal_comma || weight.is_some() {
+            for row in it {
+                // safety: the reader only yields valid records
+                let row = unsafe { row.unwrap_unchecked() };
+                let row_weight = weight.and_then(|w| self.parse_weight(row.get(w)?));
+                self.add_fields(&mut stats, sel.select(&row), row_weight);
+            }
+            return stats;
+        }
+
         // safety: we use unsafe in this hot loop so we skip unnecessary bounds checking
         // that we are certain is safe
         unsafe {
@@ -366,6 +636,125 @@ impl Args {
         stats
     }
 
+    /// adds the fields of a row to their stats, converting decimal commas
+    /// and adding the weighted samples if requested
+    #[inline]
+    fn add_fields<'a>(
+        &self,
+        stats: &mut [Stats],
+        fields: impl Iterator<Item = &'a [u8]>,
+        row_weight: Option<f64>,
+    ) {
+        for (i, field) in fields.enumerate() {
+            // safety: stats has one entry per selected column, the same as INFER_DATE_FLAGS
+            let infer_date = unsafe { *INFER_DATE_FLAGS.get_unchecked().get_unchecked(i) };
+            let stat = unsafe { stats.get_unchecked_mut(i) };
+            let (number, numeric) = if self.flag_decimal_comma {
+                match decimal_comma_to_point(field) {
+                    Some(number) => (Some(number), true),
+                    // the period is the thousands separator, so e.g. "1.5" is not a number
+                    None => (None, !field.contains(&b'.')),
+                }
+            } else {
+                (None, true)
+            };
+            let sample = number.as_ref().map_or(field, String::as_bytes);
+            stat.add(sample, infer_date, numeric);
+            if let Some(row_weight) = row_weight.filter(|_| numeric) {
+                stat.add_weighted(sample, row_weight);
+            }
+        }
+    }
+
+    /// computes the stats of each group of rows with the same --groupby key values,
+    /// in the order the groups are first seen.
+    /// Returns the key headers, the headers of the summarized columns & for each group,
+    /// its key values, record count & stats.
+    #[allow(clippy::type_complexity)]
+    fn grouped_stats(
+        &self,
+        groupby: &SelectColumns,
+        whitelist: &str,
+    ) -> CliResult<(
+        csv::ByteRecord,
+        csv::ByteRecord,
+        Vec<(Vec<Vec<u8>>, u64, Vec<Stats>)>,
+    )> {
+        let rconfig = self.rconfig();
+        let mut rdr = rconfig.reader()?;
+        let all_headers = rdr.byte_headers()?.clone();
+        let keys = groupby.selection(&all_headers, !self.flag_no_headers)?;
+        // the key columns are not summarized
+        let columns: Vec<usize> = rconfig
+            .selection(&all_headers)?
+            .iter()
+            .copied()
+            .filter(|i| !keys.contains(i))
+            .collect();
+        let key_headers: csv::ByteRecord = if self.flag_no_headers {
+            keys.iter().map(ToString::to_string).collect()
+        } else {
+            keys.select(&all_headers).collect()
+        };
+        let headers: csv::ByteRecord = columns.iter().map(|&i| &all_headers[i]).collect();
+
+        init_date_inference(
+            self.flag_infer_dates,
+            self.flag_prefer_dmy || rconfig.get_dmy_preference(),
+            &headers,
+            whitelist,
+        )?;
+        init_percentiles(self.flag_percentiles.as_deref())?;
+        let weight = self.weight_index(&all_headers)?;
+
+        let mut groups: IndexMap<Vec<Vec<u8>>, (u64, Vec<Stats>)> = IndexMap::new();
+        let mut row = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut row)? {
+            let key: Vec<Vec<u8>> = keys.select(&row).map(<[u8]>::to_vec).collect();
+            let (group_count, stats) = groups
+                .entry(key)
+                .or_insert_with(|| (0, self.new_stats(columns.len())));
+            *group_count += 1;
+            let row_weight = weight.and_then(|w| self.parse_weight(row.get(w)?));
+            self.add_fields(stats, columns.iter().map(|&i| &row[i]), row_weight);
+        }
+        let groups = groups
+            .into_iter()
+            .map(|(key, (group_count, stats))| (key, group_count, stats))
+            .collect();
+        Ok((key_headers, headers, groups))
+    }
+
+    /// returns the index of the --weight column, if one was given
+    fn weight_index(&self, headers: &csv::ByteRecord) -> CliResult<Option<usize>> {
+        let Some(weight) = self.flag_weight.as_ref() else {
+            return Ok(None);
+        };
+        let sel = weight.selection(headers, !self.flag_no_headers)?;
+        if sel.len() != 1 {
+            return fail_clierror!(
+                "--weight must select exactly one column. {} columns selected.",
+                sel.len()
+            );
+        }
+        Ok(Some(sel[0]))
+    }
+
+    /// parses a weight, returning None if it's not a valid, non-negative number
+    fn parse_weight(&self, field: &[u8]) -> Option<f64> {
+        let number = if self.flag_decimal_comma {
+            let number = decimal_comma_to_point(field);
+            if number.is_none() && field.contains(&b'.') {
+                return None;
+            }
+            number
+        } else {
+            None
+        };
+        from_bytes::<f64>(number.as_ref().map_or(field, String::as_bytes))
+            .filter(|w| w.is_finite() && *w >= 0.0)
+    }
+
     fn sel_headers<R: io::Read>(
         &self,
         rdr: &mut csv::Reader<R>,
@@ -375,6 +764,113 @@ impl Args {
         Ok((sel.select(&headers).collect(), sel))
     }
 
+    /// the options that change the computed statistics.
+    /// Options that only add statistics are checked against the cached stats columns instead.
+    fn cache_options(&self) -> serde_json::Value {
+        let infer_dates = self.flag_infer_dates;
+        json!({
+            "select": format!("{:?}", self.flag_select),
+            "no_headers": self.flag_no_headers,
+            "delimiter": self.flag_delimiter.map(Delimiter::as_byte),
+            "nulls": self.flag_nulls,
+            "round": self.flag_round,
+            "decimal_comma": self.flag_decimal_comma,
+            "weight": self.flag_weight.as_ref().map(|w| format!("{w:?}")),
+            "approx": self.flag_approx,
+            "histogram": self.flag_histogram,
+            "quantile_bins": self.flag_quantile_bins,
+            "infer_dates": infer_dates,
+            "dates_whitelist": if infer_dates {
+                self.flag_dates_whitelist.to_lowercase()
+            } else {
+                String::new()
+            },
+            "prefer_dmy": infer_dates
+                && (self.flag_prefer_dmy || self.rconfig().get_dmy_preference()),
+        })
+    }
+
+    /// returns the cached stat headers & stats records (both starting with the "field"
+    /// column) of the input, if its stats cache is still valid for these options.
+    /// The cached stats may have more or less statistics than requested, so callers
+    /// need to check the stat headers.
+    pub fn cached_stats(&self) -> Option<(csv::ByteRecord, Vec<csv::ByteRecord>)> {
+        self.valid_cache_meta()?;
+        let (cache_csv, _) = stats_cache_paths(&self.rconfig().path?);
+        let mut rdr = csv::Reader::from_path(cache_csv).ok()?;
+        let stat_headers = rdr.byte_headers().ok()?.clone();
+        let stat_records = rdr.byte_records().collect::<Result<Vec<_>, _>>().ok()?;
+        Some((stat_headers, stat_records))
+    }
+
+    /// returns the number of records of the input, if its stats cache is still valid
+    /// for these options.
+    pub fn cached_record_count(&self) -> Option<u64> {
+        Some(self.valid_cache_meta()?.record_count)
+    }
+
+    /// returns the metadata of the stats cache of the input, if the cache is still valid
+    /// for the input & these options.
+    fn valid_cache_meta(&self) -> Option<StatsCacheMeta> {
+        let path = self.rconfig().path?;
+        let (_, cache_meta) = stats_cache_paths(&path);
+        let meta: StatsCacheMeta = serde_json::from_slice(&fs::read(cache_meta).ok()?).ok()?;
+        let (file_size, file_modified) = file_fingerprint(&path)?;
+        if meta.qsv_version != env!("CARGO_PKG_VERSION")
+            || meta.file_size != file_size
+            || meta.file_modified != file_modified
+            || meta.options != self.cache_options()
+        {
+            return None;
+        }
+        Some(meta)
+    }
+
+    /// Saves the stats in the stats cache of the input. Failing to do so is not an error,
+    /// as it only makes the next invocation slower.
+    fn write_stats_cache(
+        &self,
+        stat_headers: &csv::StringRecord,
+        stat_records: &[csv::ByteRecord],
+        record_count: u64,
+    ) {
+        let Some(path) = self.rconfig().path else {
+            return;
+        };
+        let Some((file_size, file_modified)) = file_fingerprint(&path) else {
+            return;
+        };
+        let (cache_csv, cache_meta) = stats_cache_paths(&path);
+        let meta = StatsCacheMeta {
+            qsv_version: env!("CARGO_PKG_VERSION").to_string(),
+            file_size,
+            file_modified,
+            options: self.cache_options(),
+            record_count,
+        };
+
+        let write_cache = || -> CliResult<()> {
+            // remove the old metadata first, so a partially written cache is never used
+            if cache_meta.exists() {
+                fs::remove_file(&cache_meta)?;
+            }
+            let mut wtr = csv::Writer::from_path(&cache_csv)?;
+            wtr.write_record(stat_headers)?;
+            for stat_record in stat_records {
+                wtr.write_byte_record(stat_record)?;
+            }
+            wtr.flush()?;
+            fs::write(
+                &cache_meta,
+                serde_json::to_string_pretty(&meta).map_err(|e| e.to_string())?,
+            )?;
+            Ok(())
+        };
+        if let Err(e) = write_cache() {
+            log::warn!("Cannot cache the stats in {}: {e}", cache_csv.display());
+        }
+    }
+
     pub fn rconfig(&self) -> Config {
         Config::new(&self.arg_input)
             .delimiter(self.flag_delimiter)
@@ -391,11 +887,20 @@ impl Args {
                 sum:           !self.flag_typesonly,
                 range:         !self.flag_typesonly,
                 dist:          !self.flag_typesonly,
-                cardinality:   self.flag_everything || self.flag_cardinality,
+                cardinality:   self.flag_everything
+                    || self.flag_cardinality
+                    || self.flag_uniqueness,
+                uniqueness:    self.flag_uniqueness,
                 median:        !self.flag_everything && self.flag_median && !self.flag_quartiles,
                 mad:           self.flag_everything || self.flag_mad,
                 quartiles:     self.flag_everything || self.flag_quartiles,
+                percentiles:   self.flag_percentiles.is_some(),
+                histogram:     self.flag_histogram.unwrap_or_default(),
+                quantile_bins: self.flag_quantile_bins,
+                approx:        self.flag_approx,
                 mode:          self.flag_everything || self.flag_mode,
+                quality:       self.flag_quality,
+                weighted:      self.flag_weight.is_some() && !self.flag_typesonly,
                 typesonly:     self.flag_typesonly,
             }))
             .take(record_len),
@@ -445,9 +950,22 @@ impl Args {
                 "skewness",
             ]);
         }
-        if self.flag_cardinality || all {
+        let percentile_headers = parse_percentiles(self.flag_percentiles.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .map(|p| format!("p{p}"))
+            .collect_vec();
+        fields.extend(percentile_headers.iter().map(String::as_str));
+        if self.flag_cardinality || self.flag_uniqueness || all {
             fields.push("cardinality");
         }
+        if self.flag_uniqueness {
+            fields.push("uniqueness_ratio");
+        }
+        if self.flag_histogram.is_some() {
+            fields.push("histogram_bins");
+            fields.push("histogram_counts");
+        }
         if self.flag_mode || all {
             fields.push("mode");
             fields.push("mode_count");
@@ -456,6 +974,11 @@ impl Args {
             fields.push("antimode_count");
             fields.push("antimode_occurrences");
         }
+        if self.flag_quality {
+            fields.push("whitespace_count");
+            fields.push("leading_zero_count");
+            fields.push("mixed_types");
+        }
         csv::StringRecord::from(fields)
     }
 }
@@ -468,14 +991,13 @@ fn init_date_inference(
     flag_whitelist: &str,
 ) -> Result<(), String> {
     if infer_dates {
-        let dmy_preferred = prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok();
-        DMY_PREFERENCE.store(dmy_preferred, Ordering::Relaxed);
+        DMY_PREFERENCE.store(prefer_dmy, Ordering::Relaxed);
 
         let whitelist_lower = flag_whitelist.to_lowercase();
         log::info!("inferring dates with date-whitelist: {whitelist_lower}");
 
         if whitelist_lower == "all" {
-            log::info!("inferring dates for ALL fields with DMY preference: {dmy_preferred}");
+            log::info!("inferring dates for ALL fields with DMY preference: {prefer_dmy}");
             if let Err(e) = INFER_DATE_FLAGS.set(vec![true; headers.len()]) {
                 return fail_format!("Cannot init date inference flags for ALL fields: {e:?}");
             };
@@ -493,7 +1015,7 @@ fn init_date_inference(
                     if header_str.contains(whitelist_item) {
                         date_found = true;
                         log::info!(
-                            "inferring dates for {header_str} with DMY preference: {dmy_preferred}"
+                            "inferring dates for {header_str} with DMY preference: {prefer_dmy}"
                         );
                         break;
                     }
@@ -511,6 +1033,76 @@ fn init_date_inference(
     Ok(())
 }
 
+fn parse_percentiles(flag_percentiles: Option<&str>) -> Result<Vec<f64>, String> {
+    let mut percentile_list = Vec::new();
+    if let Some(list) = flag_percentiles {
+        for p in list.split(',') {
+            let p = p.trim();
+            match p.parse::<f64>() {
+                Ok(pct) if (0.0..=100.0).contains(&pct) => percentile_list.push(pct),
+                _ => {
+                    return fail_format!(
+                        "Invalid percentile: \"{p}\". Percentiles must be between 0 and 100."
+                    )
+                }
+            }
+        }
+    }
+    Ok(percentile_list)
+}
+
+#[inline]
+fn init_percentiles(flag_percentiles: Option<&str>) -> Result<(), String> {
+    let percentile_list = parse_percentiles(flag_percentiles)?;
+    if let Err(e) = PERCENTILE_LIST.set(percentile_list) {
+        return fail_format!("Cannot init percentile list: {e:?}");
+    };
+    Ok(())
+}
+
+/// converts a number with a decimal comma & optional period thousands separators
+/// (e.g. "-1.234.567,89") to one with a decimal point (e.g. "-1234567.89").
+/// Returns None if the field is not such a number, or has no separators to convert.
+fn decimal_comma_to_point(field: &[u8]) -> Option<String> {
+    let (sign, digits) = match field.first() {
+        Some(b'-' | b'+') => field.split_at(1),
+        _ => field.split_at(0),
+    };
+    let (integer, fraction) = match digits.iter().position(|&b| b == b',') {
+        Some(pos) => (&digits[..pos], Some(&digits[pos + 1..])),
+        None => (digits, None),
+    };
+    let mut groups = integer.split(|&b| b == b'.');
+    let first_group = groups.next()?;
+    let grouped = integer.contains(&b'.');
+
+    if fraction.is_none() && !grouped
+        || first_group.is_empty()
+        || grouped && first_group.len() > 3
+        || !first_group.iter().all(u8::is_ascii_digit)
+    {
+        return None;
+    }
+
+    let mut number = String::with_capacity(field.len());
+    number.extend(sign.iter().map(|&b| b as char));
+    number.extend(first_group.iter().map(|&b| b as char));
+    for group in groups {
+        if group.len() != 3 || !group.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        number.extend(group.iter().map(|&b| b as char));
+    }
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || !fraction.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        number.push('.');
+        number.extend(fraction.iter().map(|&b| b as char));
+    }
+    Some(number)
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct WhichStats {
     include_nulls: bool,
@@ -518,10 +1110,18 @@ struct WhichStats {
     range:         bool,
     dist:          bool,
     cardinality:   bool,
+    uniqueness:    bool,
     median:        bool,
     mad:           bool,
     quartiles:     bool,
+    percentiles:   bool,
+    // number of histogram bins, zero if no histogram was requested
+    histogram:     usize,
+    quantile_bins: bool,
+    approx:        bool,
     mode:          bool,
+    quality:       bool,
+    weighted:      bool,
     typesonly:     bool,
 }
 
@@ -534,16 +1134,22 @@ impl Commute for WhichStats {
 
 #[derive(Clone)]
 pub struct Stats {
-    typ:       FieldType,
-    sum:       Option<TypedSum>,
-    minmax:    Option<TypedMinMax>,
-    online:    Option<OnlineStats>,
-    nullcount: u64,
-    modes:     Option<Unsorted<Vec<u8>>>,
-    median:    Option<Unsorted<f64>>,
-    mad:       Option<Unsorted<f64>>,
-    quartiles: Option<Unsorted<f64>>,
-    which:     WhichStats,
+    typ:         FieldType,
+    sum:         Option<TypedSum>,
+    minmax:      Option<TypedMinMax>,
+    online:      Option<OnlineStats>,
+    nullcount:   u64,
+    modes:       Option<Unsorted<Vec<u8>>>,
+    #[cfg(feature = "full")]
+    cardinality: Option<ApproxCardinality>,
+    median:      Option<Unsorted<f64>>,
+    mad:         Option<Unsorted<f64>>,
+    quartiles:   Option<Unsorted<f64>>,
+    percentiles: Option<Percentiles>,
+    histogram:   Option<Vec<f64>>,
+    quality:     Option<QualityFlags>,
+    weighted:    Option<WeightedStats>,
+    which:       WhichStats,
 }
 
 fn timestamp_ms_to_rfc3339(timestamp: i64, typ: FieldType) -> String {
@@ -576,17 +1182,44 @@ impl Stats {
         if which.dist {
             online = Some(stats::OnlineStats::default());
         }
-        if which.mode || which.cardinality {
+        if which.mode || (which.cardinality && !which.approx) {
             modes = Some(stats::Unsorted::default());
         }
-        if which.quartiles {
+        #[cfg(feature = "full")]
+        let cardinality = if which.cardinality && which.approx {
+            Some(ApproxCardinality::default())
+        } else {
+            None
+        };
+        if which.quartiles && !which.approx {
             quartiles = Some(stats::Unsorted::default());
-        } else if which.median {
+        } else if which.median && !which.quartiles {
             median = Some(stats::Unsorted::default());
         }
         if which.mad {
             mad = Some(stats::Unsorted::default());
         }
+        // with --approx, the quartiles are also estimated from the t-digest
+        let percentiles = if which.percentiles || (which.quartiles && which.approx) {
+            Some(Percentiles::new(which.approx))
+        } else {
+            None
+        };
+        let histogram = if which.histogram > 0 {
+            Some(Vec::new())
+        } else {
+            None
+        };
+        let quality = if which.quality {
+            Some(QualityFlags::default())
+        } else {
+            None
+        };
+        let weighted = if which.weighted {
+            Some(WeightedStats::new(which.percentiles))
+        } else {
+            None
+        };
         Stats {
             typ: FieldType::default(),
             sum,
@@ -594,16 +1227,34 @@ impl Stats {
             online,
             nullcount: 0,
             modes,
+            #[cfg(feature = "full")]
+            cardinality,
             median,
             mad,
             quartiles,
+            percentiles,
+            histogram,
+            quality,
+            weighted,
             which,
         }
     }
 
+    /// adds a sample with the weight of its row to the weighted statistics.
+    /// Only numeric samples are weighted, nulls & other samples are skipped.
     #[inline]
-    fn add(&mut self, sample: &[u8], infer_dates: bool) {
-        let (sample_type, timestamp_val) = FieldType::from_sample(infer_dates, sample, self.typ);
+    fn add_weighted(&mut self, sample: &[u8], weight: f64) {
+        if let Some(v) = self.weighted.as_mut() {
+            if let Some(n) = from_bytes::<f64>(sample) {
+                v.add(n, weight);
+            }
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, sample: &[u8], infer_dates: bool, numeric: bool) {
+        let (sample_type, timestamp_val) =
+            FieldType::from_sample(infer_dates, numeric, sample, self.typ);
         self.typ.merge(sample_type);
 
         // we're inferring typesonly, don't add samples to compute statistics
@@ -626,6 +1277,13 @@ impl Stats {
         if let Some(v) = self.modes.as_mut() {
             v.add(sample.to_vec());
         };
+        #[cfg(feature = "full")]
+        if let Some(v) = self.cardinality.as_mut() {
+            v.add(sample);
+        };
+        if let Some(v) = self.quality.as_mut() {
+            v.add(sample, infer_dates);
+        };
         if sample_type == TNull {
             self.nullcount += 1;
         }
@@ -655,6 +1313,12 @@ impl Stats {
                     if let Some(v) = self.quartiles.as_mut() {
                         v.add(n);
                     }
+                    if let Some(v) = self.percentiles.as_mut() {
+                        v.add(n);
+                    }
+                    if let Some(v) = self.histogram.as_mut() {
+                        v.push(n);
+                    }
                     if let Some(v) = self.online.as_mut() {
                         v.add(n);
                     }
@@ -683,6 +1347,12 @@ impl Stats {
                     if let Some(v) = self.quartiles.as_mut() {
                         v.add(n);
                     }
+                    if let Some(v) = self.percentiles.as_mut() {
+                        v.add(n);
+                    }
+                    if let Some(v) = self.histogram.as_mut() {
+                        v.push(n);
+                    }
                     if let Some(v) = self.online.as_mut() {
                         v.add(n);
                     }
@@ -694,7 +1364,7 @@ impl Stats {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_record(&mut self, round_places: u32) -> csv::StringRecord {
+    pub fn to_record(&mut self, round_places: u32, record_count: u64) -> csv::StringRecord {
         // we're doing typesonly
         if self.which.typesonly {
             return csv::StringRecord::from(vec![self.typ.to_string()]);
@@ -710,7 +1380,10 @@ impl Stats {
         pieces.push(typ.to_string());
 
         // sum
-        if let Some(sum) = self.sum.as_ref().and_then(|sum| sum.show(typ)) {
+        if let (TInteger | TFloat, Some(w)) = (typ, self.weighted.as_ref()) {
+            // the weighted sum is a float, even for integer columns
+            pieces.push(util::round_num(w.sum, round_places));
+        } else if let Some(sum) = self.sum.as_ref().and_then(|sum| sum.show(typ)) {
             if typ == FieldType::TFloat {
                 if let Ok(f64_val) = sum.parse::<f64>() {
                     pieces.push(util::round_num(f64_val, round_places));
@@ -758,6 +1431,20 @@ impl Stats {
             pieces.push(empty());
             pieces.push(empty());
             pieces.push(empty());
+        } else if let (TInteger | TFloat, Some(w)) = (typ, self.weighted.as_ref()) {
+            match w.mean() {
+                Some(mean) => {
+                    let variance = w.variance();
+                    pieces.push(util::round_num(mean, round_places));
+                    pieces.push(util::round_num(variance.sqrt(), round_places));
+                    pieces.push(util::round_num(variance, round_places));
+                }
+                None => {
+                    pieces.push(empty());
+                    pieces.push(empty());
+                    pieces.push(empty());
+                }
+            }
         } else if let Some(ref v) = self.online {
             if self.typ == TFloat || self.typ == TInteger {
                 pieces.push(util::round_num(v.mean(), round_places));
@@ -796,7 +1483,7 @@ impl Stats {
         // record_count when called by those cmds, so just set sparsity to nullcount
         // (div by 1) so we don't panic.
         #[allow(clippy::cast_precision_loss)]
-        let sparsity: f64 = self.nullcount as f64 / *RECORD_COUNT.get().unwrap_or(&1) as f64;
+        let sparsity: f64 = self.nullcount as f64 / record_count as f64;
         pieces.push(util::round_num(sparsity, round_places));
 
         // median
@@ -839,11 +1526,29 @@ impl Stats {
             pieces.push(empty());
         }
 
+        // we need to prepare the percentiles data before we can query it
+        if let Some(v) = self.percentiles.as_mut() {
+            v.prepare();
+        }
+        if let Some(v) = self.weighted.as_mut() {
+            v.prepare();
+        }
+
         // quartiles
-        match self.quartiles.as_mut().and_then(|v| match typ {
-            TInteger | TFloat | TDate | TDateTime => v.quartiles(),
-            _ => None,
-        }) {
+        let quartiles = if !self.which.quartiles {
+            None
+        } else if self.which.approx {
+            self.percentiles.as_ref().and_then(|v| match typ {
+                TInteger | TFloat | TDate | TDateTime => v.quartiles(),
+                _ => None,
+            })
+        } else {
+            self.quartiles.as_mut().and_then(|v| match typ {
+                TInteger | TFloat | TDate | TDateTime => v.quartiles(),
+                _ => None,
+            })
+        };
+        match quartiles {
             None => {
                 if self.which.quartiles {
                     pieces.push(empty());
@@ -918,12 +1623,81 @@ impl Stats {
             }
         }
 
-        // mode/modes & cardinality
-        match self.modes.as_mut() {
-            None => {
-                if self.which.cardinality {
+        // percentiles
+        if self.which.percentiles {
+            let percentile_list = PERCENTILE_LIST.get().map_or(&[][..], Vec::as_slice);
+            for &p in percentile_list {
+                let percentile = match (typ, self.weighted.as_ref()) {
+                    (TInteger | TFloat, Some(w)) => w.percentile(p),
+                    (TInteger | TFloat | TDate | TDateTime, _) => {
+                        self.percentiles.as_ref().and_then(|v| v.percentile(p))
+                    }
+                    _ => None,
+                };
+                match percentile {
+                    Some(v) if typ == TDateTime || typ == TDate => {
+                        pieces.push(timestamp_ms_to_rfc3339(v as i64, typ));
+                    }
+                    Some(v) => pieces.push(util::round_num(v, round_places)),
+                    None => pieces.push(empty()),
+                }
+            }
+        }
+
+        // cardinality & uniqueness ratio
+        if self.which.cardinality {
+            #[cfg(feature = "full")]
+            let approx_cardinality = self.cardinality.as_mut().map(ApproxCardinality::count);
+            #[cfg(not(feature = "full"))]
+            let approx_cardinality = None;
+            let cardinality =
+                approx_cardinality.or_else(|| self.modes.as_mut().map(|v| v.cardinality()));
+            if let Some(cardinality) = cardinality {
+                let mut buffer = itoa::Buffer::new();
+                pieces.push(buffer.format(cardinality).to_owned());
+                if self.which.uniqueness {
+                    // like sparsity, we use the record count if available
+                    #[allow(clippy::cast_precision_loss)]
+                    let uniqueness_ratio = cardinality as f64 / record_count as f64;
+                    pieces.push(util::round_num(uniqueness_ratio, round_places));
+                }
+            } else {
+                pieces.push(empty());
+                if self.which.uniqueness {
+                    pieces.push(empty());
+                }
+            }
+        }
+
+        // histogram
+        if self.which.histogram > 0 {
+            match self.histogram.as_mut().and_then(|v| match typ {
+                TInteger | TFloat | TDate | TDateTime => {
+                    histogram(v, self.which.histogram, self.which.quantile_bins)
+                }
+                _ => None,
+            }) {
+                Some((boundaries, counts)) => {
+                    let boundaries = boundaries.into_iter().map(|b| {
+                        if typ == TDateTime || typ == TDate {
+                            timestamp_ms_to_rfc3339(b as i64, typ)
+                        } else {
+                            util::round_num(b, round_places)
+                        }
+                    });
+                    pieces.push(boundaries.join(","));
+                    pieces.push(counts.iter().join(","));
+                }
+                None => {
+                    pieces.push(empty());
                     pieces.push(empty());
                 }
+            }
+        }
+
+        // mode/modes
+        match self.modes.as_mut() {
+            None => {
                 if self.which.mode {
                     pieces.push(empty());
                     pieces.push(empty());
@@ -932,10 +1706,6 @@ impl Stats {
                 }
             }
             Some(ref mut v) => {
-                if self.which.cardinality {
-                    let mut buffer = itoa::Buffer::new();
-                    pieces.push(buffer.format(v.cardinality()).to_owned());
-                }
                 if self.which.mode {
                     // mode/s
                     let (modes_result, modes_count, mode_occurrences) = v.modes();
@@ -987,6 +1757,14 @@ impl Stats {
                 }
             }
         }
+
+        // data quality indicators
+        if let Some(v) = self.quality.as_ref() {
+            let mut buffer = itoa::Buffer::new();
+            pieces.push(buffer.format(v.whitespace).to_owned());
+            pieces.push(buffer.format(v.leading_zero).to_owned());
+            pieces.push(v.mixed_types().to_string());
+        }
         csv::StringRecord::from(pieces)
     }
 }
@@ -1000,12 +1778,385 @@ impl Commute for Stats {
         self.online.merge(other.online);
         self.nullcount += other.nullcount;
         self.modes.merge(other.modes);
+        #[cfg(feature = "full")]
+        self.cardinality.merge(other.cardinality);
         self.median.merge(other.median);
+        self.mad.merge(other.mad);
         self.quartiles.merge(other.quartiles);
+        self.percentiles.merge(other.percentiles);
+        if let (Some(v), Some(other_v)) = (self.histogram.as_mut(), other.histogram) {
+            v.extend(other_v);
+        }
+        self.quality.merge(other.quality);
+        self.weighted.merge(other.weighted);
         self.which.merge(other.which);
     }
 }
 
+// the kinds of values tracked by `QualityFlags`, to detect mixed type columns
+const NUMERIC_KIND: u8 = 1;
+const DATE_KIND: u8 = 1 << 1;
+const STRING_KIND: u8 = 1 << 2;
+
+/// `QualityFlags` counts the values with data quality issues and tracks the kinds of
+/// values seen, independently of the inferred type of the column.
+#[derive(Clone, Default)]
+struct QualityFlags {
+    whitespace:   u64,
+    leading_zero: u64,
+    kinds:        u8,
+}
+
+impl QualityFlags {
+    #[inline]
+    fn add(&mut self, sample: &[u8], infer_dates: bool) {
+        if sample.is_empty() {
+            return;
+        }
+        let Ok(value) = from_utf8(sample) else {
+            self.kinds |= STRING_KIND;
+            return;
+        };
+
+        let trimmed = value.trim();
+        if trimmed.len() != value.len() {
+            self.whitespace += 1;
+        }
+
+        if trimmed.parse::<f64>().is_ok() {
+            self.kinds |= NUMERIC_KIND;
+            let digits = trimmed.trim_start_matches(['+', '-']).as_bytes();
+            if digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit() {
+                self.leading_zero += 1;
+            }
+        } else if infer_dates
+            && parse_with_preference(trimmed, DMY_PREFERENCE.load(Ordering::Relaxed)).is_ok()
+        {
+            self.kinds |= DATE_KIND;
+        } else {
+            self.kinds |= STRING_KIND;
+        }
+    }
+
+    fn mixed_types(&self) -> bool {
+        self.kinds.count_ones() > 1
+    }
+}
+
+impl Commute for QualityFlags {
+    #[inline]
+    fn merge(&mut self, other: QualityFlags) {
+        self.whitespace += other.whitespace;
+        self.leading_zero += other.leading_zero;
+        self.kinds |= other.kinds;
+    }
+}
+
+/// returns the bin boundaries & the number of values in each bin of a histogram with
+/// `bins` equal-width bins, or equal-frequency bins if `quantiles` is set.
+/// Each bin includes its lower boundary, and the last bin also includes its upper boundary.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn histogram(values: &mut [f64], bins: usize, quantiles: bool) -> Option<(Vec<f64>, Vec<u64>)> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable_by(f64::total_cmp);
+    let (min, max) = (values[0], values[values.len() - 1]);
+
+    let boundaries: Vec<f64> = if quantiles {
+        // the boundaries are the (i * 100 / bins)th percentiles, linearly interpolated
+        (0..=bins)
+            .map(|i| {
+                let rank = i as f64 / bins as f64 * (values.len() - 1) as f64;
+                let lower = rank.floor() as usize;
+                let upper = rank.ceil() as usize;
+                (rank - lower as f64).mul_add(values[upper] - values[lower], values[lower])
+            })
+            .collect()
+    } else {
+        let width = (max - min) / bins as f64;
+        (0..=bins)
+            .map(|i| {
+                if i == bins {
+                    max
+                } else {
+                    (i as f64).mul_add(width, min)
+                }
+            })
+            .collect()
+    };
+
+    let mut counts = vec![0_u64; bins];
+    let mut bin = 0;
+    // the values are sorted, so we only need to move to the next bin
+    // when a value is past the upper boundary of the current bin
+    for &value in &*values {
+        while bin < bins - 1 && value >= boundaries[bin + 1] {
+            bin += 1;
+        }
+        counts[bin] += 1;
+    }
+    Some((boundaries, counts))
+}
+
+/// `ApproxCardinality` estimates the number of distinct values using HyperLogLog++.
+/// We use a hasher with fixed keys so the sketches of each thread can be merged.
+#[cfg(feature = "full")]
+#[derive(Clone)]
+struct ApproxCardinality(HyperLogLogPlus<Vec<u8>, BuildHasherDefault<DefaultHasher>>);
+
+#[cfg(feature = "full")]
+impl Default for ApproxCardinality {
+    fn default() -> ApproxCardinality {
+        // safety: HLL_PRECISION is within the valid 4..=18 precision range
+        ApproxCardinality(
+            HyperLogLogPlus::new(HLL_PRECISION, BuildHasherDefault::default()).unwrap(),
+        )
+    }
+}
+
+#[cfg(feature = "full")]
+impl ApproxCardinality {
+    #[inline]
+    fn add(&mut self, sample: &[u8]) {
+        self.0.insert(sample);
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn count(&mut self) -> u64 {
+        self.0.count().round() as u64
+    }
+}
+
+#[cfg(feature = "full")]
+impl Commute for ApproxCardinality {
+    #[inline]
+    fn merge(&mut self, other: ApproxCardinality) {
+        // safety: all the sketches have the same precision & hasher
+        self.0.merge(&other.0).unwrap();
+    }
+}
+
+/// `Percentiles` collects the values needed to compute percentiles.
+/// The values are either kept as is so exact percentiles can be computed, or
+/// when --approx is set, compressed into a t-digest so we only use constant memory.
+#[derive(Clone)]
+enum Percentiles {
+    Exact(Vec<f64>),
+    #[cfg(feature = "full")]
+    Approx {
+        digest: TDigest,
+        buffer: Vec<f64>,
+    },
+}
+
+impl Percentiles {
+    // without the full feature, --approx is rejected upfront
+    #[cfg_attr(not(feature = "full"), allow(unused_variables))]
+    fn new(approx: bool) -> Percentiles {
+        #[cfg(feature = "full")]
+        if approx {
+            return Percentiles::Approx {
+                digest: TDigest::new_with_size(TDIGEST_SIZE),
+                buffer: Vec::with_capacity(TDIGEST_BUFFER_SIZE),
+            };
+        }
+        Percentiles::Exact(Vec::new())
+    }
+
+    #[inline]
+    fn add(&mut self, n: f64) {
+        match self {
+            Percentiles::Exact(values) => values.push(n),
+            #[cfg(feature = "full")]
+            Percentiles::Approx { digest, buffer } => {
+                buffer.push(n);
+                // merging values into a t-digest one at a time is slow,
+                // so we buffer them and merge them in batches
+                if buffer.len() >= TDIGEST_BUFFER_SIZE {
+                    *digest = digest.merge_unsorted(std::mem::take(buffer));
+                }
+            }
+        }
+    }
+
+    /// sorts the exact values/flushes the buffered values into the t-digest.
+    /// This needs to be called before calling `percentile` or `quartiles`.
+    fn prepare(&mut self) {
+        match self {
+            Percentiles::Exact(values) => values.sort_unstable_by(f64::total_cmp),
+            #[cfg(feature = "full")]
+            Percentiles::Approx { digest, buffer } => {
+                if !buffer.is_empty() {
+                    *digest = digest.merge_unsorted(std::mem::take(buffer));
+                }
+            }
+        }
+    }
+
+    /// returns the `p`th percentile (0-100).
+    /// Exact percentiles are linearly interpolated between the closest ranks.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn percentile(&self, p: f64) -> Option<f64> {
+        match self {
+            Percentiles::Exact(values) => {
+                if values.is_empty() {
+                    return None;
+                }
+                let rank = p / 100.0 * (values.len() - 1) as f64;
+                let lower = rank.floor() as usize;
+                let upper = rank.ceil() as usize;
+                Some((rank - lower as f64).mul_add(values[upper] - values[lower], values[lower]))
+            }
+            #[cfg(feature = "full")]
+            Percentiles::Approx { digest, .. } => {
+                if digest.is_empty() {
+                    None
+                } else {
+                    Some(digest.estimate_quantile(p / 100.0))
+                }
+            }
+        }
+    }
+
+    fn quartiles(&self) -> Option<(f64, f64, f64)> {
+        Some((
+            self.percentile(25.0)?,
+            self.percentile(50.0)?,
+            self.percentile(75.0)?,
+        ))
+    }
+}
+
+impl Commute for Percentiles {
+    #[inline]
+    fn merge(&mut self, other: Percentiles) {
+        match (self, other) {
+            (Percentiles::Exact(values), Percentiles::Exact(other_values)) => {
+                values.extend(other_values);
+            }
+            #[cfg(feature = "full")]
+            (
+                Percentiles::Approx { digest, buffer },
+                Percentiles::Approx {
+                    digest: other_digest,
+                    buffer: other_buffer,
+                },
+            ) => {
+                buffer.extend(other_buffer);
+                *digest = TDigest::merge_digests(vec![std::mem::take(digest), other_digest]);
+            }
+            // WhichStats guarantees we only merge the same kind of percentiles
+            #[cfg(feature = "full")]
+            _ => unreachable!("cannot merge exact and approximate percentiles"),
+        }
+    }
+}
+
+/// The weighted sum, mean & variance of a numeric column, computed incrementally
+/// with West's algorithm, which also supports non-integer weights.
+/// The weighted samples are only kept if weighted percentiles were requested.
+#[derive(Clone, Default)]
+struct WeightedStats {
+    sum:         f64,
+    sum_weights: f64,
+    mean:        f64,
+    // the weighted sum of squared differences from the mean
+    m2:          f64,
+    samples:     Option<Vec<(f64, f64)>>,
+}
+
+impl WeightedStats {
+    fn new(percentiles: bool) -> WeightedStats {
+        WeightedStats {
+            samples: if percentiles { Some(Vec::new()) } else { None },
+            ..WeightedStats::default()
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, value: f64, weight: f64) {
+        if weight == 0.0 {
+            return;
+        }
+        if let Some(v) = self.samples.as_mut() {
+            v.push((value, weight));
+        }
+        self.sum += weight * value;
+        self.sum_weights += weight;
+        let delta = value - self.mean;
+        self.mean += (weight / self.sum_weights) * delta;
+        self.m2 += weight * delta * (value - self.mean);
+    }
+
+    fn mean(&self) -> Option<f64> {
+        if self.sum_weights > 0.0 {
+            Some(self.mean)
+        } else {
+            None
+        }
+    }
+
+    /// the population variance, like the unweighted variance
+    fn variance(&self) -> f64 {
+        if self.sum_weights > 0.0 {
+            self.m2 / self.sum_weights
+        } else {
+            0.0
+        }
+    }
+
+    /// sorts the weighted samples, so they can be queried for percentiles
+    fn prepare(&mut self) {
+        if let Some(v) = self.samples.as_mut() {
+            v.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        }
+    }
+
+    /// the weighted percentile, i.e. the smallest value whose cumulative weight is
+    /// at least p% of the total weight. `prepare` must be called first.
+    fn percentile(&self, p: f64) -> Option<f64> {
+        let samples = self.samples.as_ref()?;
+        let target = p / 100.0 * self.sum_weights;
+        let mut cumulative_weight = 0.0;
+        for &(value, weight) in samples {
+            cumulative_weight += weight;
+            if cumulative_weight >= target {
+                return Some(value);
+            }
+        }
+        // guard against rounding errors in the cumulative weight
+        samples.last().map(|&(value, _)| value)
+    }
+}
+
+impl Commute for WeightedStats {
+    #[inline]
+    fn merge(&mut self, other: WeightedStats) {
+        if let (Some(v), Some(other_v)) = (self.samples.as_mut(), other.samples) {
+            v.extend(other_v);
+        }
+        let sum_weights = self.sum_weights + other.sum_weights;
+        if sum_weights > 0.0 {
+            // Chan et al.'s parallel algorithm, generalized to weights
+            let delta = other.mean - self.mean;
+            self.mean += delta * other.sum_weights / sum_weights;
+            self.m2 +=
+                other.m2 + delta * delta * self.sum_weights * other.sum_weights / sum_weights;
+        }
+        self.sum += other.sum;
+        self.sum_weights = sum_weights;
+    }
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Copy, PartialEq, Default)]
 pub enum FieldType {
@@ -1024,10 +2175,12 @@ pub enum FieldType {
 impl FieldType {
     // infer data type
     // infer_dates signals if date inference should be attempted
+    // numeric signals if the sample can be a number
     // from a given sample & current type inference
     #[inline]
     pub fn from_sample(
         infer_dates: bool,
+        numeric: bool,
         sample: &[u8],
         current_type: FieldType,
     ) -> (FieldType, Option<i64>) {
@@ -1041,9 +2194,10 @@ impl FieldType {
 
         let string = from_utf8(sample).unwrap();
 
-        if current_type == FieldType::TFloat
-            || current_type == FieldType::TInteger
-            || current_type == FieldType::TNull
+        if numeric
+            && (current_type == FieldType::TFloat
+                || current_type == FieldType::TInteger
+                || current_type == FieldType::TNull)
         {
             if let Ok(int_val) = string.parse::<i64>() {
                 // leading zero, its a string
@@ -0,0 +1,109 @@
+static USAGE: &str = r#"
+Returns the last N rows of a CSV.
+
+If the CSV is indexed (see 'qsv index'), this is instantaneous, as only the
+returned rows are parsed. Otherwise, the CSV is streamed through, keeping only
+the last N rows in memory, so stdin is supported as well.
+
+See also 'qsv head' to return the first N rows, and 'qsv slice' for arbitrary ranges.
+
+Usage:
+    qsv tail [options] [<input>]
+    qsv tail --help
+
+tail options:
+    -N, --rows <n>         The number of rows to return.
+                           [default: 10]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers. Otherwise, the first row will always
+                           appear in the output as the header row.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{collections::VecDeque, fs};
+
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    index::Indexed,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    flag_rows:       usize,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    match args.rconfig().indexed()? {
+        None => args.no_index(),
+        Some(idxed) => args.with_index(idxed),
+    }
+}
+
+impl Args {
+    fn no_index(&self) -> CliResult<()> {
+        let mut rdr = self.rconfig().reader()?;
+        let mut wtr = self.wconfig().writer()?;
+        self.rconfig().write_headers(&mut rdr, &mut wtr)?;
+
+        if self.flag_rows == 0 {
+            return Ok(wtr.flush()?);
+        }
+
+        // a ring buffer of the last flag_rows records
+        let mut last_records: VecDeque<csv::ByteRecord> = VecDeque::with_capacity(self.flag_rows);
+        let mut record = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut record)? {
+            if last_records.len() == self.flag_rows {
+                // reuse the allocation of the oldest record
+                let mut oldest = last_records.pop_front().unwrap_or_default();
+                std::mem::swap(&mut oldest, &mut record);
+                last_records.push_back(oldest);
+            } else {
+                last_records.push_back(record.clone());
+            }
+        }
+        for r in &last_records {
+            wtr.write_byte_record(r)?;
+        }
+        Ok(wtr.flush()?)
+    }
+
+    fn with_index(&self, mut idx: Indexed<fs::File, fs::File>) -> CliResult<()> {
+        let mut wtr = self.wconfig().writer()?;
+        self.rconfig().write_headers(&mut *idx, &mut wtr)?;
+
+        let count = idx.count();
+        let rows = (self.flag_rows as u64).min(count);
+        if rows == 0 {
+            return Ok(wtr.flush()?);
+        }
+        idx.seek(count - rows)?;
+        for r in idx.byte_records().take(rows as usize) {
+            wtr.write_byte_record(&r?)?;
+        }
+        Ok(wtr.flush()?)
+    }
+
+    fn rconfig(&self) -> Config {
+        Config::new(&self.arg_input)
+            .delimiter(self.flag_delimiter)
+            .no_headers(self.flag_no_headers)
+    }
+
+    fn wconfig(&self) -> Config {
+        Config::new(&self.flag_output)
+    }
+}
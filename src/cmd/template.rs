@@ -0,0 +1,151 @@
+static USAGE: &str = r#"
+Renders each record of a CSV through a MiniJinja template
+(https://docs.rs/minijinja/latest/minijinja/syntax/index.html).
+
+The columns of each record are available in the template as variables named after
+their "safe" column names (i.e. lowercased, with non-alphanumeric characters replaced
+by underscores and an underscore prepended to names starting with a digit - see
+'qsv safenames --help'). With --no-headers, the columns are named _c1, _c2, etc.
+The 1-based record number is available as QSV_ROWNO.
+
+By default, the rendered records are concatenated, each followed by a newline,
+and written to stdout (or --output). If <outdir> is given, each record is rendered
+into its own file in <outdir> instead, named using --outfilename.
+
+Examples:
+Generate SQL INSERT statements from a CSV with name & age columns.
+
+  $ qsv template --template "INSERT INTO people VALUES ('{{ name }}', {{ age }});" data.csv
+
+Render a Markdown report for every record into the reports directory, using a template file.
+
+  $ qsv template --template-file report.md.j2 data.csv reports --outfilename {}.md
+
+For more examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_template.rs.
+
+Usage:
+    qsv template [options] (--template <str> | --template-file <file>) [<input>] [<outdir>]
+    qsv template --help
+
+template arguments:
+    <input>                    The CSV file to read. If not given, reads from stdin.
+    <outdir>                   When set, each record is rendered into its own file
+                               in this directory, which is created if needed.
+
+template options:
+    --template <str>           The MiniJinja template to render each record with.
+    --template-file <file>     The file containing the MiniJinja template.
+    --outfilename <filename>   A filename template to use when rendering into <outdir>.
+                               The string '{}' will be replaced by the record number.
+                               [default: {}.txt]
+
+Common options:
+    -h, --help                 Display this message
+    -o, --output <file>        Write output to <file> instead of stdout.
+                               Ignored when <outdir> is set.
+    -n, --no-headers           When set, the first row will not be interpreted
+                               as headers.
+    -d, --delimiter <arg>      The field delimiter for reading CSV data.
+                               Must be a single character. (default: ,)
+"#;
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use minijinja::Environment;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    util::{self, FilenameTemplate},
+    CliError, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:          Option<String>,
+    arg_outdir:         Option<String>,
+    flag_template:      Option<String>,
+    flag_template_file: Option<String>,
+    flag_outfilename:   FilenameTemplate,
+    flag_output:        Option<String>,
+    flag_no_headers:    bool,
+    flag_delimiter:     Option<Delimiter>,
+}
+
+impl From<minijinja::Error> for CliError {
+    fn from(err: minijinja::Error) -> CliError {
+        CliError::Other(format!("Template error: {err}"))
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let template_source = match (args.flag_template, args.flag_template_file) {
+        (Some(template), None) => template,
+        (None, Some(template_file)) => fs::read_to_string(template_file)?,
+        _ => return fail!("Either --template or --template-file must be set."),
+    };
+
+    let mut env = Environment::new();
+    env.add_template("template", &template_source)?;
+    let template = env.get_template("template")?;
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    let mut rdr = rconfig.reader()?;
+
+    let headers = rdr.headers()?.clone();
+    let column_names: Vec<String> = if rconfig.no_headers {
+        (1..=headers.len()).map(|i| format!("_c{i}")).collect()
+    } else {
+        util::safe_header_names(&headers, true, false, None, "_").0
+    };
+
+    let mut wtr: Option<Box<dyn Write>> = match args.arg_outdir {
+        Some(ref outdir) => {
+            fs::create_dir_all(outdir)?;
+            None
+        }
+        None => Some(match args.flag_output {
+            Some(ref output) => Box::new(BufWriter::new(fs::File::create(output)?)),
+            None => Box::new(BufWriter::new(io::stdout())),
+        }),
+    };
+
+    let mut context: HashMap<&str, String> = HashMap::with_capacity(column_names.len() + 1);
+    let mut record = csv::StringRecord::new();
+    let mut row_number = 0_u64;
+    while rdr.read_record(&mut record)? {
+        row_number += 1;
+        context.clear();
+        for (column_name, field) in column_names.iter().zip(record.iter()) {
+            context.insert(column_name, field.to_string());
+        }
+        context.insert("QSV_ROWNO", row_number.to_string());
+
+        let rendered = template.render(&context)?;
+        match (&mut wtr, &args.arg_outdir) {
+            (Some(wtr), _) => {
+                wtr.write_all(rendered.as_bytes())?;
+                wtr.write_all(b"\n")?;
+            }
+            (None, Some(outdir)) => {
+                let filename = args.flag_outfilename.filename(&row_number.to_string());
+                fs::write(Path::new(outdir).join(filename), rendered)?;
+            }
+            (None, None) => unreachable!("we either have a writer or an outdir"),
+        }
+    }
+
+    if let Some(mut wtr) = wtr {
+        wtr.flush()?;
+    }
+    Ok(())
+}
@@ -9,6 +9,11 @@ It will infer a column as boolean if it only has a domain of two values,
 and the first character of the values are one of the following case-insensitive
 combinations: t/f; t/null; 1/0; 1/null; y/n & y/null are treated as true/false.
 
+With the --nested option, column names with dots are treated as paths into
+nested JSON objects. e.g. the columns "name.first" and "name.last" become
+{"name":{"first":...,"last":...}}. If a column name collides with a nested path
+(e.g. both "name" and "name.first" are present), the later column takes precedence.
+
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_tojsonl.rs.
 
 Usage:
@@ -16,6 +21,7 @@ Usage:
     qsv tojsonl --help
 
 Tojsonl optionns:
+    --nested               Create nested JSON objects from dotted column names.
     -j, --jobs <arg>       The number of jobs to run in parallel.
                            When not set, the number of jobs is set to the
                            number of CPUs detected.
@@ -31,6 +37,7 @@ Common options:
 
 use std::{env::temp_dir, fmt::Write, fs::File, path::Path, str::FromStr};
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 use serde_json::{Map, Value};
 use strum_macros::EnumString;
@@ -45,6 +52,7 @@ use crate::{
 #[derive(Deserialize, Clone)]
 struct Args {
     arg_input:        Option<String>,
+    flag_nested:      bool,
     flag_jobs:        Option<usize>,
     flag_delimiter:   Option<Delimiter>,
     flag_output:      Option<String>,
@@ -229,8 +237,39 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     // amortize allocs
     let mut record = csv::StringRecord::new();
-
     let mut temp_string = String::with_capacity(100);
+
+    if args.flag_nested {
+        // precompute the nested path of each column
+        let header_paths: Vec<Vec<String>> = headers
+            .iter()
+            .map(|h| h.split('.').map(ToString::to_string).collect())
+            .collect();
+
+        while rdr.read_record(&mut record)? {
+            record.trim();
+            let mut json_map = IndexMap::with_capacity(headers.len());
+            for (idx, field) in record.iter().enumerate() {
+                let Some(path) = header_paths.get(idx) else {
+                    continue;
+                };
+                let field_type = field_type_vec.get(idx).unwrap_or(&JsonlType::Null);
+                insert_nested(
+                    &mut json_map,
+                    path,
+                    NestedValue::Json(typed_value(field_type, field)),
+                );
+            }
+            temp_string.clear();
+            write_nested(&mut temp_string, &json_map);
+            record.clear();
+            record.push_field(&temp_string);
+            wtr.write_record(&record)?;
+        }
+
+        return Ok(wtr.flush()?);
+    }
+
     let mut temp_string2 = String::with_capacity(50);
 
     let mut header_key = Value::String(String::with_capacity(50));
@@ -294,3 +333,67 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 fn first_lower_char(field_str: &str) -> char {
     field_str.chars().next().unwrap_or('_').to_ascii_lowercase()
 }
+
+/// A value of the --nested output. Leaves hold their JSON text, so they're written
+/// exactly like without --nested, e.g. numbers as is.
+enum NestedValue {
+    Json(String),
+    Object(IndexMap<String, NestedValue>),
+}
+
+/// convert a field to its JSON text, given its inferred type
+fn typed_value(field_type: &JsonlType, field: &str) -> String {
+    match field_type {
+        // empty booleans are falsy
+        JsonlType::Boolean => {
+            if let 't' | 'y' | '1' = first_lower_char(field) {
+                "true".to_string()
+            } else {
+                "false".to_string()
+            }
+        }
+        _ if field.is_empty() => "null".to_string(),
+        // we round-trip thru serde_json to escape the str
+        JsonlType::String => Value::String(field.to_string()).to_string(),
+        JsonlType::Null => "null".to_string(),
+        JsonlType::Integer | JsonlType::Number => field.to_string(),
+    }
+}
+
+/// insert value into the map, creating nested objects along the path as needed
+fn insert_nested(map: &mut IndexMap<String, NestedValue>, path: &[String], value: NestedValue) {
+    match path {
+        [] => {}
+        [key] => {
+            map.insert(key.clone(), value);
+        }
+        [key, rest @ ..] => {
+            let entry = map
+                .entry(key.clone())
+                .or_insert_with(|| NestedValue::Object(IndexMap::new()));
+            if let NestedValue::Json(_) = entry {
+                *entry = NestedValue::Object(IndexMap::new());
+            }
+            if let NestedValue::Object(child) = entry {
+                insert_nested(child, rest, value);
+            }
+        }
+    }
+}
+
+/// write a nested object as JSON text
+fn write_nested(out: &mut String, map: &IndexMap<String, NestedValue>) {
+    out.push('{');
+    for (idx, (key, value)) in map.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        out.push_str(&Value::String(key.clone()).to_string());
+        out.push(':');
+        match value {
+            NestedValue::Json(json) => out.push_str(json),
+            NestedValue::Object(child) => write_nested(out, child),
+        }
+    }
+    out.push('}');
+}
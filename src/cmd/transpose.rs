@@ -15,6 +15,8 @@ transpose options:
                            the number of rows.
                            Note that in general it is faster to
                            process the transpose in memory.
+                           If the input is stdin, it is first spooled to
+                           a temporary file so it can be read multiple times.
 
 Common options:
     -h, --help             Display this message
@@ -25,10 +27,11 @@ Common options:
                            entire CSV into memory. Ignored with --multipass.
 "#;
 
-use std::str;
+use std::{io, str};
 
 use csv::ByteRecord;
 use serde::Deserialize;
+use tempfile::NamedTempFile;
 
 use crate::{
     config::{Config, Delimiter},
@@ -45,7 +48,7 @@ struct Args {
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
-    let args: Args = util::get_args(USAGE, argv)?;
+    let mut args: Args = util::get_args(USAGE, argv)?;
 
     let input_is_stdin = match args.arg_input {
         Some(ref s) if s == "-" => true,
@@ -53,7 +56,17 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         _ => false,
     };
 
-    if args.flag_multipass && !input_is_stdin {
+    if args.flag_multipass {
+        // stdin is not seekable, so we spool it to a temp file
+        // that we can read multiple times
+        let _stdin_file = if input_is_stdin {
+            let mut stdin_file = NamedTempFile::new()?;
+            io::copy(&mut io::stdin().lock(), &mut stdin_file)?;
+            args.arg_input = Some(stdin_file.path().to_string_lossy().to_string());
+            Some(stdin_file)
+        } else {
+            None
+        };
         args.multipass_transpose()
     } else {
         args.in_memory_transpose()
@@ -555,7 +555,10 @@ fn to_json_instance(
         // convert csv header to string
         let header_string = from_utf8(header).unwrap().to_string();
         // convert csv value to string; no trimming reqd as it's done on the record level beforehand
-        let value_string = from_utf8(&record[i]).unwrap().to_string();
+        let Ok(value_str) = from_utf8(&record[i]) else {
+            return fail_format!("Invalid UTF-8 value. header: {header_string}");
+        };
+        let value_string = value_str.to_string();
 
         // if value_string is empty, then just put an empty JSON String
         if value_string.is_empty() {
@@ -763,6 +766,20 @@ mod tests_for_csv_to_json_conversion {
             error
         );
     }
+
+    #[test]
+    fn test_to_json_instance_invalid_utf8_error() {
+        let _ = NULL_TYPE.get_or_init(|| Value::String("null".to_string()));
+        let headers = csv::ByteRecord::from(vec!["A", "B"]);
+        let mut record = csv::ByteRecord::new();
+        record.push_field(b"hello\xff");
+        record.push_field(b"3.1415");
+
+        let result = to_json_instance(&headers, headers.len(), &record, &schema_json());
+        assert!(&result.is_err());
+        let error = result.err().unwrap();
+        assert_eq!("Invalid UTF-8 value. header: A", error);
+    }
 }
 
 /// Validate JSON instance against compiled JSON schema
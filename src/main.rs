@@ -92,11 +92,25 @@ fn main() -> QsvExitCode {
     enabled_commands.push_str("    apply       Apply series of transformations to a column\n");
 
     enabled_commands.push_str(
-        "    behead      Drop header from CSV file
+        "    autoconvert Sniff & convert a messy CSV to RFC 4180 UTF-8
+    behead      Drop header from CSV file
+    bench       Benchmark qsv commands against a CSV
     cat         Concatenate by row or column
-    count       Count records
+    checksum    Compute an order-invariant checksum of a CSV\n",
+    );
+
+    #[cfg(all(feature = "clipboard", feature = "full"))]
+    enabled_commands
+        .push_str("    clipboard   Provide input from clipboard or output to clipboard\n");
+
+    enabled_commands.push_str(
+        "    count       Count records
+    crosstab    Compute a two-way contingency table of two columns
+    datefmt     Format date/datetime columns
     dedup       Remove redundant rows
+    describegpt Infer extended metadata about a CSV using a GPT model
     diff        Create the difference between two CSVs
+    edit        Replace the value of a cell
     enum        Add a new column enumerating CSV lines
     excel       Exports an Excel sheet to a CSV
     exclude     Excludes the records in one CSV from another
@@ -124,11 +138,21 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str("    frequency   Show frequency tables\n");
 
+    #[cfg(all(feature = "polars", feature = "full"))]
+    enabled_commands
+        .push_str("    from        Convert Parquet files to CSV using the Pola.rs engine\n");
+
     #[cfg(all(feature = "generate", not(feature = "lite")))]
     enabled_commands.push_str("    generate    Generate test data by profiling a CSV\n");
 
+    #[cfg(all(feature = "apply", feature = "full"))]
+    enabled_commands
+        .push_str("    geocode     Geocode a location against the Geonames cities database\n");
+
     enabled_commands.push_str(
-        "    headers     Show header names
+        "    groupby     Aggregate records grouped on key columns
+    head        Return the first N rows of a CSV
+    headers     Show header names
     help        Show this usage message
     index       Create CSV index for faster access
     input       Read CSVs w/ special quoting, skipping, trimming & transcoding rules
@@ -140,14 +164,23 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str("    jsonl       Convert newline-delimited JSON files to CSV\n");
 
+    #[cfg(all(feature = "lens", feature = "full"))]
+    enabled_commands.push_str("    lens        View a CSV file interactively\n");
+
     #[cfg(all(feature = "luau", feature = "full"))]
     enabled_commands.push_str("    luau        Execute Luau script on CSV data\n");
 
     enabled_commands.push_str(
-        "    partition   Partition CSV data based on a column value
+        "    melt        Melt (unpivot) CSV data from wide to long format
+    outliers    Flag numeric outliers using IQR or modified z-scores
+    partition   Partition CSV data based on a column value
+    plot        Plot a quick chart in the terminal or as an SVG
     pseudo      Pseudonymise the values of a column\n",
     );
 
+    #[cfg(all(feature = "prompt", feature = "full"))]
+    enabled_commands.push_str("    prompt      Open a file dialog to pick a file\n");
+
     #[cfg(all(feature = "python", feature = "full"))]
     enabled_commands.push_str("    py          Evaluate a Python expression on CSV data\n");
 
@@ -162,18 +195,30 @@ fn main() -> QsvExitCode {
     searchset   Search CSV data with a regex set
     select      Select, re-order, duplicate or drop columns
     slice       Slice records from CSV
+    snappy      Compress/decompress data using the Snappy frame format
     sniff       Quickly sniff CSV metadata
     sort        Sort CSV data in alphabetical, numerical, reverse or random order
     sortcheck   Check if a CSV is sorted
-    split       Split CSV data into many files
-    stats       Infer data types and compute summary statistics
+    split       Split CSV data into many files\n",
+    );
+
+    #[cfg(all(feature = "polars", feature = "full"))]
+    enabled_commands.push_str(
+        "    sqlp        Run a SQL query against several CSVs using the Pola.rs engine\n",
+    );
+
+    enabled_commands.push_str(
+        "    stats       Infer data types and compute summary statistics
     table       Align CSV data into columns
+    tail        Return the last N rows of a CSV
+    template    Render CSV records through a MiniJinja template
     tojsonl     Convert CSV to newline-delimited JSON\n",
     );
 
     #[cfg(all(feature = "to", feature = "full"))]
-    enabled_commands
-        .push_str("    to          Convert CSVs to PostgreSQL/XLSX/Parquet/SQLite/Data Package\n");
+    enabled_commands.push_str(
+        "    to          Convert CSVs to PostgreSQL/XLSX/Parquet/Arrow/SQLite/Data Package\n",
+    );
 
     enabled_commands.push_str(
         "    transpose   Transpose rows/columns of CSV data
@@ -269,11 +314,20 @@ sponsored by datHere - Data Infrastructure Engineering
 enum Command {
     #[cfg(all(feature = "apply", feature = "full"))]
     Apply,
+    Autoconvert,
     Behead,
+    Bench,
     Cat,
+    Checksum,
+    #[cfg(all(feature = "clipboard", feature = "full"))]
+    Clipboard,
     Count,
+    Crosstab,
+    Datefmt,
     Dedup,
+    Describegpt,
     Diff,
+    Edit,
     Enum,
     Excel,
     Exclude,
@@ -291,8 +345,14 @@ enum Command {
     #[cfg(all(feature = "foreach", target_family = "unix", not(feature = "lite")))]
     ForEach,
     Frequency,
+    #[cfg(all(feature = "polars", feature = "full"))]
+    From,
     #[cfg(all(feature = "generate", feature = "full"))]
     Generate,
+    #[cfg(all(feature = "apply", feature = "full"))]
+    Geocode,
+    Groupby,
+    Head,
     Headers,
     Help,
     Index,
@@ -301,9 +361,16 @@ enum Command {
     #[cfg(all(feature = "polars", feature = "full"))]
     JoinP,
     Jsonl,
+    #[cfg(all(feature = "lens", feature = "full"))]
+    Lens,
     #[cfg(all(feature = "luau", feature = "full"))]
     Luau,
+    Melt,
+    Outliers,
     Partition,
+    Plot,
+    #[cfg(all(feature = "prompt", feature = "full"))]
+    Prompt,
     Pseudo,
     #[cfg(all(feature = "python", feature = "full"))]
     Py,
@@ -317,12 +384,17 @@ enum Command {
     SearchSet,
     Select,
     Slice,
+    Snappy,
     Sniff,
     Sort,
     SortCheck,
     Split,
+    #[cfg(all(feature = "polars", feature = "full"))]
+    SqlP,
     Stats,
     Table,
+    Tail,
+    Template,
     Transpose,
     #[cfg(all(feature = "to", feature = "full"))]
     To,
@@ -343,13 +415,22 @@ impl Command {
             )));
         }
         match self {
+            Command::Autoconvert => cmd::autoconvert::run(argv),
             Command::Behead => cmd::behead::run(argv),
+            Command::Bench => cmd::bench::run(argv),
             #[cfg(all(feature = "apply", feature = "full"))]
             Command::Apply => cmd::apply::run(argv),
             Command::Cat => cmd::cat::run(argv),
+            Command::Checksum => cmd::checksum::run(argv),
+            #[cfg(all(feature = "clipboard", feature = "full"))]
+            Command::Clipboard => cmd::clipboard::run(argv),
             Command::Count => cmd::count::run(argv),
+            Command::Crosstab => cmd::crosstab::run(argv),
+            Command::Datefmt => cmd::datefmt::run(argv),
             Command::Dedup => cmd::dedup::run(argv),
+            Command::Describegpt => cmd::describegpt::run(argv),
             Command::Diff => cmd::diff::run(argv),
+            Command::Edit => cmd::edit::run(argv),
             Command::Enum => cmd::enumerate::run(argv),
             Command::Excel => cmd::excel::run(argv),
             Command::Exclude => cmd::exclude::run(argv),
@@ -367,8 +448,14 @@ impl Command {
             Command::Flatten => cmd::flatten::run(argv),
             Command::Fmt => cmd::fmt::run(argv),
             Command::Frequency => cmd::frequency::run(argv),
+            #[cfg(all(feature = "polars", feature = "full"))]
+            Command::From => cmd::from::run(argv),
             #[cfg(all(feature = "generate", feature = "full"))]
             Command::Generate => cmd::generate::run(argv),
+            #[cfg(all(feature = "apply", feature = "full"))]
+            Command::Geocode => cmd::geocode::run(argv),
+            Command::Groupby => cmd::groupby::run(argv),
+            Command::Head => cmd::head::run(argv),
             Command::Headers => cmd::headers::run(argv),
             Command::Help => {
                 wout!("{USAGE}");
@@ -381,9 +468,16 @@ impl Command {
             #[cfg(all(feature = "polars", feature = "full"))]
             Command::JoinP => cmd::joinp::run(argv),
             Command::Jsonl => cmd::jsonl::run(argv),
+            #[cfg(all(feature = "lens", feature = "full"))]
+            Command::Lens => cmd::lens::run(argv),
             #[cfg(all(feature = "luau", feature = "full"))]
             Command::Luau => cmd::luau::run(argv),
+            Command::Melt => cmd::melt::run(argv),
+            Command::Outliers => cmd::outliers::run(argv),
             Command::Partition => cmd::partition::run(argv),
+            Command::Plot => cmd::plot::run(argv),
+            #[cfg(all(feature = "prompt", feature = "full"))]
+            Command::Prompt => cmd::prompt::run(argv),
             Command::Pseudo => cmd::pseudo::run(argv),
             #[cfg(all(feature = "python", feature = "full"))]
             Command::Py => cmd::python::run(argv),
@@ -397,6 +491,7 @@ impl Command {
             Command::SearchSet => cmd::searchset::run(argv),
             Command::Select => cmd::select::run(argv),
             Command::Slice => cmd::slice::run(argv),
+            Command::Snappy => cmd::snappy::run(argv),
             Command::Sniff => {
                 let rt = tokio::runtime::Runtime::new().unwrap();
                 rt.block_on(cmd::sniff::run(argv))
@@ -404,8 +499,12 @@ impl Command {
             Command::Sort => cmd::sort::run(argv),
             Command::SortCheck => cmd::sortcheck::run(argv),
             Command::Split => cmd::split::run(argv),
+            #[cfg(all(feature = "polars", feature = "full"))]
+            Command::SqlP => cmd::sqlp::run(argv),
             Command::Stats => cmd::stats::run(argv),
             Command::Table => cmd::table::run(argv),
+            Command::Tail => cmd::tail::run(argv),
+            Command::Template => cmd::template::run(argv),
             Command::Transpose => cmd::transpose::run(argv),
             #[cfg(all(feature = "to", feature = "full"))]
             Command::To => cmd::to::run(argv),
@@ -18,9 +18,13 @@ macro_rules! command_list {
     () => {
         "
     behead      Drop header from CSV file
+    bench       Benchmark qsv commands against a CSV
     cat         Concatenate by row or column
     count       Count records
+    crosstab    Compute a two-way contingency table of two columns
+    datefmt     Format date/datetime columns
     dedup       Remove redundant rows
+    edit        Replace the value of a cell
     enum        Add a new column enumerating CSV lines
     excel       Exports an Excel sheet to a CSV
     exclude     Excludes the records in one CSV from another
@@ -32,12 +36,16 @@ macro_rules! command_list {
     flatten     Show one field per line
     fmt         Format CSV output (change field delimiter)
     frequency   Show frequency tables
+    groupby     Aggregate records grouped on key columns
+    head        Return the first N rows of a CSV
     headers     Show header names
     help        Show this usage message
     index       Create CSV index for faster access
     input       Read CSVs w/ special quoting, skipping, trimming & transcoding rules
     join        Join CSV files
     jsonl       Convert newline-delimited JSON files to CSV
+    melt        Melt (unpivot) CSV data from wide to long format
+    outliers    Flag numeric outliers using IQR or modified z-scores
     partition   Partition CSV data based on a column value
     pseudo      Pseudonymise the values of a column
     rename      Rename the columns of CSV data efficiently
@@ -56,6 +64,7 @@ macro_rules! command_list {
     split       Split CSV data into many files
     stats       Infer data types and compute summary statistics
     table       Align CSV data into columns
+    tail        Return the last N rows of a CSV
     tojsonl     Convert CSV to newline-delimited JSON
     transpose   Transpose rows/columns of CSV data
     validate    Validate CSV data for RFC4180-compliance or with JSON Schema
@@ -181,9 +190,13 @@ Please choose one of the following commands:",
 #[serde(rename_all = "lowercase")]
 enum Command {
     Behead,
+    Bench,
     Cat,
     Count,
+    Crosstab,
+    Datefmt,
     Dedup,
+    Edit,
     Enum,
     Excel,
     Exclude,
@@ -195,12 +208,16 @@ enum Command {
     Flatten,
     Fmt,
     Frequency,
+    Groupby,
+    Head,
     Headers,
     Help,
     Index,
     Input,
     Join,
     Jsonl,
+    Melt,
+    Outliers,
     Partition,
     Pseudo,
     Rename,
@@ -219,6 +236,7 @@ enum Command {
     Split,
     Stats,
     Table,
+    Tail,
     Tojsonl,
     Transpose,
     Validate,
@@ -238,9 +256,13 @@ impl Command {
         }
         match self {
             Command::Behead => cmd::behead::run(argv),
+            Command::Bench => cmd::bench::run(argv),
             Command::Cat => cmd::cat::run(argv),
             Command::Count => cmd::count::run(argv),
+            Command::Crosstab => cmd::crosstab::run(argv),
+            Command::Datefmt => cmd::datefmt::run(argv),
             Command::Dedup => cmd::dedup::run(argv),
+            Command::Edit => cmd::edit::run(argv),
             Command::Enum => cmd::enumerate::run(argv),
             Command::Excel => cmd::excel::run(argv),
             Command::Exclude => cmd::exclude::run(argv),
@@ -252,6 +274,8 @@ impl Command {
             Command::Flatten => cmd::flatten::run(argv),
             Command::Fmt => cmd::fmt::run(argv),
             Command::Frequency => cmd::frequency::run(argv),
+            Command::Groupby => cmd::groupby::run(argv),
+            Command::Head => cmd::head::run(argv),
             Command::Headers => cmd::headers::run(argv),
             Command::Help => {
                 wout!("{USAGE}");
@@ -262,6 +286,8 @@ impl Command {
             Command::Input => cmd::input::run(argv),
             Command::Join => cmd::join::run(argv),
             Command::Jsonl => cmd::jsonl::run(argv),
+            Command::Melt => cmd::melt::run(argv),
+            Command::Outliers => cmd::outliers::run(argv),
             Command::Partition => cmd::partition::run(argv),
             Command::Pseudo => cmd::pseudo::run(argv),
             Command::Rename => cmd::rename::run(argv),
@@ -283,6 +309,7 @@ impl Command {
             Command::Split => cmd::split::run(argv),
             Command::Stats => cmd::stats::run(argv),
             Command::Table => cmd::table::run(argv),
+            Command::Tail => cmd::tail::run(argv),
             Command::Tojsonl => cmd::tojsonl::run(argv),
             Command::Transpose => cmd::transpose::run(argv),
             Command::Validate => cmd::validate::run(argv),
@@ -47,6 +47,72 @@ fn apply_ops_upper() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn apply_ops_pipe_delimited() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![svec!["name"], svec!["  John   Cena "], svec![" Mary  Jane"]],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("trim|upper|squeeze")
+        .arg("name")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name"], svec!["JOHN CENA"], svec!["MARY JANE"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_map() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "city", "email", "notes"],
+            svec![" john cena ", "boston ", " John@Example.COM", "a   b"],
+            svec!["mary jane", " new york", "MARY@example.com ", "c"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("--ops-map")
+        .arg("name,city:trim|titlecase; email:trim,lower;")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city", "email", "notes"],
+        svec!["John Cena", "Boston", "john@example.com", "a   b"],
+        svec!["Mary Jane", "New York", "mary@example.com", "c"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_map_invalid() {
+    let wrk = Workdir::new("apply");
+    wrk.create("data.csv", vec![svec!["name"], svec!["John"]]);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("--ops-map")
+        .arg("name")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("--ops-map")
+        .arg("name:upper")
+        .arg("--new-column")
+        .arg("new_name")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn apply_ops_escape() {
     let wrk = Workdir::new("apply");
@@ -221,6 +287,113 @@ fn apply_ops_encode() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn apply_ops_encode64_alias() {
+    let wrk = Workdir::new("apply_ops_encode64_alias");
+    wrk.create("data.csv", vec![svec!["surname"], svec!["Cena"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("encode64")
+        .arg("surname")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["surname"], svec!["Q2VuYQ=="]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_hashes() {
+    let wrk = Workdir::new("apply_ops_hashes");
+    wrk.create("data.csv", vec![svec!["word"], svec!["hello"]]);
+    for (op, hash) in [
+        ("md5", "5d41402abc4b2a76b9719d911017c592"),
+        ("sha1", "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"),
+        (
+            "sha256",
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        ),
+        (
+            "blake3",
+            "ea8f163db38682925e4491c5e58d4bb3506ef8c14eb78a86e908c5624a67200f",
+        ),
+    ] {
+        let mut cmd = wrk.command("apply");
+        cmd.arg("operations")
+            .arg(op)
+            .arg("word")
+            .arg("--new-column")
+            .arg("hash")
+            .arg("data.csv");
+
+        let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+        let expected = vec![svec!["word", "hash"], svec!["hello", hash]];
+        assert_eq!(got, expected, "{op}");
+    }
+}
+
+#[test]
+fn apply_ops_hmac() {
+    let wrk = Workdir::new("apply_ops_hmac");
+    wrk.create("data.csv", vec![svec!["email"], svec![" Hello "]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("trim,lower,hmac")
+        .arg("email")
+        .arg("--comparand")
+        .arg("secret")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["email"],
+        svec!["88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b"],
+    ];
+    assert_eq!(got, expected);
+
+    // the key is required
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("hmac")
+        .arg("email")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn apply_ops_urlencode_urldecode() {
+    let wrk = Workdir::new("apply_ops_urlencode_urldecode");
+    wrk.create("data.csv", vec![svec!["q"], svec!["a b&c=d/é"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("urlencode")
+        .arg("q")
+        .arg("--new-column")
+        .arg("encoded")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["q", "encoded"],
+        svec!["a b&c=d/é", "a%20b%26c%3Dd%2F%C3%A9"],
+    ];
+    assert_eq!(got, expected);
+
+    wrk.create(
+        "encoded.csv",
+        vec![svec!["q"], svec!["a%20b%26c%3Dd%2F%C3%A9"]],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("urldecode")
+        .arg("q")
+        .arg("encoded.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["q"], svec!["a b&c=d/é"]];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn apply_ops_decode() {
     let wrk = Workdir::new("apply");
@@ -374,6 +547,101 @@ fn apply_calcconv() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn apply_expr() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Unit Price", "qty"],
+            svec!["2", "5"],
+            svec!["2.5", "4"],
+            svec!["4", "10"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("expr")
+        .arg("--formatstr")
+        .arg("unit_price * qty * 1.2")
+        .arg("--new-column")
+        .arg("total")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["Unit Price", "qty", "total"],
+        svec!["2", "5", "12.0"],
+        svec!["2.5", "4", "12.0"],
+        svec!["4", "10", "48.0"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_expr_strings_and_conditionals() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["first", "last", "qty", "discount"],
+            svec!["John", "Doe", "20", "5"],
+            svec!["Jane", "Roe", "15", ""],
+            svec!["Mary", "Poe", "3", "1"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("expr")
+        .arg("--formatstr")
+        .arg(r#"last | upper ~ ", " ~ first ~ ": " ~ (qty - (discount or 0) if qty > 10 else qty)"#)
+        .arg("-c")
+        .arg("label")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["first", "last", "qty", "discount", "label"],
+        svec!["John", "Doe", "20", "5", "DOE, John: 15"],
+        svec!["Jane", "Roe", "15", "", "ROE, Jane: 15"],
+        svec!["Mary", "Poe", "3", "1", "POE, Mary: 3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_expr_eval_error() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![svec!["price", "qty"], svec!["2", "5"], svec!["3", ""]],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("expr")
+        .arg("--formatstr")
+        .arg("price * qty")
+        .arg("--new-column")
+        .arg("total")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[1], svec!["2", "5", "10"]);
+    assert!(got[2][2].starts_with("ERROR: "));
+}
+
+#[test]
+fn apply_expr_invalid() {
+    let wrk = Workdir::new("apply");
+    wrk.create("data.csv", vec![svec!["price", "qty"], svec!["2", "5"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("expr")
+        .arg("--formatstr")
+        .arg("price * (qty")
+        .arg("--new-column")
+        .arg("total")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn apply_calcconv_invalid() {
     let wrk = Workdir::new("apply");
@@ -1555,104 +1823,322 @@ fn apply_ops_round_5places() {
 }
 
 #[test]
-fn apply_ops_currencytonum() {
+fn apply_ops_geohash() {
     let wrk = Workdir::new("apply");
     wrk.create(
         "data.csv",
         vec![
-            svec!["money"],
-            svec!["$10.00"],
-            svec!["$-10.00"],
-            svec!["$ 12 500.00"],
-            svec!["$5"],
-            svec!["0"],
-            svec!["5"],
-            svec!["$0.25"],
-            svec!["$ 10.05"],
-            svec!["¥10,000,000.00"],
-            svec!["£423.56"],
-            svec!["€120.00"],
-            svec!["֏99,999.50"],
-            svec!["€300 999,55"],
-            svec!["This is not money. Leave untouched."],
-            svec!["₱1,234,567.89"],
-            svec!["₽234,567.89"],
-            svec!["₪ 567.89"],
-            svec!["₩ 567.89"],
-            svec!["₩ 89,123.0"],
-            svec!["ƒ 123,456.00"],
-            svec!["฿ 789,123"],
-            svec!["₫ 456"],
-            svec!["123,456.00 $"],
-            svec!["USD 10,000"],
-            svec!["EUR 1234.50"],
-            svec!["JPY 9,999,999.99"],
-            svec!["RMB 6543.21"],
-            svec!["$10.0099"],
-            svec!["$10.0777"],
-            svec!["$10.0723"],
-            svec!["$10.8723"],
-            svec!["$10.77777"],
-            svec!["$10.777"],
+            svec!["location"],
+            svec!["57.64911, 10.40744"],
+            svec!["(40.7128, -74.0060)"],
+            svec!["not a location"],
         ],
     );
     let mut cmd = wrk.command("apply");
     cmd.arg("operations")
-        .arg("currencytonum")
-        .arg("money")
+        .arg("geohash")
+        .arg("location")
+        .arg("-c")
+        .arg("geohash")
         .arg("data.csv");
 
     let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
     let expected = vec![
-        svec!["money"],
-        svec!["10.00"],
-        svec!["-10.00"],
-        svec!["12500.00"],
-        svec!["5.00"],
-        svec!["0"],
-        svec!["5.00"],
-        svec!["0.25"],
-        svec!["10.05"],
-        svec!["10000000.00"],
-        svec!["423.56"],
-        svec!["120.00"],
-        svec!["99999.50"],
-        svec!["300999.55"],
-        svec!["This is not money. Leave untouched."],
-        svec!["1234567.89"],
-        svec!["234567.89"],
-        svec!["567.89"],
-        svec!["567.89"],
-        svec!["89123.00"],
-        svec!["123456.00"],
-        svec!["789123.00"],
-        svec!["456.00"],
-        svec!["123456.00"],
-        svec!["10000.00"],
-        svec!["1234.50"],
-        svec!["9999999.99"],
-        svec!["6543.21"],
-        svec!["10.01"],
-        svec!["10.08"],
-        svec!["10.07"],
-        svec!["10.87"],
-        svec!["10.78"],
-        svec!["10.78"],
+        svec!["location", "geohash"],
+        svec!["57.64911, 10.40744", "u4pruydqq"],
+        svec!["(40.7128, -74.0060)", "dr5regw3p"],
+        svec!["not a location", "not a location"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("geohash")
+        .arg("location")
+        .arg("--formatstr")
+        .arg("5")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["location"],
+        svec!["u4pru"],
+        svec!["dr5re"],
+        svec!["not a location"],
     ];
     assert_eq!(got, expected);
 }
 
 #[test]
-fn apply_ops_numtocurrency() {
+fn apply_ops_geohash_decode() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["geohash"],
+            svec!["u4pruydqqvj"],
+            svec!["DR5REGW3P"],
+            svec!["invalid!"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("geohash_decode")
+        .arg("geohash")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["geohash"],
+        svec!["57.649111, 10.40744"],
+        svec!["40.712779, -74.005988"],
+        svec!["invalid!"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_currencycode() {
     let wrk = Workdir::new("apply");
     wrk.create(
         "data.csv",
         vec![
             svec!["money"],
             svec!["$10.00"],
-            svec!["$-10.00"],
-            svec!["$ 12 500.00"],
-            svec!["$5"],
+            svec!["€120.00"],
+            svec!["¥10,000,000.00"],
+            svec!["CAD $ 12 500.00"],
+            svec!["EUR 1234.50"],
+            svec!["RMB 88"],
+            svec!["123,456.00 $"],
+            svec!["₩ 89,123.0"],
+            svec!["5"],
+            svec!["This is not money."],
+            svec!["50%"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("currencycode")
+        .arg("money")
+        .arg("-c")
+        .arg("currency")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["money", "currency"],
+        svec!["$10.00", "USD"],
+        svec!["€120.00", "EUR"],
+        svec!["¥10,000,000.00", "JPY"],
+        svec!["CAD $ 12 500.00", "CAD"],
+        svec!["EUR 1234.50", "EUR"],
+        svec!["RMB 88", "CNY"],
+        svec!["123,456.00 $", "USD"],
+        svec!["₩ 89,123.0", "KRW"],
+        svec!["5", ""],
+        svec!["This is not money.", ""],
+        svec!["50%", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_e164() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["phone"],
+            svec!["(202) 456-1111"],
+            svec!["202.456.1111"],
+            svec!["+44 20 7930 4832"],
+            svec!["12"],
+            svec!["not a phone"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("e164")
+        .arg("phone")
+        .arg("--comparand")
+        .arg("us")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["phone"],
+        svec!["+12024561111"],
+        svec!["+12024561111"],
+        svec!["+442079304832"],
+        svec!["12"],
+        svec!["not a phone"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_e164_invalid_region() {
+    let wrk = Workdir::new("apply");
+    wrk.create("data.csv", vec![svec!["phone"], svec!["(202) 456-1111"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("e164")
+        .arg("phone")
+        .arg("--comparand")
+        .arg("XX")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn apply_ops_email() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["email"],
+            svec![" John.Doe@Example.COM "],
+            svec!["x+y@mail-srv.example.co.uk"],
+            svec!["not-an-email"],
+            svec!["a@b"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("email")
+        .arg("email")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["email"],
+        svec!["john.doe@example.com"],
+        svec!["x+y@mail-srv.example.co.uk"],
+        svec!["not-an-email"],
+        svec!["a@b"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("email_check")
+        .arg("email")
+        .arg("-c")
+        .arg("valid")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["email", "valid"],
+        svec![" John.Doe@Example.COM ", "true"],
+        svec!["x+y@mail-srv.example.co.uk", "true"],
+        svec!["not-an-email", "false"],
+        svec!["a@b", "false"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_currencytonum() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["money"],
+            svec!["$10.00"],
+            svec!["$-10.00"],
+            svec!["$ 12 500.00"],
+            svec!["$5"],
+            svec!["0"],
+            svec!["5"],
+            svec!["$0.25"],
+            svec!["$ 10.05"],
+            svec!["¥10,000,000.00"],
+            svec!["£423.56"],
+            svec!["€120.00"],
+            svec!["֏99,999.50"],
+            svec!["€300 999,55"],
+            svec!["This is not money. Leave untouched."],
+            svec!["₱1,234,567.89"],
+            svec!["₽234,567.89"],
+            svec!["₪ 567.89"],
+            svec!["₩ 567.89"],
+            svec!["₩ 89,123.0"],
+            svec!["ƒ 123,456.00"],
+            svec!["฿ 789,123"],
+            svec!["₫ 456"],
+            svec!["123,456.00 $"],
+            svec!["USD 10,000"],
+            svec!["EUR 1234.50"],
+            svec!["JPY 9,999,999.99"],
+            svec!["RMB 6543.21"],
+            svec!["$10.0099"],
+            svec!["$10.0777"],
+            svec!["$10.0723"],
+            svec!["$10.8723"],
+            svec!["$10.77777"],
+            svec!["$10.777"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("currencytonum")
+        .arg("money")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["money"],
+        svec!["10.00"],
+        svec!["-10.00"],
+        svec!["12500.00"],
+        svec!["5.00"],
+        svec!["0"],
+        svec!["5.00"],
+        svec!["0.25"],
+        svec!["10.05"],
+        svec!["10000000.00"],
+        svec!["423.56"],
+        svec!["120.00"],
+        svec!["99999.50"],
+        svec!["300999.55"],
+        svec!["This is not money. Leave untouched."],
+        svec!["1234567.89"],
+        svec!["234567.89"],
+        svec!["567.89"],
+        svec!["567.89"],
+        svec!["89123.00"],
+        svec!["123456.00"],
+        svec!["789123.00"],
+        svec!["456.00"],
+        svec!["123456.00"],
+        svec!["10000.00"],
+        svec!["1234.50"],
+        svec!["9999999.99"],
+        svec!["6543.21"],
+        svec!["10.01"],
+        svec!["10.08"],
+        svec!["10.07"],
+        svec!["10.87"],
+        svec!["10.78"],
+        svec!["10.78"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_numtocurrency() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["money"],
+            svec!["$10.00"],
+            svec!["$-10.00"],
+            svec!["$ 12 500.00"],
+            svec!["$5"],
             svec!["0"],
             svec!["5"],
             svec!["$0.25"],
@@ -2286,6 +2772,131 @@ fn apply_emptyreplace() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn apply_lookup() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "states.csv",
+        vec![
+            svec!["code", "name"],
+            svec!["NY", "New York"],
+            svec!["CA", "California"],
+            svec!["NY", "Duplicate New York"],
+        ],
+    );
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["city", "state"],
+            svec!["Albany", "NY"],
+            svec!["Fresno", "CA"],
+            svec!["Austin", "TX"],
+            svec!["Nowhere", ""],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("lookup")
+        .arg("state")
+        .arg("--map")
+        .arg("states.csv")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "state"],
+        svec!["Albany", "New York"],
+        svec!["Fresno", "California"],
+        svec!["Austin", "TX"],
+        svec!["Nowhere", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_lookup_default_new_column() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "states.csv",
+        vec![
+            svec!["code", "name"],
+            svec!["NY", "New York"],
+            svec!["CA", "California"],
+        ],
+    );
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["city", "state"],
+            svec!["Albany", "NY"],
+            svec!["Austin", "TX"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("lookup")
+        .arg("state")
+        .arg("--map")
+        .arg("states.csv")
+        .arg("--replacement")
+        .arg("Unknown")
+        .arg("-c")
+        .arg("state_name")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "state", "state_name"],
+        svec!["Albany", "NY", "New York"],
+        svec!["Austin", "TX", "Unknown"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_lookup_multiple_cols() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "codes.csv",
+        vec![svec!["code", "label"], svec!["Y", "Yes"], svec!["N", "No"]],
+    );
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "q1", "q2"],
+            svec!["1", "Y", "N"],
+            svec!["2", "N", "?"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("lookup")
+        .arg("q1,q2")
+        .arg("--map")
+        .arg("codes.csv")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "q1", "q2"],
+        svec!["1", "Yes", "No"],
+        svec!["2", "No", "?"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_lookup_invalid_map() {
+    let wrk = Workdir::new("apply");
+    wrk.create("codes.csv", vec![svec!["code"], svec!["Y"]]);
+    wrk.create("data.csv", vec![svec!["q1"], svec!["Y"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("lookup")
+        .arg("q1")
+        .arg("--map")
+        .arg("codes.csv")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn apply_datefmt() {
     let wrk = Workdir::new("apply");
@@ -2324,6 +2935,186 @@ fn apply_datefmt() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn apply_datefmt_output_tz() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Created Date"],
+            svec!["2021-03-15T14:30:00Z"],
+            svec!["2021-01-15T14:30:00Z"],
+            svec!["This is not a date"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("Created Date")
+        .arg("--output-tz")
+        .arg("America/New_York")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["Created Date"],
+        svec!["2021-03-15T10:30:00-04:00"],
+        svec!["2021-01-15T09:30:00-05:00"],
+        svec!["This is not a date"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_datefmt_output_tz_invalid() {
+    let wrk = Workdir::new("apply");
+    wrk.create("data.csv", vec![svec!["date"], svec!["2021-03-15"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("date")
+        .arg("--output-tz")
+        .arg("Mars/Olympus_Mons")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn apply_datefmt_trunc() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["date"],
+            svec!["2021-03-17T14:30:00Z"],
+            svec!["2021-03-14T20:00:00Z"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("date")
+        .arg("--trunc")
+        .arg("week")
+        .arg("--formatstr")
+        .arg("%Y-%m-%d")
+        .arg("-c")
+        .arg("week")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["date", "week"],
+        svec!["2021-03-17T14:30:00Z", "2021-03-15"],
+        svec!["2021-03-14T20:00:00Z", "2021-03-08"],
+    ];
+    assert_eq!(got, expected);
+
+    // truncation is done in the output timezone
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("date")
+        .arg("--trunc")
+        .arg("day")
+        .arg("--output-tz")
+        .arg("Asia/Manila")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["date"],
+        svec!["2021-03-17T00:00:00+08:00"],
+        svec!["2021-03-15T00:00:00+08:00"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("date")
+        .arg("--trunc")
+        .arg("month")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["date"], svec!["2021-03-01"], svec!["2021-03-01"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_datefmt_elapsed_to_column() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["start", "end"],
+            svec!["2021-01-01", "2021-03-15"],
+            svec!["1990-06-15", "2023-06-14"],
+            svec!["1990-06-15", "2023-06-15"],
+            svec!["2023-06-15", "1990-06-15"],
+            svec!["2021-01-01", ""],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("start")
+        .arg("--elapsed-to")
+        .arg("end")
+        .arg("-c")
+        .arg("days")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["start", "end", "days"],
+        svec!["2021-01-01", "2021-03-15", "73"],
+        svec!["1990-06-15", "2023-06-14", "12052"],
+        svec!["1990-06-15", "2023-06-15", "12053"],
+        svec!["2023-06-15", "1990-06-15", "-12053"],
+        svec!["2021-01-01", "", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("start")
+        .arg("--elapsed-to")
+        .arg("end")
+        .arg("--elapsed-unit")
+        .arg("years")
+        .arg("-c")
+        .arg("years")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["start", "end", "years"],
+        svec!["2021-01-01", "2021-03-15", "0"],
+        svec!["1990-06-15", "2023-06-14", "32"],
+        svec!["1990-06-15", "2023-06-15", "33"],
+        svec!["2023-06-15", "1990-06-15", "-33"],
+        svec!["2021-01-01", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_datefmt_elapsed_to_now() {
+    let wrk = Workdir::new("apply");
+    wrk.create("data.csv", vec![svec!["birthdate"], svec!["1900-01-01"]]);
+    let mut cmd = wrk.command("apply");
+    cmd.arg("datefmt")
+        .arg("birthdate")
+        .arg("--elapsed-to")
+        .arg("now")
+        .arg("--elapsed-unit")
+        .arg("years")
+        .arg("-c")
+        .arg("age")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let age: i64 = got[1][1].parse().unwrap();
+    assert!(age >= 123);
+}
+
 #[test]
 fn apply_datefmt_to_unixtime() {
     let wrk = Workdir::new("apply");
@@ -2838,3 +3629,156 @@ fn apply_geocode_fmtstring_intl() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn apply_geodist() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["from", "to"],
+            svec!["40.7128, -74.0060", "(34.0522, -118.2437)"],
+            svec!["40.7128, -74.0060", ""],
+            svec!["95.213424, 190.1234565", "34.0522, -118.2437"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("geodist")
+        .arg("from,to")
+        .arg("--new-column")
+        .arg("distance_km")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["from", "to", "distance_km"],
+        svec!["40.7128, -74.0060", "(34.0522, -118.2437)", "3935.752"],
+        svec!["40.7128, -74.0060", "", ""],
+        svec!["95.213424, 190.1234565", "34.0522, -118.2437", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("geodist")
+        .arg("from,to")
+        .arg("--formatstr")
+        .arg("mi")
+        .arg("-c")
+        .arg("distance_mi")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[1][2], "2445.563");
+}
+
+#[test]
+fn apply_geodist_lat_long_columns() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["city", "lat1", "long1", "lat2", "long2"],
+            svec!["London-Paris", "51.5074", "-0.1278", "48.8566", "2.3522"],
+            svec!["Invalid", "51.5074", "", "48.8566", "2.3522"],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("geodist")
+        .arg("lat1,long1,lat2,long2")
+        .arg("--formatstr")
+        .arg("m")
+        .arg("-c")
+        .arg("meters")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "lat1", "long1", "lat2", "long2", "meters"],
+        svec![
+            "London-Paris",
+            "51.5074",
+            "-0.1278",
+            "48.8566",
+            "2.3522",
+            "343556.535"
+        ],
+        svec!["Invalid", "51.5074", "", "48.8566", "2.3522", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_geodist_invalid() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![svec!["lat", "long", "other"], svec!["1", "2", "3"]],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("geodist")
+        .arg("lat,long,other")
+        .arg("-c")
+        .arg("distance")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("apply");
+    cmd.arg("geodist")
+        .arg("lat,long")
+        .arg("--formatstr")
+        .arg("furlongs")
+        .arg("-c")
+        .arg("distance")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn apply_ops_dns_lookup() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["host"],
+            svec!["127.0.0.1"],
+            svec!["::1"],
+            svec!["not a valid hostname"],
+            svec![""],
+        ],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("dns_lookup")
+        .arg("host")
+        .arg("--new-column")
+        .arg("ip")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["host", "ip"],
+        svec!["127.0.0.1", "127.0.0.1"],
+        svec!["::1", "::1"],
+        svec!["not a valid hostname", ""],
+        svec!["", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn apply_ops_reverse_dns_invalid() {
+    let wrk = Workdir::new("apply");
+    wrk.create(
+        "data.csv",
+        vec![svec!["ip"], svec!["not an ip address"], svec!["300.1.2.3"]],
+    );
+    let mut cmd = wrk.command("apply");
+    cmd.arg("operations")
+        .arg("reverse_dns")
+        .arg("ip")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["ip"], svec![""], svec![""]];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,72 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn autoconvert_preamble() {
+    let wrk = Workdir::new("autoconvert_preamble");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("autoconvert");
+    cmd.arg("--quiet").arg(test_file);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["h1", "h2", "h3", "h4"],
+        svec!["abcdefg", "1", "a", "3.14"],
+        svec!["a", "2", "z", "1.2020569"],
+        svec!["c", "42", "x", "1.0"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn autoconvert_semicolon() {
+    let wrk = Workdir::new("autoconvert_semicolon");
+    wrk.create_from_string(
+        "data.txt",
+        "name;city;amount\n\"Smith; John\";Berlin;1,5\nDoe;Paris;2,25\nRoe;Rome;3\nPoe;Oslo;4\n",
+    );
+
+    let mut cmd = wrk.command("autoconvert");
+    cmd.arg("--quiet").arg("data.txt");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"name,city,amount
+"Smith; John",Berlin,"1,5"
+Doe,Paris,"2,25"
+Roe,Rome,3
+Poe,Oslo,4"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn autoconvert_utf16_bom() {
+    let wrk = Workdir::new("autoconvert_utf16_bom");
+    let mut data: Vec<u8> = vec![0xFF, 0xFE];
+    for unit in "name\tcity\nJosé\tMontréal\nAnna\tGraz\nLuis\tLeón\n".encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+    std::fs::write(wrk.path("utf16.tsv"), data).unwrap();
+
+    let mut cmd = wrk.command("autoconvert");
+    cmd.arg("--quiet").arg("utf16.tsv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city"],
+        svec!["José", "Montréal"],
+        svec!["Anna", "Graz"],
+        svec!["Luis", "León"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn autoconvert_unknown_encoding() {
+    let wrk = Workdir::new("autoconvert_unknown_encoding");
+    wrk.create("in.csv", vec![svec!["a"], svec!["1"]]);
+
+    let mut cmd = wrk.command("autoconvert");
+    cmd.args(["--encoding", "no-such-encoding"]).arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
@@ -14,3 +14,24 @@ fn behead() {
     let expected = vec![svec!["a", "1"], svec!["b", "2"]];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn behead_skip_lines() {
+    let wrk = Workdir::new("behead_skip_lines").flexible(true);
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["# exported from the letters database"],
+            svec!["# this is a preamble"],
+            svec!["letter", "number"],
+            svec!["a", "1"],
+            svec!["b", "2"],
+        ],
+    );
+    let mut cmd = wrk.command("behead");
+    cmd.args(["--skip-lines", "2"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["a", "1"], svec!["b", "2"]];
+    assert_eq!(got, expected);
+}
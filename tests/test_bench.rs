@@ -0,0 +1,36 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn bench_generated() {
+    let wrk = Workdir::new("bench_generated");
+    let mut cmd = wrk.command("bench");
+    cmd.args(["--commands", "count,select"])
+        .args(["--rows", "100"])
+        .args(["--repeat", "2"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(
+        got[0],
+        svec![
+            "command",
+            "runs",
+            "min_secs",
+            "mean_secs",
+            "mb_per_sec",
+            "rows_per_sec"
+        ]
+    );
+    assert_eq!(got.len(), 3);
+    assert_eq!(got[1][0..2], svec!["count", "2"]);
+    assert_eq!(got[2][0..2], svec!["select", "2"]);
+}
+
+#[test]
+fn bench_input_unknown_command() {
+    let wrk = Workdir::new("bench_input_unknown_command");
+    wrk.create("data.csv", vec![svec!["a", "b"], svec!["1", "2"]]);
+    let mut cmd = wrk.command("bench");
+    cmd.args(["--commands", "count,nope"]).arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
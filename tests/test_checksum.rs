@@ -0,0 +1,50 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn checksum_row_order_and_quoting_invariant() {
+    let wrk = Workdir::new("checksum_row_order_and_quoting_invariant");
+    wrk.create_from_string(
+        "data1.csv",
+        "name,city\nAlice,Boston\nBob,\"New York, NY\"\n",
+    );
+    wrk.create_from_string(
+        "data2.csv",
+        "\"name\",\"city\"\n\"Bob\",\"New York, NY\"\n\"Alice\",Boston\n",
+    );
+
+    let mut cmd = wrk.command("checksum");
+    cmd.arg("data1.csv");
+    let checksum1: String = wrk.stdout(&mut cmd);
+
+    let mut cmd = wrk.command("checksum");
+    cmd.arg("data2.csv");
+    let checksum2: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(checksum1.len(), 64);
+    assert_eq!(checksum1, checksum2);
+}
+
+#[test]
+fn checksum_different_data() {
+    let wrk = Workdir::new("checksum_different_data");
+    wrk.create_from_string("data1.csv", "name,city\nAlice,Boston\nBob,Chicago\n");
+    // same fields, but split differently across the records
+    wrk.create_from_string("data2.csv", "name,city\nAlice,Chicago\nBob,Boston\n");
+    // a duplicated record
+    wrk.create_from_string(
+        "data3.csv",
+        "name,city\nAlice,Boston\nBob,Chicago\nBob,Chicago\n",
+    );
+
+    let checksums: Vec<String> = ["data1.csv", "data2.csv", "data3.csv"]
+        .iter()
+        .map(|file| {
+            let mut cmd = wrk.command("checksum");
+            cmd.arg(file);
+            wrk.stdout(&mut cmd)
+        })
+        .collect();
+
+    assert_ne!(checksums[0], checksums[1]);
+    assert_ne!(checksums[0], checksums[2]);
+}
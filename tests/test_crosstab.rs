@@ -0,0 +1,82 @@
+use crate::workdir::Workdir;
+
+fn sales() -> Vec<Vec<String>> {
+    vec![
+        svec!["region", "product"],
+        svec!["east", "apples"],
+        svec!["east", "apples"],
+        svec!["east", "pears"],
+        svec!["west", "pears"],
+        svec!["west", ""],
+    ]
+}
+
+#[test]
+fn crosstab_counts() {
+    let wrk = Workdir::new("crosstab_counts");
+    wrk.create("in.csv", sales());
+
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["region", "product", "in.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region/product", "(NULL)", "apples", "pears"],
+        svec!["east", "0", "2", "1"],
+        svec!["west", "1", "0", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_margins_no_nulls() {
+    let wrk = Workdir::new("crosstab_margins_no_nulls");
+    wrk.create("in.csv", sales());
+
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["--margins", "--no-nulls", "1", "2", "in.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region/product", "apples", "pears", "(Total)"],
+        svec!["east", "2", "1", "3"],
+        svec!["west", "0", "1", "1"],
+        svec!["(Total)", "2", "2", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_normalize_row() {
+    let wrk = Workdir::new("crosstab_normalize_row");
+    wrk.create("in.csv", sales());
+
+    let mut cmd = wrk.command("crosstab");
+    cmd.args([
+        "--normalize",
+        "row",
+        "--no-nulls",
+        "region",
+        "product",
+        "in.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region/product", "apples", "pears"],
+        svec!["east", "66.67", "33.33"],
+        svec!["west", "0", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_multiple_columns() {
+    let wrk = Workdir::new("crosstab_multiple_columns");
+    wrk.create("in.csv", sales());
+
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["region,product", "product", "in.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,71 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn datefmt() {
+    let wrk = Workdir::new("datefmt");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Created Date", "id"],
+            svec!["September 17, 2012 10:09am EST", "1"],
+            svec!["July 4, 2005", "2"],
+            svec!["This is not a date", "3"],
+            svec!["", "4"],
+        ],
+    );
+    let mut cmd = wrk.command("datefmt");
+    cmd.arg("Created Date").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["Created Date", "id"],
+        svec!["2012-09-17T15:09:00+00:00", "1"],
+        svec!["2005-07-04", "2"],
+        svec!["This is not a date", "3"],
+        svec!["", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn datefmt_prefer_dmy_new_column() {
+    let wrk = Workdir::new("datefmt_prefer_dmy_new_column");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["start", "end"],
+            svec!["03/08/2021", "04/08/2021"],
+            svec!["12/01/2020", "not a date"],
+        ],
+    );
+    let mut cmd = wrk.command("datefmt");
+    cmd.arg("start,end")
+        .args(["--formatstr", "%Y-%m-%d"])
+        .arg("--prefer-dmy")
+        .args(["--new-column", "start_iso,end_iso"])
+        .args(["--invalid", "empty"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["start", "end", "start_iso", "end_iso"],
+        svec!["03/08/2021", "04/08/2021", "2021-08-03", "2021-08-04"],
+        svec!["12/01/2020", "not a date", "2020-01-12", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn datefmt_invalid_error() {
+    let wrk = Workdir::new("datefmt_invalid_error");
+    wrk.create(
+        "data.csv",
+        vec![svec!["date"], svec!["2021-08-03"], svec!["not a date"]],
+    );
+    let mut cmd = wrk.command("datefmt");
+    cmd.arg("date").args(["--invalid", "error"]).arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "Invalid date in column 1 of record 2: not a date\n");
+    wrk.assert_err(&mut cmd);
+}
@@ -233,3 +233,60 @@ fn dedup_not_sorted2() {
     let got: String = wrk.output_stderr(&mut cmd);
     assert!(got.contains("Aborting! Input not sorted!"));
 }
+
+#[test]
+fn dedup_no_case_unsorted() {
+    let wrk = Workdir::new("dedup_no_case_unsorted");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["S"],
+            svec!["a"],
+            svec!["B"],
+            svec!["A"],
+            svec!["b"],
+            svec!["c"],
+        ],
+    );
+
+    let mut cmd = wrk.command("dedup");
+    cmd.arg("--ignore-case").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["S"], svec!["a"], svec!["b"], svec!["c"]];
+    assert_eq!(got, expected);
+
+    let got_err = wrk.output_stderr(&mut cmd);
+    assert_eq!(got_err, "2\n");
+}
+
+#[test]
+fn dedup_dupes_output() {
+    let wrk = Workdir::new("dedup_dupes_output");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["N", "S"],
+            svec!["10", "a"],
+            svec!["10", "a"],
+            svec!["2", "b"],
+            svec!["2", "b"],
+            svec!["3", "c"],
+        ],
+    );
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--dupes-output", "dupes.csv"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["N", "S"],
+        svec!["10", "a"],
+        svec!["2", "b"],
+        svec!["3", "c"],
+    ];
+    assert_eq!(got, expected);
+
+    let dupes: String = wrk.from_str(&wrk.path("dupes.csv"));
+    assert_eq!(dupes, "N,S\n10,a\n2,b\n");
+}
@@ -0,0 +1,32 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn describegpt_requires_inference_option() {
+    let wrk = Workdir::new("describegpt_requires_inference_option");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("describegpt");
+    cmd.arg("data.csv").args(["--api-key", "test"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(
+        got,
+        "Set at least one of --description, --dictionary, --tags or --all to infer.\n"
+    );
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn describegpt_requires_api_key() {
+    let wrk = Workdir::new("describegpt_requires_api_key");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("describegpt");
+    cmd.env_remove("QSV_OPENAI_KEY")
+        .arg("data.csv")
+        .arg("--all");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.starts_with("An API key is required by the OpenAI API."));
+    wrk.assert_err(&mut cmd);
+}
@@ -164,3 +164,79 @@ diffresult,case_enquiry_id,open_dt,target_dt,closed_dt,ontime,case_status,closur
         "#.trim().to_string()
     }
 }
+
+#[test]
+fn diff_ignore_columns() {
+    let wrk = Workdir::new("diff_ignore_columns");
+    wrk.create(
+        "left.csv",
+        vec![
+            svec!["id", "name", "updated"],
+            svec!["1", "Alice", "2022-01-01"],
+            svec!["2", "Bob", "2022-01-01"],
+        ],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "name", "updated"],
+            svec!["1", "Alice", "2022-02-01"],
+            svec!["2", "Robert", "2022-02-01"],
+        ],
+    );
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--ignore-columns", "2"])
+        .arg("left.csv")
+        .arg("right.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "name"],
+        svec!["-", "2", "Bob"],
+        svec!["+", "2", "Robert"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_ignore_key_column_error() {
+    let wrk = Workdir::new("diff_ignore_key_column_error");
+    wrk.create("left.csv", vec![svec!["id", "name"], svec!["1", "Alice"]]);
+    wrk.create("right.csv", vec![svec!["id", "name"], svec!["1", "Bob"]]);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--ignore-columns", "0"])
+        .arg("left.csv")
+        .arg("right.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn diff_trim() {
+    let wrk = Workdir::new("diff_trim");
+    wrk.create(
+        "left.csv",
+        vec![svec!["id", "name"], svec!["1", "Alice"], svec!["2", "Bob"]],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "name "],
+            svec![" 1", "Alice  "],
+            svec!["2", " Robert"],
+        ],
+    );
+
+    let mut cmd = wrk.command("diff");
+    cmd.arg("--trim").arg("left.csv").arg("right.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "name"],
+        svec!["-", "2", "Bob"],
+        svec!["+", "2", "Robert"],
+    ];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,161 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["name", "city"],
+        svec!["Alice", "New York, NY"],
+        svec!["Bob", "Chicago"],
+        svec!["Carol", "Denver"],
+    ]
+}
+
+#[test]
+fn edit_cell() {
+    let wrk = Workdir::new("edit_cell");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv").arg("city").arg("1").arg("Boston, MA");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "name,city\nAlice,\"New York, NY\"\nBob,\"Boston, MA\"\nCarol,Denver";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn edit_cell_index_in_place() {
+    let wrk = Workdir::new("edit_cell_index_in_place");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv")
+        .arg("1")
+        .arg("2")
+        .arg("Caroline")
+        .arg("--in-place");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("data.csv"));
+    let expected = "name,city\nAlice,\"New York, NY\"\nBob,Chicago\nCaroline,Denver\n";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn edit_patch() {
+    let wrk = Workdir::new("edit_patch");
+    wrk.create("data.csv", data());
+    wrk.create(
+        "patch.csv",
+        vec![
+            svec!["row", "column", "value"],
+            svec!["2", "city", "Austin"],
+            svec!["0", "name", "Alicia"],
+            svec!["0", "2", "Newark"],
+        ],
+    );
+    let mut cmd = wrk.command("edit");
+    cmd.args(["--patch", "patch.csv"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city"],
+        svec!["Alicia", "Newark"],
+        svec!["Bob", "Chicago"],
+        svec!["Carol", "Austin"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn edit_row_out_of_bounds() {
+    let wrk = Workdir::new("edit_row_out_of_bounds");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv").arg("city").arg("3").arg("Boston");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "Row 3 is out of bounds. The CSV only has 3 rows.\n");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn edit_unknown_column() {
+    let wrk = Workdir::new("edit_unknown_column");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv").arg("state").arg("0").arg("NY");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn edit_preserves_quoting() {
+    let wrk = Workdir::new("edit_preserves_quoting");
+    wrk.create_from_string(
+        "data.csv",
+        "\"name\",\"city\"\r\n\"Alice\",\"New York, NY\"\r\n\"Bob\",\"Chicago\"\r\n",
+    );
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv")
+        .arg("city")
+        .arg("1")
+        .arg("Boston")
+        .arg("--in-place");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("data.csv"));
+    let expected = "\"name\",\"city\"\r\n\"Alice\",\"New York, NY\"\r\n\"Bob\",Boston\r\n";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn edit_quotes_edited_field() {
+    let wrk = Workdir::new("edit_quotes_edited_field");
+    wrk.create_from_string("data.csv", "name,city\nAlice,'NYC'\nBob,Chicago");
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv")
+        .arg("city")
+        .arg("1")
+        .arg("Boston, \"MA\"");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "name,city\nAlice,'NYC'\nBob,\"Boston, \"\"MA\"\"\"";
+    assert_eq!(got, expected);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn edit_in_place_keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let wrk = Workdir::new("edit_in_place_keeps_permissions");
+    wrk.create("data.csv", data());
+    let path = wrk.path("data.csv");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv")
+        .arg("city")
+        .arg("0")
+        .arg("Boston")
+        .arg("--in-place");
+    wrk.assert_success(&mut cmd);
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+}
+
+#[test]
+fn edit_in_place_with_output() {
+    let wrk = Workdir::new("edit_in_place_with_output");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("edit");
+    cmd.arg("data.csv")
+        .arg("city")
+        .arg("0")
+        .arg("Boston")
+        .arg("--in-place")
+        .args(["--output", "out.csv"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "--in-place cannot be used with --output.\n");
+    wrk.assert_err(&mut cmd);
+}
@@ -165,3 +165,64 @@ fn enumerate_copy_name() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn enumerate_start_increment() {
+    let wrk = Workdir::new("enumerate_start_increment");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "number"],
+            svec!["a", "13"],
+            svec!["b", "24"],
+            svec!["c", "72"],
+            svec!["d", "7"],
+        ],
+    );
+    let mut cmd = wrk.command("enum");
+    cmd.args(["--start", "100"])
+        .args(["--increment", "10"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["letter", "number", "index"],
+        svec!["a", "13", "100"],
+        svec!["b", "24", "110"],
+        svec!["c", "72", "120"],
+        svec!["d", "7", "130"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn enumerate_start_increment_overflow() {
+    let wrk = Workdir::new("enumerate_start_increment_overflow");
+    wrk.create(
+        "data.csv",
+        vec![svec!["letter"], svec!["a"], svec!["b"], svec!["c"]],
+    );
+    let max = u64::MAX.to_string();
+
+    let mut cmd = wrk.command("enum");
+    cmd.args(["--start", &max])
+        .args(["--increment", "1"])
+        .arg("data.csv");
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(
+        got,
+        format!(
+            "The identifier overflowed: it can't be more than {max} with --start {max} & \
+             --increment 1.\n"
+        )
+    );
+    wrk.assert_err(&mut cmd);
+
+    // the last identifier can be the maximum
+    let mut cmd = wrk.command("enum");
+    cmd.args(["--start", &(u64::MAX - 2).to_string()])
+        .args(["--increment", "1"])
+        .arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[3], svec!["c", max]);
+}
@@ -335,6 +335,21 @@ fn excel_invalid_sheet_index() {
     wrk.assert_err(&mut cmd);
 }
 
+#[test]
+fn excel_sheet_index_equal_to_num_sheets() {
+    let wrk = Workdir::new("excel_sheet_index_equal_to_num_sheets");
+
+    let xls_file = wrk.load_test_file("excel-xls.xls");
+
+    let mut cmd = wrk.command("excel");
+    cmd.arg("--sheet").arg("8").arg(xls_file);
+
+    let got = wrk.output_stderr(&mut cmd);
+    let expected = "sheet index 8 is out of range; there are only 8 sheets (0-based)\n".to_string();
+    assert_eq!(got, expected);
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn excel_invalid_sheet_neg_index() {
     let wrk = Workdir::new("excel_invalid_sheet_neg_index");
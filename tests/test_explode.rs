@@ -78,3 +78,25 @@ fn explode_no_headers() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn explode_multiple_columns_error() {
+    let wrk = Workdir::new("explode_multiple_columns_error");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "colors"],
+            svec!["Mary", "yellow"],
+            svec!["John", "blue|orange"],
+        ],
+    );
+    let mut cmd = wrk.command("explode");
+    cmd.arg("name,colors").arg("|").arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(
+        got,
+        "Only one column can be exploded at a time. 2 columns selected.\n"
+    );
+    wrk.assert_err(&mut cmd);
+}
@@ -54,3 +54,20 @@ fn extdedup_dupesoutput() {
 
     assert_eq!(dos2unix(&dupes_output), dos2unix(&expected_output));
 }
+
+#[test]
+fn extdedup_memory_limit_no_output() {
+    let wrk = Workdir::new("extdedup-memory-limit-no-output").flexible(true);
+    wrk.clear_contents().unwrap();
+
+    let test_file = wrk.load_test_file("boston311-100-20dupes-random.csv");
+
+    let mut cmd = wrk.command("extdedup");
+    cmd.arg(test_file)
+        .args(["--memory-limit", "1"])
+        .arg("--no-output");
+
+    let got_stderr = wrk.output_stderr(&mut cmd);
+    assert_eq!(got_stderr, "20\n");
+    wrk.assert_success(&mut cmd);
+}
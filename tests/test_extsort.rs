@@ -24,3 +24,35 @@ fn extsort() {
 
     assert_eq!(dos2unix(&sorted_output), dos2unix(&expected_csv));
 }
+
+#[test]
+fn extsort_tmp_dir() {
+    let wrk = Workdir::new("extsort_tmp_dir").flexible(true);
+    wrk.clear_contents().unwrap();
+
+    let unsorted_csv = wrk.load_test_resource("adur-public-toilets.csv");
+    wrk.create_from_string("adur-public-toilets.csv", &unsorted_csv);
+    std::fs::create_dir(wrk.path("sort_tmp")).unwrap();
+
+    let mut cmd = wrk.command("extsort");
+    cmd.args(["--tmp-dir", "sort_tmp"])
+        .args(["--memory-limit", "1"])
+        .arg("adur-public-toilets.csv")
+        .arg("adur-public-toilets-extsort-test.csv");
+    wrk.assert_success(&mut cmd);
+
+    let sorted_output: String = wrk.from_str(&wrk.path("adur-public-toilets-extsort-test.csv"));
+    let expected_csv = wrk.load_test_resource("adur-public-toilets-sorted.csv");
+
+    assert_eq!(dos2unix(&sorted_output), dos2unix(&expected_csv));
+}
+
+#[test]
+fn extsort_invalid_tmp_dir() {
+    let wrk = Workdir::new("extsort_invalid_tmp_dir");
+    wrk.create("in.csv", vec![svec!["letter"], svec!["b"], svec!["a"]]);
+
+    let mut cmd = wrk.command("extsort");
+    cmd.args(["--tmp-dir", "does_not_exist"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
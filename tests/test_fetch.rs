@@ -178,6 +178,23 @@ fn fetch_simple_url_template() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn fetch_url_template_invalid_column() {
+    let wrk = Workdir::new("fetch_url_template_invalid_column");
+    wrk.create(
+        "data.csv",
+        vec![svec!["zip code"], svec!["90210"], svec!["94105"]],
+    );
+    let mut cmd = wrk.command("fetch");
+    cmd.arg("--url-template")
+        .arg("https://api.zippopotam.us/us/{zipcode}")
+        .arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains(r#""zipcode" is not a valid column name"#));
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 #[ignore = "Temporarily skip this as it seems https://zippopotam.us is not currently available"]
 fn fetch_simple_redis() {
@@ -638,6 +655,12 @@ async fn get_fullname(req: HttpRequest, name: web::Path<String>) -> Result<impl
     Ok(web::Json(obj))
 }
 
+/// handler that echoes back a JSON body
+/// fails with a 400 status if the body is not JSON
+async fn post_json(body: web::Json<serde_json::Value>) -> Result<impl Responder> {
+    Ok(web::Json(body.into_inner()))
+}
+
 // convenience macros for changing test ip/port to use
 macro_rules! test_server {
     () => {
@@ -671,6 +694,7 @@ async fn run_webserver(tx: mpsc::Sender<ServerHandle>) -> std::io::Result<()> {
             .wrap(middleware::Compress::default())
             .wrap(Governor::new(&governor_conf))
             .service(web::resource("/user/{name}").route(web::get().to(get_fullname)))
+            .service(web::resource("/post_json").route(web::post().to(post_json)))
             .service(web::resource("/").to(index))
     })
     .bind(test_server!())?
@@ -865,6 +889,58 @@ fn fetch_complex_url_template() {
     rt::System::new().block_on(server_handle.stop(true));
 }
 
+#[test]
+#[serial]
+fn fetchpost_json_body() {
+    // start webserver
+    let (tx, rx) = mpsc::channel();
+
+    println!("START Webserver ");
+    thread::spawn(move || {
+        let server_future = run_webserver(tx);
+        rt::System::new().block_on(server_future)
+    });
+
+    let server_handle = rx.recv().unwrap();
+
+    // proceed with usual unit test
+    let wrk = Workdir::new("fetchpost_json_body");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "color"],
+            svec!["Smurfette", "blue"],
+            svec!["Greedy", "green"],
+            svec!["Scaredy", "black"],
+        ],
+    );
+    let mut cmd = wrk.command("fetchpost");
+    cmd.arg(test_url!("post_json"))
+        .arg("name,color")
+        .arg("--json-body")
+        .arg("--new-column")
+        .arg("echoed_color")
+        .arg("--jql")
+        .arg(r#"."color""#)
+        .arg("--rate-limit")
+        .arg("4")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "color", "echoed_color"],
+        svec!["Smurfette", "blue", "blue"],
+        svec!["Greedy", "green", "green"],
+        svec!["Scaredy", "black", "black"],
+    ];
+
+    assert_eq!(got, expected);
+
+    // init stop webserver and wait until server gracefully exit
+    println!("STOPPING Webserver");
+    rt::System::new().block_on(server_handle.stop(true));
+}
+
 #[test]
 #[ignore = "Temporarily skip this as it seems httpbin.org is not currently available"]
 fn fetchpost_simple_test() {
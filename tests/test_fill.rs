@@ -154,3 +154,20 @@ fn fill_default() {
     let expected = svec!["dat", "dat", "abc", "dat", "zap", "bar", "bongo", "dat", "dat", "dat"];
     compare_column(&got, &expected, 0, true);
 }
+
+#[test]
+fn fill_unordered_selection() {
+    let wrk = Workdir::new("fill_unordered_selection");
+    wrk.create("in.csv", example());
+
+    let mut cmd = wrk.command("fill");
+    cmd.arg("--").arg("3,1").arg("in.csv");
+
+    let got: Vec<CsvRecord> = wrk.read_stdout(&mut cmd);
+
+    // both selected columns are filled, regardless of selection order
+    let expected_h1 = svec!["", "", "abc", "abc", "zap", "bar", "bongo", "bongo", "bongo", "bongo"];
+    let expected_h3 = svec!["egg", "egg", "foo", "egg", "foo", "foo", "foo", "jar", "jar", "jar"];
+    compare_column(&got, &expected_h1, 0, true);
+    compare_column(&got, &expected_h3, 2, true);
+}
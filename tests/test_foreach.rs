@@ -61,3 +61,115 @@ fn foreach_new_column() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn foreach_capture() {
+    let wrk = Workdir::new("foreach_capture");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "age"],
+            svec!["John", "42"],
+            svec!["Mary", "37"],
+        ],
+    );
+    let mut cmd = wrk.command("foreach");
+    cmd.arg("name")
+        .arg("echo 'Hello {}'")
+        .arg("--capture")
+        .arg("greeting")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "age", "greeting"],
+        svec!["John", "42", "Hello John"],
+        svec!["Mary", "37", "Hello Mary"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn foreach_jobs() {
+    let wrk = Workdir::new("foreach_jobs");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name"],
+            svec!["John"],
+            svec!["Mary"],
+            svec!["Anne"],
+            svec!["Paul"],
+            svec!["Ringo"],
+        ],
+    );
+    let mut cmd = wrk.command("foreach");
+    cmd.arg("name")
+        .arg("echo 'NAME = {}'")
+        .args(["--jobs", "3"])
+        .args(["--batch", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["NAME = John"],
+        svec!["NAME = Mary"],
+        svec!["NAME = Anne"],
+        svec!["NAME = Paul"],
+        svec!["NAME = Ringo"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn foreach_jobs_capture() {
+    let wrk = Workdir::new("foreach_jobs_capture");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "delay"],
+            svec!["John", "0.3"],
+            svec!["Mary", "0"],
+            svec!["Anne", "0.1"],
+        ],
+    );
+    // the slowest command is the first, yet the output stays in order
+    let mut cmd = wrk.command("foreach");
+    cmd.arg("delay")
+        .arg("sh -c 'sleep {}; echo slept {}'")
+        .args(["--jobs", "3"])
+        .args(["--capture", "slept"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "delay", "slept"],
+        svec!["John", "0.3", "slept 0.3"],
+        svec!["Mary", "0", "slept 0"],
+        svec!["Anne", "0.1", "slept 0.1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn foreach_jobs_unify() {
+    let wrk = Workdir::new("foreach_jobs_unify");
+    wrk.create(
+        "data.csv",
+        vec![svec!["name"], svec!["John"], svec!["Mary"]],
+    );
+    let mut cmd = wrk.command("foreach");
+    cmd.arg("name")
+        .arg("echo 'name,value\n{},1'")
+        .arg("--unify")
+        .args(["--jobs", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "value"],
+        svec!["John", "1"],
+        svec!["Mary", "1"],
+    ];
+    assert_eq!(got, expected);
+}
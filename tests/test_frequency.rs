@@ -125,6 +125,245 @@ fn frequency_select() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn frequency_pct() {
+    let (wrk, mut cmd) = setup("frequency_pct");
+    cmd.args(["--limit", "0"])
+        .args(["--select", "h2"])
+        .arg("--pct")
+        .arg("--cum-pct");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "pct", "cum_pct"],
+        svec!["h2", "z", "3", "50", "50"],
+        svec!["h2", "y", "2", "33.33", "83.33"],
+        svec!["h2", "x", "1", "16.67", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_pct_limit_decimals() {
+    let (wrk, mut cmd) = setup("frequency_pct_limit_decimals");
+    cmd.args(["--limit", "2"])
+        .args(["--select", "h2"])
+        .arg("--pct")
+        .args(["--pct-decimals", "1"]);
+
+    // the percentages are relative to all the values, not just the limited ones
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "pct"],
+        svec!["h2", "z", "3", "50"],
+        svec!["h2", "y", "2", "33.3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_other_label() {
+    let (wrk, mut cmd) = setup("frequency_other_label");
+    cmd.args(["--limit", "1"])
+        .args(["--select", "h1"])
+        .args(["--other-label", "Other"])
+        .arg("--cum-pct");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "cum_pct"],
+        svec!["h1", "a", "3", "50"],
+        svec!["h1", "Other", "3", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_unq_limit() {
+    let (wrk, mut cmd) = setup("frequency_unq_limit");
+    cmd.args(["--limit", "0"]).args(["--unq-limit", "3"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count"],
+        svec!["h1", "(4 UNIQUE VALUES)", "6"],
+        svec!["h2", "z", "3"],
+        svec!["h2", "y", "2"],
+        svec!["h2", "x", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_combo() {
+    let (wrk, mut cmd) = setup("frequency_combo");
+    cmd.args(["--limit", "0"]).arg("--combo");
+
+    let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let headers = got.remove(0);
+    got.sort();
+    assert_eq!(headers, svec!["h1", "h2", "count"]);
+    let expected = vec![
+        svec!["(NULL)", "x", "1"],
+        svec!["(NULL)", "z", "1"],
+        svec!["a", "y", "2"],
+        svec!["a", "z", "1"],
+        svec!["b", "z", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_combo_no_nulls() {
+    let (wrk, mut cmd) = setup("frequency_combo_no_nulls");
+    cmd.args(["--limit", "1"])
+        .arg("--combo")
+        .arg("--no-nulls")
+        .args(["--other-label", "Other"])
+        .arg("--pct");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["h1", "h2", "count", "pct"],
+        svec!["a", "y", "2", "40"],
+        svec!["Other", "", "3", "60"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_ignore_case() {
+    let wrk = Workdir::new("frequency_ignore_case");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["answer"],
+            svec!["Yes"],
+            svec!["yes "],
+            svec!["YES"],
+            svec!["yes"],
+            svec!["No"],
+        ],
+    );
+
+    let mut cmd = wrk.command("frequency");
+    cmd.arg("--ignore-case").arg("in.csv");
+
+    // the most frequent form of a value is reported
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count"],
+        svec!["answer", "yes", "4"],
+        svec!["answer", "No", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_weight() {
+    let wrk = Workdir::new("frequency_weight");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["color", "n"],
+            svec!["red", "2"],
+            svec!["blue", "1.5"],
+            svec!["red", "0.5"],
+            // rows without a valid weight are skipped
+            svec!["green", ""],
+            svec!["blue", "x"],
+        ],
+    );
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--select", "color"])
+        .args(["--weight", "n"])
+        .arg("--pct")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "pct"],
+        svec!["color", "red", "2.5", "62.5"],
+        svec!["color", "blue", "1.5", "37.5"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_weight_combo() {
+    let wrk = Workdir::new("frequency_weight_combo");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["color", "size", "n"],
+            svec!["red", "S", "2"],
+            svec!["red", "L", "3"],
+            svec!["red", "S", "4"],
+        ],
+    );
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--select", "color,size"])
+        .args(["--weight", "n"])
+        .arg("--combo")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["color", "size", "count"],
+        svec!["red", "S", "6"],
+        svec!["red", "L", "3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_json() {
+    let (wrk, mut cmd) = setup("frequency_json");
+    cmd.args(["--limit", "0"]).arg("--json");
+
+    let got: serde_json::Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+    let h1 = got["h1"].as_array().unwrap();
+    assert_eq!(h1.len(), 4);
+    assert_eq!(
+        h1[0],
+        serde_json::json!({"value": "a", "count": 3, "pct": 50})
+    );
+    // NULLs are null values, while "(NULL)" strings are kept as is
+    assert!(h1
+        .iter()
+        .any(|row| row["value"].is_null() && row["count"] == 1));
+    assert!(h1.iter().any(|row| row["value"] == "(NULL)"));
+    assert_eq!(
+        got["h2"],
+        serde_json::json!([
+            {"value": "z", "count": 3, "pct": 50},
+            {"value": "y", "count": 2, "pct": 33.33},
+            {"value": "x", "count": 1, "pct": 16.67}
+        ])
+    );
+}
+
+#[test]
+fn frequency_json_combo() {
+    let (wrk, mut cmd) = setup("frequency_json_combo");
+    cmd.args(["--limit", "1"])
+        .arg("--combo")
+        .args(["--other-label", "Other"])
+        .arg("--cum-pct")
+        .arg("--pretty-json");
+
+    let got: serde_json::Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+    let expected = serde_json::json!({
+        "h1,h2": [
+            {"value": ["a", "y"], "count": 2, "pct": 33.33, "cum_pct": 33.33},
+            {"value": "Other", "count": 4, "pct": 66.67, "cum_pct": 100}
+        ]
+    });
+    assert_eq!(got, expected);
+}
+
 // This tests that a frequency table computed by `qsv` is always the same
 // as the frequency table computed in memory.
 #[test]
@@ -262,3 +501,47 @@ fn assert_eq_ftables(got: &FTables, expected: &FTables) -> bool {
     }
     true
 }
+
+#[test]
+fn frequency_unq_limit_stats_cache() {
+    let wrk = Workdir::new("frequency_unq_limit_stats_cache");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "kind"],
+            svec!["10", "a"],
+            svec!["20", "a"],
+            svec!["30", "b"],
+            svec!["40", "b"],
+            svec!["50", "c"],
+        ],
+    );
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--cardinality").arg("--cache").arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    // tamper with the cardinality of the id column in the stats cache,
+    // so we can tell when its used
+    let cached = std::fs::read_to_string(wrk.path("in.stats.csv")).unwrap();
+    let mut lines: Vec<String> = cached.lines().map(String::from).collect();
+    let cardinality_idx = lines[0]
+        .split(',')
+        .position(|h| h == "cardinality")
+        .unwrap();
+    let mut id_stats: Vec<&str> = lines[1].split(',').collect();
+    id_stats[cardinality_idx] = "9";
+    lines[1] = id_stats.join(",");
+    std::fs::write(wrk.path("in.stats.csv"), lines.join("\n")).unwrap();
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--limit", "0"])
+        .args(["--unq-limit", "2"])
+        .arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count"],
+        svec!["id", "(9 UNIQUE VALUES)", "5"],
+        svec!["kind", "(3 UNIQUE VALUES)", "5"],
+    ];
+    assert_eq!(got, expected);
+}
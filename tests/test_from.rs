@@ -0,0 +1,63 @@
+use crate::workdir::Workdir;
+
+/// Writes cities.parquet, using sqlp to convert cities.csv.
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["New York", "NY", "8804190"],
+            svec!["Springfield", "", "155929"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select * from cities")
+        .arg("--nulls")
+        .args(["--format", "parquet"])
+        .args(["--output", "cities.parquet"]);
+    wrk.assert_success(&mut cmd);
+    wrk
+}
+
+#[test]
+fn from_parquet() {
+    let wrk = setup("from_parquet");
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet").arg("cities.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "state", "population"],
+        svec!["Boston", "MA", "675647"],
+        svec!["New York", "NY", "8804190"],
+        svec!["Springfield", "", "155929"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_limit_delimiter() {
+    let wrk = setup("from_parquet_limit_delimiter");
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .arg("cities.parquet")
+        .args(["--limit", "2"])
+        .args(["--delimiter", ";"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "city;state;population\nBoston;MA;675647\nNew York;NY;8804190";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_not_found() {
+    let wrk = Workdir::new("from_parquet_not_found");
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet").arg("nonexistent.parquet");
+
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,96 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn generate_mimic() {
+    let wrk = Workdir::new("generate_mimic");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "amount", "status", "created", "email"],
+            svec!["10", "1.50", "open", "2021-01-01", "a@example.com"],
+            svec!["20", "2.25", "closed", "2021-06-30", "b@example.com"],
+            svec!["30", "", "open", "2021-12-31", "c@example.com"],
+        ],
+    );
+    let mut cmd = wrk.command("generate");
+    cmd.args(["--rows", "50"])
+        .arg("--mimic")
+        .args(["--seed", "42"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 51);
+    assert_eq!(got[0], svec!["id", "amount", "status", "created", "email"]);
+    for row in &got[1..] {
+        let id: i64 = row[0].parse().unwrap();
+        assert!((10..=30).contains(&id));
+        assert!(row[1].is_empty() || (1.5..=2.25).contains(&row[1].parse::<f64>().unwrap()));
+        assert!(row[2] == "open" || row[2] == "closed");
+        assert!(row[3].as_str() >= "2021-01-01" && row[3].as_str() <= "2021-12-31");
+        assert!(row[4].contains('@'));
+    }
+
+    // the same seed generates the same data
+    let mut cmd = wrk.command("generate");
+    cmd.args(["--rows", "50"])
+        .arg("--mimic")
+        .args(["--seed", "42"])
+        .arg("data.csv");
+    let got2: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, got2);
+}
+
+#[test]
+fn generate_schema() {
+    let wrk = Workdir::new("generate_schema");
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+  "type": "object",
+  "properties": {
+    "age": { "type": ["integer"], "minimum": 18, "maximum": 65 },
+    "color": { "type": ["string", "null"], "enum": ["red", "blue"] },
+    "born": { "type": ["string"], "format": "date" }
+  }
+}"#,
+    );
+    let mut cmd = wrk.command("generate");
+    cmd.args(["--rows", "20"]).args(["--schema", "schema.json"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 21);
+    assert_eq!(got[0], svec!["age", "color", "born"]);
+    for row in &got[1..] {
+        let age: i64 = row[0].parse().unwrap();
+        assert!((18..=65).contains(&age));
+        assert!(["red", "blue", ""].contains(&row[1].as_str()));
+        assert_eq!(row[2].len(), 10);
+    }
+}
+
+#[test]
+fn generate_mimic_non_finite() {
+    let wrk = Workdir::new("generate_mimic_non_finite");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["reading"],
+            svec!["1.5"],
+            svec!["NaN"],
+            svec!["2.5"],
+            svec!["inf"],
+        ],
+    );
+    let mut cmd = wrk.command("generate");
+    cmd.args(["--rows", "20"])
+        .arg("--mimic")
+        .args(["--seed", "42"])
+        .arg("data.csv");
+
+    // NaN & inf aren't numbers, so the values are sampled instead of generated in a range
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 21);
+    for row in &got[1..] {
+        assert!(["1.5", "NaN", "2.5", "inf"].contains(&row[0].as_str()));
+    }
+}
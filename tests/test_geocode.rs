@@ -0,0 +1,252 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn geocode_reverse() {
+    let wrk = Workdir::new("geocode_reverse");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Location"],
+            svec!["40.812126, -73.9041813"],
+            svec!["40.66472342, -73.93867227"],
+            svec!["(40.766672, -73.9568128)"],
+            svec!["This is not a Location and it will not be geocoded"],
+            svec!["95.213424, 190,1234565"], // invalid lat, long
+        ],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("reverse").arg("Location").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["Location"],
+        svec!["The Bronx, New York"],
+        svec!["Brooklyn, New York"],
+        svec!["Manhattan, New York"],
+        svec!["This is not a Location and it will not be geocoded"],
+        svec!["95.213424, 190,1234565"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_reverse_new_column_formatstr() {
+    let wrk = Workdir::new("geocode_reverse_new_column_formatstr");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "Location"],
+            svec!["1", "40.812126, -73.9041813"],
+            svec!["2", "(40.766672, -73.9568128)"],
+            svec!["3", "not a location"],
+        ],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("reverse")
+        .arg("Location")
+        .args(["--formatstr", "city-state-country"])
+        .args(["--new-column", "City"])
+        .args(["--invalid-result", "<INVALID>"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "Location", "City"],
+        svec!["1", "40.812126, -73.9041813", "The Bronx, New York US"],
+        svec!["2", "(40.766672, -73.9568128)", "Manhattan, New York US"],
+        svec!["3", "not a location", "<INVALID>"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_reverse_invalid_formatstr() {
+    let wrk = Workdir::new("geocode_reverse_invalid_formatstr");
+    wrk.create(
+        "data.csv",
+        vec![svec!["Location"], svec!["40.812126, -73.9041813"]],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("reverse")
+        .arg("Location")
+        .args(["--formatstr", "zipcode"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+fn create_geocode_index(wrk: &Workdir) {
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["lat", "lon", "name", "admin1", "admin2", "cc"],
+            svec!["43.2", "-80.38333", "Paris", "Ontario", "", "CA"],
+            svec![
+                "48.85341",
+                "2.3488",
+                "Paris",
+                "Ile-de-France",
+                "Paris",
+                "FR"
+            ],
+            svec![
+                "33.66094",
+                "-95.55551",
+                "Paris",
+                "Texas",
+                "Lamar County",
+                "US"
+            ],
+            svec![
+                "40.6501",
+                "-73.94958",
+                "Brooklyn",
+                "New York",
+                "Kings County",
+                "US"
+            ],
+            svec![
+                "41.43977",
+                "-81.73541",
+                "Brooklyn",
+                "Ohio",
+                "Cuyahoga County",
+                "US"
+            ],
+        ],
+    );
+}
+
+#[test]
+fn geocode_forward() {
+    let wrk = Workdir::new("geocode_forward");
+    create_geocode_index(&wrk);
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Place"],
+            svec!["Paris"],
+            svec!["paris, texas"],
+            svec!["Paris, CA"],
+            svec!["Brooklyn, Ohio US"],
+            svec!["Atlantis"],
+        ],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("forward")
+        .arg("Place")
+        .args(["--index", "cities.csv"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["Place"],
+        svec!["(48.85341, 2.3488)"],
+        svec!["(33.66094, -95.55551)"],
+        svec!["(43.2, -80.38333)"],
+        svec!["(41.43977, -81.73541)"],
+        svec!["Atlantis"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_forward_new_column_formatstr() {
+    let wrk = Workdir::new("geocode_forward_new_column_formatstr");
+    create_geocode_index(&wrk);
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "Place"],
+            svec!["1", "brooklyn"],
+            svec!["2", "Paris, Lamar County"],
+            svec!["3", "Atlantis"],
+        ],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("forward")
+        .arg("Place")
+        .args(["--formatstr", "city-state-country"])
+        .args(["--new-column", "City"])
+        .args(["--invalid-result", "<INVALID>"])
+        .args(["--index", "cities.csv"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "Place", "City"],
+        svec!["1", "brooklyn", "Brooklyn, New York US"],
+        svec!["2", "Paris, Lamar County", "Paris, Texas US"],
+        svec!["3", "Atlantis", "<INVALID>"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_forward_lat_long_formats() {
+    let wrk = Workdir::new("geocode_forward_lat_long_formats");
+    create_geocode_index(&wrk);
+    wrk.create("data.csv", vec![svec!["Place"], svec!["Paris, FR"]]);
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("forward")
+        .arg("Place")
+        .args(["--formatstr", "lat"])
+        .args(["--new-column", "Lat"])
+        .args(["--index", "cities.csv"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["Place", "Lat"], svec!["Paris, FR", "48.85341"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_forward_index_env_var() {
+    let wrk = Workdir::new("geocode_forward_index_env_var");
+    create_geocode_index(&wrk);
+    wrk.create(
+        "data.csv",
+        vec![svec!["Place"], svec!["Brooklyn, New York"]],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.env("QSV_GEOCODE_INDEX", wrk.path("cities.csv"))
+        .arg("forward")
+        .arg("Place")
+        .args(["--formatstr", "lat-long"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["Place"], svec!["40.6501, -73.94958"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn geocode_forward_needs_index_or_online() {
+    let wrk = Workdir::new("geocode_forward_needs_index_or_online");
+    wrk.create("data.csv", vec![svec!["Place"], svec!["Paris"]]);
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("forward").arg("Place").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn geocode_reverse_index() {
+    let wrk = Workdir::new("geocode_reverse_index");
+    create_geocode_index(&wrk);
+    wrk.create(
+        "data.csv",
+        vec![svec!["Location"], svec!["40.812126, -73.9041813"]],
+    );
+    let mut cmd = wrk.command("geocode");
+    cmd.arg("reverse")
+        .arg("Location")
+        .args(["--formatstr", "county-state-country"])
+        .args(["--index", "cities.csv"])
+        .arg("data.csv");
+
+    // the Bronx is not in the index, so the nearest place is Brooklyn
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["Location"], svec!["Kings County, New York US"]];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,105 @@
+use crate::workdir::Workdir;
+
+fn orders() -> Vec<Vec<String>> {
+    vec![
+        svec!["customer", "id", "amount", "note"],
+        svec!["alice", "1", "10", "x"],
+        svec!["bob", "2", "5", ""],
+        svec!["alice", "3", "2.5", "y"],
+        svec!["carol", "4", "", "z"],
+        svec!["bob", "5", "7", "w"],
+    ]
+}
+
+#[test]
+fn groupby_hashed() {
+    let wrk = Workdir::new("groupby_hashed");
+    wrk.create("in.csv", orders());
+
+    let mut cmd = wrk.command("groupby");
+    cmd.arg("customer")
+        .arg("--agg")
+        .arg("count,sum:amount,mean:amount,min:amount,max:note,first:id,last:id,concat:id")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "customer",
+            "count",
+            "sum_amount",
+            "mean_amount",
+            "min_amount",
+            "max_note",
+            "first_id",
+            "last_id",
+            "concat_id"
+        ],
+        svec!["alice", "2", "12.5", "6.25", "2.5", "y", "1", "3", "1|3"],
+        svec!["bob", "2", "12", "6", "5", "w", "2", "5", "2|5"],
+        svec!["carol", "1", "0", "", "", "z", "4", "4", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn groupby_sorted_numeric_extremes() {
+    let wrk = Workdir::new("groupby_sorted_numeric_extremes");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["region", "product", "price"],
+            svec!["east", "a", "9"],
+            svec!["east", "a", "10"],
+            svec!["east", "b", "3"],
+            svec!["west", "a", "1"],
+        ],
+    );
+
+    let mut cmd = wrk.command("groupby");
+    cmd.args([
+        "region,product",
+        "--sorted",
+        "--agg",
+        "min:3,max:price",
+        "in.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "product", "min_price", "max_price"],
+        svec!["east", "a", "9", "10"],
+        svec!["east", "b", "3", "3"],
+        svec!["west", "a", "1", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn groupby_concat_separator() {
+    let wrk = Workdir::new("groupby_concat_separator");
+    wrk.create("in.csv", orders());
+
+    let mut cmd = wrk.command("groupby");
+    cmd.args(["1", "--agg", "concat:note", "--separator", ";", "in.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["customer", "concat_note"],
+        svec!["alice", "x;y"],
+        svec!["bob", "w"],
+        svec!["carol", "z"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn groupby_unknown_function() {
+    let wrk = Workdir::new("groupby_unknown_function");
+    wrk.create("in.csv", orders());
+
+    let mut cmd = wrk.command("groupby");
+    cmd.args(["customer", "--agg", "median:amount", "in.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,33 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn head() {
+    let wrk = Workdir::new("head");
+    wrk.create(
+        "data.csv",
+        vec![svec!["n"], svec!["1"], svec!["2"], svec!["3"]],
+    );
+    let mut cmd = wrk.command("head");
+    cmd.args(["--rows", "2"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["n"], svec!["1"], svec!["2"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn head_no_headers() {
+    let wrk = Workdir::new("head_no_headers");
+    wrk.create(
+        "data.csv",
+        vec![svec!["n"], svec!["1"], svec!["2"], svec!["3"]],
+    );
+    let mut cmd = wrk.command("head");
+    cmd.args(["--rows", "2"])
+        .arg("--no-headers")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["n"], svec!["1"]];
+    assert_eq!(got, expected);
+}
@@ -385,3 +385,86 @@ fn input_headertrim() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+#[cfg(feature = "full")]
+fn input_encoding_latin1() {
+    let wrk = Workdir::new("input_encoding_latin1");
+    // "café" & "Zürich" in Latin-1
+    std::fs::write(
+        wrk.path("latin1.csv"),
+        b"name,city\ncaf\xe9,Z\xfcrich\n".as_slice(),
+    )
+    .unwrap();
+
+    let mut cmd = wrk.command("input");
+    cmd.args(["--encoding", "latin1"]).arg("latin1.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "city"], svec!["café", "Zürich"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn input_encoding_utf16le() {
+    let wrk = Workdir::new("input_encoding_utf16le");
+    let mut data: Vec<u8> = vec![0xFF, 0xFE];
+    for unit in "name,city\nJosé,Montréal\n".encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+    std::fs::write(wrk.path("utf16.csv"), data).unwrap();
+
+    let mut cmd = wrk.command("input");
+    cmd.args(["--encoding", "utf-16le"]).arg("utf16.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "city"], svec!["José", "Montréal"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn input_encoding_strict() {
+    let wrk = Workdir::new("input_encoding_strict");
+    // 0x81 is a Shift-JIS lead byte, which cannot be followed by a newline
+    std::fs::write(wrk.path("sjis.csv"), b"name\n\x81\n".as_slice()).unwrap();
+
+    let mut cmd = wrk.command("input");
+    cmd.args(["--encoding", "shift_jis"])
+        .args(["--encoding-errors", "strict"])
+        .arg("sjis.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn input_encoding_unknown() {
+    let wrk = Workdir::new("input_encoding_unknown");
+    wrk.create("data.csv", vec![svec!["a"], svec!["1"]]);
+
+    let mut cmd = wrk.command("input");
+    cmd.args(["--encoding", "klingon"]).arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "Unknown encoding: klingon\n");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+#[cfg(not(feature = "full"))]
+fn input_encoding_not_available() {
+    let wrk = Workdir::new("input_encoding_not_available");
+    wrk.create("data.csv", vec![svec!["a"], svec!["1"]]);
+
+    let mut cmd = wrk.command("input");
+    cmd.args(["--encoding", "latin1"]).arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(
+        got,
+        "--encoding is only available in the full qsv variant.\n"
+    );
+    wrk.assert_err(&mut cmd);
+}
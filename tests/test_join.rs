@@ -151,7 +151,11 @@ join_test!(join_left_semi, |wrk: Workdir,
                             headers: bool| {
     cmd.arg("--left-semi");
     let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
-    let expected = make_rows(headers, true, vec![svec!["Buffalo", "NY"]]);
+    let expected = make_rows(
+        headers,
+        true,
+        vec![svec!["Boston", "MA"], svec!["Buffalo", "NY"]],
+    );
     assert_eq!(got, expected);
 });
 
@@ -168,6 +172,690 @@ join_test!(join_left_anti, |wrk: Workdir,
     assert_eq!(got, expected);
 });
 
+#[test]
+fn join_left_anti_empty_keys() {
+    let wrk = Workdir::new("join_left_anti_empty_keys");
+    wrk.create(
+        "a.csv",
+        vec![
+            svec!["id", "v"],
+            svec!["1", "a"],
+            svec!["", "b"],
+            svec!["3", "c"],
+        ],
+    );
+    wrk.create("b.csv", vec![svec!["id"], svec!["1"], svec![""]]);
+
+    // rows with an empty key never match, unless --nulls is set
+    let mut cmd = wrk.command("join");
+    cmd.arg("--left-anti").args(["id", "a.csv", "id", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["id", "v"], svec!["", "b"], svec!["3", "c"]];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--left-anti")
+        .arg("--nulls")
+        .args(["id", "a.csv", "id", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["id", "v"], svec!["3", "c"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_trim_keys_ignore_case() {
+    let wrk = Workdir::new("join_trim_keys_ignore_case");
+    wrk.create(
+        "a.csv",
+        vec![svec!["name", "city"], svec!["ACME  Corp ", "Boston"]],
+    );
+    wrk.create(
+        "b.csv",
+        vec![svec!["name", "ticker"], svec!["acme corp", "ACM"]],
+    );
+
+    // without --trim-keys, the double space inside the key doesn't match
+    let mut cmd = wrk.command("join");
+    cmd.arg("-i").args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "city", "name", "ticker"]];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("-i")
+        .arg("--trim-keys")
+        .args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city", "name", "ticker"],
+        svec!["ACME  Corp ", "Boston", "acme corp", "ACM"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn join_normalize_unicode() {
+    let wrk = Workdir::new("join_normalize_unicode");
+    // "Café" precomposed on the left, decomposed (e + combining acute) on the right
+    wrk.create("a.csv", vec![svec!["name", "x"], svec!["Caf\u{e9}", "1"]]);
+    wrk.create("b.csv", vec![svec!["name", "y"], svec!["Cafe\u{301}", "2"]]);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "x", "name", "y"]];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--normalize-unicode")
+        .args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "x", "name", "y"],
+        svec!["Caf\u{e9}", "1", "Cafe\u{301}", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_on_pairs() {
+    let wrk = Workdir::new("join_on_pairs");
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["cust_id", "region", "total"],
+            svec!["1", "east", "10"],
+            svec!["1", "west", "20"],
+            svec!["2", "east", "30"],
+        ],
+    );
+    wrk.create(
+        "customers.csv",
+        vec![
+            svec!["id", "area", "name"],
+            svec!["1", "west", "Ann"],
+            svec!["2", "east", "Bob"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args([
+        "--on",
+        "cust_id=id,region=area",
+        "orders.csv",
+        "customers.csv",
+    ]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["cust_id", "region", "total", "id", "area", "name"],
+        svec!["1", "west", "20", "1", "west", "Ann"],
+        svec!["2", "east", "30", "2", "east", "Bob"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_on_pairs_invalid() {
+    let wrk = Workdir::new("join_on_pairs_invalid");
+    wrk.create("a.csv", vec![svec!["id"], svec!["1"]]);
+    wrk.create("b.csv", vec![svec!["id"], svec!["1"]]);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--on", "id", "a.csv", "b.csv"]);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn join_numeric_keys() {
+    let wrk = Workdir::new("join_numeric_keys");
+    wrk.create(
+        "a.csv",
+        vec![
+            svec!["code", "x"],
+            svec!["007", "a"],
+            svec!["1.50", "b"],
+            svec!["abc", "c"],
+        ],
+    );
+    wrk.create(
+        "b.csv",
+        vec![
+            svec!["code", "y"],
+            svec!["7", "d"],
+            svec!["1.5", "e"],
+            svec!["abc", "f"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["code", "a.csv", "code", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["code", "x", "code", "y"],
+        svec!["abc", "c", "abc", "f"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--numeric-keys")
+        .args(["code", "a.csv", "code", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["code", "x", "code", "y"],
+        svec!["007", "a", "7", "d"],
+        svec!["1.50", "b", "1.5", "e"],
+        svec!["abc", "c", "abc", "f"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[cfg(feature = "full")]
+fn fuzzy_setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "a.csv",
+        vec![
+            svec!["name", "city"],
+            svec!["Jonathan Smith", "Boston"],
+            svec!["Mary Jones", "Denver"],
+            svec!["Zed", "Austin"],
+        ],
+    );
+    wrk.create(
+        "b.csv",
+        vec![
+            svec!["name", "id"],
+            svec!["Jonathon Smith", "1"],
+            svec!["Mary Jones", "2"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn join_fuzzy() {
+    let wrk = fuzzy_setup("join_fuzzy");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "0.9"])
+        .args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city", "name", "id", "fuzzy_score"],
+        svec!["Jonathan Smith", "Boston", "Jonathon Smith", "1", "0.9714"],
+        svec!["Mary Jones", "Denver", "Mary Jones", "2", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn join_fuzzy_left_levenshtein() {
+    let wrk = fuzzy_setup("join_fuzzy_left_levenshtein");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "0.9", "--fuzzy-metric", "levenshtein", "--left"])
+        .args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "city", "name", "id", "fuzzy_score"],
+        svec!["Jonathan Smith", "Boston", "Jonathon Smith", "1", "0.9286"],
+        svec!["Mary Jones", "Denver", "Mary Jones", "2", "1"],
+        svec!["Zed", "Austin", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn join_fuzzy_left_anti() {
+    let wrk = fuzzy_setup("join_fuzzy_left_anti");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "0.9", "--left-anti"])
+        .args(["name", "a.csv", "name", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "city"], svec!["Zed", "Austin"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn join_fuzzy_invalid() {
+    let wrk = fuzzy_setup("join_fuzzy_invalid");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "1.5"])
+        .args(["name", "a.csv", "name", "b.csv"]);
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "0.9", "--full"])
+        .args(["name", "a.csv", "name", "b.csv"]);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+#[cfg(not(feature = "full"))]
+fn join_fuzzy_not_available() {
+    let wrk = setup("join_fuzzy_not_available", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--fuzzy", "0.9"])
+        .args(["city", "cities.csv", "city", "places.csv"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(
+        got,
+        "--fuzzy and --normalize-unicode are only available in the full qsv variant.\n"
+    );
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn join_memory_limit_spill() {
+    let wrk = setup("join_memory_limit_spill", true);
+    // a 0% memory limit always spills. Spilled joins write their rows
+    // partition by partition, so only compare the sorted rows.
+    for op in [
+        "",
+        "--left",
+        "--left-anti",
+        "--left-semi",
+        "--right",
+        "--full",
+    ] {
+        let join = |spill: bool| -> Vec<Vec<String>> {
+            let mut cmd = wrk.command("join");
+            if !op.is_empty() {
+                cmd.arg(op);
+            }
+            if spill {
+                cmd.args(["--memory-limit", "0"]);
+            }
+            cmd.args(["city", "cities.csv", "city", "places.csv"]);
+            let mut rows: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+            rows[1..].sort();
+            rows
+        };
+        assert_eq!(join(true), join(false), "join {op}");
+    }
+}
+
+#[test]
+fn join_memory_limit_spill_no_headers() {
+    let wrk = setup("join_memory_limit_spill_no_headers", false);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--full", "--no-headers", "--memory-limit", "0"])
+        .args(["1", "cities.csv", "1", "places.csv"]);
+    let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    got.sort();
+    let expected = vec![
+        svec!["", "", "Orlando", "Disney World"],
+        svec!["Boston", "MA", "Boston", "Boston Garden"],
+        svec!["Boston", "MA", "Boston", "Logan Airport"],
+        svec!["Buffalo", "NY", "Buffalo", "Ralph Wilson Stadium"],
+        svec!["New York", "NY", "", ""],
+        svec!["San Francisco", "CA", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_memory_limit_spill_stable_order() {
+    let wrk = setup("join_memory_limit_spill_stable_order", true);
+    // the partitions are hashed with fixed seeds, so the order of the rows
+    // is the same for every run
+    let join = || -> String {
+        let mut cmd = wrk.command("join");
+        cmd.args(["--full", "--memory-limit", "0"]);
+        cmd.args(["city", "cities.csv", "city", "places.csv"]);
+        wrk.stdout(&mut cmd)
+    };
+    let first = join();
+    for _ in 0..3 {
+        assert_eq!(join(), first);
+    }
+}
+
+#[test]
+fn join_range() {
+    let wrk = Workdir::new("join_range");
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["id", "amount"],
+            svec!["1", "5"],
+            svec!["2", "100"],
+            svec!["3", "250.5"],
+            svec!["4", "9999"],
+        ],
+    );
+    wrk.create(
+        "tiers.csv",
+        vec![
+            svec!["tier", "min", "max"],
+            svec!["bronze", "0", "99.99"],
+            svec!["silver", "100", "499.99"],
+            svec!["promo", "50", "150"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--range", "min,max", "amount", "orders.csv", "tiers.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "amount", "tier", "min", "max"],
+        svec!["1", "5", "bronze", "0", "99.99"],
+        svec!["2", "100", "silver", "100", "499.99"],
+        svec!["2", "100", "promo", "50", "150"],
+        svec!["3", "250.5", "silver", "100", "499.99"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--range", "min,max", "--left-anti"])
+        .args(["amount", "orders.csv", "tiers.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["id", "amount"], svec!["4", "9999"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_range_ip_left() {
+    let wrk = Workdir::new("join_range_ip_left");
+    wrk.create(
+        "hits.csv",
+        vec![
+            svec!["ip"],
+            svec!["10.0.0.9"],
+            svec!["192.168.1.20"],
+            svec!["8.8.8.8"],
+        ],
+    );
+    wrk.create(
+        "networks.csv",
+        vec![
+            svec!["from", "to", "name"],
+            svec!["10.0.0.0", "10.255.255.255", "private-a"],
+            svec!["192.168.0.0", "192.168.255.255", "private-c"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--range", "from,to", "--left"])
+        .args(["ip", "hits.csv", "networks.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["ip", "from", "to", "name"],
+        svec!["10.0.0.9", "10.0.0.0", "10.255.255.255", "private-a"],
+        svec![
+            "192.168.1.20",
+            "192.168.0.0",
+            "192.168.255.255",
+            "private-c"
+        ],
+        svec!["8.8.8.8", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_range_dates() {
+    let wrk = Workdir::new("join_range_dates");
+    wrk.create(
+        "events.csv",
+        vec![svec!["day"], svec!["2023-02-14"], svec!["2023-07-04"]],
+    );
+    wrk.create(
+        "quarters.csv",
+        vec![
+            svec!["start", "end", "quarter"],
+            svec!["2023-01-01", "2023-03-31", "Q1"],
+            svec!["2023-04-01", "2023-06-30", "Q2"],
+            svec!["2023-07-01", "2023-09-30", "Q3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--range", "start,end", "day", "events.csv", "quarters.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["day", "start", "end", "quarter"],
+        svec!["2023-02-14", "2023-01-01", "2023-03-31", "Q1"],
+        svec!["2023-07-04", "2023-07-01", "2023-09-30", "Q3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_range_invalid_columns() {
+    let wrk = Workdir::new("join_range_invalid_columns");
+    wrk.create("a.csv", vec![svec!["x"], svec!["1"]]);
+    wrk.create("b.csv", vec![svec!["lo", "hi"], svec!["0", "2"]]);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--range", "lo", "x", "a.csv", "b.csv"]);
+    wrk.assert_err(&mut cmd);
+}
+
+fn asof_setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "trades.csv",
+        vec![
+            svec!["symbol", "time", "qty"],
+            svec!["AAPL", "2023-05-01 10:00:03", "100"],
+            svec!["MSFT", "2023-05-01 10:00:04", "50"],
+            svec!["AAPL", "2023-05-01 10:00:09", "20"],
+            svec!["AAPL", "2023-05-01 09:59:00", "10"],
+        ],
+    );
+    wrk.create(
+        "quotes.csv",
+        vec![
+            svec!["symbol", "time", "bid"],
+            svec!["AAPL", "2023-05-01 10:00:00", "170.1"],
+            svec!["MSFT", "2023-05-01 10:00:01", "310.2"],
+            svec!["AAPL", "2023-05-01 10:00:05", "170.3"],
+            svec!["AAPL", "2023-05-01 10:00:08", "170.4"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn join_asof_backward() {
+    let wrk = asof_setup("join_asof_backward");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--asof", "--left"])
+        .args(["symbol,time", "trades.csv", "symbol,time", "quotes.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["symbol", "time", "qty", "symbol", "time", "bid"],
+        svec![
+            "AAPL",
+            "2023-05-01 10:00:03",
+            "100",
+            "AAPL",
+            "2023-05-01 10:00:00",
+            "170.1"
+        ],
+        svec![
+            "MSFT",
+            "2023-05-01 10:00:04",
+            "50",
+            "MSFT",
+            "2023-05-01 10:00:01",
+            "310.2"
+        ],
+        svec![
+            "AAPL",
+            "2023-05-01 10:00:09",
+            "20",
+            "AAPL",
+            "2023-05-01 10:00:08",
+            "170.4"
+        ],
+        svec!["AAPL", "2023-05-01 09:59:00", "10", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_forward_tolerance() {
+    let wrk = asof_setup("join_asof_forward_tolerance");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--asof", "--direction", "forward", "--tolerance", "2"])
+        .args(["symbol,time", "trades.csv", "symbol,time", "quotes.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["symbol", "time", "qty", "symbol", "time", "bid"],
+        svec![
+            "AAPL",
+            "2023-05-01 10:00:03",
+            "100",
+            "AAPL",
+            "2023-05-01 10:00:05",
+            "170.3"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_prefer_dmy() {
+    let wrk = Workdir::new("join_asof_prefer_dmy");
+    wrk.create(
+        "trades.csv",
+        vec![svec!["date", "qty"], svec!["03/02/2023", "100"]],
+    );
+    wrk.create(
+        "quotes.csv",
+        vec![
+            svec!["date", "bid"],
+            svec!["01/02/2023", "170.1"],
+            svec!["02/03/2023", "170.2"],
+        ],
+    );
+
+    // mdy: the trade on Mar 2 matches the quote on Feb 3
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof")
+        .args(["date", "trades.csv", "date", "quotes.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["date", "qty", "date", "bid"],
+        svec!["03/02/2023", "100", "02/03/2023", "170.2"],
+    ];
+    assert_eq!(got, expected);
+
+    // dmy: the trade on Feb 3 matches the quote on Feb 1
+    let expected = vec![
+        svec!["date", "qty", "date", "bid"],
+        svec!["03/02/2023", "100", "01/02/2023", "170.1"],
+    ];
+    let mut cmd = wrk.command("join");
+    cmd.args(["--asof", "--prefer-dmy"])
+        .args(["date", "trades.csv", "date", "quotes.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.env("QSV_PREFER_DMY", "1")
+        .arg("--asof")
+        .args(["date", "trades.csv", "date", "quotes.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_nearest_numeric() {
+    let wrk = Workdir::new("join_asof_nearest_numeric");
+    wrk.create(
+        "readings.csv",
+        vec![
+            svec!["t", "temp"],
+            svec!["1", "20"],
+            svec!["6", "21"],
+            svec!["14", "22"],
+        ],
+    );
+    wrk.create(
+        "events.csv",
+        vec![
+            svec!["t", "event"],
+            svec!["0", "start"],
+            svec!["5", "open"],
+            svec!["10", "close"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--asof", "--direction", "nearest"])
+        .args(["t", "readings.csv", "t", "events.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["t", "temp", "t", "event"],
+        svec!["1", "20", "0", "start"],
+        svec!["6", "21", "5", "open"],
+        svec!["14", "22", "10", "close"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.args(["--asof", "--direction", "nearest", "--tolerance", "1"])
+        .arg("--left-anti")
+        .args(["t", "readings.csv", "t", "events.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["t", "temp"], svec!["14", "22"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_prefix_left_right() {
+    let wrk = setup("join_prefix_left_right", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--prefix-left", "l_", "--prefix-right", "r_"])
+        .args(["city", "cities.csv", "city", "places.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    // only the colliding "city" columns are prefixed
+    assert_eq!(got[0], svec!["l_city", "state", "r_city", "place"]);
+    assert_eq!(got.len(), 4);
+}
+
+#[test]
+fn join_output_select() {
+    let wrk = setup("join_output_select", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--prefix-right", "r_", "--output-select", "city,place"])
+        .args(["city", "cities.csv", "city", "places.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "place"],
+        svec!["Boston", "Logan Airport"],
+        svec!["Boston", "Boston Garden"],
+        svec!["Buffalo", "Ralph Wilson Stadium"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_output_select_no_headers_spill() {
+    let wrk = setup("join_output_select_no_headers_spill", false);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--no-headers", "--left", "--output-select", "4,2"])
+        .args(["--memory-limit", "0"])
+        .args(["1", "cities.csv", "1", "places.csv"]);
+    let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    got.sort();
+    let expected = vec![
+        svec!["", "CA"],
+        svec!["", "NY"],
+        svec!["Boston Garden", "MA"],
+        svec!["Logan Airport", "MA"],
+        svec!["Ralph Wilson Stadium", "NY"],
+    ];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn join_inner_issue11() {
     let a = vec![svec!["1", "2"], svec!["3", "4"], svec!["5", "6"]];
@@ -0,0 +1,97 @@
+use crate::workdir::Workdir;
+
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state"],
+            svec!["Boston", "MA"],
+            svec!["New York", "NY"],
+            svec!["San Francisco", "CA"],
+        ],
+    );
+    wrk.create(
+        "places.csv",
+        vec![
+            svec!["city", "place"],
+            svec!["Boston", "Logan Airport"],
+            svec!["Boston", "Boston Garden"],
+            svec!["Buffalo", "Ralph Wilson Stadium"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn joinp_inner() {
+    let wrk = setup("joinp_inner");
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["city", "cities.csv", "city", "places.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "state", "place"],
+        svec!["Boston", "MA", "Logan Airport"],
+        svec!["Boston", "MA", "Boston Garden"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn joinp_cross() {
+    let wrk = setup("joinp_cross");
+    let mut cmd = wrk.command("joinp");
+    cmd.arg("--cross")
+        .args(["city", "cities.csv", "city", "places.csv"])
+        .arg("--quiet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    // header + 3 * 3 rows
+    assert_eq!(got.len(), 10);
+}
+
+#[test]
+fn joinp_asof() {
+    let wrk = Workdir::new("joinp_asof");
+    wrk.create(
+        "trades.csv",
+        vec![
+            svec!["time", "price"],
+            svec!["5", "100"],
+            svec!["1", "101"],
+            svec!["9", "102"],
+        ],
+    );
+    wrk.create(
+        "quotes.csv",
+        vec![
+            svec!["qtime", "quote"],
+            svec!["0", "a"],
+            svec!["4", "b"],
+            svec!["8", "c"],
+        ],
+    );
+    let mut cmd = wrk.command("joinp");
+    cmd.arg("--asof")
+        .args(["time", "trades.csv", "qtime", "quotes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["time", "price", "qtime", "quote"],
+        svec!["1", "101", "0", "a"],
+        svec!["5", "100", "4", "b"],
+        svec!["9", "102", "8", "c"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn joinp_asof_multiple_columns() {
+    let wrk = setup("joinp_asof_multiple_columns");
+    let mut cmd = wrk.command("joinp");
+    cmd.arg("--asof")
+        .args(["city,state", "cities.csv", "city,place", "places.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
@@ -98,3 +98,45 @@ fn jsonl_nested() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn jsonl_union() {
+    let wrk = Workdir::new("jsonl_union");
+    wrk.create_from_string(
+        "data.jsonl",
+        r#"{"id":1,"father":"Mark"}
+{"id":2,"father":"John","mother":"Ann"}
+{"id":3,"mother":"Monika","address":{"city":"Boston"}}"#,
+    );
+    let mut cmd = wrk.command("jsonl");
+    cmd.arg("--union").arg("data.jsonl");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "father", "mother", "address.city"],
+        svec!["1", "Mark", "", ""],
+        svec!["2", "John", "Ann", ""],
+        svec!["3", "", "Monika", "Boston"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn jsonl_separator() {
+    let wrk = Workdir::new("jsonl_separator");
+    wrk.create_from_string(
+        "data.jsonl",
+        r#"{"id":1,"name":{"first":"Mark","last":"Smith"}}
+{"id":2,"name":{"first":"John","last":"Doe"}}"#,
+    );
+    let mut cmd = wrk.command("jsonl");
+    cmd.args(["--separator", "_"]).arg("data.jsonl");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "name_first", "name_last"],
+        svec!["1", "Mark", "Smith"],
+        svec!["2", "John", "Doe"],
+    ];
+    assert_eq!(got, expected);
+}
@@ -1142,3 +1142,56 @@ fn luau_filter_num() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn luau_commented_special_vars_no_index() {
+    let wrk = Workdir::new("luau_commented_special_vars_no_index");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "number"],
+            svec!["a", "13"],
+            svec!["b", "24"],
+        ],
+    );
+    let mut cmd = wrk.command("luau");
+    cmd.arg("map")
+        .arg("inc")
+        .arg("-x")
+        .arg("-- we don't need _INDEX or _LASTROW here, so no index is required\nreturn number + 1")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["letter", "number", "inc"],
+        svec!["a", "13", "14"],
+        svec!["b", "24", "25"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn luau_coalesce() {
+    let wrk = Workdir::new("luau_coalesce");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["nickname", "name"],
+            svec!["", "Robert"],
+            svec!["Liz", "Elizabeth"],
+        ],
+    );
+    let mut cmd = wrk.command("luau");
+    cmd.arg("map")
+        .arg("display_name")
+        .arg("qsv_coalesce(nil, nickname, name)")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["nickname", "name", "display_name"],
+        svec!["", "Robert", "Robert"],
+        svec!["Liz", "Elizabeth", "Liz"],
+    ];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,91 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["name", "dept", "y2021", "y2022"],
+        svec!["John", "sales", "10", "12"],
+        svec!["Mary", "ops", "7", ""],
+    ]
+}
+
+#[test]
+fn melt_id() {
+    let wrk = Workdir::new("melt_id");
+    wrk.create("in.csv", data());
+
+    let mut cmd = wrk.command("melt");
+    cmd.args(["--id", "name,dept"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "dept", "variable", "value"],
+        svec!["John", "sales", "y2021", "10"],
+        svec!["John", "sales", "y2022", "12"],
+        svec!["Mary", "ops", "y2021", "7"],
+        svec!["Mary", "ops", "y2022", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn melt_values_and_names() {
+    let wrk = Workdir::new("melt_values_and_names");
+    wrk.create("in.csv", data());
+
+    let mut cmd = wrk.command("melt");
+    cmd.args(["--id", "name"])
+        .args(["--values", "y2022"])
+        .args(["--variable-name", "year"])
+        .args(["--value-name", "amount"])
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "year", "amount"],
+        svec!["John", "y2022", "12"],
+        svec!["Mary", "y2022", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn melt_skip_empty() {
+    let wrk = Workdir::new("melt_skip_empty");
+    wrk.create("in.csv", data());
+
+    let mut cmd = wrk.command("melt");
+    cmd.args(["--id", "name"])
+        .args(["--values", "y2021,y2022"])
+        .arg("--skip-empty")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "variable", "value"],
+        svec!["John", "y2021", "10"],
+        svec!["John", "y2022", "12"],
+        svec!["Mary", "y2021", "7"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn melt_no_headers() {
+    let wrk = Workdir::new("melt_no_headers");
+    wrk.create(
+        "in.csv",
+        vec![svec!["John", "10", "12"], svec!["Mary", "7", "9"]],
+    );
+
+    let mut cmd = wrk.command("melt");
+    cmd.args(["--id", "1"]).arg("--no-headers").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["John", "2", "10"],
+        svec!["John", "3", "12"],
+        svec!["Mary", "2", "7"],
+        svec!["Mary", "3", "9"],
+    ];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,88 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["name", "score"],
+        svec!["a", "1"],
+        svec!["b", "2"],
+        svec!["c", "3"],
+        svec!["d", "4"],
+        svec!["e", "5"],
+        svec!["f", "6"],
+        svec!["g", "7"],
+        svec!["h", "8"],
+        svec!["i", "9"],
+        svec!["j", "100"],
+    ]
+}
+
+#[test]
+fn outliers_iqr() {
+    let wrk = Workdir::new("outliers_iqr");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("outliers");
+    cmd.arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let mut expected = vec![svec!["name", "score", "outlier"]];
+    for row in &data()[1..] {
+        let flag = if row[1] == "100" { "true" } else { "false" };
+        expected.push(vec![row[0].clone(), row[1].clone(), flag.to_string()]);
+    }
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn outliers_zscore_filter() {
+    let wrk = Workdir::new("outliers_zscore_filter");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["--method", "zscore"])
+        .arg("--filter")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "score"], svec!["j", "100"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn outliers_no_numeric_columns() {
+    let wrk = Workdir::new("outliers_no_numeric_columns");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["--select", "name"]).arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "No numeric columns selected.\n");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn outliers_stats_cache() {
+    let wrk = Workdir::new("outliers_stats_cache");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quartiles").arg("--cache").arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("outliers");
+    cmd.arg("--filter").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name", "score"], svec!["j", "100"]];
+    assert_eq!(got, expected);
+
+    // tamper with the stats cache, so we can tell when its used
+    let cached = std::fs::read_to_string(wrk.path("data.stats.csv")).unwrap();
+    std::fs::write(
+        wrk.path("data.stats.csv"),
+        cached.replace("Integer", "String"),
+    )
+    .unwrap();
+
+    let mut cmd = wrk.command("outliers");
+    cmd.arg("data.csv");
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "No numeric columns selected.\n");
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,83 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["region", "sales"],
+        svec!["East", "10"],
+        svec!["West", "20"],
+        svec!["East", "30"],
+        svec!["North", ""],
+    ]
+}
+
+#[test]
+fn plot_bar_sum() {
+    let wrk = Workdir::new("plot_bar_sum");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("plot");
+    cmd.args(["--kind", "bar"])
+        .arg("region")
+        .args(["--y", "sales"])
+        .args(["--width", "10"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "sales by region\nEast │██████████ 40\nWest │█████ 20";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn plot_hist() {
+    let wrk = Workdir::new("plot_hist");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("plot");
+    cmd.arg("sales")
+        .args(["--bins", "2"])
+        .args(["--width", "4"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "sales\n10 - 20 │██ 1\n20 - 30 │████ 2";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn plot_line_sparkline() {
+    let wrk = Workdir::new("plot_line_sparkline");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("plot");
+    cmd.args(["--kind", "line"]).arg("sales").arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "sales\n▁▅█\nmin: 10  max: 30";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn plot_svg() {
+    let wrk = Workdir::new("plot_svg");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("plot");
+    cmd.args(["--kind", "bar"])
+        .arg("region")
+        .args(["--svg", "chart.svg"])
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("chart.svg"));
+    assert!(got.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_eq!(got.matches("<rect x=").count(), 3);
+    assert!(got.contains("<title>North: 1</title>"));
+}
+
+#[test]
+fn plot_non_numeric() {
+    let wrk = Workdir::new("plot_non_numeric");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("plot");
+    cmd.arg("region").arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "Non-numeric value in record 1: East\n");
+    wrk.assert_err(&mut cmd);
+}
@@ -59,3 +59,40 @@ fn pseudo_no_headers() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn pseudo_mapping() {
+    let wrk = Workdir::new("pseudo_mapping");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "colors"],
+            svec!["Mary", "yellow"],
+            svec!["John", "blue"],
+            svec!["Mary", "purple"],
+            svec!["Sue", "orange"],
+        ],
+    );
+    let mut cmd = wrk.command("pseudo");
+    cmd.arg("name")
+        .arg("--mapping")
+        .arg("mapping.csv")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("mapping.csv"));
+    assert_eq!(got, "value,pseudonym\nMary,0\nJohn,1\nSue,2\n");
+}
+
+#[test]
+fn pseudo_multiple_columns_error() {
+    let wrk = Workdir::new("pseudo_multiple_columns_error");
+    wrk.create(
+        "data.csv",
+        vec![svec!["name", "colors"], svec!["Mary", "yellow"]],
+    );
+    let mut cmd = wrk.command("pseudo");
+    cmd.arg("name,colors").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
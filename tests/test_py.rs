@@ -150,6 +150,32 @@ fn py_map_datetime() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn py_map_re() {
+    let wrk = Workdir::new("py");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "phone"],
+            svec!["Alice", "(212) 555-1234"],
+            svec!["Bob", "(718) 555-9876"],
+        ],
+    );
+    let mut cmd = wrk.command("py");
+    cmd.arg("map")
+        .arg("area_code")
+        .arg(r"re.search(r'\((\d{3})\)', phone).group(1)")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "phone", "area_code"],
+        svec!["Alice", "(212) 555-1234", "212"],
+        svec!["Bob", "(718) 555-9876", "718"],
+    ];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn py_map_userhelper() {
     let wrk = Workdir::new("py");
@@ -46,3 +46,68 @@ fn rename_noheaders() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn rename_pairs() {
+    let wrk = Workdir::new("rename_pairs");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["R", "S", "T"],
+            svec!["1", "b", "x"],
+            svec!["2", "a", "y"],
+        ],
+    );
+
+    let mut cmd = wrk.command("rename");
+    cmd.arg("--pairs").arg("R=cola,T=colc").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["cola", "S", "colc"],
+        svec!["1", "b", "x"],
+        svec!["2", "a", "y"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn rename_pairs_invalid_column() {
+    let wrk = Workdir::new("rename_pairs_invalid_column");
+    wrk.create("in.csv", vec![svec!["R", "S"], svec!["1", "b"]]);
+
+    let mut cmd = wrk.command("rename");
+    cmd.arg("--pairs").arg("X=cola").arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn rename_generic_noheaders() {
+    let wrk = Workdir::new("rename_generic_noheaders");
+    wrk.create("in.csv", vec![svec!["1", "b", "x"], svec!["2", "a", "y"]]);
+
+    let mut cmd = wrk.command("rename");
+    cmd.arg("_all_generic").arg("--no-headers").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["_col_1", "_col_2", "_col_3"],
+        svec!["1", "b", "x"],
+        svec!["2", "a", "y"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn rename_generic_flag() {
+    let wrk = Workdir::new("rename_generic_flag");
+    wrk.create("in.csv", vec![svec!["a", "b"], svec!["1", "x"]]);
+
+    let mut cmd = wrk.command("rename");
+    cmd.arg("--generic").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["_col_1", "_col_2"], svec!["1", "x"]];
+    assert_eq!(got, expected);
+}
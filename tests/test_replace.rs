@@ -264,3 +264,33 @@ fn replace_groups() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn replace_literal() {
+    let wrk = Workdir::new("replace_literal");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["identifier", "price"],
+            svec!["164.0", "$1.50 (USD)"],
+            svec!["165.0", "1.50"],
+            svec!["166.0", "$2.00 (USD)"],
+        ],
+    );
+    let mut cmd = wrk.command("replace");
+    cmd.arg("--literal")
+        .args(["--select", "price"])
+        .arg(" (USD)")
+        .arg(" $1")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["identifier", "price"],
+        svec!["164.0", "$1.50 $1"],
+        svec!["165.0", "1.50"],
+        svec!["166.0", "$2.00 $1"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
@@ -157,3 +157,36 @@ fn generate_schema_with_defaults_to_stdout() {
 
     assert_json_eq!(expected_schema_json, output_schema_json);
 }
+
+#[test]
+fn generate_schema_reuses_stats_cache() {
+    let wrk = Workdir::new("generate_schema_reuses_stats_cache");
+    wrk.create(
+        "data.csv",
+        vec![svec!["id"], svec!["1"], svec!["2"], svec!["3"]],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--cardinality")
+        .arg("--infer-dates")
+        .arg("--cache")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    // tamper with the stats cache, so we can tell when its used
+    let cached = std::fs::read_to_string(wrk.path("data.stats.csv")).unwrap();
+    std::fs::write(
+        wrk.path("data.stats.csv"),
+        cached.replace("Integer", "String"),
+    )
+    .unwrap();
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("--stdout").arg("data.csv");
+    let output: String = wrk.stdout(&mut cmd);
+    let schema: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        schema["properties"]["id"]["type"],
+        serde_json::json!(["string"])
+    );
+}
@@ -0,0 +1,89 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn snappy_roundtrip() {
+    let wrk = Workdir::new("snappy_roundtrip");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "number"],
+            svec!["a", "1"],
+            svec!["b", "2"],
+            svec!["c", "3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("compress")
+        .arg("data.csv")
+        .args(["--output", "data.csv.sz"]);
+    wrk.assert_success(&mut cmd);
+
+    // snappy frame format files start with the stream identifier chunk
+    let compressed = std::fs::read(wrk.path("data.csv.sz")).unwrap();
+    assert!(compressed.starts_with(b"\xff\x06\x00\x00sNaPpY"));
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("check").arg("data.csv.sz");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("decompress").arg("data.csv.sz");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["letter", "number"],
+        svec!["a", "1"],
+        svec!["b", "2"],
+        svec!["c", "3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn snappy_check_invalid() {
+    let wrk = Workdir::new("snappy_check_invalid");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("check").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn snappy_compress_parallel() {
+    let wrk = Workdir::new("snappy_compress_parallel");
+    // several 1 MiB chunks, with a partial last chunk
+    let mut data = String::from("id,value\n");
+    for i in 0..250_000 {
+        data.push_str(&format!("{i},value{}\n", i % 1_000));
+    }
+    wrk.create_from_string("data.csv", &data);
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("compress")
+        .args(["--jobs", "1"])
+        .arg("data.csv")
+        .args(["--output", "single.csv.sz"]);
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("compress")
+        .args(["--jobs", "3"])
+        .arg("data.csv")
+        .args(["--output", "parallel.csv.sz"]);
+    wrk.assert_success(&mut cmd);
+
+    // the chunks are compressed on 64 KiB block boundaries & written in order,
+    // so the output doesn't depend on the number of jobs
+    let single = std::fs::read(wrk.path("single.csv.sz")).unwrap();
+    let parallel = std::fs::read(wrk.path("parallel.csv.sz")).unwrap();
+    assert_eq!(single, parallel);
+
+    let mut cmd = wrk.command("snappy");
+    cmd.arg("decompress").arg("parallel.csv.sz");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, data.trim_end());
+}
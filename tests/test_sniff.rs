@@ -407,3 +407,563 @@ fn sniff_flaky_delimiter_guess() {
     // guess the wrong delimiter if we don't explicitly set it.
     wrk.assert_success(&mut cmd);
 }
+
+#[test]
+fn sniff_detected_mime_csv() {
+    let wrk = Workdir::new("sniff_detected_mime_csv");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json").arg(test_file);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains(r#""detected_mime":"text/csv","#));
+}
+
+#[test]
+fn sniff_not_a_csv() {
+    let wrk = Workdir::new("sniff_not_a_csv");
+
+    for (test_file, mime) in [
+        (
+            "excel-xlsx.xlsx",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ),
+        ("excel-xls.xls", "application/vnd.ms-excel"),
+        (
+            "excel-ods.ods",
+            "application/vnd.oasis.opendocument.spreadsheet",
+        ),
+        ("boston311-100.jsonl", "application/x-ndjson"),
+    ] {
+        let mut cmd = wrk.command("sniff");
+        cmd.arg("--json").arg(wrk.load_test_file(test_file));
+
+        wrk.assert_err(&mut cmd);
+        let got = wrk.output_stderr(&mut cmd);
+        assert!(
+            got.contains(&format!(r#""detected_mime":"{mime}""#)),
+            "{test_file}: {got}"
+        );
+    }
+}
+
+#[test]
+fn sniff_bracketed_header_is_csv() {
+    let wrk = Workdir::new("sniff_bracketed_header_is_csv");
+    wrk.create(
+        "data.csv",
+        vec![svec!["[id]", "name"], svec!["1", "a"], svec!["2", "b"]],
+    );
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json").arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains(r#""detected_mime":"text/csv","#));
+    assert!(got.contains(r#""fields":["[id]","name"]"#));
+}
+
+#[test]
+fn sniff_json_array() {
+    let wrk = Workdir::new("sniff_json_array");
+    std::fs::write(
+        wrk.path("data.csv"),
+        "[\n  {\"id\": 1, \"name\": \"a\"},\n  {\"id\": 2, \"name\": \"b\"}\n]\n",
+    )
+    .unwrap();
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(
+        got.contains(r#""detected_mime":"application/json""#),
+        "{got}"
+    );
+}
+
+#[test]
+fn sniff_parquet_signature() {
+    let wrk = Workdir::new("sniff_parquet_signature");
+    std::fs::write(wrk.path("data.csv"), b"PAR1\x15\x04\x15\x10".as_slice()).unwrap();
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("Detected MIME type: application/vnd.apache.parquet"));
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn sniff_compressed() {
+    use std::io::Write;
+
+    let wrk = Workdir::new("sniff_compressed");
+    let csv = std::fs::read(wrk.load_test_file("snifftest.csv")).unwrap();
+
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gzip.write_all(&csv).unwrap();
+    let mut snappy = snap::write::FrameEncoder::new(Vec::new());
+    snappy.write_all(&csv).unwrap();
+
+    for (compression, compressed) in [
+        ("gzip", gzip.finish().unwrap()),
+        ("zstd", zstd::encode_all(csv.as_slice(), 0).unwrap()),
+        ("snappy", snappy.into_inner().unwrap()),
+    ] {
+        std::fs::write(wrk.path("snifftest.compressed"), &compressed).unwrap();
+
+        let mut cmd = wrk.command("sniff");
+        cmd.arg("--json").arg("snifftest.compressed");
+
+        let got: String = wrk.stdout(&mut cmd);
+        assert!(
+            got.contains(&format!(
+                r#""detected_mime":"text/csv","compression":"{compression}","uncompressed_size":116,"delimiter_char":",","header_row":true,"preamble_rows":3,"#
+            )),
+            "{compression}: {got}"
+        );
+        assert!(got.contains(&format!(r#""file_size":{},"#, compressed.len())));
+        assert!(got.ends_with(
+            r#""num_records":3,"avg_record_len":10,"num_fields":4,"fields":["h1","h2","h3","h4"],"types":["Text","Unsigned","Text","Float"]}"#
+        ));
+    }
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn sniff_compressed_sample() {
+    use std::io::Write;
+
+    let wrk = Workdir::new("sniff_compressed_sample");
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    writeln!(gzip, "id,value,label").unwrap();
+    for i in 0..200_000_u64 {
+        let value = i.wrapping_mul(2_654_435_761) % 1_000_003;
+        writeln!(gzip, "{i},{value},label{}", value % 97).unwrap();
+    }
+    let compressed = gzip.finish().unwrap();
+    std::fs::write(wrk.path("big.csv.gz"), &compressed).unwrap();
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json")
+        .args(["--sample", "100"])
+        .arg("big.csv.gz");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let json: serde_json::Value = serde_json::from_str(&got).unwrap();
+    assert_eq!(json["compression"], "gzip");
+    assert_eq!(json["sampled_records"], 100);
+    assert_eq!(json["estimated"], true);
+    assert_eq!(json["file_size"], compressed.len());
+    // only the start of the file was decompressed
+    assert!(json["retrieved_size"].as_u64().unwrap() < compressed.len() as u64);
+    assert_eq!(json["fields"], serde_json::json!(["id", "value", "label"]));
+}
+
+#[test]
+fn sniff_multiple_jsonl() {
+    let wrk = Workdir::new("sniff_multiple_jsonl");
+    let test_file = wrk.load_test_file("snifftest.csv");
+    let test_file2 = wrk.load_test_file("boston311-100.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json").arg(&test_file).arg(&test_file2);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let lines: Vec<&str> = got.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(r#"{"path":""#));
+    assert!(lines[0].contains(r#"snifftest.csv","#));
+    assert!(lines[0].ends_with(
+        r#""fields":["h1","h2","h3","h4"],"types":["Text","Unsigned","Text","Float"]}"#
+    ));
+    assert!(lines[1].contains(r#"boston311-100.csv","#));
+    assert!(lines[1].contains(r#""num_records":100,"#));
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn sniff_glob_csv() {
+    let wrk = Workdir::new("sniff_glob_csv");
+    wrk.create(
+        "a.csv",
+        vec![svec!["x", "y"], svec!["1", "2"], svec!["3", "4"]],
+    );
+    wrk.create(
+        "b.csv",
+        vec![
+            svec!["name", "age"],
+            svec!["foo", "30"],
+            svec!["bar", "41"],
+            svec!["baz", "52"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--csv").arg("*.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 3);
+    let col = |name: &str| got[0].iter().position(|h| h == name).unwrap();
+    assert_eq!(got[0][0], "path");
+    // local paths are canonicalized
+    assert!(got[1][0].ends_with("a.csv"));
+    assert!(got[2][0].ends_with("b.csv"));
+    assert_eq!(got[1][col("num_records")], "2");
+    assert_eq!(got[1][col("fields")], "x|y");
+    assert_eq!(got[2][col("num_records")], "3");
+    assert_eq!(got[2][col("types")], "Text|Unsigned");
+}
+
+#[test]
+fn sniff_multiple_with_error() {
+    let wrk = Workdir::new("sniff_multiple_with_error");
+    let test_file = wrk.load_test_file("snifftest.csv");
+    let jsonl_file = wrk.load_test_file("boston311-100.jsonl");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json").arg(&test_file).arg(&jsonl_file);
+
+    wrk.assert_err(&mut cmd);
+    let got = String::from_utf8_lossy(&cmd.output().unwrap().stdout).to_string();
+    let lines: Vec<&str> = got.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains(r#""num_records":3,"#));
+    assert!(lines[1].starts_with(&format!(r#"{{"path":"{jsonl_file}","errors":[{{"#)));
+    assert!(lines[1].contains(r#""detected_mime":"application/x-ndjson""#));
+    assert!(wrk
+        .output_stderr(&mut cmd)
+        .contains("1 of 2 files could not be sniffed."));
+}
+
+#[test]
+fn sniff_harvest() {
+    let wrk = Workdir::new("sniff_harvest");
+    std::fs::create_dir_all(wrk.path("data/sub")).unwrap();
+    wrk.create_from_string("data/a.csv", "x,y\n1,2\n3,4\n");
+    wrk.create_from_string("data/sub/b.tsv", "name\tage\nfoo\t30\nbar\t41\nbaz\t52\n");
+    wrk.create_from_string("data/notes.md", "# not tabular\n");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--harvest").arg("data").arg("--csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 3);
+    let col = |name: &str| got[0].iter().position(|h| h == name).unwrap();
+    assert!(got[1][0].ends_with("data/a.csv"));
+    assert!(got[2][0].ends_with("data/sub/b.tsv"));
+    assert_eq!(got[2][col("delimiter_char")], "\t");
+    assert_eq!(got[2][col("fields")], "name|age");
+    assert!(!got[1][col("last_modified")].is_empty());
+}
+
+#[test]
+fn sniff_harvest_jsonl() {
+    let wrk = Workdir::new("sniff_harvest_jsonl");
+    std::fs::create_dir_all(wrk.path("data")).unwrap();
+    wrk.create_from_string("data/a.csv", "x,y\n1,2\n3,4\n");
+    wrk.create_from_string("data/b.txt", r#"{"not":"csv"}"#);
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--harvest").arg("data");
+
+    // files that can't be sniffed don't make harvest fail
+    let got: String = wrk.stdout(&mut cmd);
+    let lines: Vec<&str> = got.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains(r#"data/a.csv","#));
+    assert!(lines[0].contains(r#""last_modified":"#));
+    assert!(lines[1].starts_with(r#"{"path":"data/b.txt","errors":[{"#));
+}
+
+#[test]
+fn sniff_schema_output_jsonschema() {
+    let wrk = Workdir::new("sniff_schema_output_jsonschema");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--schema-output")
+        .arg("snifftest.schema.json")
+        .arg(test_file);
+    wrk.assert_success(&mut cmd);
+
+    let schema: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(wrk.path("snifftest.schema.json")).unwrap())
+            .unwrap();
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    assert_eq!(
+        schema["properties"],
+        serde_json::json!({
+            "h1": { "type": "string" },
+            "h2": { "type": "integer" },
+            "h3": { "type": "string" },
+            "h4": { "type": "number" }
+        })
+    );
+    assert_eq!(
+        schema["required"],
+        serde_json::json!(["h1", "h2", "h3", "h4"])
+    );
+}
+
+#[test]
+fn sniff_schema_output_tableschema() {
+    let wrk = Workdir::new("sniff_schema_output_tableschema");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--schema-output", "snifftest.schema.json"])
+        .args(["--schema-format", "tableschema"])
+        .arg(test_file);
+    wrk.assert_success(&mut cmd);
+
+    let schema: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(wrk.path("snifftest.schema.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        schema,
+        serde_json::json!({
+            "fields": [
+                { "name": "h1", "type": "string" },
+                { "name": "h2", "type": "integer" },
+                { "name": "h3", "type": "string" },
+                { "name": "h4", "type": "number" }
+            ],
+            "missingValues": [""]
+        })
+    );
+}
+
+#[test]
+fn sniff_quick() {
+    let wrk = Workdir::new("sniff_quick");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--quick").arg("--json").arg(test_file);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.ends_with(
+        r#""delimiter_char":",","header_row":true,"preamble_rows":3,"quote_char":"none","flexible":false,"is_utf8":true}"#
+    ));
+    assert!(!got.contains("num_records"));
+}
+
+#[test]
+fn sniff_quick_text() {
+    let wrk = Workdir::new("sniff_quick_text");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--quick").arg(test_file);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected_end = r#"Delimiter: ,
+Header Row: true
+Preamble Rows: 3
+Quote Char: none
+Flexible: false
+Is UTF8: true"#;
+    assert!(got.ends_with(expected_end));
+}
+
+#[test]
+fn sniff_quick_percentage() {
+    let wrk = Workdir::new("sniff_quick_percentage");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--quick", "--sample", "0.5"]).arg(test_file);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sniff_stats_types() {
+    let wrk = Workdir::new("sniff_stats_types");
+    wrk.create_from_string(
+        "in.csv",
+        "name,age,city\nfoo,30,\nbar,,Paris\nbaz,52,\nfoo,30,Rome\nqux,41,Oslo\n",
+    );
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--stats-types").arg("--json").arg("in.csv");
+
+    let got: serde_json::Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+    assert_eq!(got["null_counts"], serde_json::json!([0, 1, 2]));
+    assert_eq!(
+        got["sample_values"],
+        serde_json::json!([
+            ["foo", "bar", "baz", "qux"],
+            ["30", "52", "41"],
+            ["Paris", "Rome", "Oslo"]
+        ])
+    );
+}
+
+#[test]
+fn sniff_stdin() {
+    let wrk = Workdir::new("sniff_stdin");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--json")
+        .stdin(std::fs::File::open(test_file).unwrap());
+
+    // stdin ends before the sample is complete, so the records are counted
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.starts_with(r#"{"path":"stdin","#));
+    assert!(
+        got.contains(r#""file_size":116,"sampled_records":3,"estimated":false,"num_records":3,"#)
+    );
+}
+
+#[test]
+fn sniff_stdin_sample() {
+    let wrk = Workdir::new("sniff_stdin_sample");
+    let mut data = String::from("id,name,amount\n");
+    for i in 0..20_000 {
+        data.push_str(&format!("{i},name{i},{}.5\n", i % 100));
+    }
+    wrk.create_from_string("in.csv", &data);
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--json", "--sample", "50"])
+        .stdin(std::fs::File::open(wrk.path("in.csv")).unwrap());
+
+    // only the sample is read from stdin
+    let got: serde_json::Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+    assert_eq!(got["sampled_records"], 50);
+    assert_eq!(got["estimated"], true);
+    assert!(got["retrieved_size"].as_u64().unwrap() < data.len() as u64);
+    assert_eq!(
+        got["types"],
+        serde_json::json!(["Unsigned", "Text", "Float"])
+    );
+}
+
+#[test]
+fn sniff_confidence() {
+    let wrk = Workdir::new("sniff_confidence");
+    wrk.create_from_string(
+        "in.csv",
+        "name;age;city\nfoo;30;Paris, FR\nbar;41;Rome, IT\nbaz;52;Oslo\nqux;63;Bern, CH\n",
+    );
+
+    let mut cmd = wrk.command("sniff");
+    cmd.arg("--confidence").arg("--json").arg("in.csv");
+
+    let got: serde_json::Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+    assert_eq!(got["delimiter_char"], ";");
+    let confidence = &got["confidence"];
+    assert!(confidence["delimiter"].as_f64().unwrap() > 0.5);
+    assert_eq!(confidence["header"], 1.0);
+    // the commas of the cities split most records into 2 fields
+    assert_eq!(confidence["candidates"][0]["delimiter"], ",");
+    assert!(
+        confidence["candidates"][0]["score"].as_f64().unwrap()
+            < confidence["delimiter"].as_f64().unwrap()
+    );
+}
+
+#[test]
+fn sniff_url_http_options() {
+    use std::io::{BufRead, BufReader, Write};
+
+    let wrk = Workdir::new("sniff_url_http_options");
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    // a minimal server that returns the request headers it got
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            request.push_str(&line);
+        }
+        let body = "h1,h2\na,1\nb,2\nc,3\n";
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/csv\r\nContent-Length: {}\r\nConnection: \
+             close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--json", "--user-agent", "qsv-test"])
+        .args(["-H", "X-Api-Key: abc:123", "--bearer-token", "t0ken"])
+        .arg(format!("http://127.0.0.1:{port}/data.csv"));
+
+    let got: String = wrk.stdout(&mut cmd);
+    let request = server.join().unwrap().to_lowercase();
+    assert!(request.contains("user-agent: qsv-test"), "{request}");
+    assert!(request.contains("x-api-key: abc:123"), "{request}");
+    assert!(request.contains("authorization: bearer t0ken"), "{request}");
+    assert!(got.contains(r#""num_fields":2,"#), "{got}");
+}
+
+#[test]
+fn sniff_requirements() {
+    let wrk = Workdir::new("sniff_requirements");
+    let test_file = wrk.load_test_file("snifftest.csv");
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--require-utf8", "--require-header"])
+        .args(["--delimiter-must-be", ","])
+        .arg(&test_file);
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("sniff");
+    cmd.args(["--max-preamble", "0", "--delimiter-must-be", ";"])
+        .arg(&test_file);
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(
+        got.contains("3 preamble rows, more than 0; delimiter is ',', not ';'"),
+        "{got}"
+    );
+}
+
+#[test]
+fn sniff_cache() {
+    let wrk = Workdir::new("sniff_cache");
+    wrk.create_from_string("in.csv", "x,y\n1,2\n3,4\n");
+    let sniff_json = |args: &[&str]| -> serde_json::Value {
+        let mut cmd = wrk.command("sniff");
+        cmd.arg("--json").args(args).arg("in.csv");
+        serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap()
+    };
+
+    // the cache is opt-in
+    sniff_json(&[]);
+    assert!(!wrk.path(".cache/qsv/sniff").exists());
+
+    let mut first = sniff_json(&["--cache"]);
+    // the cached results are returned, with the timestamp of this sniff
+    let mut cached = sniff_json(&["--cache"]);
+    assert_ne!(first["sniff_timestamp"], cached["sniff_timestamp"]);
+    first["sniff_timestamp"].take();
+    cached["sniff_timestamp"].take();
+    assert_eq!(first, cached);
+    let forced = sniff_json(&["--cache", "--force"]);
+    assert_ne!(first["sniff_timestamp"], forced["sniff_timestamp"]);
+
+    // the cache is invalidated when the file changes
+    wrk.create_from_string("in.csv", "x,y\n1,2\n3,4\n5,6\n");
+    let changed = sniff_json(&["--cache"]);
+    assert_eq!(changed["num_records"], 3);
+}
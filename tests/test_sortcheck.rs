@@ -226,3 +226,57 @@ fn sortcheck_simple_all_json_progressbar() {
     );
     wrk.assert_err(&mut cmd);
 }
+
+#[test]
+fn sortcheck_numeric_reverse() {
+    let wrk = Workdir::new("sortcheck_numeric_reverse");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["col1", "col2"],
+            svec!["100", "a"],
+            svec!["20", "b"],
+            svec!["20", "c"],
+            svec!["3", "d"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sortcheck");
+    cmd.args(["--select", "col1"])
+        .arg("--numeric")
+        .arg("--reverse")
+        .arg("in.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    // lexically, the file is not sorted in descending order
+    let mut cmd = wrk.command("sortcheck");
+    cmd.args(["--select", "col1"])
+        .arg("--reverse")
+        .arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sortcheck_first_unsorted_record() {
+    let wrk = Workdir::new("sortcheck_first_unsorted_record");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["col1", "col2"],
+            svec!["1", "d"],
+            svec!["2", "c"],
+            svec!["5", "b"],
+            svec!["4", "a"],
+            svec!["3", "a"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sortcheck");
+    cmd.arg("--all").arg("in.csv");
+
+    let got_stderr = wrk.output_stderr(&mut cmd);
+    assert!(got_stderr.contains("not sorted. First out-of-order record: 4"));
+    wrk.assert_err(&mut cmd);
+}
@@ -448,3 +448,157 @@ fn split_custom_filename_padded() {
     assert!(wrk.path("prefix-002.csv").exists());
     assert!(wrk.path("prefix-004.csv").exists());
 }
+
+#[test]
+fn split_chunks() {
+    let wrk = Workdir::new("split_chunks");
+    wrk.create("in.csv", data(true));
+
+    let mut cmd = wrk.command("split");
+    cmd.args(["--chunks", "4"]).arg(&wrk.path(".")).arg("in.csv");
+    wrk.run(&mut cmd);
+
+    split_eq!(
+        wrk,
+        "0.csv",
+        "\
+h1,h2
+a,b
+c,d
+"
+    );
+    split_eq!(
+        wrk,
+        "1.csv",
+        "\
+h1,h2
+e,f
+g,h
+"
+    );
+    split_eq!(
+        wrk,
+        "2.csv",
+        "\
+h1,h2
+i,j
+"
+    );
+    split_eq!(
+        wrk,
+        "3.csv",
+        "\
+h1,h2
+k,l
+"
+    );
+    assert!(!wrk.path("4.csv").exists());
+}
+
+#[test]
+fn split_chunks_stdin() {
+    let wrk = Workdir::new("split_chunks_stdin");
+    wrk.create("in.csv", data(true));
+
+    let mut cmd = wrk.command("split");
+    cmd.args(["--chunks", "4"])
+        .arg(&wrk.path("."))
+        .stdin(std::fs::File::open(wrk.path("in.csv")).unwrap());
+    wrk.run(&mut cmd);
+
+    split_eq!(
+        wrk,
+        "0.csv",
+        "\
+h1,h2
+a,b
+c,d
+"
+    );
+    split_eq!(
+        wrk,
+        "3.csv",
+        "\
+h1,h2
+k,l
+"
+    );
+    assert!(!wrk.path("4.csv").exists());
+}
+
+#[test]
+fn split_chunks_idx() {
+    let wrk = Workdir::new("split_chunks_idx");
+    wrk.create_indexed("in.csv", data(true));
+
+    let mut cmd = wrk.command("split");
+    cmd.args(["--chunks", "4"]).arg(&wrk.path(".")).arg("in.csv");
+    wrk.run(&mut cmd);
+
+    split_eq!(
+        wrk,
+        "0.csv",
+        "\
+h1,h2
+a,b
+c,d
+"
+    );
+    split_eq!(
+        wrk,
+        "1.csv",
+        "\
+h1,h2
+e,f
+g,h
+"
+    );
+    split_eq!(
+        wrk,
+        "2.csv",
+        "\
+h1,h2
+i,j
+"
+    );
+    split_eq!(
+        wrk,
+        "3.csv",
+        "\
+h1,h2
+k,l
+"
+    );
+    assert!(!wrk.path("4.csv").exists());
+}
+
+#[test]
+fn split_chunks_more_than_records() {
+    let wrk = Workdir::new("split_chunks_more_than_records");
+    wrk.create("in.csv", data(true));
+
+    let mut cmd = wrk.command("split");
+    cmd.args(["--chunks", "8"]).arg(&wrk.path(".")).arg("in.csv");
+    wrk.run(&mut cmd);
+
+    split_eq!(
+        wrk,
+        "5.csv",
+        "\
+h1,h2
+k,l
+"
+    );
+    split_eq!(wrk, "7.csv", "h1,h2\n");
+    assert!(!wrk.path("8.csv").exists());
+}
+
+#[test]
+fn split_chunks_zero() {
+    let wrk = Workdir::new("split_chunks_zero");
+    wrk.create("in.csv", data(true));
+
+    let mut cmd = wrk.command("split");
+    cmd.args(["--chunks", "0"]).arg(&wrk.path(".")).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
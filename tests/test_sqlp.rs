@@ -0,0 +1,231 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn sqlp_select_where() {
+    let wrk = Workdir::new("sqlp_select_where");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["Springfield", "MA", "155929"],
+            svec!["New York", "NY", "8804190"],
+            svec!["Buffalo", "NY", "278349"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv").arg(
+        "select city, population from cities where population > 200000 order by population desc",
+    );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "population"],
+        svec!["New York", "8804190"],
+        svec!["Boston", "675647"],
+        svec!["Buffalo", "278349"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_groupby() {
+    let wrk = Workdir::new("sqlp_groupby");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["Springfield", "MA", "155929"],
+            svec!["New York", "NY", "8804190"],
+            svec!["Buffalo", "NY", "278349"],
+            svec!["Portland", "ME", "68408"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv").arg(
+        "select state, count(*) as cnt, sum(population) as total from cities group by state order \
+         by state",
+    );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["state", "cnt", "total"],
+        svec!["MA", "2", "831576"],
+        svec!["ME", "1", "68408"],
+        svec!["NY", "2", "9082539"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_join_with_aliases() {
+    let wrk = Workdir::new("sqlp_join_with_aliases");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state"],
+            svec!["Boston", "MA"],
+            svec!["New York", "NY"],
+            svec!["Portland", "ME"],
+        ],
+    );
+    wrk.create(
+        "states.csv",
+        vec![
+            svec!["abbrev", "name"],
+            svec!["MA", "Massachusetts"],
+            svec!["NY", "New York"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv").arg("states.csv").arg(
+        "select _t_1.city, _t_2.name from _t_1 join _t_2 on _t_1.state = _t_2.abbrev order by \
+         _t_1.city",
+    );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "name"],
+        svec!["Boston", "Massachusetts"],
+        svec!["New York", "New York"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_table_alias_not_rewritten_in_literals() {
+    let wrk = Workdir::new("sqlp_table_alias_not_rewritten_in_literals");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "tag"],
+            svec!["Boston", "abc_t_1"],
+            svec!["New York", "xyz"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select city from _t_1 where tag = 'abc_t_1'");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["city"], svec!["Boston"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_missing_input() {
+    let wrk = Workdir::new("sqlp_missing_input");
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("nonexistent.csv").arg("select * from nonexistent");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sqlp_format_parquet() {
+    let wrk = Workdir::new("sqlp_format_parquet");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["New York", "NY", "8804190"],
+        ],
+    );
+
+    let out_file = wrk.path("cities.parquet").to_string_lossy().to_string();
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select * from cities")
+        .args(["--format", "parquet"])
+        .args(["--compression", "zstd"])
+        .args(["--row-group-size", "1"])
+        .args(["--output", &out_file]);
+
+    wrk.assert_success(&mut cmd);
+
+    // parquet files start with the "PAR1" magic bytes
+    let parquet_bytes = std::fs::read(&out_file).unwrap();
+    assert!(parquet_bytes.starts_with(b"PAR1"));
+}
+
+#[test]
+fn sqlp_format_parquet_requires_output() {
+    let wrk = Workdir::new("sqlp_format_parquet_requires_output");
+    wrk.create("cities.csv", vec![svec!["city"], svec!["Boston"]]);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select * from cities")
+        .args(["--format", "parquet"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sqlp_parquet_input() {
+    let wrk = Workdir::new("sqlp_parquet_input");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["New York", "NY", "8804190"],
+            svec!["Buffalo", "NY", "278349"],
+        ],
+    );
+
+    let out_file = wrk.path("cities.parquet").to_string_lossy().to_string();
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select * from cities")
+        .args(["--format", "parquet"])
+        .args(["--output", &out_file]);
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.parquet")
+        .arg("select city, population from cities where state = 'NY' order by city");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "population"],
+        svec!["Buffalo", "278349"],
+        svec!["New York", "8804190"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_format_arrow() {
+    let wrk = Workdir::new("sqlp_format_arrow");
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "population"],
+            svec!["Boston", "MA", "675647"],
+            svec!["New York", "NY", "8804190"],
+            svec!["Buffalo", "NY", "278349"],
+        ],
+    );
+
+    let out_file = wrk.path("cities.arrow").to_string_lossy().to_string();
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select * from cities")
+        .args(["--format", "arrow"])
+        .args(["--batch-size", "2"])
+        .args(["--output", &out_file]);
+
+    wrk.assert_success(&mut cmd);
+
+    // Arrow IPC files start with the "ARROW1" magic bytes
+    let arrow_bytes = std::fs::read(&out_file).unwrap();
+    assert!(arrow_bytes.starts_with(b"ARROW1"));
+}
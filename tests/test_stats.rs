@@ -180,6 +180,9 @@ fn get_field_value(wrk: &Workdir, cmd: &mut process::Command, field: &str) -> St
     if field == "cardinality" {
         cmd.arg("--cardinality");
     }
+    if field == "uniqueness_ratio" {
+        cmd.arg("--uniqueness");
+    }
     if field == "mode" || field == "antimode" {
         cmd.arg("--mode");
     }
@@ -511,6 +514,18 @@ stats_tests!(
 );
 
 stats_tests!(stats_cardinality, "cardinality", &["a", "b", "a"], "2");
+stats_tests!(
+    stats_uniqueness_ratio,
+    "uniqueness_ratio",
+    &["a", "b", "a", "c"],
+    "0.75"
+);
+stats_tests!(
+    stats_uniqueness_ratio_unique,
+    "uniqueness_ratio",
+    &["a", "b", "c"],
+    "1"
+);
 stats_tests!(stats_mode, "mode", &["a", "b", "a"], "a,1,2");
 stats_tests!(stats_mode_null, "mode", &["", "a", "b", "a"], "a,1,2");
 stats_tests!(stats_antimode, "antimode", &["a", "b", "a"], "b,1,1");
@@ -630,6 +645,59 @@ fn stats_prefer_dmy() {
     assert_eq!(dos2unix(&got2), dos2unix(&expected2).trim_end());
 }
 
+#[test]
+fn stats_prefer_dmy_env() {
+    let wrk = Workdir::new("stats_prefer_dmy_env");
+    let test_file = wrk.load_test_file("boston311-dmy-100.csv");
+
+    let mut cmd = wrk.command("stats");
+    cmd.env("QSV_PREFER_DMY", "1")
+        .arg("--infer-dates")
+        .arg("--dates-whitelist")
+        .arg("_dt")
+        .arg(test_file);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    wrk.create("in2.csv", got);
+
+    // removed variance & stddev columns as its causing flaky CI test for float values
+    let mut cmd = wrk.command("select");
+    cmd.arg("1-9,12-").arg("in2.csv");
+
+    let got2: String = wrk.stdout(&mut cmd);
+    let expected2 = wrk.load_test_resource("boston311-100-stats.csv");
+
+    assert_eq!(dos2unix(&got2), dos2unix(&expected2).trim_end());
+}
+
+#[test]
+fn stats_date_range_in_days() {
+    let wrk = Workdir::new("stats_date_range_in_days");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["event_date"],
+            svec!["15/01/2023"],
+            svec!["01/03/2023"],
+            svec!["02/01/2023"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--infer-dates").arg("--prefer-dmy").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let value = |field: &str| {
+        let idx = got[0].iter().position(|h| h == field).unwrap();
+        got[1][idx].clone()
+    };
+    assert_eq!(value("type"), "Date");
+    assert_eq!(value("min"), "2023-01-02");
+    assert_eq!(value("max"), "2023-03-01");
+    assert_eq!(value("range"), "58");
+}
+
 #[test]
 fn stats_prefer_mdy() {
     let wrk = Workdir::new("stats_prefer_mdy");
@@ -702,6 +770,37 @@ fn stats_no_date_inference() {
     assert_eq!(dos2unix(&got2), dos2unix(&expected2).trim_end());
 }
 
+#[test]
+fn stats_everything_parallel() {
+    let wrk = Workdir::new("stats_everything_parallel");
+    let test_file = wrk.load_test_file("boston311-100.csv");
+
+    let mut cmd = wrk.command("index");
+    cmd.arg(&test_file);
+    wrk.assert_success(&mut cmd);
+
+    // the statistics computed in parallel chunks & merged should be the same
+    // as the ones computed sequentially
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything")
+        .arg("--jobs")
+        .arg("4")
+        .arg(&test_file);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    wrk.create("in2.csv", got);
+
+    // removed variance & stddev columns as its causing flaky CI test for float values
+    let mut cmd = wrk.command("select");
+    cmd.arg("1-9,12-").arg("in2.csv");
+
+    let got2: String = wrk.stdout(&mut cmd);
+    let expected2 = wrk.load_test_resource("boston311-100-everything-nodate-stats.csv");
+
+    assert_eq!(dos2unix(&got2), dos2unix(&expected2).trim_end());
+}
+
 #[test]
 fn stats_with_date_inference() {
     let wrk = Workdir::new("stats_with_date_inference");
@@ -835,6 +934,439 @@ fn stats_leading_zero_handling() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn stats_percentiles() {
+    let wrk = Workdir::new("stats_percentiles");
+    let mut rows = vec![svec!["n"]];
+    rows.extend((1..=10).map(|i| vec![i.to_string()]));
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--percentiles").arg("10, 50,90").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let headers = &got[0];
+    let pcts: Vec<(String, String)> = headers
+        .iter()
+        .zip(got[1].iter())
+        .filter(|(h, _)| h.starts_with('p'))
+        .map(|(h, v)| (h.clone(), v.clone()))
+        .collect();
+    assert_eq!(
+        pcts,
+        vec![
+            ("p10".to_string(), "1.9".to_string()),
+            ("p50".to_string(), "5.5".to_string()),
+            ("p90".to_string(), "9.1".to_string()),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn stats_percentiles_approx() {
+    let wrk = Workdir::new("stats_percentiles_approx");
+    let mut rows = vec![svec!["n"]];
+    rows.extend((1..=1000).map(|i| vec![i.to_string()]));
+    wrk.create_indexed("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quartiles")
+        .arg("--percentiles")
+        .arg("95")
+        .arg("--approx")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let value = |field: &str| -> f64 {
+        let idx = got[0].iter().position(|h| h == field).unwrap();
+        got[1][idx].parse().unwrap()
+    };
+    assert!((value("q1") - 250.0).abs() < 5.0);
+    assert!((value("q2_median") - 500.0).abs() < 5.0);
+    assert!((value("q3") - 750.0).abs() < 5.0);
+    assert!((value("p95") - 950.0).abs() < 5.0);
+}
+
+#[test]
+fn stats_cardinality_without_uniqueness() {
+    let wrk = Workdir::new("stats_cardinality_without_uniqueness");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"], svec!["b"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--cardinality").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[0].last().unwrap(), "cardinality");
+    assert!(!got[0].iter().any(|h| h == "uniqueness_ratio"));
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn stats_cardinality_approx() {
+    let wrk = Workdir::new("stats_cardinality_approx");
+    let mut rows = vec![svec!["id", "category"]];
+    rows.extend((1..=5000).map(|i| vec![i.to_string(), (i % 10).to_string()]));
+    wrk.create_indexed("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--uniqueness").arg("--approx").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let card_idx = got[0].iter().position(|h| h == "cardinality").unwrap();
+    let ratio_idx = got[0].iter().position(|h| h == "uniqueness_ratio").unwrap();
+
+    let id_cardinality: f64 = got[1][card_idx].parse().unwrap();
+    assert!((id_cardinality - 5000.0).abs() < 100.0);
+    let id_ratio: f64 = got[1][ratio_idx].parse().unwrap();
+    assert!((id_ratio - 1.0).abs() < 0.02);
+    assert_eq!(got[2][card_idx], "10");
+    assert_eq!(got[2][ratio_idx], "0.002");
+}
+
+#[test]
+#[cfg(not(feature = "full"))]
+fn stats_approx_not_available() {
+    let wrk = Workdir::new("stats_approx_not_available");
+    wrk.create("data.csv", vec![svec!["n"], svec!["1"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quartiles").arg("--approx").arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--approx is only available in the full qsv variant."));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn stats_percentiles_invalid() {
+    let wrk = Workdir::new("stats_percentiles_invalid");
+    wrk.create("data.csv", vec![svec!["n"], svec!["1"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--percentiles").arg("50,101").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn stats_histogram() {
+    let wrk = Workdir::new("stats_histogram");
+    let mut rows = vec![svec!["n", "s"]];
+    rows.extend((1..=10).map(|i| vec![i.to_string(), format!("s{i}")]));
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--histogram").arg("3").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let bins_idx = got[0].iter().position(|h| h == "histogram_bins").unwrap();
+    let counts_idx = got[0].iter().position(|h| h == "histogram_counts").unwrap();
+    assert_eq!(got[1][bins_idx], "1,4,7,10");
+    assert_eq!(got[1][counts_idx], "3,3,4");
+    // no histogram for String columns
+    assert_eq!(got[2][bins_idx], "");
+    assert_eq!(got[2][counts_idx], "");
+}
+
+#[test]
+fn stats_histogram_quantile_bins() {
+    let wrk = Workdir::new("stats_histogram_quantile_bins");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["n"],
+            svec!["1"],
+            svec!["1"],
+            svec!["1"],
+            svec!["1"],
+            svec!["2"],
+            svec!["3"],
+            svec!["4"],
+            svec!["100"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--histogram")
+        .arg("2")
+        .arg("--quantile-bins")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let bins_idx = got[0].iter().position(|h| h == "histogram_bins").unwrap();
+    let counts_idx = got[0].iter().position(|h| h == "histogram_counts").unwrap();
+    assert_eq!(got[1][bins_idx], "1,1.5,100");
+    assert_eq!(got[1][counts_idx], "4,4");
+}
+
+#[test]
+fn stats_quality() {
+    let wrk = Workdir::new("stats_quality");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["dirty", "clean", "zip"],
+            svec![" x", "1", "02134"],
+            svec!["007", "2", "10001"],
+            svec!["12", "3", ""],
+            svec!["abc ", "4", "02101"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quality").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let quality: Vec<Vec<String>> = got
+        .iter()
+        .map(|row| {
+            vec![
+                row[0].clone(),
+                row[1].clone(),
+                row[row.len() - 3].clone(),
+                row[row.len() - 2].clone(),
+                row[row.len() - 1].clone(),
+            ]
+        })
+        .collect();
+    let expected = vec![
+        svec![
+            "field",
+            "type",
+            "whitespace_count",
+            "leading_zero_count",
+            "mixed_types"
+        ],
+        svec!["dirty", "String", "2", "1", "true"],
+        svec!["clean", "Integer", "0", "0", "false"],
+        svec!["zip", "String", "0", "2", "false"],
+    ];
+    assert_eq!(quality, expected);
+}
+
+#[test]
+fn stats_decimal_comma() {
+    let wrk = Workdir::new("stats_decimal_comma");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["amount", "day"],
+            svec!["1.234,56", "15.02.2023"],
+            svec!["2,5", "01.03.2023"],
+            svec!["-3,5", "20.12.2022"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--decimal-comma").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got: Vec<Vec<String>> = got.iter().map(|row| row[..5].to_vec()).collect();
+    let expected = vec![
+        svec!["field", "type", "sum", "min", "max"],
+        svec!["amount", "Float", "1233.56", "-3.5", "1234.56"],
+        svec!["day", "String", "", "01.03.2023", "20.12.2022"],
+    ];
+    assert_eq!(got, expected);
+
+    // without --decimal-comma, the amounts are strings
+    let mut cmd = wrk.command("stats");
+    cmd.arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[1][1], "String");
+}
+
+#[test]
+fn stats_decimal_comma_period_not_number() {
+    let wrk = Workdir::new("stats_decimal_comma_period_not_number");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["grouped", "decimal_point"],
+            svec!["1.234", "1,5"],
+            svec!["12.345.678", "1.5"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--decimal-comma").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got: Vec<Vec<String>> = got.iter().map(|row| row[..3].to_vec()).collect();
+    // with a decimal comma, the period is a thousands separator, so "1.5" is not 1.5
+    let expected = vec![
+        svec!["field", "type", "sum"],
+        svec!["grouped", "Integer", "12346912"],
+        svec!["decimal_point", "String", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn stats_weight() {
+    let wrk = Workdir::new("stats_weight");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["value", "w"],
+            svec!["10", "1"],
+            svec!["20", "3"],
+            // rows without a valid weight are excluded from the weighted stats
+            svec!["1000", ""],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--weight", "w", "--percentiles", "25,50"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let columns = [
+        "field", "sum", "max", "mean", "stddev", "variance", "p25", "p50",
+    ];
+    let positions: Vec<usize> = columns
+        .iter()
+        .map(|c| got[0].iter().position(|h| h == c).unwrap())
+        .collect();
+    let weighted: Vec<Vec<String>> = got
+        .iter()
+        .map(|row| positions.iter().map(|&i| row[i].clone()).collect())
+        .collect();
+    let expected = vec![
+        svec!["field", "sum", "max", "mean", "stddev", "variance", "p25", "p50"],
+        svec!["value", "70", "1000", "17.5", "4.3301", "18.75", "10", "20"],
+        svec!["w", "10", "3", "2.5", "0.866", "0.75", "1", "3"],
+    ];
+    assert_eq!(weighted, expected);
+}
+
+#[test]
+fn stats_groupby() {
+    let wrk = Workdir::new("stats_groupby");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["region", "amount"],
+            svec!["a", "1"],
+            svec!["b", "10"],
+            svec!["a", "3"],
+            svec!["b", "20"],
+            svec!["a", ""],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--groupby", "region"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let sparsity = got[0].iter().position(|h| h == "sparsity").unwrap();
+    let grouped: Vec<Vec<String>> = got
+        .iter()
+        .map(|row| {
+            let mut grouped = row[..6].to_vec();
+            grouped.push(row[sparsity].clone());
+            grouped
+        })
+        .collect();
+    let expected = vec![
+        svec!["region", "field", "type", "sum", "min", "max", "sparsity"],
+        svec!["a", "amount", "Integer", "4", "1", "3", "0.3333"],
+        svec!["b", "amount", "Integer", "30", "10", "20", "0"],
+    ];
+    assert_eq!(grouped, expected);
+}
+
+#[test]
+fn stats_weight_invalid_column() {
+    let wrk = Workdir::new("stats_weight_invalid_column");
+    wrk.create("data.csv", vec![svec!["a", "b"], svec!["1", "2"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--weight", "a,b"]).arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn stats_cache() {
+    let wrk = Workdir::new("stats_cache");
+    wrk.create(
+        "data.csv",
+        vec![svec!["n"], svec!["1"], svec!["2"], svec!["3"]],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("--cache").arg("data.csv");
+    let expected: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert!(wrk.path("data.stats.csv").exists());
+    assert!(wrk.path("data.stats.csv.json").exists());
+
+    // tamper with the cache, so we can tell when its used
+    let cached = std::fs::read_to_string(wrk.path("data.stats.csv")).unwrap();
+    std::fs::write(
+        wrk.path("data.stats.csv"),
+        cached.replace("Integer", "Cached"),
+    )
+    .unwrap();
+
+    // a subset of the cached statistics is also served from the cache
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, vec![svec!["field", "type"], svec!["n", "Cached"]]);
+
+    // the cache is not used when the options change the statistics
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly").arg("--nulls").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, vec![svec!["field", "type"], svec!["n", "Integer"]]);
+
+    // --force recomputes the statistics
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("--force").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, expected);
+
+    // changing the file invalidates the cache
+    wrk.create(
+        "data.csv",
+        vec![svec!["n"], svec!["a"], svec!["b"], svec!["c"]],
+    );
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, vec![svec!["field", "type"], svec!["n", "String"]]);
+}
+
+#[test]
+fn stats_cache_opt_in() {
+    let wrk = Workdir::new("stats_cache_opt_in");
+    wrk.create("data.csv", vec![svec!["n"], svec!["1"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    assert!(!wrk.path("data.stats.csv").exists());
+    assert!(!wrk.path("data.stats.csv.json").exists());
+}
+
+#[test]
+fn stats_cache_write_error() {
+    let wrk = Workdir::new("stats_cache_write_error");
+    wrk.create("data.csv", vec![svec!["n"], svec!["1"]]);
+    // the cache can't be written over a directory
+    std::fs::create_dir(wrk.path("data.stats.csv")).unwrap();
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly").arg("--cache").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, vec![svec!["field", "type"], svec!["n", "Integer"]]);
+    assert!(!wrk.path("data.stats.csv.json").exists());
+}
+
 mod stats_infer_nothing {
     // Only test CSV data with headers.
     // Empty CSV data with no headers won't produce any statistical analysis.
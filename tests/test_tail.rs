@@ -0,0 +1,58 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["n"],
+        svec!["1"],
+        svec!["2"],
+        svec!["3"],
+        svec!["4"],
+        svec!["5"],
+    ]
+}
+
+#[test]
+fn tail() {
+    let wrk = Workdir::new("tail");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("tail");
+    cmd.args(["--rows", "2"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["n"], svec!["4"], svec!["5"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn tail_indexed() {
+    let wrk = Workdir::new("tail_indexed");
+    wrk.create_indexed("data.csv", data());
+    let mut cmd = wrk.command("tail");
+    cmd.args(["--rows", "2"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["n"], svec!["4"], svec!["5"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn tail_more_rows_than_csv() {
+    let wrk = Workdir::new("tail_more_rows_than_csv");
+    wrk.create("data.csv", data());
+    let mut cmd = wrk.command("tail");
+    cmd.args(["--rows", "10"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, data());
+}
+
+#[test]
+fn tail_indexed_more_rows_than_csv() {
+    let wrk = Workdir::new("tail_indexed_more_rows_than_csv");
+    wrk.create_indexed("data.csv", data());
+    let mut cmd = wrk.command("tail");
+    cmd.args(["--rows", "10"]).arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, data());
+}
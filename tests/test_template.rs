@@ -0,0 +1,71 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["Full Name", "age"],
+        svec!["John", "42"],
+        svec!["Mary", "37"],
+    ]
+}
+
+#[test]
+fn template_stream() {
+    let wrk = Workdir::new("template_stream");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("template");
+    cmd.arg("--template")
+        .arg("INSERT INTO people VALUES ('{{ full_name }}', {{ age }});")
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected =
+        "INSERT INTO people VALUES ('John', 42);\nINSERT INTO people VALUES ('Mary', 37);";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn template_file_no_headers() {
+    let wrk = Workdir::new("template_file_no_headers");
+    wrk.create("data.csv", data());
+    wrk.create_from_string("tmpl.j2", "{{ QSV_ROWNO }}: {{ _c1 | upper }}");
+
+    let mut cmd = wrk.command("template");
+    cmd.arg("--template-file")
+        .arg("tmpl.j2")
+        .arg("--no-headers")
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "1: FULL NAME\n2: JOHN\n3: MARY";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn template_outdir() {
+    let wrk = Workdir::new("template_outdir");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("template");
+    cmd.arg("--template")
+        .arg("# {{ full_name }}\nAge: {{ age }}")
+        .arg("--outfilename")
+        .arg("person_{}.md")
+        .arg("data.csv")
+        .arg("out");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("out/person_2.md"));
+    assert_eq!(got, "# Mary\nAge: 37");
+}
+
+#[test]
+fn template_invalid_syntax() {
+    let wrk = Workdir::new("template_invalid_syntax");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("template");
+    cmd.arg("--template").arg("{{ age ").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
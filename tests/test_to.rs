@@ -0,0 +1,607 @@
+use crate::workdir::Workdir;
+
+#[test]
+fn to_xlsx_sheet_rows() {
+    let wrk = Workdir::new("to_xlsx_sheet_rows");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "word"],
+            svec!["a", "alpha"],
+            svec!["b", "bravo"],
+            svec!["c", "charlie"],
+            svec!["d", "delta"],
+            svec!["e", "echo"],
+        ],
+    );
+
+    let xlsx_file = wrk.path("data.xlsx").to_string_lossy().to_string();
+    let mut cmd = wrk.command("to");
+    cmd.arg("xlsx")
+        .arg(&xlsx_file)
+        .args(["--sheet-rows", "2"])
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("excel");
+    cmd.arg(&xlsx_file).args(["--sheet", "data_2"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["letter", "word"],
+        svec!["c", "charlie"],
+        svec!["d", "delta"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("excel");
+    cmd.arg(&xlsx_file).args(["--sheet", "data_3"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["letter", "word"], svec!["e", "echo"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn to_xlsx_sheet_rows_zero() {
+    let wrk = Workdir::new("to_xlsx_sheet_rows_zero");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("xlsx")
+        .arg("data.xlsx")
+        .args(["--sheet-rows", "0"])
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_sqlite() {
+    let wrk = Workdir::new("to_sqlite");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "number"],
+            svec!["a", "1"],
+            svec!["b", "2"],
+            svec!["c", "3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite").arg("test.db").arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains("Table 'data' (3 rows)"));
+    assert!(wrk.path("test.db").exists());
+}
+
+#[test]
+fn to_sqlite_drop_evolve_conflict() {
+    let wrk = Workdir::new("to_sqlite_drop_evolve_conflict");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--drop")
+        .arg("--evolve")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+// query a sqlite database written by `to sqlite`
+fn query_sqlite(db: &std::path::Path, sql: &str) -> Vec<String> {
+    let conn = rusqlite::Connection::open(db).unwrap();
+    let mut stmt = conn.prepare(sql).unwrap();
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0)).unwrap();
+    rows.map(Result::unwrap).collect()
+}
+
+#[test]
+fn to_sqlite_append() {
+    let wrk = Workdir::new("to_sqlite_append");
+    wrk.create(
+        "data.csv",
+        vec![svec!["letter", "number"], svec!["a", "1"], svec!["b", "2"]],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    // without --append, the existing table is an error
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--append")
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got = query_sqlite(
+        &wrk.path("test.db"),
+        "SELECT letter || number FROM data ORDER BY letter",
+    );
+    assert_eq!(got, svec!["a1", "a1", "b2", "b2"]);
+}
+
+#[test]
+fn to_sqlite_append_missing_columns() {
+    let wrk = Workdir::new("to_sqlite_append_missing_columns");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let wrk_more = wrk.path("more");
+    std::fs::create_dir_all(&wrk_more).unwrap();
+    wrk.create(
+        "more/data.csv",
+        vec![svec!["letter", "number"], svec!["b", "2"]],
+    );
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--append")
+        .arg("more/data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_sqlite_append_drop_conflict() {
+    let wrk = Workdir::new("to_sqlite_append_drop_conflict");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .arg("--append")
+        .arg("--drop")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_sqlite_index() {
+    let wrk = Workdir::new("to_sqlite_index");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "name", "state"],
+            svec!["1", "Brooklyn", "NY"],
+            svec!["2", "Paris", "TX"],
+        ],
+    );
+    wrk.create("other.csv", vec![svec!["id", "value"], svec!["1", "x"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .args(["--index", "id"])
+        .args(["--unique-index", "name,state"])
+        .arg("--quiet")
+        .arg("data.csv")
+        .arg("other.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got = query_sqlite(
+        &wrk.path("test.db"),
+        "SELECT name FROM sqlite_master WHERE type = 'index' ORDER BY name",
+    );
+    assert_eq!(
+        got,
+        svec!["idx_data_id", "idx_data_name_state", "idx_other_id"]
+    );
+
+    let got = query_sqlite(
+        &wrk.path("test.db"),
+        "SELECT sql FROM sqlite_master WHERE name = 'idx_data_name_state'",
+    );
+    assert!(got[0].starts_with("CREATE UNIQUE INDEX"));
+}
+
+#[test]
+fn to_sqlite_index_unknown_column() {
+    let wrk = Workdir::new("to_sqlite_index_unknown_column");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("test.db")
+        .args(["--index", "number"])
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_sqlite_index_dump() {
+    let wrk = Workdir::new("to_sqlite_index_dump");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("sqlite")
+        .arg("--dump")
+        .arg("-")
+        .args(["--index", "letter"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains("CREATE INDEX IF NOT EXISTS [idx_data_letter] ON [data] ([letter]);"));
+}
+
+#[test]
+fn to_postgres_missing_env_connection_string() {
+    let wrk = Workdir::new("to_postgres_missing_env_connection_string");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("env=QSV_TEST_UNSET_DATABASE_URL")
+        .arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("'QSV_TEST_UNSET_DATABASE_URL'"));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_datapackage_stdout() {
+    let wrk = Workdir::new("to_datapackage_stdout");
+    wrk.create(
+        "data.csv",
+        vec![svec!["letter", "number"], svec!["a", "1"], svec!["b", "2"]],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("datapackage").arg("-").arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let datapackage: serde_json::Value = serde_json::from_str(&got).unwrap();
+    let resource = &datapackage["resources"][0];
+    assert_eq!(resource["name"], "data");
+    let field_names: Vec<&str> = resource["schema"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(field_names, vec!["letter", "number"]);
+}
+
+// query a parquet file written by `to parquet` with DuckDB
+fn query_parquet(parquet_file: &std::path::Path, sql: &str, columns: usize) -> Vec<Vec<String>> {
+    let conn = duckdb::Connection::open_in_memory().unwrap();
+    let sql = sql.replace("{file}", &parquet_file.to_string_lossy());
+    let mut stmt = conn.prepare(&sql).unwrap();
+    let mut rows = stmt.query([]).unwrap();
+    let mut got = Vec::new();
+    while let Some(row) = rows.next().unwrap() {
+        got.push(
+            (0..columns)
+                .map(|i| row.get::<_, String>(i).unwrap_or_default())
+                .collect(),
+        );
+    }
+    got
+}
+
+#[test]
+fn to_parquet_stats_types() {
+    let wrk = Workdir::new("to_parquet_stats_types");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "price", "name", "day"],
+            svec!["1", "1.5", "apple", "2023-01-01"],
+            svec!["2", "", "banana", "2023-01-02"],
+            svec!["3", "3.25", "cherry", "2023-01-03"],
+        ],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("parquet").arg("out").arg("data.csv");
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains("Table 'data' (3 rows)"));
+
+    let parquet_file = wrk.path("out/data.parquet");
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT typeof(id), typeof(price), typeof(name), typeof(day) FROM '{file}' LIMIT 1",
+        4,
+    );
+    assert_eq!(got, vec![svec!["BIGINT", "DOUBLE", "VARCHAR", "VARCHAR"]]);
+
+    // the values round-trip
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT CAST(id AS VARCHAR), COALESCE(CAST(price AS VARCHAR), ''), name, day FROM \
+         '{file}' ORDER BY id",
+        4,
+    );
+    let expected = vec![
+        svec!["1", "1.5", "apple", "2023-01-01"],
+        svec!["2", "", "banana", "2023-01-02"],
+        svec!["3", "3.25", "cherry", "2023-01-03"],
+    ];
+    assert_eq!(got, expected);
+
+    // snappy is the default compression
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT DISTINCT compression FROM parquet_metadata('{file}')",
+        1,
+    );
+    assert_eq!(got, vec![svec!["SNAPPY"]]);
+}
+
+#[test]
+fn to_parquet_schema_file() {
+    let wrk = Workdir::new("to_parquet_schema_file");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "active", "day", "seen"],
+            svec!["007", "true", "2023-01-01", "2023-01-01 10:30:00"],
+            svec!["042", "false", "2023-02-01", "2023-02-01 08:00:00"],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+            "properties": {
+                "id": {"type": ["string", "null"]},
+                "active": {"type": "boolean"},
+                "day": {"type": ["string", "null"], "format": "date"},
+                "seen": {"type": "string", "format": "date-time"}
+            }
+        }"#,
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("parquet")
+        .arg("out")
+        .args(["--schema-file", "schema.json"])
+        .arg("--quiet")
+        .arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got = query_parquet(
+        &wrk.path("out/data.parquet"),
+        "SELECT typeof(id), typeof(active), typeof(day), typeof(seen), id FROM '{file}' ORDER BY \
+         id",
+        5,
+    );
+    let expected = vec![
+        svec!["VARCHAR", "BOOLEAN", "DATE", "TIMESTAMP", "007"],
+        svec!["VARCHAR", "BOOLEAN", "DATE", "TIMESTAMP", "042"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn to_parquet_compression_row_groups() {
+    let wrk = Workdir::new("to_parquet_compression_row_groups");
+    // DuckDB writes the rows in batches of 2048, so use enough rows for several row groups
+    let mut rows = vec![svec!["id", "value"]];
+    for i in 0..5000 {
+        rows.push(vec![i.to_string(), format!("value {i}")]);
+    }
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("parquet")
+        .arg("out")
+        .args(["--compression", "zstd"])
+        .args(["--row-group-size", "2048"])
+        .arg("data.csv");
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains("Table 'data' (5000 rows)"));
+
+    let parquet_file = wrk.path("out/data.parquet");
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT DISTINCT compression FROM parquet_metadata('{file}')",
+        1,
+    );
+    assert_eq!(got, vec![svec!["ZSTD"]]);
+
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT CAST(count(DISTINCT row_group_id) AS VARCHAR) FROM parquet_metadata('{file}')",
+        1,
+    );
+    assert_eq!(got, vec![svec!["3"]]);
+
+    let got = query_parquet(
+        &parquet_file,
+        "SELECT CAST(sum(id) AS VARCHAR), CAST(count(*) AS VARCHAR) FROM '{file}'",
+        2,
+    );
+    assert_eq!(got, vec![svec!["12497500", "5000"]]);
+}
+
+#[test]
+fn to_parquet_invalid_compression() {
+    let wrk = Workdir::new("to_parquet_invalid_compression");
+    wrk.create("data.csv", vec![svec!["id"], svec!["1"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("parquet")
+        .arg("out")
+        .args(["--compression", "lzma"])
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_parquet_pipe_options_conflict() {
+    let wrk = Workdir::new("to_parquet_pipe_options_conflict");
+    wrk.create("data.csv", vec![svec!["id"], svec!["1"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("parquet")
+        .arg("out")
+        .arg("--pipe")
+        .args(["--compression", "zstd"])
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_arrow_batch_size() {
+    let wrk = Workdir::new("to_arrow_batch_size");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "price", "name"],
+            svec!["1", "1.5", "apple"],
+            svec!["2", "", "banana"],
+            svec!["3", "3.25", "cherry"],
+        ],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("arrow")
+        .arg("out")
+        .args(["--batch-size", "2"])
+        .arg("data.csv");
+    let got: String = wrk.stdout(&mut cmd);
+    assert!(got.contains("Table 'data' (3 rows)"));
+
+    let arrow_file = std::fs::File::open(wrk.path("out/data.arrow")).unwrap();
+    let rdr = arrow::ipc::reader::FileReader::try_new(arrow_file, None).unwrap();
+    let data_types: Vec<String> = rdr
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.data_type().to_string())
+        .collect();
+    assert_eq!(data_types, vec!["Int64", "Float64", "Utf8"]);
+
+    // 3 rows in batches of 2 rows
+    let batch_rows: Vec<usize> = rdr.map(|batch| batch.unwrap().num_rows()).collect();
+    assert_eq!(batch_rows, vec![2, 1]);
+}
+
+#[test]
+fn to_arrow_batch_size_zero() {
+    let wrk = Workdir::new("to_arrow_batch_size_zero");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("arrow")
+        .arg("out")
+        .args(["--batch-size", "0"])
+        .arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--batch-size must be greater than zero."));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_postgres_dry_run() {
+    let wrk = Workdir::new("to_postgres_dry_run");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["letter", "number", "amount"],
+            svec!["a", "1", "1.5"],
+            svec!["b", "2", ""],
+        ],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("postgres://localhost/unused")
+        .arg("--dry-run")
+        .args(["--schema", "staging"])
+        .arg("--drop")
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"CREATE SCHEMA IF NOT EXISTS "staging";
+DROP TABLE IF EXISTS "staging"."data";
+CREATE TABLE "staging"."data" ("letter" TEXT, "number" BIGINT, "amount" DOUBLE PRECISION);"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn to_postgres_dry_run_env_connection_string() {
+    let wrk = Workdir::new("to_postgres_dry_run_env_connection_string");
+    wrk.create(
+        "data.csv",
+        vec![svec!["letter", "number"], svec!["a", "1"], svec!["b", "2"]],
+    );
+
+    // the dry run doesn't connect, so the connection string is not resolved
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("env=QSV_TEST_UNSET_DATABASE_URL")
+        .arg("--dry-run")
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        got,
+        r#"CREATE TABLE "data" ("letter" TEXT, "number" BIGINT);"#
+    );
+}
+
+#[test]
+fn to_postgres_dry_run_dump_conflict() {
+    let wrk = Workdir::new("to_postgres_dry_run_dump_conflict");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("--dump")
+        .arg("-")
+        .arg("--dry-run")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_postgres_batch_size_zero() {
+    let wrk = Workdir::new("to_postgres_batch_size_zero");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("postgres://localhost/unused")
+        .args(["--batch-size", "0"])
+        .arg("data.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--batch-size must be greater than zero."));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn to_postgres_resume_from_drop_conflict() {
+    let wrk = Workdir::new("to_postgres_resume_from_drop_conflict");
+    wrk.create("data.csv", vec![svec!["letter"], svec!["a"]]);
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("postgres")
+        .arg("postgres://localhost/unused")
+        .args(["--resume-from", "1"])
+        .arg("--drop")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
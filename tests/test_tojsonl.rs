@@ -281,3 +281,50 @@ fn tojsonl_boston() {
 
     assert_eq!(dos2unix(&got), dos2unix(&expected).trim_end());
 }
+
+#[test]
+fn tojsonl_nested_columns() {
+    let wrk = Workdir::new("tojsonl_nested_columns");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "name.first", "name.last", "address.geo.lat", "active"],
+            svec!["1", "Mark", "Smith", "42.36", "true"],
+            svec!["2", "John", "Doe", "", "false"],
+            svec!["3", "Bob", "", "40.71", "true"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tojsonl");
+    cmd.arg("--nested").arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"{"id":1,"name":{"first":"Mark","last":"Smith"},"address":{"geo":{"lat":42.36}},"active":true}
+{"id":2,"name":{"first":"John","last":"Doe"},"address":{"geo":{"lat":null}},"active":false}
+{"id":3,"name":{"first":"Bob","last":null},"address":{"geo":{"lat":40.71}},"active":true}"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn tojsonl_nested_same_as_flat() {
+    let wrk = Workdir::new("tojsonl_nested_same_as_flat");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "flag.on", "amount.value"],
+            svec!["1", "y", "1.50"],
+            svec!["2", "", "10"],
+            svec!["3", "", "2.25"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tojsonl");
+    cmd.arg("--nested").arg("in.csv");
+
+    // empty booleans are false & numbers are written as is, like without --nested
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"{"id":1,"flag":{"on":true},"amount":{"value":1.50}}
+{"id":2,"flag":{"on":false},"amount":{"value":10}}
+{"id":3,"flag":{"on":false},"amount":{"value":2.25}}"#;
+    assert_eq!(got, expected);
+}
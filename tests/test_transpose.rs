@@ -42,3 +42,20 @@ fn prop_transpose_multipass() {
     }
     qcheck(p as fn(CsvData) -> bool);
 }
+
+#[test]
+fn transpose_multipass_stdin() {
+    let wrk = Workdir::new("transpose_multipass_stdin");
+    wrk.create(
+        "in.csv",
+        vec![svec!["h1", "h2"], svec!["a", "b"], svec!["c", "d"]],
+    );
+
+    let mut cmd = wrk.command("transpose");
+    cmd.arg("--multipass")
+        .stdin(std::fs::File::open(wrk.path("in.csv")).unwrap());
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["h1", "a", "c"], svec!["h2", "b", "d"]];
+    assert_eq!(got, expected);
+}
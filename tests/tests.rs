@@ -34,17 +34,31 @@ mod workdir;
 mod test_apply;
 #[cfg(feature = "datapusher_plus")]
 mod test_applydp;
+#[cfg(feature = "full")]
+mod test_autoconvert;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_behead;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_bench;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_cat;
+#[cfg(feature = "full")]
+mod test_checksum;
 mod test_combos;
 mod test_comments;
 mod test_count;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_crosstab;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_datefmt;
 mod test_dedup;
 #[cfg(feature = "full")]
+mod test_describegpt;
+#[cfg(feature = "full")]
 mod test_diff;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_edit;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_enumerate;
 mod test_excel;
 mod test_exclude;
@@ -66,17 +80,35 @@ mod test_fmt;
 #[cfg(all(feature = "foreach", target_family = "unix"))]
 mod test_foreach;
 mod test_frequency;
+#[cfg(feature = "polars")]
+mod test_from;
+#[cfg(feature = "generate")]
+mod test_generate;
+#[cfg(feature = "apply")]
+mod test_geocode;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_groupby;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_head;
 mod test_headers;
 mod test_index;
 mod test_input;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_join;
+#[cfg(feature = "polars")]
+mod test_joinp;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_jsonl;
 #[cfg(feature = "luau")]
 mod test_luau;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_melt;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_outliers;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_partition;
+#[cfg(feature = "full")]
+mod test_plot;
 mod test_pseudo;
 #[cfg(feature = "python")]
 mod test_py;
@@ -92,15 +124,25 @@ mod test_search;
 mod test_searchset;
 mod test_select;
 mod test_slice;
+#[cfg(feature = "full")]
+mod test_snappy;
 mod test_sniff;
 mod test_sort;
 mod test_sortcheck;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_split;
+#[cfg(feature = "polars")]
+mod test_sqlp;
 mod test_stats;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_table;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_tail;
+#[cfg(feature = "full")]
+mod test_template;
+#[cfg(feature = "to")]
+mod test_to;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_tojsonl;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_transpose;
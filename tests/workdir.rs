@@ -101,6 +101,8 @@ impl Workdir {
 
     pub fn command(&self, sub_command: &str) -> process::Command {
         let mut cmd = process::Command::new(self.qsv_bin());
+        // keep the caches of the commands that have one out of the user's cache
+        cmd.env("XDG_CACHE_HOME", self.dir.join(".cache"));
         if sub_command.is_empty() {
             cmd.current_dir(&self.dir);
         } else {